@@ -1,5 +1,8 @@
+use crate::commands::auth::SessionState;
+use crate::db::audit::Actor;
 use crate::db::incidents::{self, Attachment};
 use crate::errors::AppError;
+use crate::permissions;
 use rusqlite::Connection;
 use std::sync::Mutex;
 use std::fs;
@@ -11,42 +14,57 @@ type DbState = Mutex<Connection>;
 #[tauri::command]
 pub fn add_attachment(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     incident_id: i64,
     file_name: String,
     file_path: String,
     file_type: String,
     file_size: Option<i64>,
 ) -> Result<Attachment, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "add_attachment")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    incidents::add_attachment(&conn, incident_id, &file_name, &file_path, &file_type, file_size)
+    incidents::add_attachment(&conn, &actor, incident_id, &file_name, &file_path, &file_type, file_size)
         .map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub fn list_attachments(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     incident_id: i64,
 ) -> Result<Vec<Attachment>, AppError> {
+    permissions::authorize(&session, "list_attachments")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     incidents::list_attachments(&conn, incident_id).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn delete_attachment(db: State<'_, DbState>, id: i64) -> Result<(), AppError> {
+pub fn delete_attachment(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_attachment")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    incidents::delete_attachment(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+    incidents::delete_attachment(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub async fn upload_attachment(
     app: AppHandle,
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     incident_id: i64,
     source_path: String,
     file_type: String,
 ) -> Result<Attachment, AppError> {
     use crate::validation;
 
+    let actor = Actor::from(&permissions::authorize(&session, "upload_attachment")?);
+
     // Validate file type
     let allowed_types = vec!["photo", "audio", "document"];
     if !allowed_types.contains(&file_type.as_str()) {
@@ -110,6 +128,7 @@ pub async fn upload_attachment(
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     incidents::add_attachment(
         &conn,
+        &actor,
         incident_id,
         &file_name,
         &relative_path,
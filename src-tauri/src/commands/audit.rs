@@ -0,0 +1,27 @@
+use crate::commands::auth::SessionState;
+use crate::db::audit::{self, AuditEntry};
+use crate::errors::AppError;
+use crate::permissions;
+use rusqlite::Connection;
+use std::sync::Mutex;
+use tauri::State;
+
+type DbState = Mutex<Connection>;
+
+#[tauri::command]
+pub fn get_audit_history(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    table_name: String,
+    record_id: i64,
+) -> Result<Vec<AuditEntry>, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "get_audit_history")?;
+
+    validation::validate_not_empty(&table_name, "Table name")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    audit::get_record_history(&conn, &table_name, record_id)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
@@ -0,0 +1,179 @@
+use crate::db::auth::{self, CreateUser, Session, User};
+use crate::errors::AppError;
+use crate::permissions;
+use rusqlite::Connection;
+use std::sync::Mutex;
+use tauri::State;
+
+type DbState = Mutex<Connection>;
+
+/// The logged-in session for this app instance, managed alongside the DB.
+pub type SessionState = Mutex<Option<Session>>;
+
+/// Returns the current session, clearing it if it has expired.
+pub fn require_session(session: &SessionState) -> Result<Session, AppError> {
+    let mut current = session.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    match current.as_ref() {
+        Some(s) if !s.is_expired() => Ok(s.clone()),
+        Some(_) => {
+            *current = None;
+            Err(AppError::Unauthorized("Session expired, please log in again".into()))
+        }
+        None => Err(AppError::Unauthorized("Not logged in".into())),
+    }
+}
+
+fn start_session(session: &SessionState, new_session: Session) -> Result<Session, AppError> {
+    let mut current = session.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    *current = Some(new_session.clone());
+    Ok(new_session)
+}
+
+#[tauri::command]
+pub fn needs_initial_setup(db: State<'_, DbState>) -> Result<bool, AppError> {
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    auth::needs_initial_setup(&conn).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// First-launch setup: sets the seeded admin's password and logs in.
+/// Refused once any active account has a password.
+#[tauri::command]
+pub fn setup_initial_admin(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    password: String,
+) -> Result<Session, AppError> {
+    let new_session = {
+        let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+        if !auth::needs_initial_setup(&conn).map_err(|e| AppError::Internal(e.to_string()))? {
+            return Err(AppError::Unauthorized("Initial setup has already been completed".into()));
+        }
+
+        let admin_id: i64 = conn
+            .query_row(
+                "SELECT id FROM users WHERE username = 'admin' AND is_active = 1",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|_| AppError::NotFound("Admin account not found".into()))?;
+
+        auth::set_password(&conn, admin_id, &password)
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        let admin = auth::get_user(&conn, admin_id).map_err(|e| AppError::Internal(e.to_string()))?;
+        auth::create_session(&conn, &admin).map_err(|e| AppError::Internal(e.to_string()))?
+    };
+
+    start_session(&session, new_session)
+}
+
+#[tauri::command]
+pub fn login(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    username: String,
+    password: String,
+) -> Result<Session, AppError> {
+    let new_session = {
+        let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+        let user = auth::authenticate(&conn, username.trim(), &password)
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .ok_or_else(|| AppError::Unauthorized("Invalid username or password".into()))?;
+        auth::create_session(&conn, &user).map_err(|e| AppError::Internal(e.to_string()))?
+    };
+
+    start_session(&session, new_session)
+}
+
+#[tauri::command]
+pub fn logout(db: State<'_, DbState>, session: State<'_, SessionState>) -> Result<(), AppError> {
+    let ended = session
+        .lock()
+        .map_err(|e| AppError::Internal(e.to_string()))?
+        .take();
+
+    if let Some(s) = ended {
+        let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+        auth::end_session(&conn, &s.token).map_err(|e| AppError::Internal(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// The logged-in user, or `None`. Re-checks the session against the DB so a
+/// revoked or deactivated account is signed out.
+#[tauri::command]
+pub fn whoami(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+) -> Result<Option<Session>, AppError> {
+    let current = match require_session(&session) {
+        Ok(s) => s,
+        Err(AppError::Unauthorized(_)) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let still_valid = {
+        let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+        auth::get_session(&conn, &current.token).map_err(|e| AppError::Internal(e.to_string()))?
+    };
+
+    if still_valid.is_none() {
+        *session.lock().map_err(|e| AppError::Internal(e.to_string()))? = None;
+    }
+    Ok(still_valid)
+}
+
+#[tauri::command]
+pub fn change_password(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    current_password: String,
+    new_password: String,
+) -> Result<Session, AppError> {
+    let current = permissions::authorize(&session, "change_password")?;
+
+    let new_session = {
+        let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+        let user = auth::authenticate(&conn, &current.username, &current_password)
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .ok_or_else(|| AppError::Unauthorized("Current password is incorrect".into()))?;
+
+        // set_password revokes every session, including this one
+        auth::set_password(&conn, user.id, &new_password)
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        auth::create_session(&conn, &user).map_err(|e| AppError::Internal(e.to_string()))?
+    };
+
+    start_session(&session, new_session)
+}
+
+// ── Users ──
+
+#[tauri::command]
+pub fn list_users(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+) -> Result<Vec<User>, AppError> {
+    permissions::authorize(&session, "list_users")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    auth::list_users(&conn).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn create_user(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: CreateUser,
+) -> Result<User, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "create_user")?;
+
+    validation::validate_not_empty(&data.username, "Username")?;
+    validation::validate_string_length(&data.username, validation::MAX_NAME_LENGTH, "Username")?;
+    validation::validate_not_empty(&data.display_name, "Display name")?;
+    validation::validate_string_length(&data.display_name, validation::MAX_NAME_LENGTH, "Display name")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    auth::create_user(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+}
@@ -1,4 +1,7 @@
+use crate::commands::auth::SessionState;
+use crate::db::benchmarks::{self, BenchmarkImportResult, BenchmarkTable, IndustryBenchmark};
 use crate::errors::AppError;
+use crate::permissions;
 use rusqlite::{params, Connection};
 use serde::Serialize;
 use std::sync::Mutex;
@@ -13,6 +16,13 @@ pub struct DashboardSummary {
     pub total_recordable: i64,
     pub days_since_last_incident: Option<i64>,
     pub trir: Option<f64>,
+    pub dart_rate: Option<f64>,
+    pub ltir: Option<f64>,
+    pub severity_rate: Option<f64>,
+    pub fatality_rate: Option<f64>,
+    pub near_miss_ratio: Option<f64>,
+    /// BLS rates for the establishment's industry, for comparison
+    pub benchmark: Option<IndustryBenchmark>,
 }
 
 #[derive(Debug, Serialize)]
@@ -50,9 +60,12 @@ pub struct CorrectiveActionSummary {
 #[tauri::command]
 pub fn get_dashboard_summary(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
     year: i64,
 ) -> Result<DashboardSummary, AppError> {
+    permissions::authorize(&session, "get_dashboard_summary")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     let year_str = format!("{year}%");
 
@@ -60,10 +73,10 @@ pub fn get_dashboard_summary(
         .query_row(
             "SELECT
                 COUNT(*),
-                SUM(CASE WHEN status = 'open' THEN 1 ELSE 0 END),
+                SUM(CASE WHEN status != 'closed' THEN 1 ELSE 0 END),
                 SUM(CASE WHEN is_recordable = 1 THEN 1 ELSE 0 END)
              FROM incidents
-             WHERE establishment_id = ?1 AND incident_date LIKE ?2",
+             WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND deleted_at IS NULL",
             params![establishment_id, year_str],
             |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
         )
@@ -72,40 +85,43 @@ pub fn get_dashboard_summary(
     let days_since: Option<i64> = conn
         .query_row(
             "SELECT CAST(julianday('now') - julianday(MAX(incident_date)) AS INTEGER)
-             FROM incidents WHERE establishment_id = ?1",
+             FROM incidents WHERE establishment_id = ?1 AND deleted_at IS NULL",
             [establishment_id],
             |row| row.get(0),
         )
         .ok()
         .flatten();
 
-    // TRIR = (Total Recordable Incidents * 200,000) / Total Hours Worked
-    let trir: Option<f64> = conn
-        .query_row(
-            "SELECT total_hours_worked FROM annual_stats
-             WHERE establishment_id = ?1 AND year = ?2",
-            params![establishment_id, year],
-            |row| row.get::<_, i64>(0),
-        )
-        .ok()
-        .filter(|&hours| hours > 0)
-        .map(|hours| (recordable as f64 * 200_000.0) / hours as f64);
+    // Rates per 200,000 hours, e.g. TRIR = (Total Recordable Incidents * 200,000) / Total Hours Worked
+    let kpis = benchmarks::get_kpis(&conn, establishment_id, year)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    let benchmark = benchmarks::benchmark_for_establishment(&conn, establishment_id)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
 
     Ok(DashboardSummary {
         total_incidents: total,
         open_incidents: open,
         total_recordable: recordable,
         days_since_last_incident: days_since,
-        trir,
+        trir: kpis.trir,
+        dart_rate: kpis.dart_rate,
+        ltir: kpis.ltir,
+        severity_rate: kpis.severity_rate,
+        fatality_rate: kpis.fatality_rate,
+        near_miss_ratio: kpis.near_miss_ratio,
+        benchmark,
     })
 }
 
 #[tauri::command]
 pub fn get_incidents_by_month(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
     year: i64,
 ) -> Result<Vec<IncidentsByMonth>, AppError> {
+    permissions::authorize(&session, "get_incidents_by_month")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     let year_str = format!("{year}%");
 
@@ -113,7 +129,7 @@ pub fn get_incidents_by_month(
         .prepare(
             "SELECT strftime('%Y-%m', incident_date) as month, COUNT(*) as cnt
              FROM incidents
-             WHERE establishment_id = ?1 AND incident_date LIKE ?2
+             WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND deleted_at IS NULL
              GROUP BY month ORDER BY month",
         )
         .map_err(|e| AppError::Database(e))?;
@@ -135,9 +151,12 @@ pub fn get_incidents_by_month(
 #[tauri::command]
 pub fn get_incidents_by_severity(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
     year: i64,
 ) -> Result<Vec<IncidentsBySeverity>, AppError> {
+    permissions::authorize(&session, "get_incidents_by_severity")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     let year_str = format!("{year}%");
 
@@ -145,7 +164,7 @@ pub fn get_incidents_by_severity(
         .prepare(
             "SELECT outcome_severity, COUNT(*)
              FROM incidents
-             WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND is_recordable = 1
+             WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND is_recordable = 1 AND deleted_at IS NULL
              GROUP BY outcome_severity",
         )
         .map_err(|e| AppError::Database(e))?;
@@ -167,9 +186,12 @@ pub fn get_incidents_by_severity(
 #[tauri::command]
 pub fn get_incidents_by_location(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
     year: i64,
 ) -> Result<Vec<IncidentsByLocation>, AppError> {
+    permissions::authorize(&session, "get_incidents_by_location")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     let year_str = format!("{year}%");
 
@@ -178,7 +200,7 @@ pub fn get_incidents_by_location(
             "SELECT COALESCE(l.name, 'Unassigned'), COUNT(*)
              FROM incidents i
              LEFT JOIN locations l ON i.location_id = l.id
-             WHERE i.establishment_id = ?1 AND i.incident_date LIKE ?2
+             WHERE i.establishment_id = ?1 AND i.incident_date LIKE ?2 AND i.deleted_at IS NULL
              GROUP BY l.name",
         )
         .map_err(|e| AppError::Database(e))?;
@@ -200,9 +222,12 @@ pub fn get_incidents_by_location(
 #[tauri::command]
 pub fn get_incidents_by_type(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
     year: i64,
 ) -> Result<Vec<IncidentsByType>, AppError> {
+    permissions::authorize(&session, "get_incidents_by_type")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     let year_str = format!("{year}%");
 
@@ -210,7 +235,7 @@ pub fn get_incidents_by_type(
         .prepare(
             "SELECT injury_illness_type, COUNT(*)
              FROM incidents
-             WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND is_recordable = 1
+             WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND is_recordable = 1 AND deleted_at IS NULL
              GROUP BY injury_illness_type",
         )
         .map_err(|e| AppError::Database(e))?;
@@ -232,8 +257,11 @@ pub fn get_incidents_by_type(
 #[tauri::command]
 pub fn get_corrective_action_summary(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
 ) -> Result<CorrectiveActionSummary, AppError> {
+    permissions::authorize(&session, "get_corrective_action_summary")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
 
     let (open, in_progress, completed, overdue): (i64, i64, i64, i64) = conn
@@ -245,7 +273,7 @@ pub fn get_corrective_action_summary(
                 COALESCE(SUM(CASE WHEN ca.status != 'completed' AND ca.due_date < date('now') THEN 1 ELSE 0 END), 0)
              FROM corrective_actions ca
              JOIN incidents i ON ca.incident_id = i.id
-             WHERE i.establishment_id = ?1",
+             WHERE i.establishment_id = ?1 AND i.deleted_at IS NULL",
             [establishment_id],
             |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
         )
@@ -258,3 +286,54 @@ pub fn get_corrective_action_summary(
         overdue,
     })
 }
+
+#[tauri::command]
+pub fn list_industry_benchmarks(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+) -> Result<Vec<IndustryBenchmark>, AppError> {
+    permissions::authorize(&session, "list_industry_benchmarks")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    benchmarks::list_benchmarks(&conn).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Writes the benchmark table to a JSON file in Downloads and returns its path.
+#[tauri::command]
+pub fn export_industry_benchmarks(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+) -> Result<String, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "export_industry_benchmarks")?;
+
+    let safe_path = validation::safe_export_path("Industry_Benchmarks", "json")?;
+
+    let table = {
+        let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+        benchmarks::export_benchmarks(&conn).map_err(|e| AppError::Internal(e.to_string()))?
+    };
+
+    let json = serde_json::to_string_pretty(&table).map_err(|e| AppError::Internal(e.to_string()))?;
+    std::fs::write(&safe_path, json)?;
+    Ok(safe_path.to_string_lossy().to_string())
+}
+
+/// Loads updated BLS rates, e.g. after a new survey year is published.
+#[tauri::command]
+pub fn import_industry_benchmarks(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    file_path: String,
+) -> Result<BenchmarkImportResult, AppError> {
+    permissions::authorize(&session, "import_industry_benchmarks")?;
+
+    let text = std::fs::read_to_string(&file_path)
+        .map_err(|e| AppError::Internal(format!("Failed to open benchmark table: {e}")))?;
+    let table: BenchmarkTable = serde_json::from_str(&text)
+        .map_err(|e| AppError::Validation(format!("Invalid benchmark table file: {e}")))?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    benchmarks::import_benchmarks(&conn, &table).map_err(|e| AppError::Internal(e.to_string()))
+}
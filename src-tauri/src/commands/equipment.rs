@@ -0,0 +1,210 @@
+use crate::commands::auth::SessionState;
+use crate::db::equipment::{
+    self, CreateEquipment, CreateEquipmentInspection, CreateEquipmentMaintenance, Equipment,
+    EquipmentFilter, EquipmentInspection, EquipmentMaintenance, OverdueInspection,
+    UpdateEquipment,
+};
+use crate::errors::AppError;
+use crate::permissions;
+use rusqlite::Connection;
+use std::sync::Mutex;
+use tauri::State;
+
+type DbState = Mutex<Connection>;
+
+// ── Equipment ──
+
+#[tauri::command]
+pub fn create_equipment(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: CreateEquipment,
+) -> Result<Equipment, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "create_equipment")?;
+
+    validation::validate_not_empty(&data.equipment_type, "Equipment type")?;
+    validation::validate_not_empty(&data.equipment_number, "Equipment number")?;
+    validation::validate_string_length(&data.equipment_number, validation::MAX_NAME_LENGTH, "Equipment number")?;
+    if let Some(ref date) = data.purchase_date {
+        validation::validate_date_format(date, "Purchase date")?;
+    }
+    if let Some(ref date) = data.next_inspection_due {
+        validation::validate_date_format(date, "Next inspection due")?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    equipment::create_equipment(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn get_equipment(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<Equipment, AppError> {
+    permissions::authorize(&session, "get_equipment")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    equipment::get_equipment(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_equipment(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    filter: EquipmentFilter,
+) -> Result<Vec<Equipment>, AppError> {
+    permissions::authorize(&session, "list_equipment")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    equipment::list_equipment(&conn, filter).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn update_equipment(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    data: UpdateEquipment,
+) -> Result<Equipment, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "update_equipment")?;
+
+    if let Some(ref number) = data.equipment_number {
+        validation::validate_not_empty(number, "Equipment number")?;
+        validation::validate_string_length(number, validation::MAX_NAME_LENGTH, "Equipment number")?;
+    }
+    if let Some(ref status) = data.status {
+        if !equipment::STATUSES.contains(&status.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Invalid equipment status: {status}. Must be one of: {}",
+                equipment::STATUSES.join(", ")
+            )));
+        }
+    }
+    if let Some(ref date) = data.purchase_date {
+        validation::validate_date_format(date, "Purchase date")?;
+    }
+    if let Some(ref date) = data.next_inspection_due {
+        validation::validate_date_format(date, "Next inspection due")?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    equipment::update_equipment(&conn, id, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn delete_equipment(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    permissions::authorize(&session, "delete_equipment")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    equipment::delete_equipment(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+// ── Inspections ──
+
+#[tauri::command]
+pub fn record_equipment_inspection(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: CreateEquipmentInspection,
+) -> Result<EquipmentInspection, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "record_equipment_inspection")?;
+
+    validation::validate_date_format(&data.inspection_date, "Inspection date")?;
+    validation::validate_not_empty(&data.inspector_name, "Inspector name")?;
+    validation::validate_not_empty(&data.inspection_type, "Inspection type")?;
+    if let Some(ref date) = data.next_inspection_due {
+        validation::validate_date_format(date, "Next inspection due")?;
+    }
+    if let Some(ref text) = data.deficiencies {
+        validation::validate_string_length(text, validation::MAX_DESCRIPTION_LENGTH, "Deficiencies")?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    equipment::record_inspection(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_equipment_inspections(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    equipment_id: i64,
+) -> Result<Vec<EquipmentInspection>, AppError> {
+    permissions::authorize(&session, "list_equipment_inspections")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    equipment::list_inspections(&conn, equipment_id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_overdue_equipment_inspections(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    as_of: Option<String>,
+) -> Result<Vec<OverdueInspection>, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "list_overdue_equipment_inspections")?;
+
+    let as_of = match as_of {
+        Some(date) => {
+            validation::validate_date_format(&date, "As-of date")?;
+            date
+        }
+        None => chrono::Local::now().date_naive().format("%Y-%m-%d").to_string(),
+    };
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    equipment::list_overdue_inspections(&conn, &as_of).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+// ── Maintenance ──
+
+#[tauri::command]
+pub fn add_equipment_maintenance(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: CreateEquipmentMaintenance,
+) -> Result<EquipmentMaintenance, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "add_equipment_maintenance")?;
+
+    validation::validate_date_format(&data.maintenance_date, "Maintenance date")?;
+    validation::validate_not_empty(&data.maintenance_type, "Maintenance type")?;
+    validation::validate_not_empty(&data.description, "Description")?;
+    validation::validate_string_length(&data.description, validation::MAX_DESCRIPTION_LENGTH, "Description")?;
+    if let Some(ref date) = data.next_maintenance_due {
+        validation::validate_date_format(date, "Next maintenance due")?;
+    }
+    if let Some(cost) = data.cost {
+        if cost < 0.0 {
+            return Err(AppError::Validation(format!("Cost cannot be negative (got: {cost})")));
+        }
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    equipment::add_maintenance(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_equipment_maintenance(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    equipment_id: i64,
+) -> Result<Vec<EquipmentMaintenance>, AppError> {
+    permissions::authorize(&session, "list_equipment_maintenance")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    equipment::list_maintenance(&conn, equipment_id).map_err(|e| AppError::Internal(e.to_string()))
+}
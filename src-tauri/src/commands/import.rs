@@ -1,5 +1,9 @@
+use crate::commands::auth::SessionState;
+use crate::db::audit::Actor;
 use crate::db::incidents::{self, CreateIncident};
 use crate::errors::AppError;
+use crate::permissions;
+use crate::redaction;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -27,6 +31,7 @@ pub struct ColumnMapping {
     pub days_restricted_count: Option<String>,
     pub injury_illness_type: Option<String>,
     pub employee_gender: Option<String>,
+    pub is_privacy_case: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -36,7 +41,12 @@ pub struct ImportResult {
 }
 
 #[tauri::command]
-pub fn preview_csv(file_path: String) -> Result<CsvPreview, AppError> {
+pub fn preview_csv(
+    session: State<'_, SessionState>,
+    file_path: String,
+) -> Result<CsvPreview, AppError> {
+    permissions::authorize(&session, "preview_csv")?;
+
     let mut rdr = csv::Reader::from_path(&file_path)
         .map_err(|e| AppError::Internal(format!("Failed to open CSV: {e}")))?;
 
@@ -68,11 +78,14 @@ pub fn preview_csv(file_path: String) -> Result<CsvPreview, AppError> {
 #[tauri::command]
 pub fn import_csv(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     file_path: String,
     establishment_id: i64,
     location_id: Option<i64>,
     mapping: ColumnMapping,
 ) -> Result<ImportResult, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "import_csv")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
 
     let mut rdr = csv::Reader::from_path(&file_path)
@@ -130,6 +143,13 @@ pub fn import_csv(
 
         let description = get_field(&mapping.description).unwrap_or_else(|| "Imported incident".to_string());
 
+        // A 300 log export prints privacy cases as "Privacy Case"; keep them
+        // private when they come back in
+        let is_privacy_case = employee_name == redaction::PRIVACY_CASE_NAME
+            || get_field(&mapping.is_privacy_case).is_some_and(|v| {
+                matches!(v.trim().to_ascii_lowercase().as_str(), "x" | "1" | "y" | "yes" | "true")
+            });
+
         let data = CreateIncident {
             establishment_id,
             location_id,
@@ -151,7 +171,9 @@ pub fn import_csv(
             employee_zip: None,
             employee_dob: None,
             employee_hire_date: None,
-            is_privacy_case: None,
+            is_privacy_case: Some(is_privacy_case),
+            privacy_concern: None,
+            privacy_description: None,
             incident_time: None,
             work_start_time: None,
             activity_before_incident: None,
@@ -168,7 +190,7 @@ pub fn import_csv(
             is_recordable: None,
         };
 
-        match incidents::create_incident(&conn, data) {
+        match incidents::create_incident(&conn, &actor, data) {
             Ok(_) => imported += 1,
             Err(e) => errors.push(format!("Row {}: {e}", row_num + 2)),
         }
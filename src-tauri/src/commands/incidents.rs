@@ -1,20 +1,48 @@
+use crate::commands::auth::SessionState;
+use crate::db::audit::Actor;
 use crate::db::incidents::{
-    self, CreateIncident, Incident, IncidentFilter, UpdateIncident,
+    self, CaseRenumbering, CreateIncident, Incident, IncidentFilter, IncidentPage, IncidentStatusChange,
+    UpdateIncident,
+};
+use crate::db::osha;
+use crate::db::search::{self, SearchHit};
+use crate::db::work_status::{
+    self, CreateWorkStatusPeriod, UpdateWorkStatusPeriod, WorkStatusPeriod,
+};
+use crate::db::recordability::{
+    self, RecordabilityDecision, RecordabilityDetermination, RecordabilityInputs, TreatmentOption,
 };
 use crate::errors::AppError;
+use crate::permissions;
+use crate::redaction::{self, Audience};
 use rusqlite::Connection;
 use std::sync::Mutex;
 use tauri::State;
 
 type DbState = Mutex<Connection>;
 
+fn validate_privacy_concern(concern: Option<&str>) -> Result<(), AppError> {
+    match concern {
+        Some(c) if !redaction::PRIVACY_CONCERNS.contains(&c) => Err(AppError::Validation(format!(
+            "Invalid privacy concern: {}. Must be one of: {}",
+            c,
+            redaction::PRIVACY_CONCERNS.join(", ")
+        ))),
+        _ => Ok(()),
+    }
+}
+
 #[tauri::command]
 pub fn create_incident(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: CreateIncident,
 ) -> Result<Incident, AppError> {
     use crate::validation;
 
+    let current = permissions::authorize(&session, "create_incident")?;
+    let actor = Actor::from(&current);
+
     // Validate required fields
     validation::validate_not_empty(&data.employee_name, "Employee name")?;
     validation::validate_string_length(&data.employee_name, validation::MAX_NAME_LENGTH, "Employee name")?;
@@ -32,33 +60,115 @@ pub fn create_incident(
         validation::validate_days_count(days, "Days of restricted work")?;
     }
 
+    validate_privacy_concern(data.privacy_concern.as_deref())?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    incidents::create_incident(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    let mut incident =
+        incidents::create_incident(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))?;
+    redaction::incident(Audience::for_role(&current.role), &mut incident);
+    Ok(incident)
 }
 
 #[tauri::command]
-pub fn get_incident(db: State<'_, DbState>, id: i64) -> Result<Incident, AppError> {
+pub fn get_incident(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<Incident, AppError> {
+    let session = permissions::authorize(&session, "get_incident")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    incidents::get_incident(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+    let mut incident =
+        incidents::get_incident(&conn, id).map_err(|e| AppError::Internal(e.to_string()))?;
+    if incident.deleted_at.is_some() && !permissions::can_view_trash(&session.role) {
+        return Err(AppError::NotFound(format!("Incident {id} not found")));
+    }
+    redaction::incident(Audience::for_role(&session.role), &mut incident);
+    Ok(incident)
 }
 
 #[tauri::command]
 pub fn list_incidents(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     filter: IncidentFilter,
-) -> Result<Vec<Incident>, AppError> {
+) -> Result<IncidentPage, AppError> {
+    let session = permissions::authorize(&session, "list_incidents")?;
+
+    if let Some(ref key) = filter.sort_by {
+        if incidents::sort_column(key).is_none() {
+            let keys: Vec<&str> = incidents::SORT_COLUMNS.iter().map(|(k, _)| *k).collect();
+            return Err(AppError::Validation(format!(
+                "Invalid sort column: {}. Must be one of: {}",
+                key,
+                keys.join(", ")
+            )));
+        }
+    }
+    if let Some(ref status) = filter.status {
+        if !incidents::INCIDENT_STATUSES.contains(&status.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Invalid status: {}. Must be one of: {}",
+                status,
+                incidents::INCIDENT_STATUSES.join(", ")
+            )));
+        }
+    }
+    if let (Some(from), Some(to)) = (filter.case_number_from, filter.case_number_to) {
+        if from > to {
+            return Err(AppError::Validation(
+                "Case number range start must not be after its end".to_string(),
+            ));
+        }
+    }
+
+    let audience = Audience::for_role(&session.role);
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let mut page = incidents::list_incidents(&conn, filter, audience)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    redaction::incidents(audience, &mut page.incidents);
+    Ok(page)
+}
+
+/// Ranked full-text matches across incidents, RCA findings and corrective
+/// actions, optionally limited to one establishment.
+#[tauri::command]
+pub fn search_incidents(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    query: String,
+    establishment_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<SearchHit>, AppError> {
+    use crate::validation;
+
+    let session = permissions::authorize(&session, "search_incidents")?;
+
+    validation::validate_string_length(&query, validation::MAX_NAME_LENGTH, "Search")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    incidents::list_incidents(&conn, filter).map_err(|e| AppError::Internal(e.to_string()))
+    search::search(
+        &conn,
+        &query,
+        establishment_id,
+        Audience::for_role(&session.role),
+        limit.unwrap_or(search::DEFAULT_LIMIT),
+    )
+    .map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub fn update_incident(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     id: i64,
     data: UpdateIncident,
 ) -> Result<Incident, AppError> {
     use crate::validation;
 
+    let current = permissions::authorize(&session, "update_incident")?;
+    let actor = Actor::from(&current);
+
     // Validate optional fields if present
     if let Some(ref name) = data.employee_name {
         validation::validate_not_empty(name, "Employee name")?;
@@ -77,13 +187,296 @@ pub fn update_incident(
     if let Some(days) = data.days_restricted_count {
         validation::validate_days_count(days, "Days of restricted work")?;
     }
+    validate_privacy_concern(data.privacy_concern.as_deref())?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let existing = incidents::get_incident(&conn, id).map_err(|e| AppError::Internal(e.to_string()))?;
+    if existing.deleted_at.is_some() {
+        return Err(AppError::Validation("Restore the incident from the trash before editing it".into()));
+    }
+    let derived = work_status::has_periods(&conn, id).map_err(|e| AppError::Internal(e.to_string()))?;
+    if derived && (data.days_away_count.is_some() || data.days_restricted_count.is_some()) {
+        return Err(AppError::Validation(
+            "Day counts are calculated from the work-status periods; edit those instead".into(),
+        ));
+    }
+    incidents::update_incident(&conn, &actor, id, data).map_err(|e| AppError::Internal(e.to_string()))?;
+    // A changed incident date or outcome moves the derived counts too
+    let mut incident = work_status::recompute_counts(&conn, &actor, id, chrono::Local::now().date_naive())
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    redaction::incident(Audience::for_role(&current.role), &mut incident);
+    Ok(incident)
+}
+
+#[tauri::command]
+pub fn delete_incident(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_incident")?);
+
+    // Deleting only moves the case to the trash; the retention check is made
+    // when it is purged
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    incidents::delete_incident(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Moves an incident along reported → under investigation → pending RCA →
+/// pending corrective actions → closed. Closing checks that the follow-up
+/// work is done; reopening a closed case needs a reason.
+#[tauri::command]
+pub fn update_incident_status(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    status: String,
+    reason: Option<String>,
+) -> Result<Incident, AppError> {
+    use crate::validation;
+
+    let current = permissions::authorize(&session, "update_incident_status")?;
+    let actor = Actor::from(&current);
+
+    if !incidents::INCIDENT_STATUSES.contains(&status.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Invalid status: {}. Must be one of: {}",
+            status,
+            incidents::INCIDENT_STATUSES.join(", ")
+        )));
+    }
+    let reason = reason.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    if let Some(ref r) = reason {
+        validation::validate_string_length(r, validation::MAX_DESCRIPTION_LENGTH, "Reason")?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let existing = incidents::get_incident(&conn, id)
+        .map_err(|_| AppError::NotFound(format!("Incident {id} not found")))?;
+    if existing.deleted_at.is_some() {
+        return Err(AppError::Validation("Restore the incident from the trash before changing its status".into()));
+    }
+
+    let min_role = incidents::transition_role(&existing.status, &status).ok_or_else(|| {
+        let next = incidents::next_statuses(&existing.status);
+        AppError::Validation(format!(
+            "Cannot move a {} incident to {}. Allowed: {}",
+            existing.status,
+            status,
+            if next.is_empty() { "none".to_string() } else { next.join(", ") }
+        ))
+    })?;
+    if !permissions::has_role(&current.role, min_role) {
+        return Err(AppError::Forbidden(format!(
+            "Moving an incident to {status} requires the {min_role} role or higher"
+        )));
+    }
+
+    if status == "closed" {
+        let blockers = incidents::closure_blockers(&conn, &existing)
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        if !blockers.is_empty() {
+            return Err(AppError::Validation(format!(
+                "The incident cannot be closed yet: {}",
+                blockers.join("; ")
+            )));
+        }
+    }
+    if incidents::is_reopen(&existing.status, &status) && reason.is_none() {
+        return Err(AppError::Validation("A reason is required to reopen a closed incident".into()));
+    }
+
+    let mut incident = incidents::update_status(&conn, &actor, id, &status, reason.as_deref(), &current.display_name)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    redaction::incident(Audience::for_role(&current.role), &mut incident);
+    Ok(incident)
+}
+
+#[tauri::command]
+pub fn list_incident_status_changes(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    incident_id: i64,
+) -> Result<Vec<IncidentStatusChange>, AppError> {
+    permissions::authorize(&session, "list_incident_status_changes")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    incidents::list_status_changes(&conn, incident_id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn renumber_incident_cases(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    establishment_id: i64,
+    year: i64,
+) -> Result<Vec<CaseRenumbering>, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "renumber_incident_cases")?);
+
+    if !(1970..=9999).contains(&year) {
+        return Err(AppError::Validation(format!("Invalid year: {year}")));
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    // Certified logs are signed with their case numbers
+    if osha::is_certified(&conn, establishment_id, year).map_err(|e| AppError::Internal(e.to_string()))? {
+        return Err(AppError::Validation(format!(
+            "The {year} log is certified; its case numbers can no longer change"
+        )));
+    }
+    incidents::renumber_cases(&conn, &actor, establishment_id, year)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+// ── Recordability ──
+
+fn validate_treatments(inputs: &RecordabilityInputs) -> Result<(), AppError> {
+    match inputs.treatments.iter().find(|t| !recordability::is_known_treatment(t)) {
+        Some(unknown) => Err(AppError::Validation(format!("Unknown treatment: {unknown}"))),
+        None => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub fn list_recordability_treatments(
+    session: State<'_, SessionState>,
+) -> Result<Vec<TreatmentOption>, AppError> {
+    permissions::authorize(&session, "list_recordability_treatments")?;
+    Ok(recordability::treatment_options())
+}
+
+/// Previews the determination without saving it.
+#[tauri::command]
+pub fn evaluate_recordability(
+    session: State<'_, SessionState>,
+    inputs: RecordabilityInputs,
+) -> Result<RecordabilityDecision, AppError> {
+    permissions::authorize(&session, "evaluate_recordability")?;
+
+    validate_treatments(&inputs)?;
+    Ok(recordability::evaluate(&inputs))
+}
+
+#[tauri::command]
+pub fn get_incident_recordability(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    incident_id: i64,
+) -> Result<Option<RecordabilityDetermination>, AppError> {
+    permissions::authorize(&session, "get_incident_recordability")?;
 
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    incidents::update_incident(&conn, id, data).map_err(|e| AppError::Internal(e.to_string()))
+    recordability::get_determination(&conn, incident_id)
+        .map_err(|e| AppError::Internal(e.to_string()))
 }
 
+/// Decides recordability from the case facts and stores the answer, with
+/// its citation and the signer, on the incident.
 #[tauri::command]
-pub fn delete_incident(db: State<'_, DbState>, id: i64) -> Result<(), AppError> {
+pub fn determine_incident_recordability(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    incident_id: i64,
+    inputs: RecordabilityInputs,
+) -> Result<RecordabilityDetermination, AppError> {
+    let current = permissions::authorize(&session, "determine_incident_recordability")?;
+    let actor = Actor::from(&current);
+
+    validate_treatments(&inputs)?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    recordability::determine(&conn, &actor, incident_id, inputs, &current.display_name)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+// ── Work status ──
+
+fn validate_period(
+    conn: &Connection,
+    incident_id: i64,
+    status: &str,
+    start_date: &str,
+    end_date: Option<&str>,
+) -> Result<(), AppError> {
+    use crate::validation;
+
+    if !work_status::WORK_STATUSES.contains(&status) {
+        return Err(AppError::Validation(format!(
+            "Invalid work status: {}. Must be one of: {}",
+            status,
+            work_status::WORK_STATUSES.join(", ")
+        )));
+    }
+    validation::validate_date_format(start_date, "Start date")?;
+    if let Some(end) = end_date {
+        validation::validate_date_format(end, "End date")?;
+        if end < start_date {
+            return Err(AppError::Validation("End date cannot be before start date".into()));
+        }
+    }
+
+    let incident =
+        incidents::get_incident(conn, incident_id).map_err(|e| AppError::Internal(e.to_string()))?;
+    if start_date < incident.incident_date.as_str() {
+        return Err(AppError::Validation(format!(
+            "Start date cannot be before the incident date ({})",
+            incident.incident_date
+        )));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_work_status_periods(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    incident_id: i64,
+) -> Result<Vec<WorkStatusPeriod>, AppError> {
+    permissions::authorize(&session, "list_work_status_periods")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    work_status::list_periods(&conn, incident_id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn add_work_status_period(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: CreateWorkStatusPeriod,
+) -> Result<WorkStatusPeriod, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "add_work_status_period")?);
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    validate_period(&conn, data.incident_id, &data.status, &data.start_date, data.end_date.as_deref())?;
+    work_status::add_period(&conn, &actor, data, chrono::Local::now().date_naive())
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn update_work_status_period(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    data: UpdateWorkStatusPeriod,
+) -> Result<WorkStatusPeriod, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "update_work_status_period")?);
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let existing = work_status::get_period(&conn, id).map_err(|e| AppError::Internal(e.to_string()))?;
+    validate_period(&conn, existing.incident_id, &data.status, &data.start_date, data.end_date.as_deref())?;
+    work_status::update_period(&conn, &actor, id, data, chrono::Local::now().date_naive())
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn delete_work_status_period(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_work_status_period")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    incidents::delete_incident(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+    work_status::delete_period(&conn, &actor, id, chrono::Local::now().date_naive())
+        .map_err(|e| AppError::Internal(e.to_string()))
 }
@@ -0,0 +1,152 @@
+use crate::commands::auth::SessionState;
+use crate::db::inspections::{
+    self, ChecklistItem, CreateInspection, Inspection, InspectionFilter, InspectionResponse,
+    InspectionTemplate, RecordResponse, UpdateInspection,
+};
+use crate::errors::AppError;
+use crate::permissions;
+use rusqlite::Connection;
+use std::sync::Mutex;
+use tauri::State;
+
+type DbState = Mutex<Connection>;
+
+// ── Templates ──
+
+#[tauri::command]
+pub fn list_inspection_templates(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+) -> Result<Vec<InspectionTemplate>, AppError> {
+    permissions::authorize(&session, "list_inspection_templates")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    inspections::list_templates(&conn).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_inspection_checklist_items(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    template_id: i64,
+) -> Result<Vec<ChecklistItem>, AppError> {
+    permissions::authorize(&session, "list_inspection_checklist_items")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    inspections::list_checklist_items(&conn, template_id)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+// ── Inspections ──
+
+#[tauri::command]
+pub fn create_inspection(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: CreateInspection,
+) -> Result<Inspection, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "create_inspection")?;
+
+    validation::validate_not_empty(&data.inspector_name, "Inspector name")?;
+    validation::validate_string_length(&data.inspector_name, validation::MAX_NAME_LENGTH, "Inspector name")?;
+    validation::validate_date_format(&data.inspection_date, "Inspection date")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    inspections::create_inspection(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn get_inspection(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<Inspection, AppError> {
+    permissions::authorize(&session, "get_inspection")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    inspections::get_inspection(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_inspections(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    filter: InspectionFilter,
+) -> Result<Vec<Inspection>, AppError> {
+    permissions::authorize(&session, "list_inspections")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    inspections::list_inspections(&conn, filter).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn update_inspection(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    data: UpdateInspection,
+) -> Result<Inspection, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "update_inspection")?;
+
+    if let Some(ref name) = data.inspector_name {
+        validation::validate_not_empty(name, "Inspector name")?;
+        validation::validate_string_length(name, validation::MAX_NAME_LENGTH, "Inspector name")?;
+    }
+    if let Some(ref date) = data.inspection_date {
+        validation::validate_date_format(date, "Inspection date")?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    inspections::update_inspection(&conn, id, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn delete_inspection(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    permissions::authorize(&session, "delete_inspection")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    inspections::delete_inspection(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+// ── Responses ──
+
+#[tauri::command]
+pub fn record_inspection_response(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: RecordResponse,
+) -> Result<InspectionResponse, AppError> {
+    permissions::authorize(&session, "record_inspection_response")?;
+
+    if !inspections::RESPONSE_STATUSES.contains(&data.status.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Invalid response status: {}. Must be one of: {}",
+            data.status,
+            inspections::RESPONSE_STATUSES.join(", ")
+        )));
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    inspections::record_response(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_inspection_responses(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    inspection_id: i64,
+) -> Result<Vec<InspectionResponse>, AppError> {
+    permissions::authorize(&session, "list_inspection_responses")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    inspections::list_responses(&conn, inspection_id)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
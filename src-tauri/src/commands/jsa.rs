@@ -1,5 +1,13 @@
-use crate::db::jsa::{self, AddJsaStep, CreateJsaInstance, JsaInstance, JsaStep, JsaTemplate};
+use crate::commands::auth::SessionState;
+use crate::db::audit::Actor;
+use crate::db::jsa::{
+    self, AddJsaControl, AddJsaHazard, AddJsaStep, AddJsaTemplateControl, AddJsaTemplateHazard,
+    AddJsaTemplateStep, ControlFinding, CreateJsaInstance, JsaControl, JsaHazard, JsaInstance,
+    JsaStep, JsaTemplate, JsaTemplateStep, UpdateJsaControl, UpdateJsaHazard,
+};
+use crate::db::trade_hazards::TradeHazard;
 use crate::errors::AppError;
+use crate::permissions;
 use rusqlite::Connection;
 use std::sync::Mutex;
 use tauri::State;
@@ -7,28 +15,126 @@ use tauri::State;
 type DbState = Mutex<Connection>;
 
 #[tauri::command]
-pub fn list_jsa_templates(db: State<'_, DbState>) -> Result<Vec<JsaTemplate>, AppError> {
+pub fn list_jsa_templates(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+) -> Result<Vec<JsaTemplate>, AppError> {
+    permissions::authorize(&session, "list_jsa_templates")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     jsa::list_templates(&conn).map_err(|e| AppError::Internal(e.to_string()))
 }
 
+#[tauri::command]
+pub fn get_jsa_template_steps(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    template_id: i64,
+) -> Result<Vec<JsaTemplateStep>, AppError> {
+    permissions::authorize(&session, "get_jsa_template_steps")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    jsa::get_template(&conn, template_id)
+        .map_err(|_| AppError::NotFound(format!("JSA template {template_id} not found")))?;
+    jsa::list_template_steps(&conn, template_id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn add_jsa_template_step(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: AddJsaTemplateStep,
+) -> Result<i64, AppError> {
+    use crate::validation;
+
+    let actor = Actor::from(&permissions::authorize(&session, "add_jsa_template_step")?);
+
+    validation::validate_not_empty(&data.task_description, "Task description")?;
+    validation::validate_string_length(&data.task_description, validation::MAX_DESCRIPTION_LENGTH, "Task description")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    jsa::add_template_step(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn add_jsa_template_hazard(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: AddJsaTemplateHazard,
+) -> Result<i64, AppError> {
+    use crate::validation;
+
+    let actor = Actor::from(&permissions::authorize(&session, "add_jsa_template_hazard")?);
+
+    validation::validate_not_empty(&data.hazard_description, "Hazard description")?;
+    validation::validate_string_length(&data.hazard_description, validation::MAX_DESCRIPTION_LENGTH, "Hazard description")?;
+    if let Some(ref severity) = data.severity {
+        validate_severity(severity)?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    jsa::add_template_hazard(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn add_jsa_template_control(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: AddJsaTemplateControl,
+) -> Result<i64, AppError> {
+    use crate::validation;
+
+    let actor = Actor::from(&permissions::authorize(&session, "add_jsa_template_control")?);
+
+    validate_control_type(&data.control_type)?;
+    validation::validate_not_empty(&data.control_description, "Control description")?;
+    validation::validate_string_length(&data.control_description, validation::MAX_DESCRIPTION_LENGTH, "Control description")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    jsa::add_template_control(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn delete_jsa_template_step(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_jsa_template_step")?);
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    jsa::delete_template_step(&conn, &actor, id).map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => AppError::NotFound(format!("JSA template step {id} not found")),
+        other => AppError::Internal(other.to_string()),
+    })
+}
+
 #[tauri::command]
 pub fn create_jsa_instance(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: CreateJsaInstance,
 ) -> Result<JsaInstance, AppError> {
     use crate::validation;
 
+    let actor = Actor::from(&permissions::authorize(&session, "create_jsa_instance")?);
+
     validation::validate_not_empty(&data.job_name, "Job name")?;
     validation::validate_not_empty(&data.prepared_by, "Prepared by")?;
     validation::validate_date_format(&data.job_date, "Job date")?;
 
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    jsa::create_instance(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    jsa::create_instance(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn get_jsa_instance(db: State<'_, DbState>, id: i64) -> Result<JsaInstance, AppError> {
+pub fn get_jsa_instance(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<JsaInstance, AppError> {
+    permissions::authorize(&session, "get_jsa_instance")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     jsa::get_instance(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
 }
@@ -36,52 +142,316 @@ pub fn get_jsa_instance(db: State<'_, DbState>, id: i64) -> Result<JsaInstance,
 #[tauri::command]
 pub fn list_jsa_instances(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
 ) -> Result<Vec<JsaInstance>, AppError> {
+    permissions::authorize(&session, "list_jsa_instances")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     jsa::list_instances(&conn, establishment_id).map_err(|e| AppError::Internal(e.to_string()))
 }
 
+/// Moves a JSA along draft → submitted → reviewed → approved → active →
+/// closed. Reviewing and approving sign the JSA with the caller's name.
 #[tauri::command]
 pub fn update_jsa_status(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     id: i64,
     status: String,
 ) -> Result<JsaInstance, AppError> {
-    let valid_statuses = vec!["draft", "reviewed", "approved", "in_progress", "completed"];
-    if !valid_statuses.contains(&status.as_str()) {
+    let current = permissions::authorize(&session, "update_jsa_status")?;
+    let actor = Actor::from(&current);
+
+    if !jsa::JSA_STATUSES.contains(&status.as_str()) {
         return Err(AppError::Validation(format!(
             "Invalid status: {}. Must be one of: {}",
             status,
-            valid_statuses.join(", ")
+            jsa::JSA_STATUSES.join(", ")
         )));
     }
 
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    jsa::update_instance_status(&conn, id, &status).map_err(|e| AppError::Internal(e.to_string()))
+    let existing = jsa::get_instance(&conn, id)
+        .map_err(|_| AppError::NotFound(format!("JSA {id} not found")))?;
+
+    let min_role = jsa::transition_role(&existing.status, &status).ok_or_else(|| {
+        let next = jsa::next_statuses(&existing.status);
+        AppError::Validation(format!(
+            "Cannot move a {} JSA to {}. Allowed: {}",
+            existing.status,
+            status,
+            if next.is_empty() { "none".to_string() } else { next.join(", ") }
+        ))
+    })?;
+    if !permissions::has_role(&current.role, min_role) {
+        return Err(AppError::Forbidden(format!(
+            "Moving a JSA to {status} requires the {min_role} role or higher"
+        )));
+    }
+
+    jsa::update_instance_status(&conn, &actor, id, &status, &current.display_name)
+        .map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn add_jsa_step(db: State<'_, DbState>, data: AddJsaStep) -> Result<JsaStep, AppError> {
+pub fn add_jsa_step(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: AddJsaStep,
+) -> Result<JsaStep, AppError> {
     use crate::validation;
 
+    let actor = Actor::from(&permissions::authorize(&session, "add_jsa_step")?);
+
     validation::validate_not_empty(&data.task_description, "Task description")?;
 
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    jsa::add_step(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    ensure_editable(jsa::status_for_instance(&conn, data.jsa_instance_id))?;
+    jsa::add_step(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub fn list_jsa_steps(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     jsa_instance_id: i64,
 ) -> Result<Vec<JsaStep>, AppError> {
+    permissions::authorize(&session, "list_jsa_steps")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     jsa::list_steps(&conn, jsa_instance_id).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn toggle_jsa_step(db: State<'_, DbState>, id: i64) -> Result<JsaStep, AppError> {
+pub fn toggle_jsa_step(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<JsaStep, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "toggle_jsa_step")?);
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    jsa::toggle_step_completion(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Rejects edits to a JSA's steps, hazards or controls once it is approved.
+fn ensure_editable(status: rusqlite::Result<String>) -> Result<(), AppError> {
+    let status = status.map_err(|e| AppError::Internal(e.to_string()))?;
+    if jsa::is_locked_status(&status) {
+        return Err(AppError::Validation(format!(
+            "This JSA is {status}; steps, hazards and controls can't be changed after approval"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_severity(severity: &str) -> Result<(), AppError> {
+    if !jsa::HAZARD_SEVERITIES.contains(&severity) {
+        return Err(AppError::Validation(format!(
+            "Invalid severity: {}. Must be one of: {}",
+            severity,
+            jsa::HAZARD_SEVERITIES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn validate_control_type(control_type: &str) -> Result<(), AppError> {
+    if jsa::control_rank(control_type).is_none() {
+        return Err(AppError::Validation(format!(
+            "Invalid control type: {}. Must be one of: {}",
+            control_type,
+            jsa::CONTROL_TYPES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+// Hazards
+
+#[tauri::command]
+pub fn add_jsa_hazard(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: AddJsaHazard,
+) -> Result<JsaHazard, AppError> {
+    use crate::validation;
+
+    let actor = Actor::from(&permissions::authorize(&session, "add_jsa_hazard")?);
+
+    validation::validate_not_empty(&data.hazard_description, "Hazard description")?;
+    validation::validate_string_length(&data.hazard_description, validation::MAX_DESCRIPTION_LENGTH, "Hazard description")?;
+    if let Some(ref severity) = data.severity {
+        validate_severity(severity)?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    ensure_editable(jsa::status_for_step(&conn, data.jsa_step_id))?;
+    jsa::add_hazard(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_jsa_hazards(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    jsa_step_id: i64,
+) -> Result<Vec<JsaHazard>, AppError> {
+    permissions::authorize(&session, "list_jsa_hazards")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    jsa::list_hazards(&conn, jsa_step_id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn update_jsa_hazard(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    data: UpdateJsaHazard,
+) -> Result<JsaHazard, AppError> {
+    use crate::validation;
+
+    let actor = Actor::from(&permissions::authorize(&session, "update_jsa_hazard")?);
+
+    if let Some(ref text) = data.hazard_description {
+        validation::validate_not_empty(text, "Hazard description")?;
+        validation::validate_string_length(text, validation::MAX_DESCRIPTION_LENGTH, "Hazard description")?;
+    }
+    if let Some(ref severity) = data.severity {
+        validate_severity(severity)?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    ensure_editable(jsa::status_for_hazard(&conn, id))?;
+    jsa::update_hazard(&conn, &actor, id, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn delete_jsa_hazard(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_jsa_hazard")?);
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    ensure_editable(jsa::status_for_hazard(&conn, id))?;
+    jsa::delete_hazard(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+// Controls
+
+#[tauri::command]
+pub fn add_jsa_control(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: AddJsaControl,
+) -> Result<JsaControl, AppError> {
+    use crate::validation;
+
+    let actor = Actor::from(&permissions::authorize(&session, "add_jsa_control")?);
+
+    validate_control_type(&data.control_type)?;
+    validation::validate_not_empty(&data.control_description, "Control description")?;
+    validation::validate_string_length(&data.control_description, validation::MAX_DESCRIPTION_LENGTH, "Control description")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    ensure_editable(jsa::status_for_hazard(&conn, data.jsa_hazard_id))?;
+    jsa::add_control(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_jsa_controls(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    jsa_hazard_id: i64,
+) -> Result<Vec<JsaControl>, AppError> {
+    permissions::authorize(&session, "list_jsa_controls")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    jsa::list_controls(&conn, jsa_hazard_id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn update_jsa_control(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    data: UpdateJsaControl,
+) -> Result<JsaControl, AppError> {
+    use crate::validation;
+
+    let actor = Actor::from(&permissions::authorize(&session, "update_jsa_control")?);
+
+    if let Some(ref control_type) = data.control_type {
+        validate_control_type(control_type)?;
+    }
+    if let Some(ref text) = data.control_description {
+        validation::validate_not_empty(text, "Control description")?;
+        validation::validate_string_length(text, validation::MAX_DESCRIPTION_LENGTH, "Control description")?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    ensure_editable(jsa::status_for_control(&conn, id))?;
+    jsa::update_control(&conn, &actor, id, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn delete_jsa_control(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_jsa_control")?);
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    ensure_editable(jsa::status_for_control(&conn, id))?;
+    jsa::delete_control(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Library hazards for the template's trade that the JSA doesn't cover yet.
+#[tauri::command]
+pub fn suggest_jsa_hazards(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    jsa_instance_id: i64,
+) -> Result<Vec<TradeHazard>, AppError> {
+    permissions::authorize(&session, "suggest_jsa_hazards")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    jsa::suggest_trade_hazards(&conn, jsa_instance_id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn add_suggested_jsa_hazard(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    jsa_step_id: i64,
+    trade_hazard_id: i64,
+    severity: Option<String>,
+) -> Result<JsaHazard, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "add_suggested_jsa_hazard")?);
+
+    if let Some(ref severity) = severity {
+        validate_severity(severity)?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    ensure_editable(jsa::status_for_step(&conn, jsa_step_id))?;
+    jsa::add_trade_hazard_to_step(&conn, &actor, jsa_step_id, trade_hazard_id, severity)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// High-severity hazards on a JSA that rely on PPE alone or have no controls.
+#[tauri::command]
+pub fn check_jsa_controls(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    jsa_instance_id: i64,
+) -> Result<Vec<ControlFinding>, AppError> {
+    permissions::authorize(&session, "check_jsa_controls")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    jsa::toggle_step_completion(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+    jsa::check_controls(&conn, jsa_instance_id).map_err(|e| AppError::Internal(e.to_string()))
 }
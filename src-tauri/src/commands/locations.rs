@@ -1,34 +1,72 @@
+use crate::commands::auth::SessionState;
+use crate::db::audit::Actor;
+use crate::db::incidents;
 use crate::db::locations::{
     self, CreateEstablishment, CreateLocation, Establishment, Location, UpdateEstablishment,
     UpdateLocation,
 };
 use crate::errors::AppError;
+use crate::permissions;
 use rusqlite::Connection;
 use std::sync::Mutex;
 use tauri::State;
 
 type DbState = Mutex<Connection>;
 
+fn validate_establishment_type(establishment_type: Option<&str>) -> Result<(), AppError> {
+    if let Some(t) = establishment_type {
+        if !locations::ESTABLISHMENT_TYPES.contains(&t) {
+            return Err(AppError::Validation(format!(
+                "Invalid establishment type: {}. Must be one of: {}",
+                t,
+                locations::ESTABLISHMENT_TYPES.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_case_number_format(format: Option<&str>) -> Result<(), AppError> {
+    match format {
+        Some(f) if !f.is_empty() => incidents::format_case_number(f, "2026", 1).map(|_| ()),
+        _ => Ok(()),
+    }
+}
+
 #[tauri::command]
 pub fn create_establishment(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: CreateEstablishment,
 ) -> Result<Establishment, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "create_establishment")?);
+
+    validate_establishment_type(data.establishment_type.as_deref())?;
+    validate_case_number_format(data.case_number_format.as_deref())?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    locations::create_establishment(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    locations::create_establishment(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub fn get_establishment(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     id: i64,
 ) -> Result<Establishment, AppError> {
+    permissions::authorize(&session, "get_establishment")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     locations::get_establishment(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn list_establishments(db: State<'_, DbState>) -> Result<Vec<Establishment>, AppError> {
+pub fn list_establishments(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+) -> Result<Vec<Establishment>, AppError> {
+    permissions::authorize(&session, "list_establishments")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     locations::list_establishments(&conn).map_err(|e| AppError::Internal(e.to_string()))
 }
@@ -36,30 +74,51 @@ pub fn list_establishments(db: State<'_, DbState>) -> Result<Vec<Establishment>,
 #[tauri::command]
 pub fn update_establishment(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     id: i64,
     data: UpdateEstablishment,
 ) -> Result<Establishment, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "update_establishment")?);
+
+    validate_establishment_type(data.establishment_type.as_deref())?;
+    validate_case_number_format(data.case_number_format.as_deref())?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    locations::update_establishment(&conn, id, data).map_err(|e| AppError::Internal(e.to_string()))
+    locations::update_establishment(&conn, &actor, id, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn delete_establishment(db: State<'_, DbState>, id: i64) -> Result<(), AppError> {
+pub fn delete_establishment(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_establishment")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    locations::delete_establishment(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+    locations::delete_establishment(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub fn create_location(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: CreateLocation,
 ) -> Result<Location, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "create_location")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    locations::create_location(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    locations::create_location(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn get_location(db: State<'_, DbState>, id: i64) -> Result<Location, AppError> {
+pub fn get_location(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<Location, AppError> {
+    permissions::authorize(&session, "get_location")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     locations::get_location(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
 }
@@ -67,8 +126,11 @@ pub fn get_location(db: State<'_, DbState>, id: i64) -> Result<Location, AppErro
 #[tauri::command]
 pub fn list_locations(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
 ) -> Result<Vec<Location>, AppError> {
+    permissions::authorize(&session, "list_locations")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     locations::list_locations(&conn, establishment_id).map_err(|e| AppError::Internal(e.to_string()))
 }
@@ -76,15 +138,24 @@ pub fn list_locations(
 #[tauri::command]
 pub fn update_location(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     id: i64,
     data: UpdateLocation,
 ) -> Result<Location, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "update_location")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    locations::update_location(&conn, id, data).map_err(|e| AppError::Internal(e.to_string()))
+    locations::update_location(&conn, &actor, id, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn delete_location(db: State<'_, DbState>, id: i64) -> Result<(), AppError> {
+pub fn delete_location(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_location")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    locations::delete_location(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+    locations::delete_location(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
 }
@@ -1,9 +1,18 @@
 pub mod attachments;
+pub mod audit;
+pub mod auth;
 pub mod dashboard;
+pub mod equipment;
 pub mod import;
 pub mod incidents;
+pub mod inspections;
 pub mod jsa;
 pub mod locations;
+pub mod near_miss;
 pub mod osha;
 pub mod rca;
+pub mod sync;
 pub mod toolbox;
+pub mod trade_hazards;
+pub mod training;
+pub mod trash;
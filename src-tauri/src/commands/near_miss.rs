@@ -0,0 +1,130 @@
+use crate::commands::auth::SessionState;
+use crate::db::audit::Actor;
+use crate::db::incidents::Incident;
+use crate::db::near_miss::{
+    self, CreateNearMiss, NearMissFilter, NearMissReport, PromoteNearMiss, TriageNearMiss,
+};
+use crate::errors::AppError;
+use crate::permissions;
+use rusqlite::Connection;
+use std::sync::Mutex;
+use tauri::State;
+
+type DbState = Mutex<Connection>;
+
+fn validate_severity(severity: &str) -> Result<(), AppError> {
+    if !near_miss::SEVERITIES.contains(&severity) {
+        return Err(AppError::Validation(format!(
+            "Invalid potential severity: {}. Must be one of: {}",
+            severity,
+            near_miss::SEVERITIES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn submit_near_miss(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: CreateNearMiss,
+) -> Result<NearMissReport, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "submit_near_miss")?;
+
+    validation::validate_date_format(&data.report_date, "Report date")?;
+    validation::validate_not_empty(&data.what_happened, "What happened")?;
+    validation::validate_string_length(&data.what_happened, validation::MAX_DESCRIPTION_LENGTH, "What happened")?;
+    validation::validate_not_empty(&data.what_could_have_happened, "What could have happened")?;
+    validation::validate_string_length(
+        &data.what_could_have_happened,
+        validation::MAX_DESCRIPTION_LENGTH,
+        "What could have happened",
+    )?;
+    if let Some(ref severity) = data.potential_severity {
+        validate_severity(severity)?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    near_miss::submit_report(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn get_near_miss(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<NearMissReport, AppError> {
+    permissions::authorize(&session, "get_near_miss")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    near_miss::get_report(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_near_misses(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    filter: NearMissFilter,
+) -> Result<Vec<NearMissReport>, AppError> {
+    permissions::authorize(&session, "list_near_misses")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    near_miss::list_reports(&conn, filter).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn triage_near_miss(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    data: TriageNearMiss,
+) -> Result<NearMissReport, AppError> {
+    permissions::authorize(&session, "triage_near_miss")?;
+
+    if let Some(ref severity) = data.potential_severity {
+        validate_severity(severity)?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    near_miss::triage_report(&conn, id, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn close_near_miss(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    corrective_action_taken: Option<String>,
+) -> Result<NearMissReport, AppError> {
+    permissions::authorize(&session, "close_near_miss")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    near_miss::close_report(&conn, id, corrective_action_taken.as_deref())
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn promote_near_miss_to_incident(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    data: PromoteNearMiss,
+) -> Result<Incident, AppError> {
+    use crate::validation;
+
+    let actor = Actor::from(&permissions::authorize(&session, "promote_near_miss_to_incident")?);
+
+    validation::validate_not_empty(&data.employee_name, "Employee name")?;
+    validation::validate_string_length(&data.employee_name, validation::MAX_NAME_LENGTH, "Employee name")?;
+    if let Some(days) = data.days_away_count {
+        validation::validate_days_count(days, "Days away from work")?;
+    }
+    if let Some(days) = data.days_restricted_count {
+        validation::validate_days_count(days, "Days of restricted work")?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    near_miss::promote_to_incident(&conn, &actor, id, data).map_err(|e| AppError::Internal(e.to_string()))
+}
@@ -1,19 +1,39 @@
+use crate::commands::auth::SessionState;
+use crate::db::audit::Actor;
+use crate::db::locations;
 use crate::db::osha::{
-    self, AnnualStats, Osha300ASummary, Osha300Row, Osha301Report, UpsertAnnualStats,
+    self, AnnualStats, Osha300ASummary, Osha300Row, Osha301Report, OshaLogAmendment,
+    PrivacyCaseEntry, UpsertAnnualStats,
 };
 use crate::errors::AppError;
+use crate::pdf;
+use crate::permissions;
+use crate::redaction::Audience;
+use chrono::Datelike;
 use rusqlite::Connection;
+use serde::Serialize;
 use std::sync::Mutex;
 use tauri::State;
 
 type DbState = Mutex<Connection>;
 
+#[derive(Debug, Serialize)]
+pub struct ItaExport {
+    pub summary_path: String,
+    pub case_data_path: Option<String>,
+    pub establishments: usize,
+    pub cases: usize,
+}
+
 #[tauri::command]
 pub fn get_osha_300_log(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
     year: i64,
 ) -> Result<Vec<Osha300Row>, AppError> {
+    permissions::authorize(&session, "get_osha_300_log")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     osha::get_osha_300_log(&conn, establishment_id, year)
         .map_err(|e| AppError::Internal(e.to_string()))
@@ -22,9 +42,12 @@ pub fn get_osha_300_log(
 #[tauri::command]
 pub fn get_osha_300a_summary(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
     year: i64,
 ) -> Result<Osha300ASummary, AppError> {
+    permissions::authorize(&session, "get_osha_300a_summary")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     osha::get_osha_300a_summary(&conn, establishment_id, year)
         .map_err(|e| AppError::Internal(e.to_string()))
@@ -33,22 +56,44 @@ pub fn get_osha_300a_summary(
 #[tauri::command]
 pub fn get_osha_301_report(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     incident_id: i64,
 ) -> Result<Osha301Report, AppError> {
+    let session = permissions::authorize(&session, "get_osha_301_report")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    osha::get_osha_301_report(&conn, incident_id)
+    osha::get_osha_301_report(&conn, incident_id, Audience::for_role(&session.role))
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// The confidential list of privacy case numbers and employee names that
+/// goes with the 300 log. Never posted.
+#[tauri::command]
+pub fn get_privacy_case_list(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    establishment_id: i64,
+    year: i64,
+) -> Result<Vec<PrivacyCaseEntry>, AppError> {
+    permissions::authorize(&session, "get_privacy_case_list")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    osha::get_privacy_case_list(&conn, establishment_id, year)
         .map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub fn export_osha_300_csv(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
     year: i64,
     establishment_name: String,
 ) -> Result<String, AppError> {
     use crate::validation;
 
+    permissions::authorize(&session, "export_osha_300_csv")?;
+
     // Validate year
     validation::validate_year(year)?;
 
@@ -75,7 +120,7 @@ pub fn export_osha_300_csv(
 
     for row in &rows {
         wtr.write_record([
-            &row.case_number.to_string(),
+            &row.case_label,
             &row.employee_name,
             &row.job_title,
             &row.incident_date,
@@ -100,13 +145,194 @@ pub fn export_osha_300_csv(
     Ok(safe_path.to_string_lossy().to_string())
 }
 
+/// Renders the Form 300 log as a printable PDF in Downloads.
+#[tauri::command]
+pub fn export_osha_300_pdf(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    establishment_id: i64,
+    year: i64,
+) -> Result<String, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "export_osha_300_pdf")?;
+
+    validation::validate_year(year)?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let establishment = locations::get_establishment(&conn, establishment_id)
+        .map_err(|_| AppError::NotFound(format!("Establishment {establishment_id} not found")))?;
+    let rows = osha::get_osha_300_log(&conn, establishment_id, year)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let header = pdf::osha::EstablishmentHeader {
+        name: establishment.name,
+        city: establishment.city.unwrap_or_default(),
+        state: establishment.state.unwrap_or_default(),
+    };
+    let doc = pdf::osha::render_300(&header, year, &rows);
+    let path = validation::safe_export_path(&format!("OSHA_300_{}_{}", header.name, year), "pdf")?;
+    doc.save(&path)?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Renders the Form 300A summary, including the certification block, as a
+/// printable PDF in Downloads.
+#[tauri::command]
+pub fn export_osha_300a_pdf(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    establishment_id: i64,
+    year: i64,
+) -> Result<String, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "export_osha_300a_pdf")?;
+
+    validation::validate_year(year)?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let summary = osha::get_osha_300a_summary(&conn, establishment_id, year)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let doc = pdf::osha::render_300a(&summary);
+    let path = validation::safe_export_path(
+        &format!("OSHA_300A_{}_{}", summary.establishment_name, year),
+        "pdf",
+    )?;
+    doc.save(&path)?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Renders the Form 301 incident report for one case as a printable PDF in
+/// Downloads.
+#[tauri::command]
+pub fn export_osha_301_pdf(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    incident_id: i64,
+) -> Result<String, AppError> {
+    use crate::validation;
+
+    let session = permissions::authorize(&session, "export_osha_301_pdf")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let report = osha::get_osha_301_report(&conn, incident_id, Audience::for_role(&session.role))
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let doc = pdf::osha::render_301(&report);
+    let path = validation::safe_export_path(&format!("OSHA_301_Case_{}", report.case_number), "pdf")?;
+    doc.save(&path)?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Problems that would block an ITA submission for each establishment.
+#[tauri::command]
+pub fn validate_osha_ita_submission(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    establishment_ids: Vec<i64>,
+    year: i64,
+    include_case_data: bool,
+) -> Result<Vec<String>, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "validate_osha_ita_submission")?;
+
+    validation::validate_year(year)?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let mut problems = Vec::new();
+    for id in establishment_ids {
+        problems.extend(
+            osha::validate_ita_submission(&conn, id, year, include_case_data)
+                .map_err(|e| AppError::Internal(e.to_string()))?,
+        );
+    }
+    Ok(problems)
+}
+
+/// Writes the ITA bulk-upload CSVs to Downloads: the 300A summary with one
+/// row per establishment and, when `include_case_data` is set, the 300/301
+/// case data. Nothing is written unless every establishment validates.
+#[tauri::command]
+pub fn export_osha_ita_csv(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    establishment_ids: Vec<i64>,
+    year: i64,
+    include_case_data: bool,
+) -> Result<ItaExport, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "export_osha_ita_csv")?;
+
+    validation::validate_year(year)?;
+    if establishment_ids.is_empty() {
+        return Err(AppError::Validation("Select at least one establishment".into()));
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let mut problems = Vec::new();
+    for &id in &establishment_ids {
+        problems.extend(
+            osha::validate_ita_submission(&conn, id, year, include_case_data)
+                .map_err(|e| AppError::Internal(e.to_string()))?,
+        );
+    }
+    if !problems.is_empty() {
+        return Err(AppError::Validation(format!(
+            "Fix these before submitting to OSHA: {}",
+            problems.join("; ")
+        )));
+    }
+
+    let summary_path = validation::safe_export_path(&format!("OSHA_ITA_300A_{year}"), "csv")?;
+    let mut wtr = csv::Writer::from_path(&summary_path)?;
+    for &id in &establishment_ids {
+        let record = osha::ita_summary_record(&conn, id, year)
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        wtr.serialize(record)?;
+    }
+    wtr.flush()?;
+
+    let mut cases = 0;
+    let case_data_path = if include_case_data {
+        let path = validation::safe_export_path(&format!("OSHA_ITA_Case_Data_{year}"), "csv")?;
+        let mut wtr = csv::Writer::from_path(&path)?;
+        for &id in &establishment_ids {
+            for record in osha::ita_case_records(&conn, id, year)
+                .map_err(|e| AppError::Internal(e.to_string()))?
+            {
+                wtr.serialize(record)?;
+                cases += 1;
+            }
+        }
+        wtr.flush()?;
+        Some(path.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    Ok(ItaExport {
+        summary_path: summary_path.to_string_lossy().to_string(),
+        case_data_path,
+        establishments: establishment_ids.len(),
+        cases,
+    })
+}
+
 #[tauri::command]
 pub fn upsert_annual_stats(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: UpsertAnnualStats,
 ) -> Result<AnnualStats, AppError> {
     use crate::validation;
 
+    let actor = Actor::from(&permissions::authorize(&session, "upsert_annual_stats")?);
+
     // Validate year
     validation::validate_year(data.year)?;
 
@@ -115,20 +341,108 @@ pub fn upsert_annual_stats(
     validation::validate_hours_worked(data.total_hours_worked)?;
 
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    osha::upsert_annual_stats(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    let certified = osha::is_certified(&conn, data.establishment_id, data.year)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    if certified {
+        return Err(AppError::Validation(format!(
+            "The {} log has been certified and its annual figures can no longer be changed",
+            data.year
+        )));
+    }
+    osha::upsert_annual_stats(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub fn get_annual_stats(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
     year: i64,
 ) -> Result<Option<AnnualStats>, AppError> {
+    permissions::authorize(&session, "get_annual_stats")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     osha::get_annual_stats(&conn, establishment_id, year)
         .map_err(|e| AppError::Internal(e.to_string()))
 }
 
+#[tauri::command]
+pub fn close_osha_log(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    establishment_id: i64,
+    year: i64,
+) -> Result<AnnualStats, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "close_osha_log")?);
+
+    // The log is closed at year end, 1904.32(a)
+    let current_year = chrono::Local::now().date_naive().year() as i64;
+    if year >= current_year {
+        return Err(AppError::Validation(format!(
+            "The {year} log cannot be closed until the year has ended"
+        )));
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let stats = osha::get_annual_stats(&conn, establishment_id, year)
+        .map_err(|e| AppError::Internal(e.to_string()))?
+        .ok_or_else(|| {
+            AppError::Validation(format!(
+                "Enter the {year} average employees and hours worked before closing the log"
+            ))
+        })?;
+    if stats.closed_at.is_some() {
+        return Err(AppError::Validation(format!("The {year} log is already closed")));
+    }
+
+    osha::close_log(&conn, &actor, establishment_id, year)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn certify_osha_log(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    establishment_id: i64,
+    year: i64,
+) -> Result<AnnualStats, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "certify_osha_log")?);
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let stats = osha::get_annual_stats(&conn, establishment_id, year)
+        .map_err(|e| AppError::Internal(e.to_string()))?
+        .ok_or_else(|| AppError::Validation(format!("The {year} log has not been closed")))?;
+    if stats.closed_at.is_none() {
+        return Err(AppError::Validation(format!("The {year} log has not been closed")));
+    }
+    if stats.certified_at.is_some() {
+        return Err(AppError::Validation(format!("The {year} log is already certified")));
+    }
+    let blank = |v: &Option<String>| v.as_deref().map(str::trim).unwrap_or("").is_empty();
+    if blank(&stats.certifier_name) || blank(&stats.certifier_title) {
+        return Err(AppError::Validation(
+            "A company executive's name and title are required to certify the summary".to_string(),
+        ));
+    }
+
+    osha::certify_log(&conn, &actor, establishment_id, year)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_osha_log_amendments(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    establishment_id: i64,
+    year: i64,
+) -> Result<Vec<OshaLogAmendment>, AppError> {
+    permissions::authorize(&session, "list_osha_log_amendments")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    osha::list_amendments(&conn, establishment_id, year)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
 fn bool_to_x(v: bool) -> String {
     if v { "X".to_string() } else { String::new() }
 }
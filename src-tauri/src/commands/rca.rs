@@ -1,9 +1,12 @@
+use crate::commands::auth::SessionState;
+use crate::db::audit::Actor;
 use crate::db::rca::{
     self, CorrectiveAction, CreateCorrectiveAction, CreateFishboneCategory, CreateFishboneCause,
     CreateFiveWhysStep, CreateRcaSession, FishboneCategory, FishboneCause, FiveWhysStep,
     RcaSession, UpdateCorrectiveAction,
 };
 use crate::errors::AppError;
+use crate::permissions;
 use rusqlite::Connection;
 use std::sync::Mutex;
 use tauri::State;
@@ -15,14 +18,23 @@ type DbState = Mutex<Connection>;
 #[tauri::command]
 pub fn create_rca_session(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: CreateRcaSession,
 ) -> Result<RcaSession, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "create_rca_session")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    rca::create_rca_session(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    rca::create_rca_session(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn get_rca_session(db: State<'_, DbState>, id: i64) -> Result<RcaSession, AppError> {
+pub fn get_rca_session(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<RcaSession, AppError> {
+    permissions::authorize(&session, "get_rca_session")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     rca::get_rca_session(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
 }
@@ -30,8 +42,11 @@ pub fn get_rca_session(db: State<'_, DbState>, id: i64) -> Result<RcaSession, Ap
 #[tauri::command]
 pub fn list_rca_sessions(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     incident_id: i64,
 ) -> Result<Vec<RcaSession>, AppError> {
+    permissions::authorize(&session, "list_rca_sessions")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     rca::list_rca_sessions(&conn, incident_id).map_err(|e| AppError::Internal(e.to_string()))
 }
@@ -39,18 +54,27 @@ pub fn list_rca_sessions(
 #[tauri::command]
 pub fn complete_rca_session(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     id: i64,
     root_cause_summary: String,
 ) -> Result<RcaSession, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "complete_rca_session")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    rca::complete_rca_session(&conn, id, &root_cause_summary)
+    rca::complete_rca_session(&conn, &actor, id, &root_cause_summary)
         .map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn delete_rca_session(db: State<'_, DbState>, id: i64) -> Result<(), AppError> {
+pub fn delete_rca_session(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_rca_session")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    rca::delete_rca_session(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+    rca::delete_rca_session(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 // ── Five Whys ──
@@ -58,17 +82,23 @@ pub fn delete_rca_session(db: State<'_, DbState>, id: i64) -> Result<(), AppErro
 #[tauri::command]
 pub fn add_five_whys_step(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: CreateFiveWhysStep,
 ) -> Result<FiveWhysStep, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "add_five_whys_step")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    rca::add_five_whys_step(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    rca::add_five_whys_step(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub fn list_five_whys_steps(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     rca_session_id: i64,
 ) -> Result<Vec<FiveWhysStep>, AppError> {
+    permissions::authorize(&session, "list_five_whys_steps")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     rca::list_five_whys_steps(&conn, rca_session_id)
         .map_err(|e| AppError::Internal(e.to_string()))
@@ -77,12 +107,15 @@ pub fn list_five_whys_steps(
 #[tauri::command]
 pub fn update_five_whys_step(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     id: i64,
     question: String,
     answer: String,
 ) -> Result<FiveWhysStep, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "update_five_whys_step")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    rca::update_five_whys_step(&conn, id, &question, &answer)
+    rca::update_five_whys_step(&conn, &actor, id, &question, &answer)
         .map_err(|e| AppError::Internal(e.to_string()))
 }
 
@@ -91,17 +124,23 @@ pub fn update_five_whys_step(
 #[tauri::command]
 pub fn add_fishbone_category(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: CreateFishboneCategory,
 ) -> Result<FishboneCategory, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "add_fishbone_category")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    rca::add_fishbone_category(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    rca::add_fishbone_category(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub fn list_fishbone_categories(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     rca_session_id: i64,
 ) -> Result<Vec<FishboneCategory>, AppError> {
+    permissions::authorize(&session, "list_fishbone_categories")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     rca::list_fishbone_categories(&conn, rca_session_id)
         .map_err(|e| AppError::Internal(e.to_string()))
@@ -110,28 +149,40 @@ pub fn list_fishbone_categories(
 #[tauri::command]
 pub fn add_fishbone_cause(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: CreateFishboneCause,
 ) -> Result<FishboneCause, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "add_fishbone_cause")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    rca::add_fishbone_cause(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    rca::add_fishbone_cause(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub fn update_fishbone_cause(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     id: i64,
     cause_text: Option<String>,
     is_root_cause: Option<bool>,
 ) -> Result<FishboneCause, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "update_fishbone_cause")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    rca::update_fishbone_cause(&conn, id, cause_text.as_deref(), is_root_cause)
+    rca::update_fishbone_cause(&conn, &actor, id, cause_text.as_deref(), is_root_cause)
         .map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn delete_fishbone_cause(db: State<'_, DbState>, id: i64) -> Result<(), AppError> {
+pub fn delete_fishbone_cause(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_fishbone_cause")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    rca::delete_fishbone_cause(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+    rca::delete_fishbone_cause(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 // ── Corrective Actions ──
@@ -139,17 +190,23 @@ pub fn delete_fishbone_cause(db: State<'_, DbState>, id: i64) -> Result<(), AppE
 #[tauri::command]
 pub fn create_corrective_action(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: CreateCorrectiveAction,
 ) -> Result<CorrectiveAction, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "create_corrective_action")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    rca::create_corrective_action(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    rca::create_corrective_action(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub fn list_corrective_actions(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     incident_id: i64,
 ) -> Result<Vec<CorrectiveAction>, AppError> {
+    permissions::authorize(&session, "list_corrective_actions")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     rca::list_corrective_actions(&conn, incident_id)
         .map_err(|e| AppError::Internal(e.to_string()))
@@ -158,15 +215,24 @@ pub fn list_corrective_actions(
 #[tauri::command]
 pub fn update_corrective_action(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     id: i64,
     data: UpdateCorrectiveAction,
 ) -> Result<CorrectiveAction, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "update_corrective_action")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    rca::update_corrective_action(&conn, id, data).map_err(|e| AppError::Internal(e.to_string()))
+    rca::update_corrective_action(&conn, &actor, id, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn delete_corrective_action(db: State<'_, DbState>, id: i64) -> Result<(), AppError> {
+pub fn delete_corrective_action(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_corrective_action")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    rca::delete_corrective_action(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+    rca::delete_corrective_action(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
 }
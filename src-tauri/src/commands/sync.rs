@@ -0,0 +1,188 @@
+use crate::commands::auth::SessionState;
+use crate::db::audit::Actor;
+use crate::db::sync::{self, SyncConflict, SyncFailure, SyncStatus};
+use crate::errors::AppError;
+use crate::permissions;
+use crate::sync_client;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::State;
+
+type DbState = Mutex<Connection>;
+
+#[derive(Debug, Serialize, Default)]
+pub struct SyncReport {
+    pub pushed: usize,
+    pub pulled: usize,
+    pub applied: usize,
+    pub merged: usize,
+    pub conflicts: usize,
+    pub failed: usize,
+    pub errors: Vec<String>,
+}
+
+#[tauri::command]
+pub fn get_sync_status(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+) -> Result<SyncStatus, AppError> {
+    permissions::authorize(&session, "get_sync_status")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    sync::get_sync_status(&conn).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn set_sync_endpoint(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    endpoint: Option<String>,
+) -> Result<SyncStatus, AppError> {
+    permissions::authorize(&session, "set_sync_endpoint")?;
+
+    let endpoint = endpoint.map(|e| e.trim().to_string()).filter(|e| !e.is_empty());
+    if let Some(ref url) = endpoint {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(AppError::Validation(format!(
+                "Sync endpoint must be an http:// or https:// URL (got: {url})"
+            )));
+        }
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    sync::set_sync_endpoint(&conn, endpoint.as_deref()).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Pushes local changes, then pulls and applies everything other devices
+/// have pushed since the last pull. The DB lock is released during HTTP calls
+/// so the app stays usable while syncing.
+#[tauri::command]
+pub fn sync_now(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+) -> Result<SyncReport, AppError> {
+    permissions::authorize(&session, "sync_now")?;
+
+    let (status, pending) = {
+        let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+        let status = sync::get_sync_status(&conn).map_err(|e| AppError::Internal(e.to_string()))?;
+        let pending = sync::pending_changes(&conn).map_err(|e| AppError::Internal(e.to_string()))?;
+        (status, pending)
+    };
+    let endpoint = status
+        .sync_endpoint
+        .ok_or_else(|| AppError::Validation("No sync endpoint configured".into()))?;
+
+    let mut report = SyncReport::default();
+
+    if !pending.changes.is_empty() {
+        report.pushed = sync_client::push(&endpoint, &status.device_id, &pending.changes)
+            .map_err(|e| AppError::Internal(format!("{e:#}")))?;
+    }
+    {
+        let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+        sync::mark_pushed(&conn, &pending).map_err(|e| AppError::Internal(e.to_string()))?;
+    }
+
+    let mut since = status.last_pull_seq;
+    loop {
+        let batch = sync_client::pull(&endpoint, &status.device_id, since)
+            .map_err(|e| AppError::Internal(format!("{e:#}")))?;
+        let changes: Vec<sync::Change> = batch.changes.into_iter().map(|c| c.change).collect();
+        report.pulled += changes.len();
+
+        let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+        let applied = sync::apply_remote_changes(&conn, &changes)
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        // Changes that failed are kept locally for retry, so the cursor moves on
+        sync::set_last_pull_seq(&conn, batch.last_seq).map_err(|e| AppError::Internal(e.to_string()))?;
+        report.applied += applied.applied;
+        report.merged += applied.merged;
+        report.conflicts += applied.conflicts;
+        report.failed += applied.failed;
+        report.errors.extend(applied.errors);
+
+        since = batch.last_seq;
+        if !batch.has_more {
+            break;
+        }
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    sync::mark_sync_completed(&conn).map_err(|e| AppError::Internal(e.to_string()))?;
+    Ok(report)
+}
+
+// ── Conflicts ──
+
+#[tauri::command]
+pub fn list_sync_conflicts(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    status: Option<String>,
+) -> Result<Vec<SyncConflict>, AppError> {
+    permissions::authorize(&session, "list_sync_conflicts")?;
+
+    if let Some(ref s) = status {
+        if s != "open" && s != "resolved" {
+            return Err(AppError::Validation(format!(
+                "Invalid conflict status: {s}. Must be open or resolved"
+            )));
+        }
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    sync::list_conflicts(&conn, status.as_deref()).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// `resolution` is `local`, `remote` or `custom`; `value` is required for
+/// `custom` and ignored otherwise.
+#[tauri::command]
+pub fn resolve_sync_conflict(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    resolution: String,
+    value: Option<serde_json::Value>,
+) -> Result<SyncConflict, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "resolve_sync_conflict")?);
+
+    if !sync::RESOLUTIONS.contains(&resolution.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Invalid resolution: {resolution}. Must be one of: {}",
+            sync::RESOLUTIONS.join(", ")
+        )));
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    sync::resolve_conflict(&conn, &actor, id, &resolution, value)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+// ── Failed changes ──
+
+#[tauri::command]
+pub fn list_sync_failures(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+) -> Result<Vec<SyncFailure>, AppError> {
+    permissions::authorize(&session, "list_sync_failures")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    sync::list_failed_changes(&conn).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Gives up on a remote change that keeps failing, releasing the changes
+/// queued behind it for the same record.
+#[tauri::command]
+pub fn discard_sync_failure(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "discard_sync_failure")?);
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    sync::discard_failed_change(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
+}
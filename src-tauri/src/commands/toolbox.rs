@@ -1,8 +1,11 @@
+use crate::commands::auth::SessionState;
+use crate::db::audit::Actor;
 use crate::db::toolbox::{
     self, AddAttendee, CreateToolboxTalk, SignAttendee, ToolboxTalk, ToolboxTalkAttendee,
     ToolboxTalkTopic,
 };
 use crate::errors::AppError;
+use crate::permissions;
 use rusqlite::Connection;
 use std::sync::Mutex;
 use tauri::State;
@@ -13,14 +16,23 @@ type DbState = Mutex<Connection>;
 #[tauri::command]
 pub fn list_toolbox_topics(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     include_inactive: bool,
 ) -> Result<Vec<ToolboxTalkTopic>, AppError> {
+    permissions::authorize(&session, "list_toolbox_topics")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     toolbox::list_topics(&conn, include_inactive).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn get_toolbox_topic(db: State<'_, DbState>, id: i64) -> Result<ToolboxTalkTopic, AppError> {
+pub fn get_toolbox_topic(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<ToolboxTalkTopic, AppError> {
+    permissions::authorize(&session, "get_toolbox_topic")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     toolbox::get_topic(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
 }
@@ -29,20 +41,29 @@ pub fn get_toolbox_topic(db: State<'_, DbState>, id: i64) -> Result<ToolboxTalkT
 #[tauri::command]
 pub fn create_toolbox_talk(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: CreateToolboxTalk,
 ) -> Result<ToolboxTalk, AppError> {
     use crate::validation;
 
+    let actor = Actor::from(&permissions::authorize(&session, "create_toolbox_talk")?);
+
     validation::validate_not_empty(&data.title, "Title")?;
     validation::validate_not_empty(&data.conducted_by, "Conducted by")?;
     validation::validate_date_format(&data.date, "Date")?;
 
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    toolbox::create_talk(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    toolbox::create_talk(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn get_toolbox_talk(db: State<'_, DbState>, id: i64) -> Result<ToolboxTalk, AppError> {
+pub fn get_toolbox_talk(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<ToolboxTalk, AppError> {
+    permissions::authorize(&session, "get_toolbox_talk")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     toolbox::get_talk(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
 }
@@ -50,37 +71,71 @@ pub fn get_toolbox_talk(db: State<'_, DbState>, id: i64) -> Result<ToolboxTalk,
 #[tauri::command]
 pub fn list_toolbox_talks(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     establishment_id: i64,
 ) -> Result<Vec<ToolboxTalk>, AppError> {
+    permissions::authorize(&session, "list_toolbox_talks")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     toolbox::list_talks(&conn, establishment_id).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn complete_toolbox_talk(db: State<'_, DbState>, talk_id: i64) -> Result<ToolboxTalk, AppError> {
+pub fn complete_toolbox_talk(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    talk_id: i64,
+) -> Result<ToolboxTalk, AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "complete_toolbox_talk")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    toolbox::complete_talk(&conn, talk_id).map_err(|e| AppError::Internal(e.to_string()))
+    toolbox::complete_talk(&conn, &actor, talk_id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn delete_toolbox_talk(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_toolbox_talk")?);
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let talk = toolbox::get_talk(&conn, id).map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => AppError::NotFound(format!("Toolbox talk {id} not found")),
+        e => AppError::Internal(e.to_string()),
+    })?;
+    if talk.deleted_at.is_some() {
+        return Err(AppError::Validation(format!("Toolbox talk {id} is already in the trash")));
+    }
+    toolbox::delete_talk(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 // Attendees
 #[tauri::command]
 pub fn add_toolbox_attendee(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: AddAttendee,
 ) -> Result<ToolboxTalkAttendee, AppError> {
     use crate::validation;
 
+    let actor = Actor::from(&permissions::authorize(&session, "add_toolbox_attendee")?);
+
     validation::validate_not_empty(&data.employee_name, "Employee name")?;
 
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    toolbox::add_attendee(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    toolbox::add_attendee(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
 pub fn list_toolbox_attendees(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     talk_id: i64,
 ) -> Result<Vec<ToolboxTalkAttendee>, AppError> {
+    permissions::authorize(&session, "list_toolbox_attendees")?;
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
     toolbox::list_attendees(&conn, talk_id).map_err(|e| AppError::Internal(e.to_string()))
 }
@@ -88,18 +143,27 @@ pub fn list_toolbox_attendees(
 #[tauri::command]
 pub fn sign_toolbox_attendee(
     db: State<'_, DbState>,
+    session: State<'_, SessionState>,
     data: SignAttendee,
 ) -> Result<ToolboxTalkAttendee, AppError> {
     use crate::validation;
 
+    let actor = Actor::from(&permissions::authorize(&session, "sign_toolbox_attendee")?);
+
     validation::validate_not_empty(&data.signature_data, "Signature data")?;
 
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    toolbox::sign_attendee(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+    toolbox::sign_attendee(&conn, &actor, data).map_err(|e| AppError::Internal(e.to_string()))
 }
 
 #[tauri::command]
-pub fn delete_toolbox_attendee(db: State<'_, DbState>, id: i64) -> Result<(), AppError> {
+pub fn delete_toolbox_attendee(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "delete_toolbox_attendee")?);
+
     let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
-    toolbox::delete_attendee(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+    toolbox::delete_attendee(&conn, &actor, id).map_err(|e| AppError::Internal(e.to_string()))
 }
@@ -0,0 +1,192 @@
+use crate::commands::auth::SessionState;
+use crate::db::trade_hazards::{
+    self, CreateTradeHazard, HazardLibrary, LibraryImportResult, TradeHazard, TradeHazardFilter,
+    UpdateTradeHazard,
+};
+use crate::errors::AppError;
+use crate::permissions;
+use rusqlite::Connection;
+use std::sync::Mutex;
+use tauri::State;
+
+type DbState = Mutex<Connection>;
+
+fn validate_controls(controls: &[String]) -> Result<(), AppError> {
+    use crate::validation;
+
+    for control in controls {
+        validation::validate_string_length(control, validation::MAX_NAME_LENGTH, "Recommended control")?;
+    }
+    Ok(())
+}
+
+// ── Hazards ──
+
+#[tauri::command]
+pub fn list_trade_hazards(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    filter: TradeHazardFilter,
+) -> Result<Vec<TradeHazard>, AppError> {
+    permissions::authorize(&session, "list_trade_hazards")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    trade_hazards::list_hazards(&conn, filter).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Free-text search across all trades, optionally narrowed to one.
+#[tauri::command]
+pub fn search_trade_hazards(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    query: String,
+    trade: Option<String>,
+) -> Result<Vec<TradeHazard>, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "search_trade_hazards")?;
+
+    validation::validate_not_empty(&query, "Search text")?;
+    validation::validate_string_length(&query, validation::MAX_NAME_LENGTH, "Search text")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    trade_hazards::list_hazards(&conn, TradeHazardFilter {
+        trade,
+        search: Some(query),
+        ..Default::default()
+    })
+    .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_hazard_trades(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+) -> Result<Vec<String>, AppError> {
+    permissions::authorize(&session, "list_hazard_trades")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    trade_hazards::list_trades(&conn).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn get_trade_hazard(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<TradeHazard, AppError> {
+    permissions::authorize(&session, "get_trade_hazard")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    trade_hazards::get_hazard(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn create_trade_hazard(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: CreateTradeHazard,
+) -> Result<TradeHazard, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "create_trade_hazard")?;
+
+    validation::validate_not_empty(&data.trade, "Trade")?;
+    validation::validate_string_length(&data.trade, validation::MAX_NAME_LENGTH, "Trade")?;
+    validation::validate_not_empty(&data.hazard_name, "Hazard name")?;
+    validation::validate_string_length(&data.hazard_name, validation::MAX_NAME_LENGTH, "Hazard name")?;
+    if let Some(ref text) = data.hazard_description {
+        validation::validate_string_length(text, validation::MAX_DESCRIPTION_LENGTH, "Hazard description")?;
+    }
+    validate_controls(&data.recommended_controls)?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    trade_hazards::create_hazard(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn update_trade_hazard(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    data: UpdateTradeHazard,
+) -> Result<TradeHazard, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "update_trade_hazard")?;
+
+    if let Some(ref trade) = data.trade {
+        validation::validate_not_empty(trade, "Trade")?;
+        validation::validate_string_length(trade, validation::MAX_NAME_LENGTH, "Trade")?;
+    }
+    if let Some(ref name) = data.hazard_name {
+        validation::validate_not_empty(name, "Hazard name")?;
+        validation::validate_string_length(name, validation::MAX_NAME_LENGTH, "Hazard name")?;
+    }
+    if let Some(ref text) = data.hazard_description {
+        validation::validate_string_length(text, validation::MAX_DESCRIPTION_LENGTH, "Hazard description")?;
+    }
+    if let Some(ref controls) = data.recommended_controls {
+        validate_controls(controls)?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    trade_hazards::update_hazard(&conn, id, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn delete_trade_hazard(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    permissions::authorize(&session, "delete_trade_hazard")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    trade_hazards::delete_hazard(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+// ── Library ──
+
+/// Writes the hazard library to a JSON file in Downloads and returns its path.
+#[tauri::command]
+pub fn export_trade_hazard_library(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    include_inactive: bool,
+) -> Result<String, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "export_trade_hazard_library")?;
+
+    let safe_path = validation::safe_export_path("Trade_Hazard_Library", "json")?;
+
+    let library = {
+        let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+        trade_hazards::export_library(&conn, include_inactive)
+            .map_err(|e| AppError::Internal(e.to_string()))?
+    };
+
+    let json = serde_json::to_string_pretty(&library).map_err(|e| AppError::Internal(e.to_string()))?;
+    std::fs::write(&safe_path, json)?;
+    Ok(safe_path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub fn import_trade_hazard_library(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    file_path: String,
+    deactivate_missing: bool,
+) -> Result<LibraryImportResult, AppError> {
+    permissions::authorize(&session, "import_trade_hazard_library")?;
+
+    let text = std::fs::read_to_string(&file_path)
+        .map_err(|e| AppError::Internal(format!("Failed to open hazard library: {e}")))?;
+    let library: HazardLibrary = serde_json::from_str(&text)
+        .map_err(|e| AppError::Validation(format!("Invalid hazard library file: {e}")))?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    trade_hazards::import_library(&conn, &library, deactivate_missing)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
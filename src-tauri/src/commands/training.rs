@@ -0,0 +1,224 @@
+use crate::commands::auth::SessionState;
+use crate::db::training::{
+    self, CreateTrainingCourse, CreateTrainingRecord, EmployeeCompliance, TrainingCourse,
+    TrainingRecord, TrainingRecordFilter, UpdateTrainingCourse, UpdateTrainingRecord,
+};
+use crate::errors::AppError;
+use crate::permissions;
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use std::sync::Mutex;
+use tauri::State;
+
+type DbState = Mutex<Connection>;
+
+/// Default look-ahead window for "expiring soon" in the compliance matrix
+const DEFAULT_EXPIRING_WITHIN_DAYS: i64 = 30;
+
+// ── Courses ──
+
+#[tauri::command]
+pub fn list_training_courses(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    include_inactive: bool,
+) -> Result<Vec<TrainingCourse>, AppError> {
+    permissions::authorize(&session, "list_training_courses")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    training::list_courses(&conn, include_inactive).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn get_training_course(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<TrainingCourse, AppError> {
+    permissions::authorize(&session, "get_training_course")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    training::get_course(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn create_training_course(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: CreateTrainingCourse,
+) -> Result<TrainingCourse, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "create_training_course")?;
+
+    validation::validate_not_empty(&data.name, "Course name")?;
+    validation::validate_string_length(&data.name, validation::MAX_NAME_LENGTH, "Course name")?;
+    if let Some(months) = data.certification_valid_months {
+        if months < 0 {
+            return Err(AppError::Validation(format!(
+                "Certification validity cannot be negative (got: {months})"
+            )));
+        }
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    training::create_course(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn update_training_course(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    data: UpdateTrainingCourse,
+) -> Result<TrainingCourse, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "update_training_course")?;
+
+    if let Some(ref name) = data.name {
+        validation::validate_not_empty(name, "Course name")?;
+        validation::validate_string_length(name, validation::MAX_NAME_LENGTH, "Course name")?;
+    }
+    if let Some(months) = data.certification_valid_months {
+        if months < 0 {
+            return Err(AppError::Validation(format!(
+                "Certification validity cannot be negative (got: {months})"
+            )));
+        }
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    training::update_course(&conn, id, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn delete_training_course(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    permissions::authorize(&session, "delete_training_course")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    training::delete_course(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+// ── Records ──
+
+#[tauri::command]
+pub fn create_training_record(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    data: CreateTrainingRecord,
+) -> Result<TrainingRecord, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "create_training_record")?;
+
+    validation::validate_not_empty(&data.employee_name, "Employee name")?;
+    validation::validate_string_length(&data.employee_name, validation::MAX_NAME_LENGTH, "Employee name")?;
+    validation::validate_date_format(&data.completion_date, "Completion date")?;
+    if let Some(ref date) = data.expiration_date {
+        validation::validate_date_format(date, "Expiration date")?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    training::create_record(&conn, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn get_training_record(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<TrainingRecord, AppError> {
+    permissions::authorize(&session, "get_training_record")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    training::get_record(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn list_training_records(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    filter: TrainingRecordFilter,
+) -> Result<Vec<TrainingRecord>, AppError> {
+    permissions::authorize(&session, "list_training_records")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    training::list_records(&conn, filter).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn update_training_record(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+    data: UpdateTrainingRecord,
+) -> Result<TrainingRecord, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "update_training_record")?;
+
+    if let Some(ref name) = data.employee_name {
+        validation::validate_not_empty(name, "Employee name")?;
+        validation::validate_string_length(name, validation::MAX_NAME_LENGTH, "Employee name")?;
+    }
+    if let Some(ref date) = data.completion_date {
+        validation::validate_date_format(date, "Completion date")?;
+    }
+    if let Some(ref date) = data.expiration_date {
+        validation::validate_date_format(date, "Expiration date")?;
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    training::update_record(&conn, id, data).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub fn delete_training_record(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    id: i64,
+) -> Result<(), AppError> {
+    permissions::authorize(&session, "delete_training_record")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    training::delete_record(&conn, id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+// ── Compliance ──
+
+#[tauri::command]
+pub fn get_training_compliance_matrix(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    as_of: Option<String>,
+    expiring_within_days: Option<i64>,
+    employee_names: Option<Vec<String>>,
+) -> Result<Vec<EmployeeCompliance>, AppError> {
+    use crate::validation;
+
+    permissions::authorize(&session, "get_training_compliance_matrix")?;
+
+    let as_of = match as_of {
+        Some(date) => {
+            validation::validate_date_format(&date, "As-of date")?;
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .map_err(|e| AppError::Validation(e.to_string()))?
+        }
+        None => chrono::Local::now().date_naive(),
+    };
+    let window = expiring_within_days.unwrap_or(DEFAULT_EXPIRING_WITHIN_DAYS);
+    if window < 0 {
+        return Err(AppError::Validation(format!(
+            "Expiring-within days cannot be negative (got: {window})"
+        )));
+    }
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    training::get_compliance_matrix(&conn, as_of, window, employee_names)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
@@ -0,0 +1,170 @@
+use crate::commands::auth::SessionState;
+use crate::db::audit::Actor;
+use crate::db::trash::{self, TrashItem};
+use crate::db::{incidents, locations, osha, toolbox};
+use crate::errors::AppError;
+use crate::permissions;
+use rusqlite::{Connection, ErrorCode};
+use std::sync::Mutex;
+use tauri::State;
+
+type DbState = Mutex<Connection>;
+
+fn validate_kind(kind: &str) -> Result<(), AppError> {
+    if trash::table_for_kind(kind).is_none() {
+        let kinds: Vec<&str> = trash::TRASH_KINDS.iter().map(|(k, _)| *k).collect();
+        return Err(AppError::Validation(format!(
+            "Invalid kind: {}. Must be one of: {}",
+            kind,
+            kinds.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// The establishment a trashed row belongs to, and whether that row is in
+/// the trash at all.
+fn trashed_row(conn: &Connection, kind: &str, id: i64) -> anyhow::Result<(i64, bool)> {
+    Ok(match kind {
+        "incident" => {
+            let inc = incidents::get_incident(conn, id)?;
+            (inc.establishment_id, inc.deleted_at.is_some())
+        }
+        "establishment" => {
+            let est = locations::get_establishment(conn, id)?;
+            (est.id, est.deleted_at.is_some())
+        }
+        "location" => {
+            let loc = locations::get_location(conn, id)?;
+            (loc.establishment_id, loc.deleted_at.is_some())
+        }
+        _ => {
+            let talk = toolbox::get_talk(conn, id)?;
+            (talk.establishment_id, talk.deleted_at.is_some())
+        }
+    })
+}
+
+/// A purge that trips a foreign key means other records still point at the row.
+fn purge_error(e: anyhow::Error) -> AppError {
+    let constraint = e
+        .downcast_ref::<rusqlite::Error>()
+        .and_then(|e| e.sqlite_error_code())
+        == Some(ErrorCode::ConstraintViolation);
+    if constraint {
+        AppError::Validation("Other records still refer to this item; purge or move them first".into())
+    } else {
+        AppError::Internal(e.to_string())
+    }
+}
+
+/// Check OSHA's five-year retention before anything leaves the database for good.
+fn check_retention(conn: &Connection, kind: &str, id: i64) -> Result<(), AppError> {
+    let today = chrono::Local::now().date_naive();
+    match kind {
+        "incident" => {
+            let existing = incidents::get_incident(conn, id).map_err(|e| AppError::Internal(e.to_string()))?;
+            if existing.is_recordable {
+                if let Some(year) = osha::incident_year(&existing) {
+                    if osha::is_retained(year, today) {
+                        return Err(AppError::Validation(format!(
+                            "Recordable cases from {year} must be kept until {}",
+                            osha::retained_until(year)
+                        )));
+                    }
+                }
+            }
+        }
+        "establishment" => {
+            let latest = osha::latest_record_year(conn, id).map_err(|e| AppError::Internal(e.to_string()))?;
+            if let Some(year) = latest {
+                if osha::is_retained(year, today) {
+                    return Err(AppError::Validation(format!(
+                        "This establishment holds OSHA records from {year}, which must be kept until {}",
+                        osha::retained_until(year)
+                    )));
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_trash(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    establishment_id: Option<i64>,
+) -> Result<Vec<TrashItem>, AppError> {
+    permissions::authorize(&session, "list_trash")?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    trash::list_trash(&conn, establishment_id).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Takes an item back out of the trash. Records that belong to a trashed
+/// establishment wait until the establishment itself is restored.
+#[tauri::command]
+pub fn restore_from_trash(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    kind: String,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "restore_from_trash")?);
+    validate_kind(&kind)?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let (establishment_id, trashed) =
+        trashed_row(&conn, &kind, id).map_err(|_| AppError::NotFound(format!("{kind} {id} not found")))?;
+    if !trashed {
+        return Err(AppError::Validation(format!("This {kind} is not in the trash")));
+    }
+    if kind != "establishment" {
+        let est = locations::get_establishment(&conn, establishment_id)
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        if est.deleted_at.is_some() {
+            return Err(AppError::Validation(format!(
+                "Restore the establishment {} first",
+                est.name
+            )));
+        }
+    }
+
+    match kind.as_str() {
+        "incident" => incidents::restore_incident(&conn, &actor, id).map(|_| ()),
+        "establishment" => locations::restore_establishment(&conn, &actor, id).map(|_| ()),
+        "location" => locations::restore_location(&conn, &actor, id).map(|_| ()),
+        _ => toolbox::restore_talk(&conn, &actor, id).map(|_| ()).map_err(anyhow::Error::from),
+    }
+    .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Removes a trashed item for good, unless OSHA still requires it to be kept.
+#[tauri::command]
+pub fn purge_from_trash(
+    db: State<'_, DbState>,
+    session: State<'_, SessionState>,
+    kind: String,
+    id: i64,
+) -> Result<(), AppError> {
+    let actor = Actor::from(&permissions::authorize(&session, "purge_from_trash")?);
+    validate_kind(&kind)?;
+
+    let conn = db.lock().map_err(|e| AppError::Internal(e.to_string()))?;
+    let (_, trashed) =
+        trashed_row(&conn, &kind, id).map_err(|_| AppError::NotFound(format!("{kind} {id} not found")))?;
+    if !trashed {
+        return Err(AppError::Validation(format!("Only items in the trash can be purged; move this {kind} there first")));
+    }
+    check_retention(&conn, &kind, id)?;
+
+    match kind.as_str() {
+        "incident" => incidents::purge_incident(&conn, &actor, id),
+        "establishment" => locations::purge_establishment(&conn, &actor, id),
+        "location" => locations::purge_location(&conn, &actor, id),
+        _ => toolbox::purge_talk(&conn, &actor, id).map_err(anyhow::Error::from),
+    }
+    .map_err(purge_error)
+}
@@ -0,0 +1,239 @@
+use rusqlite::{params, Connection, Result};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::db::auth::Session;
+
+/// Who is making a change. Passed to every audited mutation so the audit log
+/// can attribute it; `system()` covers imports, background jobs and tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Actor {
+    pub user_id: Option<i64>,
+}
+
+impl Actor {
+    pub fn system() -> Self {
+        Actor { user_id: None }
+    }
+
+    pub fn user(user_id: i64) -> Self {
+        Actor { user_id: Some(user_id) }
+    }
+}
+
+impl From<&Session> for Actor {
+    fn from(session: &Session) -> Self {
+        Actor::user(session.user_id)
+    }
+}
+
+/// Display name of the acting user, for records that carry a signer. `None`
+/// for the system actor.
+pub fn actor_name(conn: &Connection, actor: &Actor) -> Result<Option<String>> {
+    use rusqlite::OptionalExtension;
+
+    let Some(user_id) = actor.user_id else {
+        return Ok(None);
+    };
+    conn.query_row("SELECT display_name FROM users WHERE id = ?1", [user_id], |row| row.get(0))
+        .optional()
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub user_id: Option<i64>,
+    pub username: Option<String>,
+    pub action: String,
+    pub table_name: Option<String>,
+    pub record_id: Option<i64>,
+    pub old_values: Option<Value>,
+    pub new_values: Option<Value>,
+    /// Top-level fields whose value differs between the two snapshots
+    pub changed_fields: Vec<String>,
+    pub device_id: Option<String>,
+    pub created_at: String,
+}
+
+fn to_json<T: Serialize>(value: Option<&T>) -> Result<Option<String>> {
+    value
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+/// Writes one audit_log row with JSON snapshots of the record before and
+/// after the change. Returns a plain rusqlite error so both the anyhow and
+/// rusqlite-style db modules can call it with `?`.
+pub fn record<T: Serialize>(
+    conn: &Connection,
+    actor: &Actor,
+    action: &str,
+    table_name: &str,
+    record_id: i64,
+    old: Option<&T>,
+    new: Option<&T>,
+) -> Result<()> {
+    let device_id = super::device_id(conn)?;
+
+    conn.execute(
+        "INSERT INTO audit_log (user_id, action, table_name, record_id, old_values, new_values, device_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            actor.user_id,
+            action,
+            table_name,
+            record_id,
+            to_json(old)?,
+            to_json(new)?,
+            device_id,
+        ],
+    )?;
+
+    Ok(())
+}
+
+pub fn log_create<T: Serialize>(
+    conn: &Connection,
+    actor: &Actor,
+    table_name: &str,
+    record_id: i64,
+    new: &T,
+) -> Result<()> {
+    record(conn, actor, "create", table_name, record_id, None, Some(new))
+}
+
+pub fn log_update<T: Serialize>(
+    conn: &Connection,
+    actor: &Actor,
+    table_name: &str,
+    record_id: i64,
+    old: &T,
+    new: &T,
+) -> Result<()> {
+    record(conn, actor, "update", table_name, record_id, Some(old), Some(new))
+}
+
+pub fn log_delete<T: Serialize>(
+    conn: &Connection,
+    actor: &Actor,
+    table_name: &str,
+    record_id: i64,
+    old: &T,
+) -> Result<()> {
+    record(conn, actor, "delete", table_name, record_id, Some(old), None)
+}
+
+fn changed_fields(old: Option<&Value>, new: Option<&Value>) -> Vec<String> {
+    let empty = serde_json::Map::new();
+    let old = old.and_then(Value::as_object).unwrap_or(&empty);
+    let new = new.and_then(Value::as_object).unwrap_or(&empty);
+
+    let mut fields: Vec<String> = old
+        .keys()
+        .chain(new.keys().filter(|k| !old.contains_key(*k)))
+        .filter(|k| old.get(*k) != new.get(*k))
+        .filter(|k| k.as_str() != "updated_at")
+        .cloned()
+        .collect();
+    fields.sort();
+    fields
+}
+
+/// Full change history of one record, oldest first.
+pub fn get_record_history(
+    conn: &Connection,
+    table_name: &str,
+    record_id: i64,
+) -> Result<Vec<AuditEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT a.rowid, a.user_id, u.username, a.action, a.table_name, a.record_id,
+                a.old_values, a.new_values, a.device_id, a.created_at
+         FROM audit_log a
+         LEFT JOIN users u ON a.user_id = u.id
+         WHERE a.table_name = ?1 AND a.record_id = ?2
+         ORDER BY a.rowid",
+    )?;
+
+    let rows = stmt
+        .query_map(params![table_name, record_id], |row| {
+            let old: Option<String> = row.get(6)?;
+            let new: Option<String> = row.get(7)?;
+            let old_values = old.and_then(|s| serde_json::from_str(&s).ok());
+            let new_values = new.and_then(|s| serde_json::from_str(&s).ok());
+            Ok(AuditEntry {
+                id: row.get(0)?,
+                user_id: row.get(1)?,
+                username: row.get(2)?,
+                action: row.get(3)?,
+                table_name: row.get(4)?,
+                record_id: row.get(5)?,
+                changed_fields: changed_fields(old_values.as_ref(), new_values.as_ref()),
+                old_values,
+                new_values,
+                device_id: row.get(8)?,
+                created_at: row.get(9)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::incidents::{self, CreateIncident, UpdateIncident};
+    use crate::db::locations::{create_establishment, CreateEstablishment};
+    use crate::db::open_test_db;
+
+    #[test]
+    fn test_incident_history_is_recorded() {
+        let conn = open_test_db();
+        let admin = Actor::user(1);
+        let est = create_establishment(&conn, &admin, CreateEstablishment {
+            name: "Audit Co".into(),
+            street_address: None, city: None, state: None,
+            zip_code: None, industry_description: None, naics_code: None,
+            ein: None, company_name: None, establishment_type: None, case_number_format: None,
+        }).unwrap();
+
+        let inc = incidents::create_incident(&conn, &admin, CreateIncident {
+            establishment_id: est.id, location_id: None,
+            employee_name: "Pat Welder".into(), incident_date: "2026-04-02".into(),
+            description: "Flash burn".into(),
+            employee_job_title: None, employee_address: None, employee_city: None,
+            employee_state: None, employee_zip: None, employee_dob: None,
+            employee_hire_date: None, employee_gender: None, is_privacy_case: None, privacy_concern: None, privacy_description: None,
+            incident_time: None, work_start_time: None, where_occurred: None,
+            activity_before_incident: None, how_injury_occurred: None,
+            injury_description: None, object_substance: None, physician_name: None,
+            treatment_facility: None, facility_address: None, facility_city_state_zip: None,
+            treated_in_er: None, hospitalized_overnight: None,
+            outcome_severity: None, days_away_count: None, days_restricted_count: None,
+            date_of_death: None, injury_illness_type: None, is_recordable: None,
+        }).unwrap();
+
+        let update: UpdateIncident = serde_json::from_value(serde_json::json!({
+            "days_away_count": 3,
+            "outcome_severity": "days_away",
+        })).unwrap();
+        incidents::update_incident(&conn, &Actor::system(), inc.id, update).unwrap();
+        incidents::delete_incident(&conn, &admin, inc.id).unwrap();
+
+        let history = get_record_history(&conn, "incidents", inc.id).unwrap();
+        let actions: Vec<&str> = history.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, ["create", "update", "delete"]);
+
+        assert_eq!(history[0].username.as_deref(), Some("admin"));
+        assert!(history[0].old_values.is_none());
+        assert_eq!(history[1].user_id, None);
+        assert_eq!(history[1].changed_fields, ["days_away_count", "outcome_severity"]);
+        assert_eq!(history[2].old_values.as_ref().unwrap()["days_away_count"], 3);
+        assert!(history[2].new_values.is_none());
+
+        // Every entry carries the same device id
+        let device = super::super::device_id(&conn).unwrap();
+        assert!(history.iter().all(|e| e.device_id.as_deref() == Some(device.as_str())));
+    }
+}
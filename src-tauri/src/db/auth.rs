@@ -0,0 +1,341 @@
+use anyhow::{anyhow, Context, Result};
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use chrono::{Duration, NaiveDateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AppError;
+
+pub const ROLES: &[&str] = &["admin", "safety_manager", "supervisor", "field_worker"];
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+pub const SESSION_TTL_HOURS: i64 = 12;
+
+/// Matches SQLite's `datetime('now')` so stored timestamps compare as text.
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct User {
+    pub id: i64,
+    pub username: String,
+    pub display_name: String,
+    pub role: String,
+    pub is_active: bool,
+    pub has_password: bool,
+    pub last_login_at: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateUser {
+    pub username: String,
+    pub display_name: String,
+    pub role: String,
+    pub password: String,
+}
+
+/// An authenticated session. The token never leaves the backend; the
+/// frontend only sees who is logged in and until when.
+#[derive(Debug, Serialize, Clone)]
+pub struct Session {
+    #[serde(skip_serializing)]
+    pub token: String,
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: String,
+    pub role: String,
+    pub expires_at: String,
+}
+
+impl Session {
+    pub fn is_expired(&self) -> bool {
+        match NaiveDateTime::parse_from_str(&self.expires_at, TIMESTAMP_FORMAT) {
+            Ok(expires) => expires <= Utc::now().naive_utc(),
+            Err(_) => true,
+        }
+    }
+}
+
+// ── Passwords ──
+
+pub fn hash_password(password: &str) -> Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|h| h.to_string())
+        .map_err(|e| anyhow!("Failed to hash password: {e}"))
+}
+
+pub fn verify_password(hash: &str, password: &str) -> bool {
+    match PasswordHash::new(hash) {
+        Ok(parsed) => Argon2::default()
+            .verify_password(password.as_bytes(), &parsed)
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+fn validate_password(password: &str) -> Result<()> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Password must be at least {MIN_PASSWORD_LENGTH} characters"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+// ── Users ──
+
+fn row_to_user(row: &rusqlite::Row<'_>) -> rusqlite::Result<User> {
+    Ok(User {
+        id: row.get(0)?,
+        username: row.get(1)?,
+        display_name: row.get(2)?,
+        role: row.get(3)?,
+        is_active: row.get(4)?,
+        has_password: row.get(5)?,
+        last_login_at: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+const SELECT_COLS: &str = "id, username, display_name, role, is_active,
+    password_hash IS NOT NULL, last_login_at, created_at";
+
+pub fn create_user(conn: &Connection, data: CreateUser) -> Result<User> {
+    if !ROLES.contains(&data.role.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Invalid role: {}. Must be one of: {}",
+            data.role,
+            ROLES.join(", ")
+        ))
+        .into());
+    }
+    validate_password(&data.password)?;
+    let hash = hash_password(&data.password)?;
+
+    conn.execute(
+        "INSERT INTO users (username, display_name, role, password_hash) VALUES (?1, ?2, ?3, ?4)",
+        params![data.username, data.display_name, data.role, hash],
+    )
+    .context("Failed to create user")?;
+
+    let id = conn.last_insert_rowid();
+    get_user(conn, id)
+}
+
+pub fn get_user(conn: &Connection, id: i64) -> Result<User> {
+    conn.query_row(
+        &format!("SELECT {SELECT_COLS} FROM users WHERE id = ?1"),
+        [id],
+        row_to_user,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            AppError::NotFound(format!("User {id} not found")).into()
+        }
+        _ => anyhow::Error::new(e),
+    })
+}
+
+pub fn list_users(conn: &Connection) -> Result<Vec<User>> {
+    let mut stmt = conn.prepare(&format!("SELECT {SELECT_COLS} FROM users ORDER BY username"))?;
+    let rows = stmt
+        .query_map([], row_to_user)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Sets a new password and signs the user out everywhere else.
+pub fn set_password(conn: &Connection, user_id: i64, password: &str) -> Result<()> {
+    validate_password(password)?;
+    let hash = hash_password(password)?;
+
+    let changes = conn
+        .execute(
+            "UPDATE users SET password_hash = ?1 WHERE id = ?2",
+            params![hash, user_id],
+        )
+        .context("Failed to set password")?;
+    if changes == 0 {
+        return Err(AppError::NotFound(format!("User {user_id} not found")).into());
+    }
+
+    conn.execute("DELETE FROM user_sessions WHERE user_id = ?1", [user_id])
+        .context("Failed to revoke sessions")?;
+    Ok(())
+}
+
+/// True until at least one active account has a password. The seeded admin
+/// has none, so first launch has to go through initial setup.
+pub fn needs_initial_setup(conn: &Connection) -> Result<bool> {
+    let configured: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE is_active = 1 AND password_hash IS NOT NULL)",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(!configured)
+}
+
+/// Returns the user if the credentials match an active account with a password.
+pub fn authenticate(conn: &Connection, username: &str, password: &str) -> Result<Option<User>> {
+    let found: Option<(i64, Option<String>, bool)> = conn
+        .query_row(
+            "SELECT id, password_hash, is_active FROM users WHERE username = ?1",
+            [username],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?;
+
+    match found {
+        Some((id, Some(hash), true)) if verify_password(&hash, password) => {
+            Ok(Some(get_user(conn, id)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+// ── Sessions ──
+
+fn new_token() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+pub fn create_session(conn: &Connection, user: &User) -> Result<Session> {
+    let token = new_token();
+    let expires_at = (Utc::now() + Duration::hours(SESSION_TTL_HOURS))
+        .format(TIMESTAMP_FORMAT)
+        .to_string();
+
+    conn.execute(
+        "INSERT INTO user_sessions (user_id, session_token, expires_at) VALUES (?1, ?2, ?3)",
+        params![user.id, token, expires_at],
+    )
+    .context("Failed to create session")?;
+
+    conn.execute(
+        "UPDATE users SET last_login_at = datetime('now') WHERE id = ?1",
+        [user.id],
+    )?;
+
+    Ok(Session {
+        token,
+        user_id: user.id,
+        username: user.username.clone(),
+        display_name: user.display_name.clone(),
+        role: user.role.clone(),
+        expires_at,
+    })
+}
+
+/// Looks up an unexpired session for an active user and marks it as seen.
+pub fn get_session(conn: &Connection, token: &str) -> Result<Option<Session>> {
+    let session = conn
+        .query_row(
+            "SELECT s.session_token, u.id, u.username, u.display_name, u.role, s.expires_at
+             FROM user_sessions s
+             JOIN users u ON s.user_id = u.id
+             WHERE s.session_token = ?1
+               AND s.expires_at > datetime('now')
+               AND u.is_active = 1",
+            [token],
+            |row| {
+                Ok(Session {
+                    token: row.get(0)?,
+                    user_id: row.get(1)?,
+                    username: row.get(2)?,
+                    display_name: row.get(3)?,
+                    role: row.get(4)?,
+                    expires_at: row.get(5)?,
+                })
+            },
+        )
+        .optional()?;
+
+    if session.is_some() {
+        conn.execute(
+            "UPDATE user_sessions SET last_seen_at = datetime('now') WHERE session_token = ?1",
+            [token],
+        )?;
+    }
+    Ok(session)
+}
+
+pub fn end_session(conn: &Connection, token: &str) -> Result<()> {
+    conn.execute("DELETE FROM user_sessions WHERE session_token = ?1", [token])
+        .context("Failed to end session")?;
+    Ok(())
+}
+
+pub fn purge_expired_sessions(conn: &Connection) -> Result<usize> {
+    let n = conn
+        .execute("DELETE FROM user_sessions WHERE expires_at <= datetime('now')", [])
+        .context("Failed to purge expired sessions")?;
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::open_test_db;
+
+    #[test]
+    fn test_password_hash_is_salted() {
+        let a = hash_password("correct horse").unwrap();
+        let b = hash_password("correct horse").unwrap();
+        assert_ne!(a, b);
+        assert!(verify_password(&a, "correct horse"));
+        assert!(!verify_password(&a, "wrong horse"));
+    }
+
+    #[test]
+    fn test_login_and_session_lifecycle() {
+        let conn = open_test_db();
+        assert!(needs_initial_setup(&conn).unwrap());
+
+        // Seeded admin can't log in until a password is set
+        assert!(authenticate(&conn, "admin", "").unwrap().is_none());
+        let admin_id: i64 = conn
+            .query_row("SELECT id FROM users WHERE username = 'admin'", [], |r| r.get(0))
+            .unwrap();
+        set_password(&conn, admin_id, "s3cure-pass").unwrap();
+        assert!(!needs_initial_setup(&conn).unwrap());
+
+        assert!(authenticate(&conn, "admin", "nope-nope").unwrap().is_none());
+        let user = authenticate(&conn, "admin", "s3cure-pass").unwrap().unwrap();
+        let session = create_session(&conn, &user).unwrap();
+        assert!(!session.is_expired());
+        assert_eq!(get_session(&conn, &session.token).unwrap().unwrap().role, "admin");
+
+        end_session(&conn, &session.token).unwrap();
+        assert!(get_session(&conn, &session.token).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_expired_and_revoked_sessions() {
+        let conn = open_test_db();
+        let user = create_user(&conn, CreateUser {
+            username: "fw".into(), display_name: "Field Worker".into(),
+            role: "field_worker".into(), password: "hardhat-on".into(),
+        }).unwrap();
+        assert!(create_user(&conn, CreateUser {
+            username: "x".into(), display_name: "X".into(),
+            role: "superuser".into(), password: "hardhat-on".into(),
+        }).is_err());
+
+        let session = create_session(&conn, &user).unwrap();
+        conn.execute(
+            "UPDATE user_sessions SET expires_at = datetime('now', '-1 minute') WHERE session_token = ?1",
+            [&session.token],
+        ).unwrap();
+        assert!(get_session(&conn, &session.token).unwrap().is_none());
+        assert_eq!(purge_expired_sessions(&conn).unwrap(), 1);
+
+        // Changing the password revokes outstanding sessions
+        let session = create_session(&conn, &user).unwrap();
+        set_password(&conn, user.id, "new-hardhat").unwrap();
+        assert!(get_session(&conn, &session.token).unwrap().is_none());
+        assert!(set_password(&conn, user.id, "short").is_err());
+    }
+}
@@ -0,0 +1,317 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AppError;
+
+/// Version stamped on exported benchmark tables; imports reject anything newer.
+pub const BENCHMARK_FORMAT_VERSION: u32 = 1;
+
+/// Hours worked by 100 full-time employees in a year, the base for OSHA rates.
+const RATE_BASE_HOURS: f64 = 200_000.0;
+
+/// Hours worked by 100,000 full-time equivalents, the base for BLS fatality rates.
+const FATALITY_BASE_HOURS: f64 = 200_000_000.0;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IndustryBenchmark {
+    pub naics_code: String,
+    pub industry: String,
+    pub source_year: i64,
+    pub trir: Option<f64>,
+    pub dart_rate: Option<f64>,
+    pub ltir: Option<f64>,
+    pub fatality_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BenchmarkTable {
+    pub format_version: u32,
+    pub exported_at: String,
+    pub benchmarks: Vec<IndustryBenchmark>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct BenchmarkImportResult {
+    pub created: usize,
+    pub updated: usize,
+    pub errors: Vec<String>,
+}
+
+/// Case counts and rates for one establishment-year. Rates are `None` until
+/// hours worked are entered in annual stats.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct SafetyKpis {
+    pub hours_worked: Option<i64>,
+    pub recordable_cases: i64,
+    pub dart_cases: i64,
+    pub lost_time_cases: i64,
+    pub deaths: i64,
+    pub days_away: i64,
+    pub incidents: i64,
+    pub near_misses: i64,
+    pub trir: Option<f64>,
+    /// Days away, restricted or transferred cases per 200,000 hours
+    pub dart_rate: Option<f64>,
+    /// Days-away cases per 200,000 hours, comparable to the BLS DAFW rate
+    pub ltir: Option<f64>,
+    /// Days away per 200,000 hours
+    pub severity_rate: Option<f64>,
+    /// Deaths per 100,000 full-time equivalents
+    pub fatality_rate: Option<f64>,
+    /// Near misses reported per incident; `None` when there were no incidents
+    pub near_miss_ratio: Option<f64>,
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+pub fn get_kpis(conn: &Connection, establishment_id: i64, year: i64) -> Result<SafetyKpis> {
+    let year_str = format!("{year}%");
+
+    let (incidents, recordable, dart, lost_time, deaths, days_away): (i64, i64, i64, i64, i64, i64) = conn
+        .query_row(
+            "SELECT
+                COUNT(*),
+                COALESCE(SUM(CASE WHEN is_recordable = 1 THEN 1 ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN is_recordable = 1 AND outcome_severity IN ('days_away', 'job_transfer_restriction') THEN 1 ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN is_recordable = 1 AND outcome_severity = 'days_away' THEN 1 ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN is_recordable = 1 AND outcome_severity = 'death' THEN 1 ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN is_recordable = 1 THEN days_away_count ELSE 0 END), 0)
+             FROM incidents
+             WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND deleted_at IS NULL",
+            params![establishment_id, year_str],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)),
+        )
+        .context("Failed to count incidents")?;
+
+    let near_misses: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM near_miss_reports WHERE establishment_id = ?1 AND report_date LIKE ?2",
+            params![establishment_id, year_str],
+            |row| row.get(0),
+        )
+        .context("Failed to count near misses")?;
+
+    let hours: Option<i64> = conn
+        .query_row(
+            "SELECT total_hours_worked FROM annual_stats WHERE establishment_id = ?1 AND year = ?2",
+            params![establishment_id, year],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("Failed to get hours worked")?;
+
+    let worked = hours.filter(|&h| h > 0).map(|h| h as f64);
+    let rate = |count: i64, base: f64| worked.map(|h| round2(count as f64 * base / h));
+
+    Ok(SafetyKpis {
+        hours_worked: hours,
+        recordable_cases: recordable,
+        dart_cases: dart,
+        lost_time_cases: lost_time,
+        deaths,
+        days_away,
+        incidents,
+        near_misses,
+        trir: rate(recordable, RATE_BASE_HOURS),
+        dart_rate: rate(dart, RATE_BASE_HOURS),
+        ltir: rate(lost_time, RATE_BASE_HOURS),
+        severity_rate: rate(days_away, RATE_BASE_HOURS),
+        fatality_rate: rate(deaths, FATALITY_BASE_HOURS),
+        near_miss_ratio: (incidents > 0).then(|| round2(near_misses as f64 / incidents as f64)),
+    })
+}
+
+// ── Benchmarks ──
+
+const SELECT_COLS: &str = "naics_code, industry, source_year, trir, dart_rate, ltir, fatality_rate";
+
+fn row_to_benchmark(row: &rusqlite::Row<'_>) -> rusqlite::Result<IndustryBenchmark> {
+    Ok(IndustryBenchmark {
+        naics_code: row.get(0)?,
+        industry: row.get(1)?,
+        source_year: row.get(2)?,
+        trir: row.get(3)?,
+        dart_rate: row.get(4)?,
+        ltir: row.get(5)?,
+        fatality_rate: row.get(6)?,
+    })
+}
+
+pub fn list_benchmarks(conn: &Connection) -> Result<Vec<IndustryBenchmark>> {
+    let mut stmt = conn.prepare(&format!("SELECT {SELECT_COLS} FROM industry_benchmarks ORDER BY naics_code"))?;
+    let rows = stmt
+        .query_map([], row_to_benchmark)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// The most specific benchmark for a NAICS code: `236220` uses `2362` if
+/// there is no exact row, then `236`, `23`, and finally the all-industry
+/// row keyed by the empty code.
+pub fn find_benchmark(conn: &Connection, naics_code: Option<&str>) -> Result<Option<IndustryBenchmark>> {
+    let code = naics_code.map(str::trim).unwrap_or("");
+    conn.query_row(
+        &format!(
+            "SELECT {SELECT_COLS} FROM industry_benchmarks
+             WHERE substr(?1, 1, length(naics_code)) = naics_code
+             ORDER BY length(naics_code) DESC LIMIT 1"
+        ),
+        [code],
+        row_to_benchmark,
+    )
+    .optional()
+    .context("Failed to look up industry benchmark")
+}
+
+pub fn benchmark_for_establishment(conn: &Connection, establishment_id: i64) -> Result<Option<IndustryBenchmark>> {
+    let naics: Option<String> = conn
+        .query_row(
+            "SELECT naics_code FROM establishments WHERE id = ?1",
+            [establishment_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("Failed to get establishment")?
+        .ok_or_else(|| AppError::NotFound(format!("Establishment {establishment_id} not found")))?;
+    find_benchmark(conn, naics.as_deref())
+}
+
+pub fn export_benchmarks(conn: &Connection) -> Result<BenchmarkTable> {
+    Ok(BenchmarkTable {
+        format_version: BENCHMARK_FORMAT_VERSION,
+        exported_at: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        benchmarks: list_benchmarks(conn)?,
+    })
+}
+
+/// Merges a benchmark table into this install, matching rows on NAICS code.
+pub fn import_benchmarks(conn: &Connection, table: &BenchmarkTable) -> Result<BenchmarkImportResult> {
+    if table.format_version > BENCHMARK_FORMAT_VERSION {
+        return Err(AppError::Validation(format!(
+            "Benchmark table format {} is newer than this app supports ({BENCHMARK_FORMAT_VERSION})",
+            table.format_version
+        ))
+        .into());
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let mut result = BenchmarkImportResult::default();
+
+    for (i, entry) in table.benchmarks.iter().enumerate() {
+        let code = entry.naics_code.trim();
+        let industry = entry.industry.trim();
+        if !code.chars().all(|c| c.is_ascii_digit()) || code.len() > 6 {
+            result.errors.push(format!("Entry {}: NAICS code must be up to 6 digits", i + 1));
+            continue;
+        }
+        if industry.is_empty() {
+            result.errors.push(format!("Entry {}: industry is required", i + 1));
+            continue;
+        }
+        let rates = [entry.trir, entry.dart_rate, entry.ltir, entry.fatality_rate];
+        if rates.iter().flatten().any(|r| !r.is_finite() || *r < 0.0) {
+            result.errors.push(format!("Entry {}: rates cannot be negative", i + 1));
+            continue;
+        }
+
+        let changes = tx
+            .execute(
+                "UPDATE industry_benchmarks SET industry = ?1, source_year = ?2, trir = ?3, dart_rate = ?4,
+                        ltir = ?5, fatality_rate = ?6, updated_at = datetime('now')
+                 WHERE naics_code = ?7",
+                params![industry, entry.source_year, entry.trir, entry.dart_rate, entry.ltir, entry.fatality_rate, code],
+            )
+            .with_context(|| format!("Failed to update benchmark {code}"))?;
+        if changes > 0 {
+            result.updated += 1;
+            continue;
+        }
+        tx.execute(
+            "INSERT INTO industry_benchmarks (naics_code, industry, source_year, trir, dart_rate, ltir, fatality_rate)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![code, industry, entry.source_year, entry.trir, entry.dart_rate, entry.ltir, entry.fatality_rate],
+        )
+        .with_context(|| format!("Failed to create benchmark {code}"))?;
+        result.created += 1;
+    }
+
+    tx.commit()?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::audit::Actor;
+    use crate::db::open_test_db;
+
+    #[test]
+    fn test_kpis_and_benchmark_lookup() {
+        let conn = open_test_db();
+        conn.execute(
+            "INSERT INTO establishments (name, naics_code) VALUES ('ABC Construction', '236220')",
+            [],
+        )
+        .unwrap();
+        let est = conn.last_insert_rowid();
+        for (severity, days) in [("days_away", 15), ("job_transfer_restriction", 0), ("other_recordable", 0)] {
+            conn.execute(
+                "INSERT INTO incidents (establishment_id, employee_name, incident_date, description,
+                                        outcome_severity, days_away_count, is_recordable)
+                 VALUES (?1, 'Worker', '2026-04-01', 'Injury', ?2, ?3, 1)",
+                params![est, severity, days],
+            )
+            .unwrap();
+        }
+        for _ in 0..6 {
+            conn.execute(
+                "INSERT INTO near_miss_reports (establishment_id, report_date, what_happened, what_could_have_happened)
+                 VALUES (?1, '2026-05-01', 'Slipped', 'Fall')",
+                [est],
+            )
+            .unwrap();
+        }
+
+        let kpis = get_kpis(&conn, est, 2026).unwrap();
+        assert_eq!(kpis.dart_cases, 2);
+        assert!(kpis.trir.is_none());
+        assert_eq!(kpis.near_miss_ratio, Some(2.0));
+
+        crate::db::osha::upsert_annual_stats(&conn, &Actor::system(), crate::db::osha::UpsertAnnualStats {
+            establishment_id: est, year: 2026, avg_employees: 50, total_hours_worked: 100_000,
+            certifier_name: None, certifier_title: None, certifier_phone: None, certification_date: None,
+        })
+        .unwrap();
+        let kpis = get_kpis(&conn, est, 2026).unwrap();
+        assert_eq!(kpis.trir, Some(6.0));
+        assert_eq!(kpis.dart_rate, Some(4.0));
+        assert_eq!(kpis.ltir, Some(2.0));
+        assert_eq!(kpis.severity_rate, Some(30.0));
+        assert_eq!(kpis.fatality_rate, Some(0.0));
+
+        let bench = benchmark_for_establishment(&conn, est).unwrap().unwrap();
+        assert_eq!(bench.naics_code, "2362");
+        assert_eq!(find_benchmark(&conn, Some("541330")).unwrap().unwrap().naics_code, "");
+
+        let result = import_benchmarks(&conn, &BenchmarkTable {
+            format_version: BENCHMARK_FORMAT_VERSION,
+            exported_at: String::new(),
+            benchmarks: vec![
+                IndustryBenchmark {
+                    naics_code: "236220".into(), industry: "Commercial building construction".into(),
+                    source_year: 2024, trir: Some(1.8), dart_rate: Some(1.0), ltir: Some(0.6), fatality_rate: None,
+                },
+                IndustryBenchmark {
+                    naics_code: "23".into(), industry: "Construction".into(),
+                    source_year: 2024, trir: Some(-1.0), dart_rate: None, ltir: None, fatality_rate: None,
+                },
+            ],
+        })
+        .unwrap();
+        assert_eq!((result.created, result.updated, result.errors.len()), (1, 0, 1));
+        assert_eq!(benchmark_for_establishment(&conn, est).unwrap().unwrap().trir, Some(1.8));
+    }
+}
@@ -0,0 +1,632 @@
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AppError;
+
+pub const STATUSES: &[&str] = &["active", "out_of_service", "in_repair", "retired"];
+pub const INSPECTION_RESULTS: &[&str] = &["pass", "conditional", "fail"];
+
+/// Default re-inspection interval in days when an inspection doesn't set
+/// `next_inspection_due` explicitly.
+const INSPECTION_INTERVALS: &[(&str, i64)] = &[
+    ("pre_use", 1),
+    ("daily", 1),
+    ("weekly", 7),
+    ("monthly", 30),
+    ("quarterly", 91),
+    ("annual", 365),
+];
+
+// ── Equipment ──
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Equipment {
+    pub id: i64,
+    pub equipment_type: String,
+    pub equipment_number: String,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub serial_number: Option<String>,
+    pub purchase_date: Option<String>,
+    pub location_id: Option<i64>,
+    pub status: String,
+    pub last_inspection_date: Option<String>,
+    pub next_inspection_due: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateEquipment {
+    pub equipment_type: String,
+    pub equipment_number: String,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub serial_number: Option<String>,
+    pub purchase_date: Option<String>,
+    pub location_id: Option<i64>,
+    pub next_inspection_due: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateEquipment {
+    pub equipment_type: Option<String>,
+    pub equipment_number: Option<String>,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub serial_number: Option<String>,
+    pub purchase_date: Option<String>,
+    pub location_id: Option<i64>,
+    pub status: Option<String>,
+    pub next_inspection_due: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EquipmentFilter {
+    pub location_id: Option<i64>,
+    pub equipment_type: Option<String>,
+    pub status: Option<String>,
+}
+
+// ── Inspections & maintenance ──
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EquipmentInspection {
+    pub id: i64,
+    pub equipment_id: i64,
+    pub inspection_date: String,
+    pub inspector_name: String,
+    pub inspection_type: String,
+    pub result: String,
+    pub deficiencies: Option<String>,
+    pub corrective_actions: Option<String>,
+    pub next_inspection_due: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateEquipmentInspection {
+    pub equipment_id: i64,
+    pub inspection_date: String,
+    pub inspector_name: String,
+    pub inspection_type: String,
+    pub result: String,
+    pub deficiencies: Option<String>,
+    pub corrective_actions: Option<String>,
+    pub next_inspection_due: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EquipmentMaintenance {
+    pub id: i64,
+    pub equipment_id: i64,
+    pub maintenance_date: String,
+    pub maintenance_type: String,
+    pub description: String,
+    pub performed_by: Option<String>,
+    pub parts_replaced: Option<String>,
+    pub cost: Option<f64>,
+    pub next_maintenance_due: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateEquipmentMaintenance {
+    pub equipment_id: i64,
+    pub maintenance_date: String,
+    pub maintenance_type: String,
+    pub description: String,
+    pub performed_by: Option<String>,
+    pub parts_replaced: Option<String>,
+    pub cost: Option<f64>,
+    pub next_maintenance_due: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct OverdueInspection {
+    pub equipment_id: i64,
+    pub equipment_number: String,
+    pub equipment_type: String,
+    pub location_id: Option<i64>,
+    pub location_name: Option<String>,
+    pub status: String,
+    pub last_inspection_date: Option<String>,
+    pub next_inspection_due: String,
+    pub days_overdue: i64,
+}
+
+// ── Equipment CRUD ──
+
+fn row_to_equipment(row: &rusqlite::Row<'_>) -> rusqlite::Result<Equipment> {
+    Ok(Equipment {
+        id: row.get(0)?,
+        equipment_type: row.get(1)?,
+        equipment_number: row.get(2)?,
+        manufacturer: row.get(3)?,
+        model: row.get(4)?,
+        serial_number: row.get(5)?,
+        purchase_date: row.get(6)?,
+        location_id: row.get(7)?,
+        status: row.get(8)?,
+        last_inspection_date: row.get(9)?,
+        next_inspection_due: row.get(10)?,
+        notes: row.get(11)?,
+        created_at: row.get(12)?,
+        updated_at: row.get(13)?,
+    })
+}
+
+const SELECT_COLS: &str = "id, equipment_type, equipment_number, manufacturer, model,
+    serial_number, purchase_date, location_id, status, last_inspection_date,
+    next_inspection_due, notes, created_at, updated_at";
+
+pub fn create_equipment(conn: &Connection, data: CreateEquipment) -> Result<Equipment> {
+    conn.execute(
+        "INSERT INTO equipment (equipment_type, equipment_number, manufacturer, model,
+                                serial_number, purchase_date, location_id, next_inspection_due, notes)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            data.equipment_type,
+            data.equipment_number,
+            data.manufacturer,
+            data.model,
+            data.serial_number,
+            data.purchase_date,
+            data.location_id,
+            data.next_inspection_due,
+            data.notes,
+        ],
+    )
+    .context("Failed to create equipment")?;
+
+    let id = conn.last_insert_rowid();
+    get_equipment(conn, id)
+}
+
+pub fn get_equipment(conn: &Connection, id: i64) -> Result<Equipment> {
+    conn.query_row(
+        &format!("SELECT {SELECT_COLS} FROM equipment WHERE id = ?1"),
+        [id],
+        row_to_equipment,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            AppError::NotFound(format!("Equipment {id} not found")).into()
+        }
+        _ => anyhow::Error::new(e),
+    })
+}
+
+pub fn list_equipment(conn: &Connection, filter: EquipmentFilter) -> Result<Vec<Equipment>> {
+    let mut sql = format!("SELECT {SELECT_COLS} FROM equipment WHERE 1 = 1");
+    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+    if let Some(loc_id) = filter.location_id {
+        sql.push_str(" AND location_id = ?");
+        values.push(Box::new(loc_id));
+    }
+    if let Some(ref t) = filter.equipment_type {
+        sql.push_str(" AND equipment_type = ?");
+        values.push(Box::new(t.clone()));
+    }
+    if let Some(ref status) = filter.status {
+        sql.push_str(" AND status = ?");
+        values.push(Box::new(status.clone()));
+    }
+
+    sql.push_str(" ORDER BY equipment_type, equipment_number");
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+    let rows = stmt
+        .query_map(params.as_slice(), row_to_equipment)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+pub fn update_equipment(conn: &Connection, id: i64, data: UpdateEquipment) -> Result<Equipment> {
+    let _existing = get_equipment(conn, id)?;
+
+    let mut sets = Vec::new();
+    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+    if let Some(ref v) = data.equipment_type {
+        sets.push("equipment_type = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.equipment_number {
+        sets.push("equipment_number = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.manufacturer {
+        sets.push("manufacturer = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.model {
+        sets.push("model = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.serial_number {
+        sets.push("serial_number = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.purchase_date {
+        sets.push("purchase_date = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(v) = data.location_id {
+        sets.push("location_id = ?");
+        values.push(Box::new(v));
+    }
+    if let Some(ref v) = data.status {
+        sets.push("status = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.next_inspection_due {
+        sets.push("next_inspection_due = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.notes {
+        sets.push("notes = ?");
+        values.push(Box::new(v.clone()));
+    }
+
+    if !sets.is_empty() {
+        sets.push("updated_at = datetime('now')");
+        let sql = format!("UPDATE equipment SET {} WHERE id = ?", sets.join(", "));
+        values.push(Box::new(id));
+        let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+        conn.execute(&sql, params.as_slice())
+            .context("Failed to update equipment")?;
+    }
+
+    get_equipment(conn, id)
+}
+
+pub fn delete_equipment(conn: &Connection, id: i64) -> Result<()> {
+    let changes = conn
+        .execute("DELETE FROM equipment WHERE id = ?1", [id])
+        .context("Failed to delete equipment")?;
+
+    if changes == 0 {
+        return Err(AppError::NotFound(format!("Equipment {id} not found")).into());
+    }
+    Ok(())
+}
+
+// ── Inspections ──
+
+fn default_next_inspection(inspection_date: &str, inspection_type: &str) -> Option<String> {
+    let days = INSPECTION_INTERVALS
+        .iter()
+        .find(|(t, _)| *t == inspection_type)
+        .map(|(_, d)| *d)?;
+    let date = NaiveDate::parse_from_str(inspection_date, "%Y-%m-%d").ok()?;
+    Some((date + Duration::days(days)).format("%Y-%m-%d").to_string())
+}
+
+/// Records an inspection and rolls it up onto the equipment row:
+/// `last_inspection_date` and `next_inspection_due` follow the newest
+/// inspection, a `fail` takes the unit out of service, and a `pass` puts an
+/// out-of-service unit back into service.
+pub fn record_inspection(
+    conn: &Connection,
+    data: CreateEquipmentInspection,
+) -> Result<EquipmentInspection> {
+    if !INSPECTION_RESULTS.contains(&data.result.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Invalid inspection result: {}. Must be one of: {}",
+            data.result,
+            INSPECTION_RESULTS.join(", ")
+        ))
+        .into());
+    }
+
+    let equipment = get_equipment(conn, data.equipment_id)?;
+    let next_due = data
+        .next_inspection_due
+        .clone()
+        .or_else(|| default_next_inspection(&data.inspection_date, &data.inspection_type));
+
+    let tx = conn.unchecked_transaction()?;
+
+    tx.execute(
+        "INSERT INTO equipment_inspections (equipment_id, inspection_date, inspector_name,
+                                            inspection_type, result, deficiencies,
+                                            corrective_actions, next_inspection_due)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            data.equipment_id,
+            data.inspection_date,
+            data.inspector_name,
+            data.inspection_type,
+            data.result,
+            data.deficiencies,
+            data.corrective_actions,
+            next_due,
+        ],
+    )
+    .context("Failed to record equipment inspection")?;
+    let id = tx.last_insert_rowid();
+
+    // A back-dated inspection shouldn't roll the schedule backwards
+    let is_latest = equipment
+        .last_inspection_date
+        .as_deref()
+        .is_none_or(|last| data.inspection_date.as_str() >= last);
+
+    if is_latest {
+        let status = match data.result.as_str() {
+            "fail" if equipment.status != "retired" => "out_of_service".to_string(),
+            "pass" if equipment.status == "out_of_service" => "active".to_string(),
+            _ => equipment.status.clone(),
+        };
+
+        tx.execute(
+            "UPDATE equipment SET
+                last_inspection_date = ?1,
+                next_inspection_due = COALESCE(?2, next_inspection_due),
+                status = ?3,
+                updated_at = datetime('now')
+             WHERE id = ?4",
+            params![data.inspection_date, next_due, status, data.equipment_id],
+        )
+        .context("Failed to update equipment inspection dates")?;
+    }
+
+    tx.commit()?;
+
+    conn.query_row(
+        "SELECT id, equipment_id, inspection_date, inspector_name, inspection_type, result,
+                deficiencies, corrective_actions, next_inspection_due, created_at
+         FROM equipment_inspections WHERE id = ?1",
+        [id],
+        row_to_inspection,
+    )
+    .map_err(anyhow::Error::new)
+}
+
+fn row_to_inspection(row: &rusqlite::Row<'_>) -> rusqlite::Result<EquipmentInspection> {
+    Ok(EquipmentInspection {
+        id: row.get(0)?,
+        equipment_id: row.get(1)?,
+        inspection_date: row.get(2)?,
+        inspector_name: row.get(3)?,
+        inspection_type: row.get(4)?,
+        result: row.get(5)?,
+        deficiencies: row.get(6)?,
+        corrective_actions: row.get(7)?,
+        next_inspection_due: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+pub fn list_inspections(conn: &Connection, equipment_id: i64) -> Result<Vec<EquipmentInspection>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, equipment_id, inspection_date, inspector_name, inspection_type, result,
+                deficiencies, corrective_actions, next_inspection_due, created_at
+         FROM equipment_inspections WHERE equipment_id = ?1
+         ORDER BY inspection_date DESC, id DESC",
+    )?;
+
+    let rows = stmt
+        .query_map([equipment_id], row_to_inspection)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+/// Every unit whose `next_inspection_due` is before `as_of`, across all
+/// locations, most overdue first. Retired units are excluded.
+pub fn list_overdue_inspections(conn: &Connection, as_of: &str) -> Result<Vec<OverdueInspection>> {
+    let mut stmt = conn.prepare(
+        "SELECT e.id, e.equipment_number, e.equipment_type, e.location_id, l.name,
+                e.status, e.last_inspection_date, e.next_inspection_due,
+                CAST(julianday(?1) - julianday(e.next_inspection_due) AS INTEGER)
+         FROM equipment e
+         LEFT JOIN locations l ON e.location_id = l.id
+         WHERE e.next_inspection_due < ?1
+           AND e.status != 'retired'
+         ORDER BY e.next_inspection_due",
+    )?;
+
+    let rows = stmt
+        .query_map([as_of], |row| {
+            Ok(OverdueInspection {
+                equipment_id: row.get(0)?,
+                equipment_number: row.get(1)?,
+                equipment_type: row.get(2)?,
+                location_id: row.get(3)?,
+                location_name: row.get(4)?,
+                status: row.get(5)?,
+                last_inspection_date: row.get(6)?,
+                next_inspection_due: row.get(7)?,
+                days_overdue: row.get(8)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+// ── Maintenance ──
+
+pub fn add_maintenance(
+    conn: &Connection,
+    data: CreateEquipmentMaintenance,
+) -> Result<EquipmentMaintenance> {
+    let _equipment = get_equipment(conn, data.equipment_id)?;
+
+    conn.execute(
+        "INSERT INTO equipment_maintenance (equipment_id, maintenance_date, maintenance_type,
+                                            description, performed_by, parts_replaced, cost,
+                                            next_maintenance_due)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            data.equipment_id,
+            data.maintenance_date,
+            data.maintenance_type,
+            data.description,
+            data.performed_by,
+            data.parts_replaced,
+            data.cost,
+            data.next_maintenance_due,
+        ],
+    )
+    .context("Failed to add maintenance record")?;
+
+    let id = conn.last_insert_rowid();
+    conn.query_row(
+        "SELECT id, equipment_id, maintenance_date, maintenance_type, description,
+                performed_by, parts_replaced, cost, next_maintenance_due, created_at
+         FROM equipment_maintenance WHERE id = ?1",
+        [id],
+        row_to_maintenance,
+    )
+    .map_err(anyhow::Error::new)
+}
+
+fn row_to_maintenance(row: &rusqlite::Row<'_>) -> rusqlite::Result<EquipmentMaintenance> {
+    Ok(EquipmentMaintenance {
+        id: row.get(0)?,
+        equipment_id: row.get(1)?,
+        maintenance_date: row.get(2)?,
+        maintenance_type: row.get(3)?,
+        description: row.get(4)?,
+        performed_by: row.get(5)?,
+        parts_replaced: row.get(6)?,
+        cost: row.get(7)?,
+        next_maintenance_due: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+pub fn list_maintenance(conn: &Connection, equipment_id: i64) -> Result<Vec<EquipmentMaintenance>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, equipment_id, maintenance_date, maintenance_type, description,
+                performed_by, parts_replaced, cost, next_maintenance_due, created_at
+         FROM equipment_maintenance WHERE equipment_id = ?1
+         ORDER BY maintenance_date DESC, id DESC",
+    )?;
+
+    let rows = stmt
+        .query_map([equipment_id], row_to_maintenance)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::audit::Actor;
+    use crate::db::locations::{create_establishment, create_location, CreateEstablishment, CreateLocation};
+    use crate::db::open_test_db;
+
+    fn setup(conn: &Connection) -> i64 {
+        let est = create_establishment(conn, &Actor::system(), CreateEstablishment {
+            name: "Test Co".into(),
+            street_address: None, city: None, state: None,
+            zip_code: None, industry_description: None, naics_code: None,
+            ein: None, company_name: None, establishment_type: None, case_number_format: None,
+        }).unwrap();
+        create_location(conn, &Actor::system(), CreateLocation {
+            establishment_id: est.id, name: "Yard".into(),
+            address: None, city: None, state: None,
+        }).unwrap().id
+    }
+
+    fn make_equipment(conn: &Connection, loc_id: i64, number: &str, due: Option<&str>) -> Equipment {
+        create_equipment(conn, CreateEquipment {
+            equipment_type: "forklift".into(), equipment_number: number.into(),
+            manufacturer: None, model: None, serial_number: None, purchase_date: None,
+            location_id: Some(loc_id), next_inspection_due: due.map(String::from), notes: None,
+        }).unwrap()
+    }
+
+    fn inspect(conn: &Connection, equipment_id: i64, date: &str, result: &str) -> EquipmentInspection {
+        record_inspection(conn, CreateEquipmentInspection {
+            equipment_id, inspection_date: date.into(), inspector_name: "Ira".into(),
+            inspection_type: "monthly".into(), result: result.into(),
+            deficiencies: None, corrective_actions: None, next_inspection_due: None,
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_inspection_updates_equipment() {
+        let conn = open_test_db();
+        let loc_id = setup(&conn);
+        let fl = make_equipment(&conn, loc_id, "FL-01", None);
+
+        let insp = inspect(&conn, fl.id, "2026-03-01", "pass");
+        assert_eq!(insp.next_inspection_due.as_deref(), Some("2026-03-31"));
+
+        let after_pass = get_equipment(&conn, fl.id).unwrap();
+        assert_eq!(after_pass.last_inspection_date.as_deref(), Some("2026-03-01"));
+        assert_eq!(after_pass.next_inspection_due.as_deref(), Some("2026-03-31"));
+        assert_eq!(after_pass.status, "active");
+
+        inspect(&conn, fl.id, "2026-03-31", "fail");
+        assert_eq!(get_equipment(&conn, fl.id).unwrap().status, "out_of_service");
+
+        // Back-dated entry is kept in history but doesn't move the schedule
+        inspect(&conn, fl.id, "2026-02-01", "pass");
+        let current = get_equipment(&conn, fl.id).unwrap();
+        assert_eq!(current.last_inspection_date.as_deref(), Some("2026-03-31"));
+        assert_eq!(current.status, "out_of_service");
+        assert_eq!(list_inspections(&conn, fl.id).unwrap().len(), 3);
+
+        inspect(&conn, fl.id, "2026-04-02", "pass");
+        assert_eq!(get_equipment(&conn, fl.id).unwrap().status, "active");
+    }
+
+    #[test]
+    fn test_overdue_inspections() {
+        let conn = open_test_db();
+        let loc_id = setup(&conn);
+
+        make_equipment(&conn, loc_id, "FL-01", Some("2026-01-15"));
+        make_equipment(&conn, loc_id, "FL-02", Some("2026-03-01"));
+        make_equipment(&conn, loc_id, "FL-03", None);
+        let retired = make_equipment(&conn, loc_id, "FL-04", Some("2025-12-01"));
+        update_equipment(&conn, retired.id, UpdateEquipment {
+            status: Some("retired".into()),
+            equipment_type: None, equipment_number: None, manufacturer: None, model: None,
+            serial_number: None, purchase_date: None, location_id: None,
+            next_inspection_due: None, notes: None,
+        }).unwrap();
+
+        let overdue = list_overdue_inspections(&conn, "2026-02-01").unwrap();
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].equipment_number, "FL-01");
+        assert_eq!(overdue[0].days_overdue, 17);
+        assert_eq!(overdue[0].location_name.as_deref(), Some("Yard"));
+    }
+
+    #[test]
+    fn test_maintenance_history() {
+        let conn = open_test_db();
+        let loc_id = setup(&conn);
+        let fl = make_equipment(&conn, loc_id, "FL-01", None);
+
+        add_maintenance(&conn, CreateEquipmentMaintenance {
+            equipment_id: fl.id, maintenance_date: "2026-02-10".into(),
+            maintenance_type: "repair".into(), description: "Replaced mast chain".into(),
+            performed_by: Some("Dealer".into()), parts_replaced: Some("Mast chain".into()),
+            cost: Some(840.0), next_maintenance_due: None,
+        }).unwrap();
+
+        assert_eq!(list_maintenance(&conn, fl.id).unwrap().len(), 1);
+        assert!(record_inspection(&conn, CreateEquipmentInspection {
+            equipment_id: fl.id, inspection_date: "2026-02-11".into(), inspector_name: "Ira".into(),
+            inspection_type: "daily".into(), result: "ok".into(),
+            deficiencies: None, corrective_actions: None, next_inspection_due: None,
+        }).is_err());
+    }
+}
@@ -1,13 +1,20 @@
 use anyhow::{Context, Result};
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 
+use crate::db::audit::{self, Actor};
+use crate::db::osha;
+use crate::db::search;
+use crate::db::trash;
 use crate::errors::AppError;
+use crate::redaction::Audience;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Incident {
     pub id: i64,
     pub case_number: Option<i64>,
+    /// The case number as printed, in the establishment's format
+    pub case_label: Option<String>,
     pub establishment_id: i64,
     pub location_id: Option<i64>,
 
@@ -21,6 +28,8 @@ pub struct Incident {
     pub employee_hire_date: Option<String>,
     pub employee_gender: Option<String>,
     pub is_privacy_case: bool,
+    pub privacy_concern: Option<String>,
+    pub privacy_description: Option<String>,
 
     pub incident_date: String,
     pub incident_time: Option<String>,
@@ -56,6 +65,9 @@ pub struct Incident {
 
     pub created_at: String,
     pub updated_at: String,
+    /// Set while the incident is in the trash
+    pub deleted_at: Option<String>,
+    pub deleted_by: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -72,6 +84,8 @@ pub struct CreateIncident {
     pub employee_hire_date: Option<String>,
     pub employee_gender: Option<String>,
     pub is_privacy_case: Option<bool>,
+    pub privacy_concern: Option<String>,
+    pub privacy_description: Option<String>,
     pub incident_date: String,
     pub incident_time: Option<String>,
     pub work_start_time: Option<String>,
@@ -108,6 +122,8 @@ pub struct UpdateIncident {
     pub employee_hire_date: Option<String>,
     pub employee_gender: Option<String>,
     pub is_privacy_case: Option<bool>,
+    pub privacy_concern: Option<String>,
+    pub privacy_description: Option<String>,
     pub incident_date: Option<String>,
     pub incident_time: Option<String>,
     pub work_start_time: Option<String>,
@@ -129,41 +145,395 @@ pub struct UpdateIncident {
     pub date_of_death: Option<String>,
     pub injury_illness_type: Option<String>,
     pub is_recordable: Option<bool>,
-    pub status: Option<String>,
     pub completed_by: Option<String>,
     pub completed_by_title: Option<String>,
     pub completed_by_phone: Option<String>,
     pub completed_date: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Default)]
 pub struct IncidentFilter {
     pub establishment_id: i64,
     pub location_id: Option<i64>,
+    /// Any of these locations; combined with `location_id` when both are set
+    #[serde(default)]
+    pub location_ids: Vec<i64>,
     pub status: Option<String>,
     pub outcome_severity: Option<String>,
+    pub injury_illness_type: Option<String>,
+    pub is_recordable: Option<bool>,
+    pub case_number_from: Option<i64>,
+    pub case_number_to: Option<i64>,
     pub date_from: Option<String>,
     pub date_to: Option<String>,
     pub search: Option<String>,
+    /// One of the keys in [`SORT_COLUMNS`]; newest first when unset
+    pub sort_by: Option<String>,
+    #[serde(default)]
+    pub sort_dir: SortDirection,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortDirection {
+    fn sql(self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+}
+
+/// Columns the incident list can be sorted on, keyed by the name the UI sends.
+pub const SORT_COLUMNS: &[(&str, &str)] = &[
+    ("incident_date", "incident_date"),
+    ("case_number", "case_number"),
+    ("employee_name", "employee_name COLLATE NOCASE"),
+    ("outcome_severity", "outcome_severity"),
+    ("injury_illness_type", "injury_illness_type"),
+    ("status", "status"),
+    ("days_away_count", "days_away_count"),
+    ("days_restricted_count", "days_restricted_count"),
+    ("created_at", "created_at"),
+    ("updated_at", "updated_at"),
+];
+
+/// Name sort for restricted audiences: privacy cases sort under the masked
+/// name, so their position doesn't give the employee away.
+const MASKED_NAME_SORT: &str =
+    "(CASE WHEN is_privacy_case = 1 THEN 'Privacy Case' ELSE employee_name END) COLLATE NOCASE";
+
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+pub const MAX_PAGE_SIZE: i64 = 500;
+
+/// One page of the incident list and the number of rows matching the filter.
+#[derive(Debug, Serialize)]
+pub struct IncidentPage {
+    pub incidents: Vec<Incident>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+pub fn sort_column(key: &str) -> Option<&'static str> {
+    SORT_COLUMNS.iter().find(|(k, _)| *k == key).map(|(_, col)| *col)
+}
+
+// ── Case numbers ──
+
+/// Display format used when an establishment has none set.
+pub const DEFAULT_CASE_NUMBER_FORMAT: &str = "{seq}";
+
+/// Renders a case number. `{year}` is the four-digit year and `{seq}` the
+/// sequence, zero-padded with `{seq:4}`; anything else is copied as is, so
+/// `{year}-EST01-{seq:4}` gives `2026-EST01-0007`.
+pub fn format_case_number(format: &str, year: &str, seq: i64) -> std::result::Result<String, AppError> {
+    let invalid = |msg: String| AppError::Validation(format!("Invalid case number format: {msg}"));
+    let mut out = String::new();
+    let mut rest = format;
+    let mut has_seq = false;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let len = rest[start..].find('}').ok_or_else(|| invalid("unclosed {".into()))?;
+        let token = &rest[start + 1..start + len];
+        match token.split_once(':') {
+            None if token == "year" => out.push_str(year),
+            None if token == "seq" => {
+                out.push_str(&seq.to_string());
+                has_seq = true;
+            }
+            Some(("seq", width)) => {
+                let width: usize = width
+                    .parse()
+                    .ok()
+                    .filter(|w| (1..=9).contains(w))
+                    .ok_or_else(|| invalid(format!("padding in {{{token}}} must be 1 to 9")))?;
+                out.push_str(&format!("{seq:0width$}"));
+                has_seq = true;
+            }
+            _ => return Err(invalid(format!("unknown field {{{token}}}"))),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    if rest.contains('}') {
+        return Err(invalid("unmatched }".into()));
+    }
+    out.push_str(rest);
+
+    if !has_seq {
+        return Err(invalid("it must include {seq}".into()));
+    }
+    Ok(out)
 }
 
-fn next_case_number(conn: &Connection, establishment_id: i64, year: &str) -> Result<i64> {
+/// Case numbers restart each calendar year.
+fn case_year(incident_date: &str) -> &str {
+    incident_date.get(..4).unwrap_or(incident_date)
+}
+
+/// The next free number in an establishment-year and its label. Call inside
+/// an immediate transaction so no other writer can take the same number.
+pub(crate) fn next_case_number(conn: &Connection, establishment_id: i64, year: &str) -> Result<(i64, String)> {
     let max: Option<i64> = conn
         .query_row(
             "SELECT MAX(case_number) FROM incidents
-             WHERE establishment_id = ?1 AND incident_date LIKE ?2",
-            params![establishment_id, format!("{year}%")],
+             WHERE establishment_id = ?1 AND substr(incident_date, 1, 4) = ?2",
+            params![establishment_id, year],
             |row| row.get(0),
         )
         .context("Failed to query max case number")?;
 
-    Ok(max.unwrap_or(0) + 1)
+    let seq = max.unwrap_or(0) + 1;
+    Ok((seq, case_label(conn, establishment_id, year, seq)?))
+}
+
+fn case_label(conn: &Connection, establishment_id: i64, year: &str, seq: i64) -> Result<String> {
+    let format: Option<String> = conn
+        .query_row(
+            "SELECT case_number_format FROM establishments WHERE id = ?1",
+            [establishment_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("Failed to get case number format")?
+        .flatten();
+    // A format synced from a newer install that this one can't read falls
+    // back to the plain number rather than blocking new cases
+    Ok(format_case_number(format.as_deref().unwrap_or(DEFAULT_CASE_NUMBER_FORMAT), year, seq)
+        .unwrap_or_else(|_| seq.to_string()))
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CaseRenumbering {
+    pub incident_id: i64,
+    pub old_case_number: Option<i64>,
+    pub new_case_number: i64,
+    pub old_case_label: Option<String>,
+    pub new_case_label: String,
+}
+
+/// Numbers an establishment's cases for `year` 1, 2, 3… in the order they
+/// occurred and relabels them in the current format. Each case whose number
+/// or label changes is written to the audit log.
+pub fn renumber_cases(
+    conn: &Connection,
+    actor: &Actor,
+    establishment_id: i64,
+    year: i64,
+) -> Result<Vec<CaseRenumbering>> {
+    super::immediate_transaction(conn, |conn| {
+        let year = format!("{year:04}");
+        let cases = {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {SELECT_COLS} FROM incidents
+                 WHERE establishment_id = ?1 AND substr(incident_date, 1, 4) = ?2
+                 ORDER BY incident_date, COALESCE(incident_time, ''), id"
+            ))?;
+            let rows = stmt
+                .query_map(params![establishment_id, year], row_to_incident)?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+
+        // Clear first so the unique index never sees two cases mid-shuffle
+        conn.execute(
+            "UPDATE incidents SET case_number = NULL, case_number_duplicate = 0
+             WHERE establishment_id = ?1 AND substr(incident_date, 1, 4) = ?2",
+            params![establishment_id, year],
+        )
+        .context("Failed to clear case numbers")?;
+
+        let mut changes = Vec::new();
+        for (i, existing) in cases.iter().enumerate() {
+            let seq = i as i64 + 1;
+            let label = case_label(conn, establishment_id, &year, seq)?;
+            let changed = existing.case_number != Some(seq) || existing.case_label.as_deref() != Some(label.as_str());
+            conn.execute(
+                "UPDATE incidents SET case_number = ?1, case_label = ?2,
+                        updated_at = CASE WHEN ?3 THEN datetime('now') ELSE updated_at END
+                 WHERE id = ?4",
+                params![seq, label, changed, existing.id],
+            )
+            .context("Failed to renumber case")?;
+
+            if changed {
+                let updated = get_incident(conn, existing.id)?;
+                audit::log_update(conn, actor, "incidents", existing.id, existing, &updated)?;
+                changes.push(CaseRenumbering {
+                    incident_id: existing.id,
+                    old_case_number: existing.case_number,
+                    new_case_number: seq,
+                    old_case_label: existing.case_label.clone(),
+                    new_case_label: label,
+                });
+            }
+        }
+        Ok(changes)
+    })
+}
+
+// ── Status workflow ──
+
+/// Lifecycle order. New cases start as `reported`.
+pub const INCIDENT_STATUSES: &[&str] = &[
+    "reported",
+    "under_investigation",
+    "pending_rca",
+    "pending_corrective_actions",
+    "closed",
+];
+
+/// Allowed transitions and the minimum role for each. Cases that need no
+/// RCA or follow-up can close straight from investigation; reopening a
+/// closed case sends it back to investigation and needs a reason.
+const TRANSITIONS: &[(&str, &str, &str)] = &[
+    ("reported", "under_investigation", "supervisor"),
+    ("under_investigation", "pending_rca", "supervisor"),
+    ("under_investigation", "closed", "supervisor"),
+    ("pending_rca", "under_investigation", "supervisor"),
+    ("pending_rca", "pending_corrective_actions", "supervisor"),
+    ("pending_rca", "closed", "supervisor"),
+    ("pending_corrective_actions", "pending_rca", "supervisor"),
+    ("pending_corrective_actions", "closed", "supervisor"),
+    ("closed", "under_investigation", "safety_manager"),
+];
+
+/// Minimum role for moving an incident from `from` to `to`, or `None` if the
+/// workflow doesn't allow it.
+pub fn transition_role(from: &str, to: &str) -> Option<&'static str> {
+    TRANSITIONS
+        .iter()
+        .find(|(f, t, _)| *f == from && *t == to)
+        .map(|(_, _, role)| *role)
+}
+
+/// Statuses reachable from `from`.
+pub fn next_statuses(from: &str) -> Vec<&'static str> {
+    TRANSITIONS
+        .iter()
+        .filter(|(f, _, _)| *f == from)
+        .map(|(_, t, _)| *t)
+        .collect()
+}
+
+pub fn is_reopen(from: &str, to: &str) -> bool {
+    from == "closed" && to != "closed"
+}
+
+/// Everything that stops the incident from being closed. An empty list
+/// means it can close.
+pub fn closure_blockers(conn: &Connection, incident: &Incident) -> Result<Vec<String>> {
+    let mut blockers = Vec::new();
+
+    let open_rca: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM rca_sessions WHERE incident_id = ?1 AND status != 'completed'",
+        [incident.id],
+        |row| row.get(0),
+    )?;
+    if open_rca > 0 {
+        blockers.push(format!("{open_rca} root cause analysis session(s) still in progress"));
+    }
+
+    let open_actions: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM corrective_actions WHERE incident_id = ?1 AND status != 'completed'",
+        [incident.id],
+        |row| row.get(0),
+    )?;
+    if open_actions > 0 {
+        blockers.push(format!("{open_actions} corrective action(s) not completed"));
+    }
+
+    // The 301 must say who completed it and when, 1904.29(b)(3)
+    if incident.is_recordable {
+        let blank = |v: &Option<String>| v.as_deref().is_none_or(|s| s.trim().is_empty());
+        if blank(&incident.completed_by) {
+            blockers.push("Recordable cases need the name of who completed the report".into());
+        }
+        if blank(&incident.completed_date) {
+            blockers.push("Recordable cases need the date the report was completed".into());
+        }
+    }
+    Ok(blockers)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IncidentStatusChange {
+    pub id: i64,
+    pub incident_id: i64,
+    pub from_status: String,
+    pub to_status: String,
+    pub reason: Option<String>,
+    pub changed_by: Option<String>,
+    pub changed_at: String,
+}
+
+/// Moves an incident to `status` and records the change with `reason`. The
+/// transition, closure checks and reopen reason must already have been
+/// checked.
+pub fn update_status(
+    conn: &Connection,
+    actor: &Actor,
+    id: i64,
+    status: &str,
+    reason: Option<&str>,
+    changed_by: &str,
+) -> Result<Incident> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_incident(conn, id)?;
+        conn.execute(
+            "UPDATE incidents SET status = ?1, updated_at = datetime('now') WHERE id = ?2",
+            params![status, id],
+        )
+        .context("Failed to update incident status")?;
+        conn.execute(
+            "INSERT INTO incident_status_changes (incident_id, from_status, to_status, reason, changed_by)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![id, existing.status, status, reason, changed_by],
+        )
+        .context("Failed to record status change")?;
+
+        let updated = get_incident(conn, id)?;
+        audit::log_update(conn, actor, "incidents", id, &existing, &updated)?;
+        Ok(updated)
+    })
+}
+
+pub fn list_status_changes(conn: &Connection, incident_id: i64) -> Result<Vec<IncidentStatusChange>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, incident_id, from_status, to_status, reason, changed_by, changed_at
+         FROM incident_status_changes WHERE incident_id = ?1 ORDER BY changed_at, id",
+    )?;
+    let rows = stmt
+        .query_map([incident_id], |row| {
+            Ok(IncidentStatusChange {
+                id: row.get(0)?,
+                incident_id: row.get(1)?,
+                from_status: row.get(2)?,
+                to_status: row.get(3)?,
+                reason: row.get(4)?,
+                changed_by: row.get(5)?,
+                changed_at: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to list status changes")?;
+    Ok(rows)
 }
 
 fn row_to_incident(row: &rusqlite::Row<'_>) -> rusqlite::Result<Incident> {
     Ok(Incident {
         id: row.get(0)?,
         case_number: row.get(1)?,
+        case_label: row.get(44)?,
         establishment_id: row.get(2)?,
         location_id: row.get(3)?,
         employee_name: row.get(4)?,
@@ -176,6 +546,8 @@ fn row_to_incident(row: &rusqlite::Row<'_>) -> rusqlite::Result<Incident> {
         employee_hire_date: row.get(11)?,
         employee_gender: row.get(12)?,
         is_privacy_case: row.get::<_, i32>(13)? != 0,
+        privacy_concern: row.get(42)?,
+        privacy_description: row.get(43)?,
         incident_date: row.get(14)?,
         incident_time: row.get(15)?,
         work_start_time: row.get(16)?,
@@ -204,6 +576,8 @@ fn row_to_incident(row: &rusqlite::Row<'_>) -> rusqlite::Result<Incident> {
         completed_date: row.get(39)?,
         created_at: row.get(40)?,
         updated_at: row.get(41)?,
+        deleted_at: row.get(45)?,
+        deleted_by: row.get(46)?,
     })
 }
 
@@ -218,11 +592,15 @@ const SELECT_COLS: &str = "id, case_number, establishment_id, location_id,
     outcome_severity, days_away_count, days_restricted_count, date_of_death,
     injury_illness_type, is_recordable, status,
     completed_by, completed_by_title, completed_by_phone, completed_date,
-    created_at, updated_at";
+    created_at, updated_at, privacy_concern, privacy_description, case_label, deleted_at, deleted_by";
 
-pub fn create_incident(conn: &Connection, data: CreateIncident) -> Result<Incident> {
-    let year = &data.incident_date[..4];
-    let case_num = next_case_number(conn, data.establishment_id, year)?;
+pub fn create_incident(conn: &Connection, actor: &Actor, data: CreateIncident) -> Result<Incident> {
+    super::immediate_transaction(conn, |conn| insert_incident(conn, actor, data))
+}
+
+fn insert_incident(conn: &Connection, actor: &Actor, data: CreateIncident) -> Result<Incident> {
+    let (case_num, case_label) =
+        next_case_number(conn, data.establishment_id, case_year(&data.incident_date))?;
 
     conn.execute(
         &format!(
@@ -236,11 +614,11 @@ pub fn create_incident(conn: &Connection, data: CreateIncident) -> Result<Incide
                 physician_name, treatment_facility, facility_address, facility_city_state_zip,
                 treated_in_er, hospitalized_overnight,
                 outcome_severity, days_away_count, days_restricted_count, date_of_death,
-                injury_illness_type, is_recordable
+                injury_illness_type, is_recordable, privacy_concern, privacy_description, case_label, status
             ) VALUES (
                 ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13,
                 ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26,
-                ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34
+                ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34, ?35, ?36, ?37, 'reported'
             )"
         ),
         params![
@@ -256,7 +634,7 @@ pub fn create_incident(conn: &Connection, data: CreateIncident) -> Result<Incide
             data.employee_dob,
             data.employee_hire_date,
             data.employee_gender,
-            data.is_privacy_case.unwrap_or(false) as i32,
+            (data.is_privacy_case.unwrap_or(false) || data.privacy_concern.is_some()) as i32,
             data.incident_date,
             data.incident_time,
             data.work_start_time,
@@ -278,12 +656,17 @@ pub fn create_incident(conn: &Connection, data: CreateIncident) -> Result<Incide
             data.date_of_death,
             data.injury_illness_type.as_deref().unwrap_or("injury"),
             data.is_recordable.unwrap_or(true) as i32,
+            data.privacy_concern,
+            data.privacy_description,
+            case_label,
         ],
     )
     .context("Failed to create incident")?;
 
     let id = conn.last_insert_rowid();
-    get_incident(conn, id)
+    let created = get_incident(conn, id)?;
+    audit::log_create(conn, actor, "incidents", id, &created)?;
+    Ok(created)
 }
 
 pub fn get_incident(conn: &Connection, id: i64) -> Result<Incident> {
@@ -300,8 +683,10 @@ pub fn get_incident(conn: &Connection, id: i64) -> Result<Incident> {
     })
 }
 
-pub fn list_incidents(conn: &Connection, filter: IncidentFilter) -> Result<Vec<Incident>> {
-    let mut sql = format!("SELECT {SELECT_COLS} FROM incidents WHERE establishment_id = ?");
+/// Restricted audiences never match privacy cases by search, since the
+/// indexed text includes the employee's name, and sort them by the masked name.
+pub fn list_incidents(conn: &Connection, filter: IncidentFilter, audience: Audience) -> Result<IncidentPage> {
+    let mut sql = String::from(" FROM incidents WHERE establishment_id = ? AND deleted_at IS NULL");
     let mut values: Vec<Box<dyn rusqlite::types::ToSql>> =
         vec![Box::new(filter.establishment_id)];
 
@@ -309,6 +694,13 @@ pub fn list_incidents(conn: &Connection, filter: IncidentFilter) -> Result<Vec<I
         sql.push_str(" AND location_id = ?");
         values.push(Box::new(*loc_id));
     }
+    if !filter.location_ids.is_empty() {
+        let marks = vec!["?"; filter.location_ids.len()].join(", ");
+        sql.push_str(&format!(" AND location_id IN ({marks})"));
+        for id in &filter.location_ids {
+            values.push(Box::new(*id));
+        }
+    }
     if let Some(ref status) = filter.status {
         sql.push_str(" AND status = ?");
         values.push(Box::new(status.clone()));
@@ -317,6 +709,22 @@ pub fn list_incidents(conn: &Connection, filter: IncidentFilter) -> Result<Vec<I
         sql.push_str(" AND outcome_severity = ?");
         values.push(Box::new(sev.clone()));
     }
+    if let Some(ref kind) = filter.injury_illness_type {
+        sql.push_str(" AND injury_illness_type = ?");
+        values.push(Box::new(kind.clone()));
+    }
+    if let Some(recordable) = filter.is_recordable {
+        sql.push_str(" AND is_recordable = ?");
+        values.push(Box::new(recordable as i32));
+    }
+    if let Some(from) = filter.case_number_from {
+        sql.push_str(" AND case_number >= ?");
+        values.push(Box::new(from));
+    }
+    if let Some(to) = filter.case_number_to {
+        sql.push_str(" AND case_number <= ?");
+        values.push(Box::new(to));
+    }
     if let Some(ref from) = filter.date_from {
         sql.push_str(" AND incident_date >= ?");
         values.push(Box::new(from.clone()));
@@ -325,26 +733,58 @@ pub fn list_incidents(conn: &Connection, filter: IncidentFilter) -> Result<Vec<I
         sql.push_str(" AND incident_date <= ?");
         values.push(Box::new(to.clone()));
     }
-    if let Some(ref search) = filter.search {
-        sql.push_str(" AND (employee_name LIKE ? OR description LIKE ?)");
-        let pat = format!("%{search}%");
-        values.push(Box::new(pat.clone()));
-        values.push(Box::new(pat));
+    if let Some(query) = filter.search.as_deref().and_then(search::fts_query) {
+        sql.push_str(
+            " AND id IN (SELECT incident_id FROM incident_search
+                         WHERE incident_search MATCH ? AND source = 'incident')",
+        );
+        values.push(Box::new(query));
+        if audience == Audience::Restricted {
+            sql.push_str(" AND is_privacy_case = 0");
+        }
     }
 
-    sql.push_str(" ORDER BY incident_date DESC, id DESC");
-
-    let mut stmt = conn.prepare(&sql)?;
     let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+    let total: i64 = conn
+        .query_row(&format!("SELECT COUNT(*){sql}"), params.as_slice(), |row| row.get(0))
+        .context("Failed to count incidents")?;
+
+    let order = match filter.sort_by.as_deref() {
+        Some(key) => {
+            let col = match sort_column(key) {
+                Some(_) if key == "employee_name" && audience == Audience::Restricted => MASKED_NAME_SORT,
+                Some(col) => col,
+                None => return Err(AppError::Validation(format!("Cannot sort incidents by {key}")).into()),
+            };
+            let dir = filter.sort_dir.sql();
+            format!("{col} {dir}, id {dir}")
+        }
+        None => "incident_date DESC, id DESC".to_string(),
+    };
+    let limit = filter.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = filter.offset.unwrap_or(0).max(0);
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {SELECT_COLS}{sql} ORDER BY {order} LIMIT {limit} OFFSET {offset}"
+    ))?;
     let rows = stmt
         .query_map(params.as_slice(), row_to_incident)?
         .collect::<Result<Vec<_>, _>>()?;
 
-    Ok(rows)
+    Ok(IncidentPage { incidents: rows, total, limit, offset })
 }
 
-pub fn update_incident(conn: &Connection, id: i64, data: UpdateIncident) -> Result<Incident> {
-    let _existing = get_incident(conn, id)?;
+pub fn update_incident(
+    conn: &Connection,
+    actor: &Actor,
+    id: i64,
+    data: UpdateIncident,
+) -> Result<Incident> {
+    super::immediate_transaction(conn, |conn| apply_update(conn, actor, id, data))
+}
+
+fn apply_update(conn: &Connection, actor: &Actor, id: i64, data: UpdateIncident) -> Result<Incident> {
+    let existing = get_incident(conn, id)?;
 
     // Build dynamic SET clause
     macro_rules! push_field {
@@ -387,7 +827,11 @@ pub fn update_incident(conn: &Connection, id: i64, data: UpdateIncident) -> Resu
     push_field!(sets, values, data.employee_dob, "employee_dob");
     push_field!(sets, values, data.employee_hire_date, "employee_hire_date");
     push_field!(sets, values, data.employee_gender, "employee_gender");
-    push_bool_field!(sets, values, data.is_privacy_case, "is_privacy_case");
+    // Naming a privacy concern makes it a privacy case
+    let is_privacy_case = data.privacy_concern.as_ref().map(|_| true).or(data.is_privacy_case);
+    push_bool_field!(sets, values, is_privacy_case, "is_privacy_case");
+    push_field!(sets, values, data.privacy_concern, "privacy_concern");
+    push_field!(sets, values, data.privacy_description, "privacy_description");
     push_field!(sets, values, data.incident_date, "incident_date");
     push_field!(sets, values, data.incident_time, "incident_time");
     push_field!(sets, values, data.work_start_time, "work_start_time");
@@ -409,12 +853,23 @@ pub fn update_incident(conn: &Connection, id: i64, data: UpdateIncident) -> Resu
     push_field!(sets, values, data.date_of_death, "date_of_death");
     push_field!(sets, values, data.injury_illness_type, "injury_illness_type");
     push_bool_field!(sets, values, data.is_recordable, "is_recordable");
-    push_field!(sets, values, data.status, "status");
     push_field!(sets, values, data.completed_by, "completed_by");
     push_field!(sets, values, data.completed_by_title, "completed_by_title");
     push_field!(sets, values, data.completed_by_phone, "completed_by_phone");
     push_field!(sets, values, data.completed_date, "completed_date");
 
+    // Moving a case into another year takes the next number in that year
+    if let Some(ref date) = data.incident_date {
+        if existing.case_number.is_some() && case_year(date) != case_year(&existing.incident_date) {
+            let (number, label) = next_case_number(conn, existing.establishment_id, case_year(date))?;
+            sets.push("case_number = ?");
+            values.push(Box::new(number));
+            sets.push("case_label = ?");
+            values.push(Box::new(label));
+            sets.push("case_number_duplicate = 0");
+        }
+    }
+
     if !sets.is_empty() {
         sets.push("updated_at = datetime('now')");
         let sql = format!("UPDATE incidents SET {} WHERE id = ?", sets.join(", "));
@@ -424,18 +879,46 @@ pub fn update_incident(conn: &Connection, id: i64, data: UpdateIncident) -> Resu
             .context("Failed to update incident")?;
     }
 
-    get_incident(conn, id)
+    let updated = get_incident(conn, id)?;
+    if !sets.is_empty() {
+        osha::record_amendments(conn, actor, &existing, &updated)?;
+        audit::log_update(conn, actor, "incidents", id, &existing, &updated)?;
+    }
+    Ok(updated)
+}
+
+/// Moves the incident to the trash. Its attachments, RCA and corrective
+/// actions stay with it until it is purged.
+pub fn delete_incident(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_incident(conn, id)?;
+        trash::mark_deleted(conn, actor, "incidents", id)
+            .map_err(|e| trash::state_error(e, || format!("Incident {id} is already in the trash")))?;
+        audit::log_delete(conn, actor, "incidents", id, &existing)?;
+        Ok(())
+    })
 }
 
-pub fn delete_incident(conn: &Connection, id: i64) -> Result<()> {
-    let changes = conn
-        .execute("DELETE FROM incidents WHERE id = ?1", [id])
-        .context("Failed to delete incident")?;
+pub fn restore_incident(conn: &Connection, actor: &Actor, id: i64) -> Result<Incident> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_incident(conn, id)?;
+        trash::clear_deleted(conn, "incidents", id)
+            .map_err(|e| trash::state_error(e, || format!("Incident {id} is not in the trash")))?;
+        let restored = get_incident(conn, id)?;
+        audit::record(conn, actor, "restore", "incidents", id, Some(&existing), Some(&restored))?;
+        Ok(restored)
+    })
+}
 
-    if changes == 0 {
-        return Err(AppError::NotFound(format!("Incident {id} not found")).into());
-    }
-    Ok(())
+/// Deletes a trashed incident for good, with everything attached to it.
+pub fn purge_incident(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_incident(conn, id)?;
+        trash::purge_row(conn, "incidents", id)
+            .map_err(|e| trash::state_error(e, || format!("Incident {id} is not in the trash")))?;
+        audit::record(conn, actor, "purge", "incidents", id, Some(&existing), None)?;
+        Ok(())
+    })
 }
 
 // ── Attachments ──
@@ -453,20 +936,29 @@ pub struct Attachment {
 
 pub fn add_attachment(
     conn: &Connection,
+    actor: &Actor,
     incident_id: i64,
     file_name: &str,
     file_path: &str,
     file_type: &str,
     file_size: Option<i64>,
 ) -> Result<Attachment> {
-    conn.execute(
-        "INSERT INTO attachments (incident_id, file_name, file_path, file_type, file_size)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
-        params![incident_id, file_name, file_path, file_type, file_size],
-    )
-    .context("Failed to add attachment")?;
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO attachments (incident_id, file_name, file_path, file_type, file_size)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![incident_id, file_name, file_path, file_type, file_size],
+        )
+        .context("Failed to add attachment")?;
 
-    let id = conn.last_insert_rowid();
+        let id = conn.last_insert_rowid();
+        let created = get_attachment(conn, id)?;
+        audit::log_create(conn, actor, "attachments", id, &created)?;
+        Ok(created)
+    })
+}
+
+fn get_attachment(conn: &Connection, id: i64) -> Result<Attachment> {
     conn.query_row(
         "SELECT id, incident_id, file_name, file_path, file_type, file_size, created_at
          FROM attachments WHERE id = ?1",
@@ -483,7 +975,12 @@ pub fn add_attachment(
             })
         },
     )
-    .map_err(|e| anyhow::Error::new(e))
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            AppError::NotFound(format!("Attachment {id} not found")).into()
+        }
+        _ => anyhow::Error::new(e),
+    })
 }
 
 pub fn list_attachments(conn: &Connection, incident_id: i64) -> Result<Vec<Attachment>> {
@@ -509,15 +1006,19 @@ pub fn list_attachments(conn: &Connection, incident_id: i64) -> Result<Vec<Attac
     Ok(rows)
 }
 
-pub fn delete_attachment(conn: &Connection, id: i64) -> Result<()> {
-    let changes = conn
-        .execute("DELETE FROM attachments WHERE id = ?1", [id])
-        .context("Failed to delete attachment")?;
+pub fn delete_attachment(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_attachment(conn, id)?;
+        let changes = conn
+            .execute("DELETE FROM attachments WHERE id = ?1", [id])
+            .context("Failed to delete attachment")?;
 
-    if changes == 0 {
-        return Err(AppError::NotFound(format!("Attachment {id} not found")).into());
-    }
-    Ok(())
+        if changes == 0 {
+            return Err(AppError::NotFound(format!("Attachment {id} not found")).into());
+        }
+        audit::log_delete(conn, actor, "attachments", id, &existing)?;
+        Ok(())
+    })
 }
 
 #[cfg(test)]
@@ -529,6 +1030,7 @@ mod tests {
     fn setup_test_data(conn: &Connection) -> (i64, i64) {
         let est = create_establishment(
             conn,
+            &Actor::system(),
             CreateEstablishment {
                 name: "Test Co".into(),
                 street_address: None,
@@ -537,11 +1039,16 @@ mod tests {
                 zip_code: None,
                 industry_description: None,
                 naics_code: None,
+                ein: None,
+                company_name: None,
+                establishment_type: None,
+                case_number_format: None,
             },
         )
         .unwrap();
         let loc = create_location(
             conn,
+            &Actor::system(),
             CreateLocation {
                 establishment_id: est.id,
                 name: "Site A".into(),
@@ -568,6 +1075,8 @@ mod tests {
             employee_hire_date: None,
             employee_gender: Some("male".into()),
             is_privacy_case: None,
+            privacy_concern: None,
+            privacy_description: None,
             incident_date: "2026-01-15".into(),
             incident_time: Some("09:30".into()),
             work_start_time: Some("07:00".into()),
@@ -597,13 +1106,13 @@ mod tests {
         let conn = open_test_db();
         let (est_id, loc_id) = setup_test_data(&conn);
 
-        let inc = create_incident(&conn, make_incident(est_id, loc_id)).unwrap();
+        let inc = create_incident(&conn, &Actor::system(), make_incident(est_id, loc_id)).unwrap();
         assert_eq!(inc.case_number, Some(1));
         assert_eq!(inc.employee_name, "John Doe");
         assert_eq!(inc.outcome_severity, "days_away");
 
         // Second incident gets case_number 2
-        let inc2 = create_incident(&conn, make_incident(est_id, loc_id)).unwrap();
+        let inc2 = create_incident(&conn, &Actor::system(), make_incident(est_id, loc_id)).unwrap();
         assert_eq!(inc2.case_number, Some(2));
 
         let fetched = get_incident(&conn, inc.id).unwrap();
@@ -611,9 +1120,9 @@ mod tests {
 
         let updated = update_incident(
             &conn,
+            &Actor::system(),
             inc.id,
             UpdateIncident {
-                status: Some("closed".into()),
                 description: Some("Updated description".into()),
                 location_id: None,
                 employee_name: None,
@@ -626,6 +1135,8 @@ mod tests {
                 employee_hire_date: None,
                 employee_gender: None,
                 is_privacy_case: None,
+                privacy_concern: None,
+                privacy_description: None,
                 incident_date: None,
                 incident_time: None,
                 work_start_time: None,
@@ -653,10 +1164,12 @@ mod tests {
             },
         )
         .unwrap();
-        assert_eq!(updated.status, "closed");
+        assert_eq!(updated.status, "reported");
         assert_eq!(updated.description, "Updated description");
 
-        delete_incident(&conn, inc.id).unwrap();
+        delete_incident(&conn, &Actor::system(), inc.id).unwrap();
+        assert!(get_incident(&conn, inc.id).unwrap().deleted_at.is_some());
+        purge_incident(&conn, &Actor::system(), inc.id).unwrap();
         assert!(get_incident(&conn, inc.id).is_err());
     }
 
@@ -665,36 +1178,227 @@ mod tests {
         let conn = open_test_db();
         let (est_id, loc_id) = setup_test_data(&conn);
 
-        create_incident(&conn, make_incident(est_id, loc_id)).unwrap();
+        create_incident(&conn, &Actor::system(), make_incident(est_id, loc_id)).unwrap();
 
         let all = list_incidents(
             &conn,
             IncidentFilter {
                 establishment_id: est_id,
-                location_id: None,
-                status: None,
-                outcome_severity: None,
-                date_from: None,
-                date_to: None,
-                search: None,
+                ..Default::default()
             },
+            Audience::Full,
         )
         .unwrap();
-        assert_eq!(all.len(), 1);
+        assert_eq!(all.incidents.len(), 1);
+        assert_eq!(all.total, 1);
 
         let filtered = list_incidents(
             &conn,
             IncidentFilter {
                 establishment_id: est_id,
-                location_id: None,
                 status: Some("closed".into()),
-                outcome_severity: None,
-                date_from: None,
-                date_to: None,
-                search: None,
+                ..Default::default()
             },
+            Audience::Full,
         )
         .unwrap();
-        assert_eq!(filtered.len(), 0);
+        assert_eq!(filtered.incidents.len(), 0);
+    }
+
+    #[test]
+    fn test_incident_pagination_and_sorting() {
+        let conn = open_test_db();
+        let (est_id, loc_id) = setup_test_data(&conn);
+        for name in ["Carol", "alice", "Bob", "Dave", "Erin"] {
+            let mut data = make_incident(est_id, loc_id);
+            data.employee_name = name.into();
+            data.is_recordable = Some(name != "Erin");
+            create_incident(&conn, &Actor::system(), data).unwrap();
+        }
+
+        let page = list_incidents(&conn, IncidentFilter {
+            establishment_id: est_id,
+            sort_by: Some("employee_name".into()),
+            sort_dir: SortDirection::Asc,
+            limit: Some(2),
+            offset: Some(2),
+            ..Default::default()
+        }, Audience::Full)
+        .unwrap();
+        assert_eq!(page.total, 5);
+        let names: Vec<&str> = page.incidents.iter().map(|i| i.employee_name.as_str()).collect();
+        assert_eq!(names, ["Carol", "Dave"]);
+
+        let recordable = list_incidents(&conn, IncidentFilter {
+            establishment_id: est_id,
+            is_recordable: Some(true),
+            case_number_from: Some(2),
+            location_ids: vec![loc_id],
+            ..Default::default()
+        }, Audience::Full)
+        .unwrap();
+        assert_eq!(recordable.total, 3);
+
+        let bad_sort = list_incidents(&conn, IncidentFilter {
+            establishment_id: est_id,
+            sort_by: Some("id; DROP TABLE incidents".into()),
+            ..Default::default()
+        }, Audience::Full);
+        assert!(bad_sort.is_err());
+    }
+
+    #[test]
+    fn test_restricted_search_skips_privacy_cases() {
+        let conn = open_test_db();
+        let (est_id, loc_id) = setup_test_data(&conn);
+        let mut data = make_incident(est_id, loc_id);
+        data.employee_name = "Jordan Quill".into();
+        data.is_privacy_case = Some(true);
+        data.privacy_concern = Some("sexual_assault".into());
+        create_incident(&conn, &Actor::system(), data).unwrap();
+
+        let by_name = || IncidentFilter {
+            establishment_id: est_id,
+            search: Some("Quill".into()),
+            ..Default::default()
+        };
+        assert_eq!(list_incidents(&conn, by_name(), Audience::Full).unwrap().total, 1);
+        assert_eq!(list_incidents(&conn, by_name(), Audience::Restricted).unwrap().total, 0);
+    }
+
+    #[test]
+    fn test_restricted_name_sort_uses_masked_name() {
+        let conn = open_test_db();
+        let (est_id, loc_id) = setup_test_data(&conn);
+        for (name, privacy) in [("Aaron Abel", true), ("Mia Moss", false), ("Zed Zane", false)] {
+            let mut data = make_incident(est_id, loc_id);
+            data.employee_name = name.into();
+            data.is_privacy_case = Some(privacy);
+            data.privacy_concern = privacy.then(|| "sexual_assault".into());
+            create_incident(&conn, &Actor::system(), data).unwrap();
+        }
+
+        let by_name = || IncidentFilter {
+            establishment_id: est_id,
+            sort_by: Some("employee_name".into()),
+            sort_dir: SortDirection::Asc,
+            ..Default::default()
+        };
+        let names = |audience| -> Vec<String> {
+            list_incidents(&conn, by_name(), audience).unwrap().incidents.into_iter().map(|i| i.employee_name).collect()
+        };
+        assert_eq!(names(Audience::Full), ["Aaron Abel", "Mia Moss", "Zed Zane"]);
+        // "Privacy Case" sorts between M and Z, not where the real name would
+        assert_eq!(names(Audience::Restricted), ["Mia Moss", "Aaron Abel", "Zed Zane"]);
+    }
+
+    #[test]
+    fn test_case_number_formats() {
+        assert_eq!(format_case_number("{year}-EST01-{seq:4}", "2026", 7).unwrap(), "2026-EST01-0007");
+        assert_eq!(format_case_number("{seq}", "2026", 12).unwrap(), "12");
+        assert_eq!(format_case_number("C{seq:2}", "2026", 123).unwrap(), "C123");
+        for bad in ["{year}", "{seq", "{seq:0}", "{seq:x}", "{name}-{seq}", "{seq}}"] {
+            assert!(format_case_number(bad, "2026", 1).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_case_numbering_per_year_and_renumber() {
+        let conn = open_test_db();
+        let (est_id, loc_id) = setup_test_data(&conn);
+        conn.execute("UPDATE establishments SET case_number_format = '{year}-N-{seq:3}' WHERE id = ?1", [est_id])
+            .unwrap();
+
+        let at = |date: &str| {
+            let mut data = make_incident(est_id, loc_id);
+            data.incident_date = date.into();
+            create_incident(&conn, &Actor::system(), data).unwrap()
+        };
+        let march = at("2026-03-01");
+        let january = at("2026-01-10");
+        let last_year = at("2025-12-30");
+        assert_eq!((march.case_number, march.case_label.as_deref()), (Some(1), Some("2026-N-001")));
+        assert_eq!(january.case_number, Some(2));
+        assert_eq!(last_year.case_label.as_deref(), Some("2025-N-001"));
+
+        // The index rejects a second case 1 in the same establishment-year
+        let dup = conn.execute("UPDATE incidents SET case_number = 1 WHERE id = ?1", [january.id]);
+        assert!(dup.is_err());
+
+        // Moving a case to another year gives it that year's next number
+        let moved = update_incident(&conn, &Actor::system(), last_year.id,
+            serde_json::from_value(serde_json::json!({ "incident_date": "2026-02-01" })).unwrap()).unwrap();
+        assert_eq!((moved.case_number, moved.case_label.as_deref()), (Some(3), Some("2026-N-003")));
+
+        // A duplicate the migration flagged is tolerated until the year is renumbered
+        conn.execute("UPDATE incidents SET case_number = 1, case_number_duplicate = 1 WHERE id = ?1", [january.id])
+            .unwrap();
+
+        let changes = renumber_cases(&conn, &Actor::user(1), est_id, 2026).unwrap();
+        let order: Vec<(i64, i64)> = changes.iter().map(|c| (c.incident_id, c.new_case_number)).collect();
+        assert_eq!(order, [(january.id, 1), (moved.id, 2), (march.id, 3)]);
+        assert_eq!(get_incident(&conn, march.id).unwrap().case_label.as_deref(), Some("2026-N-003"));
+        let flagged: i64 = conn
+            .query_row("SELECT COUNT(*) FROM incidents WHERE case_number_duplicate = 1", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(flagged, 0);
+
+        let history = audit::get_record_history(&conn, "incidents", march.id).unwrap();
+        assert!(history.last().unwrap().changed_fields.contains(&"case_number".to_string()));
+        assert!(renumber_cases(&conn, &Actor::user(1), est_id, 2026).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_status_workflow_and_closure_checks() {
+        use crate::db::rca::{self, CreateCorrectiveAction, CreateRcaSession, UpdateCorrectiveAction};
+
+        let conn = open_test_db();
+        let (est_id, loc_id) = setup_test_data(&conn);
+        let actor = Actor::system();
+        let inc = create_incident(&conn, &actor, make_incident(est_id, loc_id)).unwrap();
+        assert_eq!(inc.status, "reported");
+
+        assert_eq!(transition_role("reported", "under_investigation"), Some("supervisor"));
+        assert_eq!(transition_role("reported", "closed"), None);
+        assert_eq!(transition_role("closed", "under_investigation"), Some("safety_manager"));
+        assert!(is_reopen("closed", "under_investigation"));
+
+        update_status(&conn, &actor, inc.id, "under_investigation", None, "Sam").unwrap();
+        update_status(&conn, &actor, inc.id, "pending_rca", None, "Sam").unwrap();
+
+        let session = rca::create_rca_session(&conn, &actor, CreateRcaSession {
+            incident_id: inc.id, method: "five_whys".into(),
+        }).unwrap();
+        let action = rca::create_corrective_action(&conn, &actor, CreateCorrectiveAction {
+            incident_id: inc.id, rca_session_id: Some(session.id),
+            description: "Guardrail on level 2".into(), assigned_to: None, due_date: None,
+        }).unwrap();
+
+        // Open RCA, open action and the missing 301 sign-off all block closing
+        let incident = get_incident(&conn, inc.id).unwrap();
+        assert_eq!(closure_blockers(&conn, &incident).unwrap().len(), 4);
+
+        rca::complete_rca_session(&conn, &actor, session.id, "No guardrail").unwrap();
+        rca::update_corrective_action(&conn, &actor, action.id, UpdateCorrectiveAction {
+            description: None, assigned_to: None, due_date: None,
+            status: Some("completed".into()), completed_date: Some("2026-01-19".into()), notes: None,
+        }).unwrap();
+        update_incident(&conn, &actor, inc.id, serde_json::from_value(serde_json::json!({
+            "completed_by": "Riley Safety", "completed_date": "2026-01-20",
+        })).unwrap()).unwrap();
+        let incident = get_incident(&conn, inc.id).unwrap();
+        assert!(closure_blockers(&conn, &incident).unwrap().is_empty());
+
+        update_status(&conn, &actor, inc.id, "closed", None, "Sam").unwrap();
+        let reopened = update_status(&conn, &actor, inc.id, "under_investigation", Some("New medical info"), "Morgan")
+            .unwrap();
+        assert_eq!(reopened.status, "under_investigation");
+
+        let history = list_status_changes(&conn, inc.id).unwrap();
+        assert_eq!(history.len(), 4);
+        let last = history.last().unwrap();
+        assert_eq!((last.from_status.as_str(), last.to_status.as_str()), ("closed", "under_investigation"));
+        assert_eq!(last.reason.as_deref(), Some("New medical info"));
+        assert_eq!(last.changed_by.as_deref(), Some("Morgan"));
     }
 }
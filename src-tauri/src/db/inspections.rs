@@ -0,0 +1,555 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AppError;
+
+pub const RESPONSE_STATUSES: &[&str] = &["pass", "fail", "na"];
+
+// ── Templates ──
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InspectionTemplate {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChecklistItem {
+    pub id: i64,
+    pub template_id: i64,
+    pub item_number: i64,
+    pub item_description: String,
+    pub is_critical: bool,
+}
+
+// ── Inspections ──
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Inspection {
+    pub id: i64,
+    pub template_id: i64,
+    pub establishment_id: i64,
+    pub location_id: Option<i64>,
+    pub inspector_name: String,
+    pub inspection_date: String,
+    pub overall_status: String,
+    pub notes: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InspectionResponse {
+    pub id: i64,
+    pub inspection_id: i64,
+    pub checklist_item_id: i64,
+    pub status: String,
+    pub notes: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInspection {
+    pub template_id: i64,
+    pub establishment_id: i64,
+    pub location_id: Option<i64>,
+    pub inspector_name: String,
+    pub inspection_date: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateInspection {
+    pub location_id: Option<i64>,
+    pub inspector_name: Option<String>,
+    pub inspection_date: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InspectionFilter {
+    pub establishment_id: i64,
+    pub location_id: Option<i64>,
+    pub overall_status: Option<String>,
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordResponse {
+    pub inspection_id: i64,
+    pub checklist_item_id: i64,
+    pub status: String,
+    pub notes: Option<String>,
+}
+
+// ── Template queries ──
+
+pub fn list_templates(conn: &Connection) -> Result<Vec<InspectionTemplate>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, description, category, is_active
+         FROM inspection_templates WHERE is_active = 1 ORDER BY category, name",
+    )?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(InspectionTemplate {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                category: row.get(3)?,
+                is_active: row.get::<_, i32>(4)? != 0,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+pub fn list_checklist_items(conn: &Connection, template_id: i64) -> Result<Vec<ChecklistItem>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, template_id, item_number, item_description, is_critical
+         FROM inspection_checklist_items WHERE template_id = ?1 ORDER BY item_number",
+    )?;
+
+    let rows = stmt
+        .query_map([template_id], |row| {
+            Ok(ChecklistItem {
+                id: row.get(0)?,
+                template_id: row.get(1)?,
+                item_number: row.get(2)?,
+                item_description: row.get(3)?,
+                is_critical: row.get::<_, i32>(4)? != 0,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+// ── Inspection CRUD ──
+
+fn row_to_inspection(row: &rusqlite::Row<'_>) -> rusqlite::Result<Inspection> {
+    Ok(Inspection {
+        id: row.get(0)?,
+        template_id: row.get(1)?,
+        establishment_id: row.get(2)?,
+        location_id: row.get(3)?,
+        inspector_name: row.get(4)?,
+        inspection_date: row.get(5)?,
+        overall_status: row.get(6)?,
+        notes: row.get(7)?,
+        created_at: row.get(8)?,
+    })
+}
+
+const SELECT_COLS: &str = "id, template_id, establishment_id, location_id, inspector_name,
+    inspection_date, overall_status, notes, created_at";
+
+pub fn create_inspection(conn: &Connection, data: CreateInspection) -> Result<Inspection> {
+    conn.execute(
+        "INSERT INTO inspections (template_id, establishment_id, location_id, inspector_name, inspection_date, notes)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            data.template_id,
+            data.establishment_id,
+            data.location_id,
+            data.inspector_name,
+            data.inspection_date,
+            data.notes,
+        ],
+    )
+    .context("Failed to create inspection")?;
+
+    let id = conn.last_insert_rowid();
+    get_inspection(conn, id)
+}
+
+pub fn get_inspection(conn: &Connection, id: i64) -> Result<Inspection> {
+    conn.query_row(
+        &format!("SELECT {SELECT_COLS} FROM inspections WHERE id = ?1"),
+        [id],
+        row_to_inspection,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            AppError::NotFound(format!("Inspection {id} not found")).into()
+        }
+        _ => anyhow::Error::new(e),
+    })
+}
+
+pub fn list_inspections(conn: &Connection, filter: InspectionFilter) -> Result<Vec<Inspection>> {
+    let mut sql = format!("SELECT {SELECT_COLS} FROM inspections WHERE establishment_id = ?");
+    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> =
+        vec![Box::new(filter.establishment_id)];
+
+    if let Some(loc_id) = filter.location_id {
+        sql.push_str(" AND location_id = ?");
+        values.push(Box::new(loc_id));
+    }
+    if let Some(ref status) = filter.overall_status {
+        sql.push_str(" AND overall_status = ?");
+        values.push(Box::new(status.clone()));
+    }
+    if let Some(ref from) = filter.date_from {
+        sql.push_str(" AND inspection_date >= ?");
+        values.push(Box::new(from.clone()));
+    }
+    if let Some(ref to) = filter.date_to {
+        sql.push_str(" AND inspection_date <= ?");
+        values.push(Box::new(to.clone()));
+    }
+
+    sql.push_str(" ORDER BY inspection_date DESC, id DESC");
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+    let rows = stmt
+        .query_map(params.as_slice(), row_to_inspection)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+pub fn update_inspection(conn: &Connection, id: i64, data: UpdateInspection) -> Result<Inspection> {
+    let _existing = get_inspection(conn, id)?;
+
+    let mut sets = Vec::new();
+    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+    if let Some(loc_id) = data.location_id {
+        sets.push("location_id = ?");
+        values.push(Box::new(loc_id));
+    }
+    if let Some(ref v) = data.inspector_name {
+        sets.push("inspector_name = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.inspection_date {
+        sets.push("inspection_date = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.notes {
+        sets.push("notes = ?");
+        values.push(Box::new(v.clone()));
+    }
+
+    if !sets.is_empty() {
+        let sql = format!("UPDATE inspections SET {} WHERE id = ?", sets.join(", "));
+        values.push(Box::new(id));
+        let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+        conn.execute(&sql, params.as_slice())
+            .context("Failed to update inspection")?;
+    }
+
+    get_inspection(conn, id)
+}
+
+pub fn delete_inspection(conn: &Connection, id: i64) -> Result<()> {
+    let changes = conn
+        .execute("DELETE FROM inspections WHERE id = ?1", [id])
+        .context("Failed to delete inspection")?;
+
+    if changes == 0 {
+        return Err(AppError::NotFound(format!("Inspection {id} not found")).into());
+    }
+    Ok(())
+}
+
+// ── Responses ──
+
+/// Records a pass/fail/na response for one checklist item, replacing any
+/// earlier response for the same item, then recomputes `overall_status`.
+pub fn record_response(conn: &Connection, data: RecordResponse) -> Result<InspectionResponse> {
+    if !RESPONSE_STATUSES.contains(&data.status.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Invalid response status: {}. Must be one of: {}",
+            data.status,
+            RESPONSE_STATUSES.join(", ")
+        ))
+        .into());
+    }
+
+    let inspection = get_inspection(conn, data.inspection_id)?;
+
+    let item_template: Option<i64> = conn
+        .query_row(
+            "SELECT template_id FROM inspection_checklist_items WHERE id = ?1",
+            [data.checklist_item_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    match item_template {
+        None => {
+            return Err(AppError::NotFound(format!(
+                "Checklist item {} not found",
+                data.checklist_item_id
+            ))
+            .into())
+        }
+        Some(template_id) if template_id != inspection.template_id => {
+            return Err(AppError::Validation(format!(
+                "Checklist item {} does not belong to the template of inspection {}",
+                data.checklist_item_id, inspection.id
+            ))
+            .into())
+        }
+        Some(_) => {}
+    }
+
+    let existing: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM inspection_responses WHERE inspection_id = ?1 AND checklist_item_id = ?2",
+            params![data.inspection_id, data.checklist_item_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    let id = match existing {
+        Some(id) => {
+            conn.execute(
+                "UPDATE inspection_responses SET status = ?1, notes = ?2 WHERE id = ?3",
+                params![data.status, data.notes, id],
+            )
+            .context("Failed to update inspection response")?;
+            id
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO inspection_responses (inspection_id, checklist_item_id, status, notes)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![data.inspection_id, data.checklist_item_id, data.status, data.notes],
+            )
+            .context("Failed to record inspection response")?;
+            conn.last_insert_rowid()
+        }
+    };
+
+    refresh_overall_status(conn, data.inspection_id)?;
+
+    conn.query_row(
+        "SELECT id, inspection_id, checklist_item_id, status, notes, created_at
+         FROM inspection_responses WHERE id = ?1",
+        [id],
+        |row| {
+            Ok(InspectionResponse {
+                id: row.get(0)?,
+                inspection_id: row.get(1)?,
+                checklist_item_id: row.get(2)?,
+                status: row.get(3)?,
+                notes: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        },
+    )
+    .map_err(anyhow::Error::new)
+}
+
+pub fn list_responses(conn: &Connection, inspection_id: i64) -> Result<Vec<InspectionResponse>> {
+    let mut stmt = conn.prepare(
+        "SELECT r.id, r.inspection_id, r.checklist_item_id, r.status, r.notes, r.created_at
+         FROM inspection_responses r
+         JOIN inspection_checklist_items ci ON r.checklist_item_id = ci.id
+         WHERE r.inspection_id = ?1 ORDER BY ci.item_number",
+    )?;
+
+    let rows = stmt
+        .query_map([inspection_id], |row| {
+            Ok(InspectionResponse {
+                id: row.get(0)?,
+                inspection_id: row.get(1)?,
+                checklist_item_id: row.get(2)?,
+                status: row.get(3)?,
+                notes: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+/// Derives `overall_status` from the recorded responses:
+/// - `failed` if any critical item failed
+/// - `pending` if nothing has been answered yet
+/// - `in_progress` while checklist items remain unanswered
+/// - `passed_with_deficiencies` if only non-critical items failed
+/// - `passed` otherwise
+fn compute_overall_status(conn: &Connection, inspection_id: i64) -> Result<String> {
+    let (total_items, answered, critical_fails, fails): (i64, i64, i64, i64) = conn.query_row(
+        "SELECT
+            (SELECT COUNT(*) FROM inspection_checklist_items
+             WHERE template_id = i.template_id),
+            COUNT(r.id),
+            COALESCE(SUM(CASE WHEN r.status = 'fail' AND ci.is_critical = 1 THEN 1 ELSE 0 END), 0),
+            COALESCE(SUM(CASE WHEN r.status = 'fail' THEN 1 ELSE 0 END), 0)
+         FROM inspections i
+         LEFT JOIN inspection_responses r ON r.inspection_id = i.id
+         LEFT JOIN inspection_checklist_items ci ON r.checklist_item_id = ci.id
+         WHERE i.id = ?1",
+        [inspection_id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+    )?;
+
+    let status = if critical_fails > 0 {
+        "failed"
+    } else if answered == 0 {
+        "pending"
+    } else if answered < total_items {
+        "in_progress"
+    } else if fails > 0 {
+        "passed_with_deficiencies"
+    } else {
+        "passed"
+    };
+
+    Ok(status.to_string())
+}
+
+fn refresh_overall_status(conn: &Connection, inspection_id: i64) -> Result<()> {
+    let status = compute_overall_status(conn, inspection_id)?;
+    conn.execute(
+        "UPDATE inspections SET overall_status = ?1 WHERE id = ?2",
+        params![status, inspection_id],
+    )
+    .context("Failed to update inspection status")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::audit::Actor;
+    use crate::db::locations::{create_establishment, CreateEstablishment};
+    use crate::db::open_test_db;
+
+    fn setup(conn: &Connection) -> i64 {
+        create_establishment(
+            conn,
+            &Actor::system(),
+            CreateEstablishment {
+                name: "Test Co".into(),
+                street_address: None, city: None, state: None,
+                zip_code: None, industry_description: None, naics_code: None,
+                ein: None, company_name: None, establishment_type: None, case_number_format: None,
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    fn scaffold_inspection(conn: &Connection, est_id: i64) -> (Inspection, Vec<ChecklistItem>) {
+        let template = list_templates(conn)
+            .unwrap()
+            .into_iter()
+            .find(|t| t.name == "Scaffold Inspection")
+            .unwrap();
+        let items = list_checklist_items(conn, template.id).unwrap();
+
+        let inspection = create_inspection(conn, CreateInspection {
+            template_id: template.id, establishment_id: est_id, location_id: None,
+            inspector_name: "Pat Inspector".into(), inspection_date: "2026-04-01".into(),
+            notes: None,
+        }).unwrap();
+
+        (inspection, items)
+    }
+
+    fn respond(conn: &Connection, inspection_id: i64, item_id: i64, status: &str) {
+        record_response(conn, RecordResponse {
+            inspection_id, checklist_item_id: item_id, status: status.into(), notes: None,
+        }).unwrap();
+    }
+
+    #[test]
+    fn test_inspection_crud() {
+        let conn = open_test_db();
+        let est_id = setup(&conn);
+
+        assert_eq!(list_templates(&conn).unwrap().len(), 7);
+
+        let (inspection, _) = scaffold_inspection(&conn, est_id);
+        assert_eq!(inspection.overall_status, "pending");
+
+        let updated = update_inspection(&conn, inspection.id, UpdateInspection {
+            notes: Some("North elevation".into()),
+            location_id: None, inspector_name: None, inspection_date: None,
+        }).unwrap();
+        assert_eq!(updated.notes.as_deref(), Some("North elevation"));
+
+        let all = list_inspections(&conn, InspectionFilter {
+            establishment_id: est_id, location_id: None, overall_status: None,
+            date_from: None, date_to: None,
+        }).unwrap();
+        assert_eq!(all.len(), 1);
+
+        delete_inspection(&conn, inspection.id).unwrap();
+        assert!(get_inspection(&conn, inspection.id).is_err());
+    }
+
+    #[test]
+    fn test_overall_status_from_responses() {
+        let conn = open_test_db();
+        let est_id = setup(&conn);
+        let (inspection, items) = scaffold_inspection(&conn, est_id);
+        let non_critical = items.iter().find(|i| !i.is_critical).unwrap();
+        let critical = items.iter().find(|i| i.is_critical).unwrap();
+
+        respond(&conn, inspection.id, non_critical.id, "fail");
+        assert_eq!(get_inspection(&conn, inspection.id).unwrap().overall_status, "in_progress");
+
+        for item in items.iter().filter(|i| i.id != non_critical.id) {
+            respond(&conn, inspection.id, item.id, "pass");
+        }
+        assert_eq!(
+            get_inspection(&conn, inspection.id).unwrap().overall_status,
+            "passed_with_deficiencies"
+        );
+
+        // Re-answering an item replaces the earlier response
+        respond(&conn, inspection.id, critical.id, "fail");
+        assert_eq!(list_responses(&conn, inspection.id).unwrap().len(), items.len());
+        assert_eq!(get_inspection(&conn, inspection.id).unwrap().overall_status, "failed");
+
+        respond(&conn, inspection.id, critical.id, "na");
+        respond(&conn, inspection.id, non_critical.id, "pass");
+        assert_eq!(get_inspection(&conn, inspection.id).unwrap().overall_status, "passed");
+    }
+
+    #[test]
+    fn test_response_rejects_foreign_item() {
+        let conn = open_test_db();
+        let est_id = setup(&conn);
+        let (inspection, _) = scaffold_inspection(&conn, est_id);
+
+        let other_template = list_templates(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|t| t.name == "Ladder Inspection")
+            .unwrap();
+        conn.execute(
+            "INSERT INTO inspection_checklist_items (template_id, item_number, item_description)
+             VALUES (?1, 1, 'Rungs intact')",
+            [other_template.id],
+        ).unwrap();
+        let other_item = conn.last_insert_rowid();
+
+        assert!(record_response(&conn, RecordResponse {
+            inspection_id: inspection.id, checklist_item_id: other_item,
+            status: "pass".into(), notes: None,
+        }).is_err());
+        assert!(record_response(&conn, RecordResponse {
+            inspection_id: inspection.id, checklist_item_id: other_item,
+            status: "maybe".into(), notes: None,
+        }).is_err());
+
+        assert!(record_response(&conn, RecordResponse {
+            inspection_id: inspection.id, checklist_item_id: 9999,
+            status: "pass".into(), notes: None,
+        }).is_err());
+    }
+}
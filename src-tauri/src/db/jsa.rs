@@ -1,6 +1,32 @@
 use rusqlite::{params, Connection, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::db::audit::{self, Actor};
+use crate::db::trade_hazards::{self, TradeHazard};
+
+pub const HAZARD_SEVERITIES: &[&str] = &["low", "medium", "high", "critical"];
+
+/// Hierarchy of controls, most to least effective.
+pub const CONTROL_TYPES: &[&str] = &["elimination", "substitution", "engineering", "administrative", "ppe"];
+
+/// Workflow order. Steps, hazards and controls are locked from `approved` on.
+pub const JSA_STATUSES: &[&str] = &["draft", "submitted", "reviewed", "approved", "active", "closed"];
+
+/// Allowed transitions and the minimum role for each. Sending a JSA back to
+/// draft is allowed until it is approved.
+const TRANSITIONS: &[(&str, &str, &str)] = &[
+    ("draft", "submitted", "supervisor"),
+    ("submitted", "draft", "supervisor"),
+    ("submitted", "reviewed", "supervisor"),
+    ("reviewed", "draft", "safety_manager"),
+    ("reviewed", "approved", "safety_manager"),
+    ("approved", "active", "supervisor"),
+    ("active", "closed", "supervisor"),
+];
+
+/// Severities that must not rely on PPE alone.
+const HIGH_SEVERITIES: &[&str] = &["high", "critical"];
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct JsaTemplate {
     pub id: i64,
@@ -22,6 +48,11 @@ pub struct JsaInstance {
     pub reviewed_by: Option<String>,
     pub approved_by: Option<String>,
     pub status: String,
+    pub submitted_at: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub approved_at: Option<String>,
+    pub activated_at: Option<String>,
+    pub closed_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,6 +64,64 @@ pub struct JsaStep {
     pub is_completed: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsaHazard {
+    pub id: i64,
+    pub jsa_step_id: i64,
+    pub hazard_description: String,
+    pub severity: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsaControl {
+    pub id: i64,
+    pub jsa_hazard_id: i64,
+    pub control_type: String,
+    pub control_description: String,
+    pub is_implemented: bool,
+}
+
+/// A template step with its hazards and their controls, copied into every
+/// instance created from the template.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsaTemplateStep {
+    pub id: i64,
+    pub template_id: i64,
+    pub step_number: i64,
+    pub task_description: String,
+    pub hazards: Vec<JsaTemplateHazard>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsaTemplateHazard {
+    pub id: i64,
+    pub template_step_id: i64,
+    pub hazard_description: String,
+    pub severity: String,
+    pub controls: Vec<JsaTemplateControl>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsaTemplateControl {
+    pub id: i64,
+    pub template_hazard_id: i64,
+    pub control_type: String,
+    pub control_description: String,
+}
+
+/// A hazard whose controls don't hold up against the hierarchy of controls.
+#[derive(Debug, Serialize)]
+pub struct ControlFinding {
+    pub jsa_hazard_id: i64,
+    pub jsa_step_id: i64,
+    pub step_number: i64,
+    pub hazard_description: String,
+    pub severity: String,
+    /// `ppe_only` or `no_controls`
+    pub issue: String,
+    pub message: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateJsaInstance {
     pub template_id: Option<i64>,
@@ -43,6 +132,27 @@ pub struct CreateJsaInstance {
     pub prepared_by: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AddJsaTemplateStep {
+    pub template_id: i64,
+    pub step_number: i64,
+    pub task_description: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddJsaTemplateHazard {
+    pub template_step_id: i64,
+    pub hazard_description: String,
+    pub severity: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddJsaTemplateControl {
+    pub template_hazard_id: i64,
+    pub control_type: String,
+    pub control_description: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AddJsaStep {
     pub jsa_instance_id: i64,
@@ -50,6 +160,34 @@ pub struct AddJsaStep {
     pub task_description: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AddJsaHazard {
+    pub jsa_step_id: i64,
+    pub hazard_description: String,
+    pub severity: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateJsaHazard {
+    pub hazard_description: Option<String>,
+    pub severity: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddJsaControl {
+    pub jsa_hazard_id: i64,
+    pub control_type: String,
+    pub control_description: String,
+    pub is_implemented: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateJsaControl {
+    pub control_type: Option<String>,
+    pub control_description: Option<String>,
+    pub is_implemented: Option<bool>,
+}
+
 // Templates
 pub fn list_templates(conn: &Connection) -> Result<Vec<JsaTemplate>> {
     let mut stmt = conn.prepare(
@@ -70,9 +208,190 @@ pub fn list_templates(conn: &Connection) -> Result<Vec<JsaTemplate>> {
     rows.collect()
 }
 
+pub fn get_template(conn: &Connection, id: i64) -> Result<JsaTemplate> {
+    conn.query_row(
+        "SELECT id, name, description, trade, is_active FROM jsa_templates WHERE id = ?",
+        [id],
+        |row| {
+            Ok(JsaTemplate {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                trade: row.get(3)?,
+                is_active: row.get::<_, i64>(4)? == 1,
+            })
+        },
+    )
+}
+
+/// Steps of a template in order, each with its hazards and controls.
+pub fn list_template_steps(conn: &Connection, template_id: i64) -> Result<Vec<JsaTemplateStep>> {
+    let mut step_stmt = conn.prepare(
+        "SELECT id, template_id, step_number, task_description
+         FROM jsa_template_steps WHERE template_id = ? ORDER BY step_number, id",
+    )?;
+    let mut hazard_stmt = conn.prepare(
+        "SELECT id, template_step_id, hazard_description, severity
+         FROM jsa_template_hazards WHERE template_step_id = ? ORDER BY id",
+    )?;
+    let mut control_stmt = conn.prepare(
+        "SELECT id, template_hazard_id, control_type, control_description
+         FROM jsa_template_controls WHERE template_hazard_id = ? ORDER BY id",
+    )?;
+
+    let mut steps = step_stmt
+        .query_map([template_id], |row| {
+            Ok(JsaTemplateStep {
+                id: row.get(0)?,
+                template_id: row.get(1)?,
+                step_number: row.get(2)?,
+                task_description: row.get(3)?,
+                hazards: Vec::new(),
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    for step in &mut steps {
+        step.hazards = hazard_stmt
+            .query_map([step.id], |row| {
+                Ok(JsaTemplateHazard {
+                    id: row.get(0)?,
+                    template_step_id: row.get(1)?,
+                    hazard_description: row.get(2)?,
+                    severity: row.get(3)?,
+                    controls: Vec::new(),
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        for hazard in &mut step.hazards {
+            let mut controls = control_stmt
+                .query_map([hazard.id], |row| {
+                    Ok(JsaTemplateControl {
+                        id: row.get(0)?,
+                        template_hazard_id: row.get(1)?,
+                        control_type: row.get(2)?,
+                        control_description: row.get(3)?,
+                    })
+                })?
+                .collect::<Result<Vec<_>>>()?;
+            controls.sort_by_key(|c| control_rank(&c.control_type).unwrap_or(CONTROL_TYPES.len()));
+            hazard.controls = controls;
+        }
+    }
+
+    Ok(steps)
+}
+
+/// A template step with its hazards and controls.
+pub fn get_template_step(conn: &Connection, id: i64) -> Result<JsaTemplateStep> {
+    let template_id: i64 =
+        conn.query_row("SELECT template_id FROM jsa_template_steps WHERE id = ?", [id], |row| row.get(0))?;
+    list_template_steps(conn, template_id)?
+        .into_iter()
+        .find(|step| step.id == id)
+        .ok_or(rusqlite::Error::QueryReturnedNoRows)
+}
+
+pub fn get_template_hazard(conn: &Connection, id: i64) -> Result<JsaTemplateHazard> {
+    conn.query_row(
+        "SELECT id, template_step_id, hazard_description, severity FROM jsa_template_hazards WHERE id = ?",
+        [id],
+        |row| {
+            Ok(JsaTemplateHazard {
+                id: row.get(0)?,
+                template_step_id: row.get(1)?,
+                hazard_description: row.get(2)?,
+                severity: row.get(3)?,
+                controls: Vec::new(),
+            })
+        },
+    )
+}
+
+pub fn get_template_control(conn: &Connection, id: i64) -> Result<JsaTemplateControl> {
+    conn.query_row(
+        "SELECT id, template_hazard_id, control_type, control_description FROM jsa_template_controls WHERE id = ?",
+        [id],
+        |row| {
+            Ok(JsaTemplateControl {
+                id: row.get(0)?,
+                template_hazard_id: row.get(1)?,
+                control_type: row.get(2)?,
+                control_description: row.get(3)?,
+            })
+        },
+    )
+}
+
+pub fn add_template_step(conn: &Connection, actor: &Actor, data: AddJsaTemplateStep) -> Result<i64> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO jsa_template_steps (template_id, step_number, task_description) VALUES (?, ?, ?)",
+            params![data.template_id, data.step_number, data.task_description],
+        )?;
+
+        let id = conn.last_insert_rowid();
+        let created = get_template_step(conn, id)?;
+        audit::log_create(conn, actor, "jsa_template_steps", id, &created)?;
+        Ok(id)
+    })
+}
+
+pub fn add_template_hazard(conn: &Connection, actor: &Actor, data: AddJsaTemplateHazard) -> Result<i64> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO jsa_template_hazards (template_step_id, hazard_description, severity) VALUES (?, ?, ?)",
+            params![
+                data.template_step_id,
+                data.hazard_description,
+                data.severity.as_deref().unwrap_or("medium"),
+            ],
+        )?;
+
+        let id = conn.last_insert_rowid();
+        let created = get_template_hazard(conn, id)?;
+        audit::log_create(conn, actor, "jsa_template_hazards", id, &created)?;
+        Ok(id)
+    })
+}
+
+pub fn add_template_control(conn: &Connection, actor: &Actor, data: AddJsaTemplateControl) -> Result<i64> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO jsa_template_controls (template_hazard_id, control_type, control_description) VALUES (?, ?, ?)",
+            params![data.template_hazard_id, data.control_type, data.control_description],
+        )?;
+
+        let id = conn.last_insert_rowid();
+        let created = get_template_control(conn, id)?;
+        audit::log_create(conn, actor, "jsa_template_controls", id, &created)?;
+        Ok(id)
+    })
+}
+
+/// Removes a template step along with its hazards and controls. The audit
+/// entry keeps the whole step as it was.
+pub fn delete_template_step(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_template_step(conn, id)?;
+        conn.execute("DELETE FROM jsa_template_steps WHERE id = ?", [id])?;
+        audit::log_delete(conn, actor, "jsa_template_steps", id, &existing)?;
+        Ok(())
+    })
+}
+
 // Instances
-pub fn create_instance(conn: &Connection, data: CreateJsaInstance) -> Result<JsaInstance> {
-    conn.execute(
+
+/// Creates the instance header and, when it comes from a template, copies the
+/// template's steps, hazards and controls into it.
+pub fn create_instance(
+    conn: &Connection,
+    actor: &Actor,
+    data: CreateJsaInstance,
+) -> Result<JsaInstance> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
         "INSERT INTO jsa_instances (template_id, establishment_id, location_id, job_name, job_date, prepared_by)
          VALUES (?, ?, ?, ?, ?, ?)",
         params![
@@ -85,76 +404,201 @@ pub fn create_instance(conn: &Connection, data: CreateJsaInstance) -> Result<Jsa
         ],
     )?;
 
-    let id = conn.last_insert_rowid();
-    get_instance(conn, id)
+    let id = tx.last_insert_rowid();
+    let created = get_instance(&tx, id)?;
+    audit::log_create(&tx, actor, "jsa_instances", id, &created)?;
+
+    if let Some(template_id) = data.template_id {
+        copy_template_content(&tx, actor, template_id, id)?;
+    }
+
+    tx.commit()?;
+    Ok(created)
+}
+
+fn copy_template_content(
+    conn: &Connection,
+    actor: &Actor,
+    template_id: i64,
+    jsa_instance_id: i64,
+) -> Result<()> {
+    for template_step in list_template_steps(conn, template_id)? {
+        let step = add_step(conn, actor, AddJsaStep {
+            jsa_instance_id,
+            step_number: template_step.step_number,
+            task_description: template_step.task_description,
+        })?;
+
+        for template_hazard in template_step.hazards {
+            let hazard = add_hazard(conn, actor, AddJsaHazard {
+                jsa_step_id: step.id,
+                hazard_description: template_hazard.hazard_description,
+                severity: Some(template_hazard.severity),
+            })?;
+
+            for template_control in template_hazard.controls {
+                add_control(conn, actor, AddJsaControl {
+                    jsa_hazard_id: hazard.id,
+                    control_type: template_control.control_type,
+                    control_description: template_control.control_description,
+                    is_implemented: None,
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+const INSTANCE_COLS: &str = "id, template_id, establishment_id, location_id, job_name, job_date,
+    prepared_by, reviewed_by, approved_by, status,
+    submitted_at, reviewed_at, approved_at, activated_at, closed_at";
+
+fn row_to_instance(row: &rusqlite::Row<'_>) -> Result<JsaInstance> {
+    Ok(JsaInstance {
+        id: row.get(0)?,
+        template_id: row.get(1)?,
+        establishment_id: row.get(2)?,
+        location_id: row.get(3)?,
+        job_name: row.get(4)?,
+        job_date: row.get(5)?,
+        prepared_by: row.get(6)?,
+        reviewed_by: row.get(7)?,
+        approved_by: row.get(8)?,
+        status: row.get(9)?,
+        submitted_at: row.get(10)?,
+        reviewed_at: row.get(11)?,
+        approved_at: row.get(12)?,
+        activated_at: row.get(13)?,
+        closed_at: row.get(14)?,
+    })
 }
 
 pub fn get_instance(conn: &Connection, id: i64) -> Result<JsaInstance> {
     conn.query_row(
-        "SELECT id, template_id, establishment_id, location_id, job_name, job_date,
-                prepared_by, reviewed_by, approved_by, status
-         FROM jsa_instances WHERE id = ?",
+        &format!("SELECT {INSTANCE_COLS} FROM jsa_instances WHERE id = ?"),
         [id],
-        |row| {
-            Ok(JsaInstance {
-                id: row.get(0)?,
-                template_id: row.get(1)?,
-                establishment_id: row.get(2)?,
-                location_id: row.get(3)?,
-                job_name: row.get(4)?,
-                job_date: row.get(5)?,
-                prepared_by: row.get(6)?,
-                reviewed_by: row.get(7)?,
-                approved_by: row.get(8)?,
-                status: row.get(9)?,
-            })
-        },
+        row_to_instance,
     )
 }
 
 pub fn list_instances(conn: &Connection, establishment_id: i64) -> Result<Vec<JsaInstance>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, template_id, establishment_id, location_id, job_name, job_date,
-                prepared_by, reviewed_by, approved_by, status
-         FROM jsa_instances WHERE establishment_id = ? ORDER BY job_date DESC",
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {INSTANCE_COLS} FROM jsa_instances WHERE establishment_id = ? ORDER BY job_date DESC"
+    ))?;
 
-    let rows = stmt.query_map([establishment_id], |row| {
-        Ok(JsaInstance {
-            id: row.get(0)?,
-            template_id: row.get(1)?,
-            establishment_id: row.get(2)?,
-            location_id: row.get(3)?,
-            job_name: row.get(4)?,
-            job_date: row.get(5)?,
-            prepared_by: row.get(6)?,
-            reviewed_by: row.get(7)?,
-            approved_by: row.get(8)?,
-            status: row.get(9)?,
-        })
-    })?;
+    let rows = stmt.query_map([establishment_id], row_to_instance)?;
 
     rows.collect()
 }
 
-pub fn update_instance_status(conn: &Connection, id: i64, status: &str) -> Result<JsaInstance> {
-    conn.execute(
-        "UPDATE jsa_instances SET status = ? WHERE id = ?",
-        params![status, id],
-    )?;
-    get_instance(conn, id)
+/// Minimum role for moving a JSA from `from` to `to`, or `None` if the
+/// workflow doesn't allow it.
+pub fn transition_role(from: &str, to: &str) -> Option<&'static str> {
+    TRANSITIONS
+        .iter()
+        .find(|(f, t, _)| *f == from && *t == to)
+        .map(|(_, _, role)| *role)
+}
+
+/// Statuses reachable from `from`.
+pub fn next_statuses(from: &str) -> Vec<&'static str> {
+    TRANSITIONS
+        .iter()
+        .filter(|(f, _, _)| *f == from)
+        .map(|(_, t, _)| *t)
+        .collect()
+}
+
+/// Approved, active and closed JSAs can't have their steps, hazards or
+/// controls changed.
+pub fn is_locked_status(status: &str) -> bool {
+    matches!(status, "approved" | "active" | "closed")
+}
+
+/// Status of the JSA that owns a step, hazard or control.
+pub fn status_for_instance(conn: &Connection, jsa_instance_id: i64) -> Result<String> {
+    conn.query_row("SELECT status FROM jsa_instances WHERE id = ?", [jsa_instance_id], |row| row.get(0))
+}
+
+pub fn status_for_step(conn: &Connection, jsa_step_id: i64) -> Result<String> {
+    conn.query_row(
+        "SELECT i.status FROM jsa_steps s JOIN jsa_instances i ON s.jsa_instance_id = i.id
+         WHERE s.id = ?",
+        [jsa_step_id],
+        |row| row.get(0),
+    )
+}
+
+pub fn status_for_hazard(conn: &Connection, jsa_hazard_id: i64) -> Result<String> {
+    conn.query_row(
+        "SELECT i.status FROM jsa_hazards h
+         JOIN jsa_steps s ON h.jsa_step_id = s.id
+         JOIN jsa_instances i ON s.jsa_instance_id = i.id
+         WHERE h.id = ?",
+        [jsa_hazard_id],
+        |row| row.get(0),
+    )
+}
+
+pub fn status_for_control(conn: &Connection, jsa_control_id: i64) -> Result<String> {
+    conn.query_row(
+        "SELECT i.status FROM jsa_controls c
+         JOIN jsa_hazards h ON c.jsa_hazard_id = h.id
+         JOIN jsa_steps s ON h.jsa_step_id = s.id
+         JOIN jsa_instances i ON s.jsa_instance_id = i.id
+         WHERE c.id = ?",
+        [jsa_control_id],
+        |row| row.get(0),
+    )
+}
+
+/// Moves a JSA to `status` and stamps the matching timestamp. Reviewing and
+/// approving also record `signer` as the reviewer or approver; sending a JSA
+/// back to draft clears earlier stamps. The transition must already have been
+/// checked against `transition_role`.
+pub fn update_instance_status(
+    conn: &Connection,
+    actor: &Actor,
+    id: i64,
+    status: &str,
+    signer: &str,
+) -> Result<JsaInstance> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_instance(conn, id)?;
+        conn.execute(
+            "UPDATE jsa_instances SET
+                status = ?1,
+                submitted_at = CASE ?1 WHEN 'submitted' THEN datetime('now') WHEN 'draft' THEN NULL ELSE submitted_at END,
+                reviewed_by = CASE ?1 WHEN 'reviewed' THEN ?3 WHEN 'draft' THEN NULL ELSE reviewed_by END,
+                reviewed_at = CASE ?1 WHEN 'reviewed' THEN datetime('now') WHEN 'draft' THEN NULL ELSE reviewed_at END,
+                approved_by = CASE ?1 WHEN 'approved' THEN ?3 WHEN 'draft' THEN NULL ELSE approved_by END,
+                approved_at = CASE ?1 WHEN 'approved' THEN datetime('now') WHEN 'draft' THEN NULL ELSE approved_at END,
+                activated_at = CASE ?1 WHEN 'active' THEN datetime('now') ELSE activated_at END,
+                closed_at = CASE ?1 WHEN 'closed' THEN datetime('now') ELSE closed_at END,
+                updated_at = datetime('now')
+             WHERE id = ?2",
+            params![status, id, signer],
+        )?;
+        let updated = get_instance(conn, id)?;
+        audit::log_update(conn, actor, "jsa_instances", id, &existing, &updated)?;
+        Ok(updated)
+    })
 }
 
 // Steps
-pub fn add_step(conn: &Connection, data: AddJsaStep) -> Result<JsaStep> {
-    conn.execute(
-        "INSERT INTO jsa_steps (jsa_instance_id, step_number, task_description)
-         VALUES (?, ?, ?)",
-        params![data.jsa_instance_id, data.step_number, data.task_description],
-    )?;
+pub fn add_step(conn: &Connection, actor: &Actor, data: AddJsaStep) -> Result<JsaStep> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO jsa_steps (jsa_instance_id, step_number, task_description)
+             VALUES (?, ?, ?)",
+            params![data.jsa_instance_id, data.step_number, data.task_description],
+        )?;
 
-    let id = conn.last_insert_rowid();
-    get_step(conn, id)
+        let id = conn.last_insert_rowid();
+        let created = get_step(conn, id)?;
+        audit::log_create(conn, actor, "jsa_steps", id, &created)?;
+        Ok(created)
+    })
 }
 
 pub fn get_step(conn: &Connection, id: i64) -> Result<JsaStep> {
@@ -193,10 +637,483 @@ pub fn list_steps(conn: &Connection, jsa_instance_id: i64) -> Result<Vec<JsaStep
     rows.collect()
 }
 
-pub fn toggle_step_completion(conn: &Connection, id: i64) -> Result<JsaStep> {
-    conn.execute(
-        "UPDATE jsa_steps SET is_completed = NOT is_completed WHERE id = ?",
+pub fn toggle_step_completion(conn: &Connection, actor: &Actor, id: i64) -> Result<JsaStep> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_step(conn, id)?;
+        conn.execute(
+            "UPDATE jsa_steps SET is_completed = NOT is_completed WHERE id = ?",
+            [id],
+        )?;
+        let updated = get_step(conn, id)?;
+        audit::log_update(conn, actor, "jsa_steps", id, &existing, &updated)?;
+        Ok(updated)
+    })
+}
+
+// Hazards
+fn row_to_hazard(row: &rusqlite::Row<'_>) -> Result<JsaHazard> {
+    Ok(JsaHazard {
+        id: row.get(0)?,
+        jsa_step_id: row.get(1)?,
+        hazard_description: row.get(2)?,
+        severity: row.get(3)?,
+    })
+}
+
+pub fn add_hazard(conn: &Connection, actor: &Actor, data: AddJsaHazard) -> Result<JsaHazard> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO jsa_hazards (jsa_step_id, hazard_description, severity)
+             VALUES (?, ?, ?)",
+            params![
+                data.jsa_step_id,
+                data.hazard_description,
+                data.severity.as_deref().unwrap_or("medium"),
+            ],
+        )?;
+
+        let id = conn.last_insert_rowid();
+        let created = get_hazard(conn, id)?;
+        audit::log_create(conn, actor, "jsa_hazards", id, &created)?;
+        Ok(created)
+    })
+}
+
+pub fn get_hazard(conn: &Connection, id: i64) -> Result<JsaHazard> {
+    conn.query_row(
+        "SELECT id, jsa_step_id, hazard_description, severity FROM jsa_hazards WHERE id = ?",
         [id],
+        row_to_hazard,
+    )
+}
+
+pub fn list_hazards(conn: &Connection, jsa_step_id: i64) -> Result<Vec<JsaHazard>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, jsa_step_id, hazard_description, severity
+         FROM jsa_hazards WHERE jsa_step_id = ? ORDER BY id",
     )?;
-    get_step(conn, id)
+
+    let rows = stmt.query_map([jsa_step_id], row_to_hazard)?;
+    rows.collect()
+}
+
+pub fn update_hazard(
+    conn: &Connection,
+    actor: &Actor,
+    id: i64,
+    data: UpdateJsaHazard,
+) -> Result<JsaHazard> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_hazard(conn, id)?;
+        conn.execute(
+            "UPDATE jsa_hazards SET hazard_description = ?, severity = ? WHERE id = ?",
+            params![
+                data.hazard_description.as_deref().unwrap_or(&existing.hazard_description),
+                data.severity.as_deref().unwrap_or(&existing.severity),
+                id,
+            ],
+        )?;
+        let updated = get_hazard(conn, id)?;
+        audit::log_update(conn, actor, "jsa_hazards", id, &existing, &updated)?;
+        Ok(updated)
+    })
+}
+
+pub fn delete_hazard(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_hazard(conn, id)?;
+        conn.execute("DELETE FROM jsa_hazards WHERE id = ?", [id])?;
+        audit::log_delete(conn, actor, "jsa_hazards", id, &existing)?;
+        Ok(())
+    })
+}
+
+// Controls
+fn row_to_control(row: &rusqlite::Row<'_>) -> Result<JsaControl> {
+    Ok(JsaControl {
+        id: row.get(0)?,
+        jsa_hazard_id: row.get(1)?,
+        control_type: row.get(2)?,
+        control_description: row.get(3)?,
+        is_implemented: row.get::<_, i64>(4)? == 1,
+    })
+}
+
+/// Position in the hierarchy of controls; 0 is most effective.
+pub fn control_rank(control_type: &str) -> Option<usize> {
+    CONTROL_TYPES.iter().position(|t| *t == control_type)
+}
+
+pub fn add_control(conn: &Connection, actor: &Actor, data: AddJsaControl) -> Result<JsaControl> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO jsa_controls (jsa_hazard_id, control_type, control_description, is_implemented)
+             VALUES (?, ?, ?, ?)",
+            params![
+                data.jsa_hazard_id,
+                data.control_type,
+                data.control_description,
+                data.is_implemented.unwrap_or(false),
+            ],
+        )?;
+
+        let id = conn.last_insert_rowid();
+        let created = get_control(conn, id)?;
+        audit::log_create(conn, actor, "jsa_controls", id, &created)?;
+        Ok(created)
+    })
+}
+
+pub fn get_control(conn: &Connection, id: i64) -> Result<JsaControl> {
+    conn.query_row(
+        "SELECT id, jsa_hazard_id, control_type, control_description, is_implemented
+         FROM jsa_controls WHERE id = ?",
+        [id],
+        row_to_control,
+    )
+}
+
+/// Controls for a hazard, most effective type first.
+pub fn list_controls(conn: &Connection, jsa_hazard_id: i64) -> Result<Vec<JsaControl>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, jsa_hazard_id, control_type, control_description, is_implemented
+         FROM jsa_controls WHERE jsa_hazard_id = ? ORDER BY id",
+    )?;
+
+    let mut rows = stmt
+        .query_map([jsa_hazard_id], row_to_control)?
+        .collect::<Result<Vec<_>>>()?;
+    rows.sort_by_key(|c| control_rank(&c.control_type).unwrap_or(CONTROL_TYPES.len()));
+    Ok(rows)
+}
+
+pub fn update_control(
+    conn: &Connection,
+    actor: &Actor,
+    id: i64,
+    data: UpdateJsaControl,
+) -> Result<JsaControl> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_control(conn, id)?;
+        conn.execute(
+            "UPDATE jsa_controls SET control_type = ?, control_description = ?, is_implemented = ?
+             WHERE id = ?",
+            params![
+                data.control_type.as_deref().unwrap_or(&existing.control_type),
+                data.control_description.as_deref().unwrap_or(&existing.control_description),
+                data.is_implemented.unwrap_or(existing.is_implemented),
+                id,
+            ],
+        )?;
+        let updated = get_control(conn, id)?;
+        audit::log_update(conn, actor, "jsa_controls", id, &existing, &updated)?;
+        Ok(updated)
+    })
+}
+
+pub fn delete_control(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_control(conn, id)?;
+        conn.execute("DELETE FROM jsa_controls WHERE id = ?", [id])?;
+        audit::log_delete(conn, actor, "jsa_controls", id, &existing)?;
+        Ok(())
+    })
+}
+
+/// Flags high and critical hazards on a JSA that rely on PPE alone (the
+/// last line of defence) or have no controls at all.
+pub fn check_controls(conn: &Connection, jsa_instance_id: i64) -> Result<Vec<ControlFinding>> {
+    let mut stmt = conn.prepare(
+        "SELECT h.id, h.jsa_step_id, s.step_number, h.hazard_description, h.severity,
+                COUNT(c.id),
+                COALESCE(SUM(c.control_type = 'ppe'), 0)
+         FROM jsa_hazards h
+         JOIN jsa_steps s ON h.jsa_step_id = s.id
+         LEFT JOIN jsa_controls c ON c.jsa_hazard_id = h.id
+         WHERE s.jsa_instance_id = ?
+         GROUP BY h.id
+         ORDER BY s.step_number, h.id",
+    )?;
+
+    let rows = stmt.query_map([jsa_instance_id], |row| {
+        Ok((
+            JsaHazard {
+                id: row.get(0)?,
+                jsa_step_id: row.get(1)?,
+                hazard_description: row.get(3)?,
+                severity: row.get(4)?,
+            },
+            row.get::<_, i64>(2)?,
+            row.get::<_, i64>(5)?,
+            row.get::<_, i64>(6)?,
+        ))
+    })?;
+
+    let mut findings = Vec::new();
+    for row in rows {
+        let (hazard, step_number, total, ppe) = row?;
+        if !HIGH_SEVERITIES.contains(&hazard.severity.as_str()) {
+            continue;
+        }
+
+        let (issue, message) = if total == 0 {
+            ("no_controls", format!(
+                "Step {step_number}: {} hazard \"{}\" has no controls",
+                hazard.severity, hazard.hazard_description
+            ))
+        } else if ppe == total {
+            ("ppe_only", format!(
+                "Step {step_number}: {} hazard \"{}\" is controlled only by PPE; add an elimination, substitution, engineering or administrative control",
+                hazard.severity, hazard.hazard_description
+            ))
+        } else {
+            continue;
+        };
+
+        findings.push(ControlFinding {
+            jsa_hazard_id: hazard.id,
+            jsa_step_id: hazard.jsa_step_id,
+            step_number,
+            hazard_description: hazard.hazard_description,
+            severity: hazard.severity,
+            issue: issue.to_string(),
+            message,
+        });
+    }
+
+    Ok(findings)
+}
+
+// Trade hazard suggestions
+
+/// Best-guess hierarchy level for a free-text library control. Library
+/// controls are untyped, so anything unrecognised is treated as
+/// administrative and left for the preparer to reclassify.
+pub fn classify_control(text: &str) -> &'static str {
+    let text = text.to_lowercase();
+    let has = |words: &[&str]| words.iter().any(|w| text.contains(w));
+
+    if has(&["de-energize", "eliminate", "remove "]) {
+        "elimination"
+    } else if has(&["substitut", "replace with"]) {
+        "substitution"
+    } else if has(&["ppe", "respirator", "gloves", "boots", "glasses", "goggles", "face shield",
+        "hearing protection", "arc-rated", "personal fall arrest", "harness", "hard hat"]) {
+        "ppe"
+    } else if has(&["guard", "shoring", "shield", "sloping", "ventilation", "vacuum", "wet method",
+        "ground-fault", "gfci", "interlock", "mechanical lift", "engineered", "collection",
+        "trigger"]) {
+        "engineering"
+    } else {
+        "administrative"
+    }
+}
+
+/// Active library hazards for the trade of the instance's template that
+/// aren't already on the JSA. Instances without a template, or whose
+/// template has no trade, get no suggestions.
+pub fn suggest_trade_hazards(conn: &Connection, jsa_instance_id: i64) -> Result<Vec<TradeHazard>> {
+    let trade: Option<String> = conn.query_row(
+        "SELECT t.trade FROM jsa_instances i
+         LEFT JOIN jsa_templates t ON i.template_id = t.id
+         WHERE i.id = ?",
+        [jsa_instance_id],
+        |row| row.get(0),
+    )?;
+    let Some(trade) = trade else {
+        return Ok(Vec::new());
+    };
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM trade_hazards th
+         WHERE th.trade = ? AND th.is_active = 1
+           AND NOT EXISTS (
+               SELECT 1 FROM jsa_hazards h
+               JOIN jsa_steps s ON h.jsa_step_id = s.id
+               WHERE s.jsa_instance_id = ?
+                 AND (lower(h.hazard_description) = lower(th.hazard_name)
+                      OR lower(h.hazard_description) LIKE lower(th.hazard_name) || ':%')
+           )
+         ORDER BY th.hazard_name",
+        trade_hazards::SELECT_COLS
+    ))?;
+    let rows = stmt.query_map(
+        params![trade_hazards::normalize_trade(&trade), jsa_instance_id],
+        trade_hazards::row_to_hazard,
+    )?;
+
+    rows.collect()
+}
+
+/// Adds a library hazard to a step, with its recommended controls typed by
+/// `classify_control`.
+pub fn add_trade_hazard_to_step(
+    conn: &Connection,
+    actor: &Actor,
+    jsa_step_id: i64,
+    trade_hazard_id: i64,
+    severity: Option<String>,
+) -> Result<JsaHazard> {
+    let library = conn.query_row(
+        &format!("SELECT {} FROM trade_hazards WHERE id = ?", trade_hazards::SELECT_COLS),
+        [trade_hazard_id],
+        trade_hazards::row_to_hazard,
+    )?;
+
+    let description = match library.hazard_description {
+        Some(ref detail) if !detail.trim().is_empty() => format!("{}: {}", library.hazard_name, detail.trim()),
+        _ => library.hazard_name.clone(),
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    let hazard = add_hazard(&tx, actor, AddJsaHazard {
+        jsa_step_id,
+        hazard_description: description,
+        severity,
+    })?;
+    for control in &library.recommended_controls {
+        add_control(&tx, actor, AddJsaControl {
+            jsa_hazard_id: hazard.id,
+            control_type: classify_control(control).to_string(),
+            control_description: control.clone(),
+            is_implemented: None,
+        })?;
+    }
+    tx.commit()?;
+
+    Ok(hazard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::locations::{create_establishment, CreateEstablishment};
+    use crate::db::open_test_db;
+
+    fn setup_step(conn: &Connection) -> JsaStep {
+        let actor = Actor::system();
+        let est = create_establishment(conn, &actor, CreateEstablishment {
+            name: "Test Co".into(),
+            street_address: None, city: None, state: None,
+            zip_code: None, industry_description: None, naics_code: None,
+            ein: None, company_name: None, establishment_type: None, case_number_format: None,
+        }).unwrap();
+        let jsa = create_instance(conn, &actor, CreateJsaInstance {
+            template_id: None, establishment_id: est.id, location_id: None,
+            job_name: "Roof tear-off".into(), job_date: "2026-06-01".into(), prepared_by: "Sam".into(),
+        }).unwrap();
+        add_step(conn, &actor, AddJsaStep {
+            jsa_instance_id: jsa.id, step_number: 1, task_description: "Remove shingles".into(),
+        }).unwrap()
+    }
+
+    fn hazard(conn: &Connection, step_id: i64, text: &str, severity: &str) -> JsaHazard {
+        add_hazard(conn, &Actor::system(), AddJsaHazard {
+            jsa_step_id: step_id, hazard_description: text.into(), severity: Some(severity.into()),
+        }).unwrap()
+    }
+
+    fn control(conn: &Connection, hazard_id: i64, control_type: &str) -> JsaControl {
+        add_control(conn, &Actor::system(), AddJsaControl {
+            jsa_hazard_id: hazard_id, control_type: control_type.into(),
+            control_description: "control".into(), is_implemented: None,
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_check_controls_flags_ppe_only_high_hazards() {
+        let conn = open_test_db();
+        let step = setup_step(&conn);
+
+        let fall = hazard(&conn, step.id, "Fall from roof edge", "critical");
+        control(&conn, fall.id, "ppe");
+        let cut = hazard(&conn, step.id, "Cuts from flashing", "medium");
+        control(&conn, cut.id, "ppe");
+        let debris = hazard(&conn, step.id, "Falling debris", "high");
+        let guarded = hazard(&conn, step.id, "Skylight opening", "high");
+        control(&conn, guarded.id, "ppe");
+        control(&conn, guarded.id, "engineering");
+
+        let findings = check_controls(&conn, step.jsa_instance_id).unwrap();
+        let issues: Vec<(i64, &str)> = findings.iter().map(|f| (f.jsa_hazard_id, f.issue.as_str())).collect();
+        assert_eq!(issues, vec![(fall.id, "ppe_only"), (debris.id, "no_controls")]);
+
+        let ordered: Vec<String> = list_controls(&conn, guarded.id).unwrap()
+            .into_iter().map(|c| c.control_type).collect();
+        assert_eq!(ordered, vec!["engineering", "ppe"]);
+    }
+
+    #[test]
+    fn test_create_instance_copies_template_content() {
+        let conn = open_test_db();
+        let actor = Actor::system();
+        let est = create_establishment(&conn, &actor, CreateEstablishment {
+            name: "Test Co".into(),
+            street_address: None, city: None, state: None,
+            zip_code: None, industry_description: None, naics_code: None,
+            ein: None, company_name: None, establishment_type: None, case_number_format: None,
+        }).unwrap();
+        let template_id: i64 = conn.query_row(
+            "SELECT id FROM jsa_templates WHERE name = 'Electrical Panel Installation'", [], |r| r.get(0),
+        ).unwrap();
+
+        let jsa = create_instance(&conn, &actor, CreateJsaInstance {
+            template_id: Some(template_id), establishment_id: est.id, location_id: None,
+            job_name: "Panel A".into(), job_date: "2026-06-01".into(), prepared_by: "Sam".into(),
+        }).unwrap();
+
+        let steps = list_steps(&conn, jsa.id).unwrap();
+        assert_eq!(steps.len(), 3);
+        let hazards = list_hazards(&conn, steps[0].id).unwrap();
+        assert_eq!(hazards[0].hazard_description, "Unexpected energization");
+        let controls = list_controls(&conn, hazards[0].id).unwrap();
+        assert_eq!(controls[0].control_type, "elimination");
+
+        // Copies are independent of the template
+        let first = list_template_steps(&conn, template_id).unwrap()[0].id;
+        delete_template_step(&conn, &actor, first).unwrap();
+        assert_eq!(list_steps(&conn, jsa.id).unwrap().len(), 3);
+        let history = audit::get_record_history(&conn, "jsa_template_steps", first).unwrap();
+        assert_eq!(history[0].action, "delete");
+
+        // Electrical library hazards are suggested until added
+        let suggested = suggest_trade_hazards(&conn, jsa.id).unwrap();
+        let arc = suggested.iter().find(|h| h.hazard_name == "Arc Flash").unwrap();
+        let added = add_trade_hazard_to_step(&conn, &actor, steps[2].id, arc.id, Some("high".into())).unwrap();
+        let types: Vec<String> = list_controls(&conn, added.id).unwrap().into_iter().map(|c| c.control_type).collect();
+        assert_eq!(types, vec!["elimination", "administrative", "ppe"]);
+        let remaining = suggest_trade_hazards(&conn, jsa.id).unwrap();
+        assert_eq!(remaining.len(), suggested.len() - 1);
+        assert!(remaining.iter().all(|h| h.hazard_name != "Arc Flash"));
+    }
+
+    #[test]
+    fn test_status_workflow_stamps_signers() {
+        let conn = open_test_db();
+        let actor = Actor::system();
+        let step = setup_step(&conn);
+        let id = step.jsa_instance_id;
+
+        assert_eq!(transition_role("draft", "submitted"), Some("supervisor"));
+        assert_eq!(transition_role("reviewed", "approved"), Some("safety_manager"));
+        assert_eq!(transition_role("draft", "approved"), None);
+        assert_eq!(transition_role("approved", "draft"), None);
+        assert!(next_statuses("closed").is_empty());
+
+        update_instance_status(&conn, &actor, id, "submitted", "Sam").unwrap();
+        let reviewed = update_instance_status(&conn, &actor, id, "reviewed", "Riley").unwrap();
+        assert_eq!(reviewed.reviewed_by.as_deref(), Some("Riley"));
+        assert!(reviewed.reviewed_at.is_some());
+        assert!(reviewed.approved_by.is_none());
+
+        let returned = update_instance_status(&conn, &actor, id, "draft", "Morgan").unwrap();
+        assert!(returned.reviewed_by.is_none() && returned.submitted_at.is_none());
+
+        update_instance_status(&conn, &actor, id, "submitted", "Sam").unwrap();
+        update_instance_status(&conn, &actor, id, "reviewed", "Riley").unwrap();
+        let approved = update_instance_status(&conn, &actor, id, "approved", "Morgan").unwrap();
+        assert_eq!(approved.reviewed_by.as_deref(), Some("Riley"));
+        assert_eq!(approved.approved_by.as_deref(), Some("Morgan"));
+        assert!(is_locked_status(&status_for_step(&conn, step.id).unwrap()));
+    }
 }
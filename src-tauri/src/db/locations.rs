@@ -2,6 +2,8 @@ use anyhow::{Context, Result};
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 
+use crate::db::audit::{self, Actor};
+use crate::db::trash;
 use crate::errors::AppError;
 
 // ── Establishment ──
@@ -16,10 +18,25 @@ pub struct Establishment {
     pub zip_code: Option<String>,
     pub industry_description: Option<String>,
     pub naics_code: Option<String>,
+    /// Federal Employer Identification Number, 9 digits
+    pub ein: Option<String>,
+    /// Legal company name when it differs from the establishment name
+    pub company_name: Option<String>,
+    /// `private`, `state_government` or `local_government`
+    pub establishment_type: String,
+    /// Case number display format, e.g. `{year}-EST01-{seq:4}`; plain
+    /// numbers when unset
+    pub case_number_format: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// Set while the establishment is in the trash
+    pub deleted_at: Option<String>,
+    pub deleted_by: Option<String>,
 }
 
+/// Establishment types as reported to OSHA's ITA.
+pub const ESTABLISHMENT_TYPES: &[&str] = &["private", "state_government", "local_government"];
+
 #[derive(Debug, Deserialize)]
 pub struct CreateEstablishment {
     pub name: String,
@@ -29,6 +46,10 @@ pub struct CreateEstablishment {
     pub zip_code: Option<String>,
     pub industry_description: Option<String>,
     pub naics_code: Option<String>,
+    pub ein: Option<String>,
+    pub company_name: Option<String>,
+    pub establishment_type: Option<String>,
+    pub case_number_format: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -40,31 +61,50 @@ pub struct UpdateEstablishment {
     pub zip_code: Option<String>,
     pub industry_description: Option<String>,
     pub naics_code: Option<String>,
+    pub ein: Option<String>,
+    pub company_name: Option<String>,
+    pub establishment_type: Option<String>,
+    pub case_number_format: Option<String>,
 }
 
-pub fn create_establishment(conn: &Connection, data: CreateEstablishment) -> Result<Establishment> {
-    conn.execute(
-        "INSERT INTO establishments (name, street_address, city, state, zip_code, industry_description, naics_code)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-        params![
-            data.name,
-            data.street_address,
-            data.city,
-            data.state,
-            data.zip_code,
-            data.industry_description,
-            data.naics_code,
-        ],
-    )
-    .context("Failed to create establishment")?;
+pub fn create_establishment(
+    conn: &Connection,
+    actor: &Actor,
+    data: CreateEstablishment,
+) -> Result<Establishment> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO establishments (name, street_address, city, state, zip_code, industry_description, naics_code,
+                                         ein, company_name, establishment_type, case_number_format)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, NULLIF(?11, ''))",
+            params![
+                data.name,
+                data.street_address,
+                data.city,
+                data.state,
+                data.zip_code,
+                data.industry_description,
+                data.naics_code,
+                data.ein,
+                data.company_name,
+                data.establishment_type.as_deref().unwrap_or("private"),
+                data.case_number_format,
+            ],
+        )
+        .context("Failed to create establishment")?;
 
-    let id = conn.last_insert_rowid();
-    get_establishment(conn, id)
+        let id = conn.last_insert_rowid();
+        let created = get_establishment(conn, id)?;
+        audit::log_create(conn, actor, "establishments", id, &created)?;
+        Ok(created)
+    })
 }
 
 pub fn get_establishment(conn: &Connection, id: i64) -> Result<Establishment> {
     conn.query_row(
-        "SELECT id, name, street_address, city, state, zip_code, industry_description, naics_code, created_at, updated_at
+        "SELECT id, name, street_address, city, state, zip_code, industry_description, naics_code,
+                ein, company_name, establishment_type, created_at, updated_at, case_number_format,
+                deleted_at, deleted_by
          FROM establishments WHERE id = ?1",
         [id],
         |row| {
@@ -77,8 +117,14 @@ pub fn get_establishment(conn: &Connection, id: i64) -> Result<Establishment> {
                 zip_code: row.get(5)?,
                 industry_description: row.get(6)?,
                 naics_code: row.get(7)?,
-                created_at: row.get(8)?,
-                updated_at: row.get(9)?,
+                ein: row.get(8)?,
+                company_name: row.get(9)?,
+                establishment_type: row.get(10)?,
+                created_at: row.get(11)?,
+                updated_at: row.get(12)?,
+                case_number_format: row.get(13)?,
+                deleted_at: row.get(14)?,
+                deleted_by: row.get(15)?,
             })
         },
     )
@@ -92,8 +138,10 @@ pub fn get_establishment(conn: &Connection, id: i64) -> Result<Establishment> {
 
 pub fn list_establishments(conn: &Connection) -> Result<Vec<Establishment>> {
     let mut stmt = conn.prepare(
-        "SELECT id, name, street_address, city, state, zip_code, industry_description, naics_code, created_at, updated_at
-         FROM establishments ORDER BY name",
+        "SELECT id, name, street_address, city, state, zip_code, industry_description, naics_code,
+                ein, company_name, establishment_type, created_at, updated_at, case_number_format,
+                deleted_at, deleted_by
+         FROM establishments WHERE deleted_at IS NULL ORDER BY name",
     )?;
 
     let rows = stmt
@@ -107,8 +155,14 @@ pub fn list_establishments(conn: &Connection) -> Result<Vec<Establishment>> {
                 zip_code: row.get(5)?,
                 industry_description: row.get(6)?,
                 naics_code: row.get(7)?,
-                created_at: row.get(8)?,
-                updated_at: row.get(9)?,
+                ein: row.get(8)?,
+                company_name: row.get(9)?,
+                establishment_type: row.get(10)?,
+                created_at: row.get(11)?,
+                updated_at: row.get(12)?,
+                case_number_format: row.get(13)?,
+                deleted_at: row.get(14)?,
+                deleted_by: row.get(15)?,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -118,65 +172,110 @@ pub fn list_establishments(conn: &Connection) -> Result<Vec<Establishment>> {
 
 pub fn update_establishment(
     conn: &Connection,
+    actor: &Actor,
     id: i64,
     data: UpdateEstablishment,
 ) -> Result<Establishment> {
-    // Verify exists
-    let _existing = get_establishment(conn, id)?;
+    super::immediate_transaction(conn, |conn| {
+        // Verify exists
+        let existing = get_establishment(conn, id)?;
 
-    let mut sets = Vec::new();
-    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+        let mut sets = Vec::new();
+        let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
 
-    if let Some(ref name) = data.name {
-        sets.push("name = ?");
-        values.push(Box::new(name.clone()));
-    }
-    if let Some(ref v) = data.street_address {
-        sets.push("street_address = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(ref v) = data.city {
-        sets.push("city = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(ref v) = data.state {
-        sets.push("state = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(ref v) = data.zip_code {
-        sets.push("zip_code = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(ref v) = data.industry_description {
-        sets.push("industry_description = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(ref v) = data.naics_code {
-        sets.push("naics_code = ?");
-        values.push(Box::new(v.clone()));
-    }
+        if let Some(ref name) = data.name {
+            sets.push("name = ?");
+            values.push(Box::new(name.clone()));
+        }
+        if let Some(ref v) = data.street_address {
+            sets.push("street_address = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.city {
+            sets.push("city = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.state {
+            sets.push("state = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.zip_code {
+            sets.push("zip_code = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.industry_description {
+            sets.push("industry_description = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.naics_code {
+            sets.push("naics_code = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.ein {
+            sets.push("ein = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.company_name {
+            sets.push("company_name = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.establishment_type {
+            sets.push("establishment_type = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.case_number_format {
+            sets.push("case_number_format = NULLIF(?, '')");
+            values.push(Box::new(v.clone()));
+        }
 
-    if !sets.is_empty() {
-        sets.push("updated_at = datetime('now')");
-        let sql = format!("UPDATE establishments SET {} WHERE id = ?", sets.join(", "));
-        values.push(Box::new(id));
-        let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
-        conn.execute(&sql, params.as_slice())
-            .context("Failed to update establishment")?;
-    }
+        if !sets.is_empty() {
+            sets.push("updated_at = datetime('now')");
+            let sql = format!("UPDATE establishments SET {} WHERE id = ?", sets.join(", "));
+            values.push(Box::new(id));
+            let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+            conn.execute(&sql, params.as_slice())
+                .context("Failed to update establishment")?;
+        }
+
+        let updated = get_establishment(conn, id)?;
+        if !sets.is_empty() {
+            audit::log_update(conn, actor, "establishments", id, &existing, &updated)?;
+        }
+        Ok(updated)
+    })
+}
 
-    get_establishment(conn, id)
+/// Moves the establishment to the trash. Its locations, incidents and other
+/// records are left as they are and reappear when it is restored.
+pub fn delete_establishment(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_establishment(conn, id)?;
+        trash::mark_deleted(conn, actor, "establishments", id)
+            .map_err(|e| trash::state_error(e, || format!("Establishment {id} is already in the trash")))?;
+        audit::log_delete(conn, actor, "establishments", id, &existing)?;
+        Ok(())
+    })
 }
 
-pub fn delete_establishment(conn: &Connection, id: i64) -> Result<()> {
-    let changes = conn
-        .execute("DELETE FROM establishments WHERE id = ?1", [id])
-        .context("Failed to delete establishment")?;
+pub fn restore_establishment(conn: &Connection, actor: &Actor, id: i64) -> Result<Establishment> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_establishment(conn, id)?;
+        trash::clear_deleted(conn, "establishments", id)
+            .map_err(|e| trash::state_error(e, || format!("Establishment {id} is not in the trash")))?;
+        let restored = get_establishment(conn, id)?;
+        audit::record(conn, actor, "restore", "establishments", id, Some(&existing), Some(&restored))?;
+        Ok(restored)
+    })
+}
 
-    if changes == 0 {
-        return Err(AppError::NotFound(format!("Establishment {id} not found")).into());
-    }
-    Ok(())
+pub fn purge_establishment(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_establishment(conn, id)?;
+        trash::purge_row(conn, "establishments", id)
+            .map_err(|e| trash::state_error(e, || format!("Establishment {id} is not in the trash")))?;
+        audit::record(conn, actor, "purge", "establishments", id, Some(&existing), None)?;
+        Ok(())
+    })
 }
 
 // ── Location ──
@@ -192,6 +291,9 @@ pub struct Location {
     pub is_active: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// Set while the location is in the trash
+    pub deleted_at: Option<String>,
+    pub deleted_by: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -212,27 +314,32 @@ pub struct UpdateLocation {
     pub is_active: Option<bool>,
 }
 
-pub fn create_location(conn: &Connection, data: CreateLocation) -> Result<Location> {
-    conn.execute(
-        "INSERT INTO locations (establishment_id, name, address, city, state)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
-        params![
-            data.establishment_id,
-            data.name,
-            data.address,
-            data.city,
-            data.state,
-        ],
-    )
-    .context("Failed to create location")?;
+pub fn create_location(conn: &Connection, actor: &Actor, data: CreateLocation) -> Result<Location> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO locations (establishment_id, name, address, city, state)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                data.establishment_id,
+                data.name,
+                data.address,
+                data.city,
+                data.state,
+            ],
+        )
+        .context("Failed to create location")?;
 
-    let id = conn.last_insert_rowid();
-    get_location(conn, id)
+        let id = conn.last_insert_rowid();
+        let created = get_location(conn, id)?;
+        audit::log_create(conn, actor, "locations", id, &created)?;
+        Ok(created)
+    })
 }
 
 pub fn get_location(conn: &Connection, id: i64) -> Result<Location> {
     conn.query_row(
-        "SELECT id, establishment_id, name, address, city, state, is_active, created_at, updated_at
+        "SELECT id, establishment_id, name, address, city, state, is_active, created_at, updated_at,
+                deleted_at, deleted_by
          FROM locations WHERE id = ?1",
         [id],
         |row| {
@@ -246,6 +353,8 @@ pub fn get_location(conn: &Connection, id: i64) -> Result<Location> {
                 is_active: row.get::<_, i32>(6)? != 0,
                 created_at: row.get(7)?,
                 updated_at: row.get(8)?,
+                deleted_at: row.get(9)?,
+                deleted_by: row.get(10)?,
             })
         },
     )
@@ -259,8 +368,9 @@ pub fn get_location(conn: &Connection, id: i64) -> Result<Location> {
 
 pub fn list_locations(conn: &Connection, establishment_id: i64) -> Result<Vec<Location>> {
     let mut stmt = conn.prepare(
-        "SELECT id, establishment_id, name, address, city, state, is_active, created_at, updated_at
-         FROM locations WHERE establishment_id = ?1 ORDER BY name",
+        "SELECT id, establishment_id, name, address, city, state, is_active, created_at, updated_at,
+                deleted_at, deleted_by
+         FROM locations WHERE establishment_id = ?1 AND deleted_at IS NULL ORDER BY name",
     )?;
 
     let rows = stmt
@@ -275,6 +385,8 @@ pub fn list_locations(conn: &Connection, establishment_id: i64) -> Result<Vec<Lo
                 is_active: row.get::<_, i32>(6)? != 0,
                 created_at: row.get(7)?,
                 updated_at: row.get(8)?,
+                deleted_at: row.get(9)?,
+                deleted_by: row.get(10)?,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -282,54 +394,86 @@ pub fn list_locations(conn: &Connection, establishment_id: i64) -> Result<Vec<Lo
     Ok(rows)
 }
 
-pub fn update_location(conn: &Connection, id: i64, data: UpdateLocation) -> Result<Location> {
-    let _existing = get_location(conn, id)?;
+pub fn update_location(
+    conn: &Connection,
+    actor: &Actor,
+    id: i64,
+    data: UpdateLocation,
+) -> Result<Location> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_location(conn, id)?;
 
-    let mut sets = Vec::new();
-    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+        let mut sets = Vec::new();
+        let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
 
-    if let Some(ref name) = data.name {
-        sets.push("name = ?");
-        values.push(Box::new(name.clone()));
-    }
-    if let Some(ref v) = data.address {
-        sets.push("address = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(ref v) = data.city {
-        sets.push("city = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(ref v) = data.state {
-        sets.push("state = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(active) = data.is_active {
-        sets.push("is_active = ?");
-        values.push(Box::new(active as i32));
-    }
+        if let Some(ref name) = data.name {
+            sets.push("name = ?");
+            values.push(Box::new(name.clone()));
+        }
+        if let Some(ref v) = data.address {
+            sets.push("address = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.city {
+            sets.push("city = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.state {
+            sets.push("state = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(active) = data.is_active {
+            sets.push("is_active = ?");
+            values.push(Box::new(active as i32));
+        }
 
-    if !sets.is_empty() {
-        sets.push("updated_at = datetime('now')");
-        let sql = format!("UPDATE locations SET {} WHERE id = ?", sets.join(", "));
-        values.push(Box::new(id));
-        let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
-        conn.execute(&sql, params.as_slice())
-            .context("Failed to update location")?;
-    }
+        if !sets.is_empty() {
+            sets.push("updated_at = datetime('now')");
+            let sql = format!("UPDATE locations SET {} WHERE id = ?", sets.join(", "));
+            values.push(Box::new(id));
+            let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+            conn.execute(&sql, params.as_slice())
+                .context("Failed to update location")?;
+        }
 
-    get_location(conn, id)
+        let updated = get_location(conn, id)?;
+        if !sets.is_empty() {
+            audit::log_update(conn, actor, "locations", id, &existing, &updated)?;
+        }
+        Ok(updated)
+    })
 }
 
-pub fn delete_location(conn: &Connection, id: i64) -> Result<()> {
-    let changes = conn
-        .execute("DELETE FROM locations WHERE id = ?1", [id])
-        .context("Failed to delete location")?;
+/// Moves the location to the trash.
+pub fn delete_location(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_location(conn, id)?;
+        trash::mark_deleted(conn, actor, "locations", id)
+            .map_err(|e| trash::state_error(e, || format!("Location {id} is already in the trash")))?;
+        audit::log_delete(conn, actor, "locations", id, &existing)?;
+        Ok(())
+    })
+}
 
-    if changes == 0 {
-        return Err(AppError::NotFound(format!("Location {id} not found")).into());
-    }
-    Ok(())
+pub fn restore_location(conn: &Connection, actor: &Actor, id: i64) -> Result<Location> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_location(conn, id)?;
+        trash::clear_deleted(conn, "locations", id)
+            .map_err(|e| trash::state_error(e, || format!("Location {id} is not in the trash")))?;
+        let restored = get_location(conn, id)?;
+        audit::record(conn, actor, "restore", "locations", id, Some(&existing), Some(&restored))?;
+        Ok(restored)
+    })
+}
+
+pub fn purge_location(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_location(conn, id)?;
+        trash::purge_row(conn, "locations", id)
+            .map_err(|e| trash::state_error(e, || format!("Location {id} is not in the trash")))?;
+        audit::record(conn, actor, "purge", "locations", id, Some(&existing), None)?;
+        Ok(())
+    })
 }
 
 #[cfg(test)]
@@ -340,6 +484,7 @@ mod tests {
     fn create_test_establishment(conn: &Connection) -> Establishment {
         create_establishment(
             conn,
+            &Actor::system(),
             CreateEstablishment {
                 name: "Test Construction Co".into(),
                 street_address: Some("123 Main St".into()),
@@ -348,6 +493,10 @@ mod tests {
                 zip_code: Some("62701".into()),
                 industry_description: Some("General Construction".into()),
                 naics_code: Some("236220".into()),
+                ein: None,
+                company_name: None,
+                establishment_type: None,
+                case_number_format: None,
             },
         )
         .expect("create establishment")
@@ -364,6 +513,7 @@ mod tests {
 
         let updated = update_establishment(
             &conn,
+            &Actor::system(),
             est.id,
             UpdateEstablishment {
                 name: Some("Updated Co".into()),
@@ -373,6 +523,10 @@ mod tests {
                 zip_code: None,
                 industry_description: None,
                 naics_code: None,
+                ein: None,
+                company_name: None,
+                establishment_type: None,
+                case_number_format: None,
             },
         )
         .unwrap();
@@ -381,7 +535,11 @@ mod tests {
         let all = list_establishments(&conn).unwrap();
         assert_eq!(all.len(), 1);
 
-        delete_establishment(&conn, est.id).unwrap();
+        delete_establishment(&conn, &Actor::system(), est.id).unwrap();
+        assert!(list_establishments(&conn).unwrap().is_empty());
+        restore_establishment(&conn, &Actor::system(), est.id).unwrap();
+        delete_establishment(&conn, &Actor::system(), est.id).unwrap();
+        purge_establishment(&conn, &Actor::system(), est.id).unwrap();
         assert!(get_establishment(&conn, est.id).is_err());
     }
 
@@ -392,6 +550,7 @@ mod tests {
 
         let loc = create_location(
             &conn,
+            &Actor::system(),
             CreateLocation {
                 establishment_id: est.id,
                 name: "Main Office".into(),
@@ -406,6 +565,7 @@ mod tests {
 
         let updated = update_location(
             &conn,
+            &Actor::system(),
             loc.id,
             UpdateLocation {
                 name: None,
@@ -421,7 +581,43 @@ mod tests {
         let locs = list_locations(&conn, est.id).unwrap();
         assert_eq!(locs.len(), 1);
 
-        delete_location(&conn, loc.id).unwrap();
+        delete_location(&conn, &Actor::system(), loc.id).unwrap();
+        assert!(get_location(&conn, loc.id).unwrap().deleted_at.is_some());
+        let again = delete_location(&conn, &Actor::system(), loc.id).unwrap_err();
+        assert_eq!(again.to_string(), format!("Location {} is already in the trash", loc.id));
+        purge_location(&conn, &Actor::system(), loc.id).unwrap();
         assert!(get_location(&conn, loc.id).is_err());
     }
+
+    #[test]
+    fn test_change_rolls_back_when_audit_fails() {
+        let conn = open_test_db();
+        let est = create_test_establishment(&conn);
+        conn.execute_batch("DROP TABLE audit_log").unwrap();
+
+        let renamed = update_establishment(&conn, &Actor::system(), est.id, UpdateEstablishment {
+            name: Some("Unaudited".into()),
+            street_address: None, city: None, state: None, zip_code: None, industry_description: None,
+            naics_code: None, ein: None, company_name: None, establishment_type: None, case_number_format: None,
+        });
+        assert!(renamed.is_err());
+        assert_eq!(get_establishment(&conn, est.id).unwrap().name, est.name);
+        assert!(delete_establishment(&conn, &Actor::system(), est.id).is_err());
+        assert!(get_establishment(&conn, est.id).unwrap().deleted_at.is_none());
+    }
+
+    #[test]
+    fn test_trash_errors_only_blame_the_trash_state_when_no_row_matched() {
+        let conn = open_test_db();
+        let est = create_test_establishment(&conn);
+        conn.execute_batch(
+            "CREATE TRIGGER block_updates BEFORE UPDATE ON establishments
+             BEGIN SELECT RAISE(ABORT, 'establishments are read-only'); END",
+        )
+        .unwrap();
+
+        let err = delete_establishment(&conn, &Actor::system(), est.id).unwrap_err();
+        assert!(err.to_string().contains("read-only"), "{err}");
+        assert!(get_establishment(&conn, est.id).unwrap().deleted_at.is_none());
+    }
 }
@@ -0,0 +1,6 @@
+-- Link near misses that turned out to involve an injury to the incident they became
+
+ALTER TABLE near_miss_reports ADD COLUMN incident_id INTEGER REFERENCES incidents(id) ON DELETE SET NULL;
+ALTER TABLE near_miss_reports ADD COLUMN updated_at TEXT;
+
+CREATE INDEX idx_near_miss_establishment ON near_miss_reports(establishment_id);
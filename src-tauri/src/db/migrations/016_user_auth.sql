@@ -0,0 +1,11 @@
+-- Password credentials and session bookkeeping for local login.
+-- password_hash holds a PHC-format Argon2id string (algorithm, params and salt embedded).
+-- The seeded admin starts without a password and is set up on first launch.
+
+ALTER TABLE users ADD COLUMN password_hash TEXT;
+ALTER TABLE users ADD COLUMN last_login_at TEXT;
+
+ALTER TABLE user_sessions ADD COLUMN last_seen_at TEXT;
+
+CREATE INDEX idx_user_sessions_user ON user_sessions(user_id);
+CREATE INDEX idx_user_sessions_expires ON user_sessions(expires_at);
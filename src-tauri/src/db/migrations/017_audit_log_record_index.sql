@@ -0,0 +1,2 @@
+-- Per-record audit history lookups
+CREATE INDEX idx_audit_log_record ON audit_log(table_name, record_id);
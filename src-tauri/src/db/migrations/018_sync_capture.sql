@@ -0,0 +1,478 @@
+-- Change capture for sync.
+--
+-- Every synced table gets a stable uuid (assigned by the app, since integer
+-- ids collide across devices) and triggers that append to sync_log. The
+-- triggers are skipped while sync_control.applying_remote is set so changes
+-- pulled from the server are not echoed back. Rows that existed before this
+-- migration are queued as inserts so the first sync pushes them.
+
+ALTER TABLE sync_log ADD COLUMN record_uuid TEXT;
+ALTER TABLE sync_state ADD COLUMN last_pull_seq INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE sync_control (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    applying_remote INTEGER NOT NULL DEFAULT 0
+);
+INSERT INTO sync_control (id) VALUES (1);
+
+CREATE INDEX idx_sync_log_pending ON sync_log(synced, table_name, record_id);
+
+-- establishments
+ALTER TABLE establishments ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_establishments_uuid ON establishments(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'establishments', id, 'insert' FROM establishments;
+CREATE TRIGGER sync_establishments_insert AFTER INSERT ON establishments
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('establishments', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_establishments_update AFTER UPDATE ON establishments
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('establishments', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_establishments_delete AFTER DELETE ON establishments
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('establishments', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- locations
+ALTER TABLE locations ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_locations_uuid ON locations(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'locations', id, 'insert' FROM locations;
+CREATE TRIGGER sync_locations_insert AFTER INSERT ON locations
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('locations', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_locations_update AFTER UPDATE ON locations
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('locations', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_locations_delete AFTER DELETE ON locations
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('locations', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- incidents
+ALTER TABLE incidents ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_incidents_uuid ON incidents(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'incidents', id, 'insert' FROM incidents;
+CREATE TRIGGER sync_incidents_insert AFTER INSERT ON incidents
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('incidents', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_incidents_update AFTER UPDATE ON incidents
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('incidents', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_incidents_delete AFTER DELETE ON incidents
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('incidents', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- rca_sessions
+ALTER TABLE rca_sessions ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_rca_sessions_uuid ON rca_sessions(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'rca_sessions', id, 'insert' FROM rca_sessions;
+CREATE TRIGGER sync_rca_sessions_insert AFTER INSERT ON rca_sessions
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('rca_sessions', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_rca_sessions_update AFTER UPDATE ON rca_sessions
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('rca_sessions', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_rca_sessions_delete AFTER DELETE ON rca_sessions
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('rca_sessions', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- five_whys_steps
+ALTER TABLE five_whys_steps ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_five_whys_steps_uuid ON five_whys_steps(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'five_whys_steps', id, 'insert' FROM five_whys_steps;
+CREATE TRIGGER sync_five_whys_steps_insert AFTER INSERT ON five_whys_steps
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('five_whys_steps', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_five_whys_steps_update AFTER UPDATE ON five_whys_steps
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('five_whys_steps', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_five_whys_steps_delete AFTER DELETE ON five_whys_steps
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('five_whys_steps', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- fishbone_categories
+ALTER TABLE fishbone_categories ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_fishbone_categories_uuid ON fishbone_categories(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'fishbone_categories', id, 'insert' FROM fishbone_categories;
+CREATE TRIGGER sync_fishbone_categories_insert AFTER INSERT ON fishbone_categories
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('fishbone_categories', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_fishbone_categories_update AFTER UPDATE ON fishbone_categories
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('fishbone_categories', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_fishbone_categories_delete AFTER DELETE ON fishbone_categories
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('fishbone_categories', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- fishbone_causes
+ALTER TABLE fishbone_causes ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_fishbone_causes_uuid ON fishbone_causes(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'fishbone_causes', id, 'insert' FROM fishbone_causes;
+CREATE TRIGGER sync_fishbone_causes_insert AFTER INSERT ON fishbone_causes
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('fishbone_causes', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_fishbone_causes_update AFTER UPDATE ON fishbone_causes
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('fishbone_causes', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_fishbone_causes_delete AFTER DELETE ON fishbone_causes
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('fishbone_causes', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- corrective_actions
+ALTER TABLE corrective_actions ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_corrective_actions_uuid ON corrective_actions(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'corrective_actions', id, 'insert' FROM corrective_actions;
+CREATE TRIGGER sync_corrective_actions_insert AFTER INSERT ON corrective_actions
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('corrective_actions', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_corrective_actions_update AFTER UPDATE ON corrective_actions
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('corrective_actions', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_corrective_actions_delete AFTER DELETE ON corrective_actions
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('corrective_actions', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- annual_stats
+ALTER TABLE annual_stats ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_annual_stats_uuid ON annual_stats(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'annual_stats', id, 'insert' FROM annual_stats;
+CREATE TRIGGER sync_annual_stats_insert AFTER INSERT ON annual_stats
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('annual_stats', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_annual_stats_update AFTER UPDATE ON annual_stats
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('annual_stats', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_annual_stats_delete AFTER DELETE ON annual_stats
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('annual_stats', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- toolbox_talks
+ALTER TABLE toolbox_talks ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_toolbox_talks_uuid ON toolbox_talks(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'toolbox_talks', id, 'insert' FROM toolbox_talks;
+CREATE TRIGGER sync_toolbox_talks_insert AFTER INSERT ON toolbox_talks
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('toolbox_talks', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_toolbox_talks_update AFTER UPDATE ON toolbox_talks
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('toolbox_talks', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_toolbox_talks_delete AFTER DELETE ON toolbox_talks
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('toolbox_talks', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- toolbox_talk_attendees
+ALTER TABLE toolbox_talk_attendees ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_toolbox_talk_attendees_uuid ON toolbox_talk_attendees(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'toolbox_talk_attendees', id, 'insert' FROM toolbox_talk_attendees;
+CREATE TRIGGER sync_toolbox_talk_attendees_insert AFTER INSERT ON toolbox_talk_attendees
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('toolbox_talk_attendees', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_toolbox_talk_attendees_update AFTER UPDATE ON toolbox_talk_attendees
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('toolbox_talk_attendees', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_toolbox_talk_attendees_delete AFTER DELETE ON toolbox_talk_attendees
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('toolbox_talk_attendees', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- jsa_instances
+ALTER TABLE jsa_instances ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_jsa_instances_uuid ON jsa_instances(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'jsa_instances', id, 'insert' FROM jsa_instances;
+CREATE TRIGGER sync_jsa_instances_insert AFTER INSERT ON jsa_instances
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('jsa_instances', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_jsa_instances_update AFTER UPDATE ON jsa_instances
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('jsa_instances', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_jsa_instances_delete AFTER DELETE ON jsa_instances
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('jsa_instances', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- jsa_steps
+ALTER TABLE jsa_steps ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_jsa_steps_uuid ON jsa_steps(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'jsa_steps', id, 'insert' FROM jsa_steps;
+CREATE TRIGGER sync_jsa_steps_insert AFTER INSERT ON jsa_steps
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('jsa_steps', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_jsa_steps_update AFTER UPDATE ON jsa_steps
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('jsa_steps', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_jsa_steps_delete AFTER DELETE ON jsa_steps
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('jsa_steps', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- jsa_hazards
+ALTER TABLE jsa_hazards ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_jsa_hazards_uuid ON jsa_hazards(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'jsa_hazards', id, 'insert' FROM jsa_hazards;
+CREATE TRIGGER sync_jsa_hazards_insert AFTER INSERT ON jsa_hazards
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('jsa_hazards', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_jsa_hazards_update AFTER UPDATE ON jsa_hazards
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('jsa_hazards', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_jsa_hazards_delete AFTER DELETE ON jsa_hazards
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('jsa_hazards', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- jsa_controls
+ALTER TABLE jsa_controls ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_jsa_controls_uuid ON jsa_controls(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'jsa_controls', id, 'insert' FROM jsa_controls;
+CREATE TRIGGER sync_jsa_controls_insert AFTER INSERT ON jsa_controls
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('jsa_controls', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_jsa_controls_update AFTER UPDATE ON jsa_controls
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('jsa_controls', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_jsa_controls_delete AFTER DELETE ON jsa_controls
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('jsa_controls', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- inspections
+ALTER TABLE inspections ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_inspections_uuid ON inspections(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'inspections', id, 'insert' FROM inspections;
+CREATE TRIGGER sync_inspections_insert AFTER INSERT ON inspections
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('inspections', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_inspections_update AFTER UPDATE ON inspections
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('inspections', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_inspections_delete AFTER DELETE ON inspections
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('inspections', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- inspection_responses
+ALTER TABLE inspection_responses ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_inspection_responses_uuid ON inspection_responses(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'inspection_responses', id, 'insert' FROM inspection_responses;
+CREATE TRIGGER sync_inspection_responses_insert AFTER INSERT ON inspection_responses
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('inspection_responses', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_inspection_responses_update AFTER UPDATE ON inspection_responses
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('inspection_responses', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_inspection_responses_delete AFTER DELETE ON inspection_responses
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('inspection_responses', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- near_miss_reports
+ALTER TABLE near_miss_reports ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_near_miss_reports_uuid ON near_miss_reports(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'near_miss_reports', id, 'insert' FROM near_miss_reports;
+CREATE TRIGGER sync_near_miss_reports_insert AFTER INSERT ON near_miss_reports
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('near_miss_reports', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_near_miss_reports_update AFTER UPDATE ON near_miss_reports
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('near_miss_reports', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_near_miss_reports_delete AFTER DELETE ON near_miss_reports
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('near_miss_reports', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- training_courses
+ALTER TABLE training_courses ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_training_courses_uuid ON training_courses(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'training_courses', id, 'insert' FROM training_courses;
+CREATE TRIGGER sync_training_courses_insert AFTER INSERT ON training_courses
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('training_courses', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_training_courses_update AFTER UPDATE ON training_courses
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('training_courses', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_training_courses_delete AFTER DELETE ON training_courses
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('training_courses', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- training_records
+ALTER TABLE training_records ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_training_records_uuid ON training_records(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'training_records', id, 'insert' FROM training_records;
+CREATE TRIGGER sync_training_records_insert AFTER INSERT ON training_records
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('training_records', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_training_records_update AFTER UPDATE ON training_records
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('training_records', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_training_records_delete AFTER DELETE ON training_records
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('training_records', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- equipment
+ALTER TABLE equipment ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_equipment_uuid ON equipment(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'equipment', id, 'insert' FROM equipment;
+CREATE TRIGGER sync_equipment_insert AFTER INSERT ON equipment
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('equipment', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_equipment_update AFTER UPDATE ON equipment
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('equipment', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_equipment_delete AFTER DELETE ON equipment
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('equipment', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- equipment_inspections
+ALTER TABLE equipment_inspections ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_equipment_inspections_uuid ON equipment_inspections(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'equipment_inspections', id, 'insert' FROM equipment_inspections;
+CREATE TRIGGER sync_equipment_inspections_insert AFTER INSERT ON equipment_inspections
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('equipment_inspections', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_equipment_inspections_update AFTER UPDATE ON equipment_inspections
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('equipment_inspections', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_equipment_inspections_delete AFTER DELETE ON equipment_inspections
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('equipment_inspections', OLD.id, OLD.uuid, 'delete');
+END;
+
+-- equipment_maintenance
+ALTER TABLE equipment_maintenance ADD COLUMN uuid TEXT;
+CREATE UNIQUE INDEX idx_equipment_maintenance_uuid ON equipment_maintenance(uuid);
+INSERT INTO sync_log (table_name, record_id, operation) SELECT 'equipment_maintenance', id, 'insert' FROM equipment_maintenance;
+CREATE TRIGGER sync_equipment_maintenance_insert AFTER INSERT ON equipment_maintenance
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('equipment_maintenance', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_equipment_maintenance_update AFTER UPDATE ON equipment_maintenance
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('equipment_maintenance', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_equipment_maintenance_delete AFTER DELETE ON equipment_maintenance
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('equipment_maintenance', OLD.id, OLD.uuid, 'delete');
+END;
@@ -0,0 +1,35 @@
+-- Field-level merge for sync.
+--
+-- sync_base holds the last state of each row exchanged with the server
+-- (wire format, foreign keys as uuids) so a pull can tell which side changed
+-- a field. Fields both sides changed that matter for OSHA recordkeeping are
+-- queued in sync_conflicts instead of being decided automatically.
+
+CREATE TABLE sync_base (
+    table_name TEXT NOT NULL,
+    record_uuid TEXT NOT NULL,
+    data TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (table_name, record_uuid)
+);
+
+CREATE TABLE sync_conflicts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    table_name TEXT NOT NULL,
+    record_id INTEGER NOT NULL,
+    record_uuid TEXT NOT NULL,
+    field_name TEXT NOT NULL,
+    local_value TEXT,
+    remote_value TEXT,
+    local_updated_at TEXT,
+    remote_updated_at TEXT,
+    status TEXT NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'resolved')),
+    resolution TEXT CHECK (resolution IN ('local', 'remote', 'custom')),
+    resolved_value TEXT,
+    resolved_by INTEGER REFERENCES users(id),
+    resolved_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_sync_conflicts_status ON sync_conflicts(status);
+CREATE INDEX idx_sync_conflicts_record ON sync_conflicts(table_name, record_id, field_name);
@@ -0,0 +1,4 @@
+-- Trade hazard library: one entry per hazard within a trade, so imported
+-- libraries can update existing entries in place
+CREATE UNIQUE INDEX idx_trade_hazards_trade_name ON trade_hazards(trade, hazard_name);
+CREATE INDEX idx_trade_hazards_osha_standard ON trade_hazards(osha_standard);
@@ -0,0 +1,107 @@
+-- Template-level JSA content, deep-copied into each new instance
+
+CREATE TABLE jsa_template_steps (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    template_id INTEGER NOT NULL REFERENCES jsa_templates(id) ON DELETE CASCADE,
+    step_number INTEGER NOT NULL,
+    task_description TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE jsa_template_hazards (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    template_step_id INTEGER NOT NULL REFERENCES jsa_template_steps(id) ON DELETE CASCADE,
+    hazard_description TEXT NOT NULL,
+    severity TEXT NOT NULL DEFAULT 'medium',
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE jsa_template_controls (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    template_hazard_id INTEGER NOT NULL REFERENCES jsa_template_hazards(id) ON DELETE CASCADE,
+    control_type TEXT NOT NULL,
+    control_description TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_jsa_template_steps_template ON jsa_template_steps(template_id);
+CREATE INDEX idx_jsa_template_hazards_step ON jsa_template_hazards(template_step_id);
+CREATE INDEX idx_jsa_template_controls_hazard ON jsa_template_controls(template_hazard_id);
+
+-- Seed content for the templates from 005_jsa_seed
+
+INSERT INTO jsa_template_steps (template_id, step_number, task_description)
+SELECT t.id, v.column2, v.column3
+FROM (VALUES
+    ('Excavation Work', 1, 'Call 811 and mark underground utilities'),
+    ('Excavation Work', 2, 'Excavate and install protective system'),
+    ('Excavation Work', 3, 'Enter trench and perform work'),
+    ('Hot Work Operations', 1, 'Inspect area and obtain hot work permit'),
+    ('Hot Work Operations', 2, 'Weld, cut or braze'),
+    ('Hot Work Operations', 3, 'Maintain fire watch after work'),
+    ('Roofing Installation', 1, 'Set up access and fall protection'),
+    ('Roofing Installation', 2, 'Hoist materials to roof'),
+    ('Roofing Installation', 3, 'Install roofing materials'),
+    ('Confined Space Entry', 1, 'Test and ventilate atmosphere'),
+    ('Confined Space Entry', 2, 'Enter space under permit'),
+    ('Confined Space Entry', 3, 'Perform work and exit'),
+    ('Electrical Panel Installation', 1, 'Lock out and verify zero energy'),
+    ('Electrical Panel Installation', 2, 'Mount panel'),
+    ('Electrical Panel Installation', 3, 'Terminate conductors and energize')
+) v
+JOIN jsa_templates t ON t.name = v.column1;
+
+INSERT INTO jsa_template_hazards (template_step_id, hazard_description, severity)
+SELECT s.id, v.column3, v.column4
+FROM (VALUES
+    ('Excavation Work', 1, 'Striking buried utilities', 'critical'),
+    ('Excavation Work', 2, 'Trench cave-in', 'critical'),
+    ('Excavation Work', 3, 'Hazardous atmosphere in trench', 'high'),
+    ('Hot Work Operations', 1, 'Combustibles near work area', 'high'),
+    ('Hot Work Operations', 2, 'Burns and eye injury from arc or flame', 'high'),
+    ('Hot Work Operations', 3, 'Smoldering fire after work ends', 'medium'),
+    ('Roofing Installation', 1, 'Fall from ladder or roof edge', 'critical'),
+    ('Roofing Installation', 2, 'Struck by falling materials', 'high'),
+    ('Roofing Installation', 3, 'Fall through skylights or openings', 'critical'),
+    ('Confined Space Entry', 1, 'Oxygen-deficient or toxic atmosphere', 'critical'),
+    ('Confined Space Entry', 2, 'Entrant unable to self-rescue', 'high'),
+    ('Confined Space Entry', 3, 'Engulfment or entrapment', 'high'),
+    ('Electrical Panel Installation', 1, 'Unexpected energization', 'critical'),
+    ('Electrical Panel Installation', 2, 'Back strain lifting panel', 'medium'),
+    ('Electrical Panel Installation', 3, 'Arc flash on energizing', 'high')
+) v
+JOIN jsa_templates t ON t.name = v.column1
+JOIN jsa_template_steps s ON s.template_id = t.id AND s.step_number = v.column2;
+
+INSERT INTO jsa_template_controls (template_hazard_id, control_type, control_description)
+SELECT h.id, v.column3, v.column4
+FROM (VALUES
+    ('Excavation Work', 'Striking buried utilities', 'administrative', 'Confirm 811 locate ticket and hand-dig within tolerance zone'),
+    ('Excavation Work', 'Trench cave-in', 'engineering', 'Slope, shore or shield trenches 5 feet or deeper'),
+    ('Excavation Work', 'Trench cave-in', 'administrative', 'Competent person inspects daily and after rain'),
+    ('Excavation Work', 'Hazardous atmosphere in trench', 'engineering', 'Ventilate trench with forced air'),
+    ('Excavation Work', 'Hazardous atmosphere in trench', 'administrative', 'Test atmosphere before entry in trenches over 4 feet'),
+    ('Hot Work Operations', 'Combustibles near work area', 'elimination', 'Remove combustibles within 35 feet'),
+    ('Hot Work Operations', 'Combustibles near work area', 'administrative', 'Issue hot work permit'),
+    ('Hot Work Operations', 'Burns and eye injury from arc or flame', 'engineering', 'Set up welding screens'),
+    ('Hot Work Operations', 'Burns and eye injury from arc or flame', 'ppe', 'Welding helmet, FR clothing and gloves'),
+    ('Hot Work Operations', 'Smoldering fire after work ends', 'administrative', 'Fire watch for 30 minutes after work'),
+    ('Roofing Installation', 'Fall from ladder or roof edge', 'engineering', 'Guardrails or warning lines at roof edge'),
+    ('Roofing Installation', 'Fall from ladder or roof edge', 'ppe', 'Personal fall arrest system'),
+    ('Roofing Installation', 'Struck by falling materials', 'engineering', 'Toe boards and debris chute'),
+    ('Roofing Installation', 'Struck by falling materials', 'administrative', 'Barricade drop zone below'),
+    ('Roofing Installation', 'Fall through skylights or openings', 'engineering', 'Cover and secure skylights and openings'),
+    ('Confined Space Entry', 'Oxygen-deficient or toxic atmosphere', 'engineering', 'Continuous forced-air ventilation'),
+    ('Confined Space Entry', 'Oxygen-deficient or toxic atmosphere', 'administrative', 'Test for oxygen, flammables and toxics before and during entry'),
+    ('Confined Space Entry', 'Entrant unable to self-rescue', 'engineering', 'Retrieval system with tripod and winch'),
+    ('Confined Space Entry', 'Entrant unable to self-rescue', 'administrative', 'Attendant posted and rescue service confirmed'),
+    ('Confined Space Entry', 'Engulfment or entrapment', 'elimination', 'Isolate and lock out all inlet lines'),
+    ('Electrical Panel Installation', 'Unexpected energization', 'elimination', 'De-energize and apply lockout/tagout'),
+    ('Electrical Panel Installation', 'Unexpected energization', 'administrative', 'Verify absence of voltage with tested meter'),
+    ('Electrical Panel Installation', 'Back strain lifting panel', 'engineering', 'Use panel lift or cart'),
+    ('Electrical Panel Installation', 'Arc flash on energizing', 'administrative', 'Energize with doors closed, standing to the side'),
+    ('Electrical Panel Installation', 'Arc flash on energizing', 'ppe', 'Arc-rated clothing and face shield')
+) v
+JOIN jsa_templates t ON t.name = v.column1
+JOIN jsa_template_steps s ON s.template_id = t.id
+JOIN jsa_template_hazards h ON h.template_step_id = s.id AND h.hazard_description = v.column2;
@@ -0,0 +1,14 @@
+-- JSA review/approval workflow:
+-- draft -> submitted -> reviewed -> approved -> active -> closed
+
+ALTER TABLE jsa_instances ADD COLUMN submitted_at TEXT;
+ALTER TABLE jsa_instances ADD COLUMN reviewed_at TEXT;
+ALTER TABLE jsa_instances ADD COLUMN approved_at TEXT;
+ALTER TABLE jsa_instances ADD COLUMN activated_at TEXT;
+ALTER TABLE jsa_instances ADD COLUMN closed_at TEXT;
+
+-- Map the old free-form statuses onto the workflow
+UPDATE jsa_instances SET status = 'active' WHERE status = 'in_progress';
+UPDATE jsa_instances SET status = 'closed' WHERE status = 'completed';
+UPDATE jsa_instances SET status = 'draft'
+WHERE status NOT IN ('draft', 'submitted', 'reviewed', 'approved', 'active', 'closed');
@@ -0,0 +1,6 @@
+-- Establishment details required by OSHA's Injury Tracking Application (ITA)
+
+ALTER TABLE establishments ADD COLUMN ein TEXT;
+ALTER TABLE establishments ADD COLUMN company_name TEXT;
+-- private, state_government or local_government
+ALTER TABLE establishments ADD COLUMN establishment_type TEXT NOT NULL DEFAULT 'private';
@@ -0,0 +1,9 @@
+-- Recordability determination under 29 CFR 1904.4-1904.7, stored on the
+-- incident so the answer on the 300 log can be traced to the facts behind it.
+-- recordability_inputs holds the answers given, as JSON.
+
+ALTER TABLE incidents ADD COLUMN recordability_inputs TEXT;
+ALTER TABLE incidents ADD COLUMN recordability_reason TEXT;
+ALTER TABLE incidents ADD COLUMN recordability_citation TEXT;
+ALTER TABLE incidents ADD COLUMN recordability_determined_by TEXT;
+ALTER TABLE incidents ADD COLUMN recordability_determined_at TEXT;
@@ -0,0 +1,34 @@
+-- Work-status periods per incident. days_away_count, days_restricted_count
+-- and outcome_severity are derived from these once an incident has any.
+-- end_date is inclusive; NULL means the period is still running.
+
+CREATE TABLE incident_work_status (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    incident_id INTEGER NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    status TEXT NOT NULL,  -- full_duty, restricted, away, transferred
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    uuid TEXT
+);
+
+CREATE INDEX idx_incident_work_status_incident ON incident_work_status(incident_id, start_date);
+CREATE UNIQUE INDEX idx_incident_work_status_uuid ON incident_work_status(uuid);
+
+CREATE TRIGGER sync_incident_work_status_insert AFTER INSERT ON incident_work_status
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('incident_work_status', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_incident_work_status_update AFTER UPDATE ON incident_work_status
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('incident_work_status', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_incident_work_status_delete AFTER DELETE ON incident_work_status
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('incident_work_status', OLD.id, OLD.uuid, 'delete');
+END;
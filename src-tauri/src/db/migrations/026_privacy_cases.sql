@@ -0,0 +1,6 @@
+-- Privacy concern cases, 29 CFR 1904.29(b)(6)-(9). privacy_concern names the
+-- (b)(7) category; privacy_description is the general wording printed in
+-- place of the description wherever the case is redacted.
+
+ALTER TABLE incidents ADD COLUMN privacy_concern TEXT;
+ALTER TABLE incidents ADD COLUMN privacy_description TEXT;
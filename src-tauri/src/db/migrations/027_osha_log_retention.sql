@@ -0,0 +1,41 @@
+-- Year-end close and certification of the OSHA 300 log, and the amendment
+-- history required by 29 CFR 1904.33(b) once a year is certified.
+
+ALTER TABLE annual_stats ADD COLUMN closed_at TEXT;
+ALTER TABLE annual_stats ADD COLUMN closed_by TEXT;
+ALTER TABLE annual_stats ADD COLUMN certified_at TEXT;
+ALTER TABLE annual_stats ADD COLUMN certified_by TEXT;
+
+CREATE TABLE osha_log_amendments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    incident_id INTEGER NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    establishment_id INTEGER NOT NULL REFERENCES establishments(id),
+    year INTEGER NOT NULL,
+    case_number INTEGER,
+    field_name TEXT NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    amended_by TEXT,
+    amended_at TEXT NOT NULL DEFAULT (datetime('now')),
+    uuid TEXT
+);
+
+CREATE INDEX idx_osha_log_amendments_year ON osha_log_amendments(establishment_id, year);
+CREATE INDEX idx_osha_log_amendments_incident ON osha_log_amendments(incident_id);
+CREATE UNIQUE INDEX idx_osha_log_amendments_uuid ON osha_log_amendments(uuid);
+
+CREATE TRIGGER sync_osha_log_amendments_insert AFTER INSERT ON osha_log_amendments
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('osha_log_amendments', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_osha_log_amendments_update AFTER UPDATE ON osha_log_amendments
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('osha_log_amendments', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_osha_log_amendments_delete AFTER DELETE ON osha_log_amendments
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('osha_log_amendments', OLD.id, OLD.uuid, 'delete');
+END;
@@ -0,0 +1,34 @@
+-- Industry injury and fatality rates used to benchmark each establishment.
+-- Rates are per 100 full-time workers (200,000 hours) except fatality_rate,
+-- which is per 100,000 full-time equivalent workers as BLS CFOI reports it.
+-- Codes are NAICS prefixes; an establishment uses its longest matching one.
+
+CREATE TABLE industry_benchmarks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    naics_code TEXT NOT NULL UNIQUE,
+    industry TEXT NOT NULL,
+    source_year INTEGER NOT NULL,
+    trir REAL,
+    dart_rate REAL,
+    ltir REAL,
+    fatality_rate REAL,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- BLS Survey of Occupational Injuries and Illnesses (TRC, DART and DAFW case
+-- rates) and Census of Fatal Occupational Injuries. Refresh with
+-- import_industry_benchmarks when new tables are published.
+INSERT INTO industry_benchmarks (naics_code, industry, source_year, trir, dart_rate, ltir, fatality_rate) VALUES
+('', 'Private industry', 2023, 2.4, 1.5, 0.9, 3.5),
+('23', 'Construction', 2023, 2.3, 1.4, 0.9, 9.6),
+('236', 'Construction of buildings', 2023, 2.0, 1.2, 0.8, NULL),
+('2361', 'Residential building construction', 2023, 2.1, 1.3, 0.9, NULL),
+('2362', 'Nonresidential building construction', 2023, 1.9, 1.1, 0.7, NULL),
+('237', 'Heavy and civil engineering construction', 2023, 1.9, 1.2, 0.7, NULL),
+('238', 'Specialty trade contractors', 2023, 2.6, 1.6, 1.0, NULL),
+('23811', 'Poured concrete foundation and structure contractors', 2023, 2.7, 1.8, 1.1, NULL),
+('23813', 'Framing contractors', 2023, 3.2, 2.0, 1.4, NULL),
+('23816', 'Roofing contractors', 2023, 3.0, 2.2, 1.6, NULL),
+('23821', 'Electrical contractors and other wiring installation contractors', 2023, 2.1, 1.2, 0.8, NULL),
+('23822', 'Plumbing, heating, and air-conditioning contractors', 2023, 2.9, 1.8, 1.1, NULL),
+('2383', 'Building finishing contractors', 2023, 2.5, 1.5, 1.0, NULL);
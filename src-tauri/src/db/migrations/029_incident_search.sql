@@ -0,0 +1,171 @@
+-- Full-text index over incident narratives, RCA findings and corrective
+-- actions. One row per source record; `source` is 'incident', 'rca' or
+-- 'corrective_action' and every row carries the incident it belongs to.
+
+CREATE VIRTUAL TABLE incident_search USING fts5(
+    source UNINDEXED,
+    source_id UNINDEXED,
+    incident_id UNINDEXED,
+    employee_name,
+    content,
+    tokenize = 'porter unicode61'
+);
+
+-- Incidents
+
+CREATE TRIGGER search_incidents_insert AFTER INSERT ON incidents BEGIN
+    INSERT INTO incident_search (source, source_id, incident_id, employee_name, content)
+    VALUES ('incident', NEW.id, NEW.id, NEW.employee_name,
+            concat_ws(' ', NEW.description, NEW.where_occurred, NEW.activity_before_incident,
+                      NEW.how_injury_occurred, NEW.injury_description, NEW.object_substance,
+                      NEW.employee_job_title));
+END;
+
+CREATE TRIGGER search_incidents_update AFTER UPDATE OF
+    employee_name, description, where_occurred, activity_before_incident, how_injury_occurred,
+    injury_description, object_substance, employee_job_title
+ON incidents BEGIN
+    DELETE FROM incident_search WHERE source = 'incident' AND source_id = OLD.id;
+    INSERT INTO incident_search (source, source_id, incident_id, employee_name, content)
+    VALUES ('incident', NEW.id, NEW.id, NEW.employee_name,
+            concat_ws(' ', NEW.description, NEW.where_occurred, NEW.activity_before_incident,
+                      NEW.how_injury_occurred, NEW.injury_description, NEW.object_substance,
+                      NEW.employee_job_title));
+END;
+
+CREATE TRIGGER search_incidents_delete AFTER DELETE ON incidents BEGIN
+    DELETE FROM incident_search WHERE incident_id = OLD.id;
+END;
+
+-- RCA sessions: the root cause summary plus every 5 Whys step and fishbone
+-- cause, rebuilt whenever any of them changes
+
+CREATE TRIGGER search_rca_insert AFTER INSERT ON rca_sessions BEGIN
+    INSERT INTO incident_search (source, source_id, incident_id, content)
+    VALUES ('rca', NEW.id, NEW.incident_id, COALESCE(NEW.root_cause_summary, ''));
+END;
+
+CREATE TRIGGER search_rca_update AFTER UPDATE OF root_cause_summary ON rca_sessions BEGIN
+    DELETE FROM incident_search WHERE source = 'rca' AND source_id = OLD.id;
+    INSERT INTO incident_search (source, source_id, incident_id, content)
+    SELECT 'rca', r.id, r.incident_id,
+           concat_ws(' ', r.root_cause_summary,
+                     (SELECT group_concat(question || ' ' || answer, ' ') FROM five_whys_steps WHERE rca_session_id = r.id),
+                     (SELECT group_concat(c.cause_text, ' ') FROM fishbone_causes c
+                      JOIN fishbone_categories fc ON c.category_id = fc.id WHERE fc.rca_session_id = r.id))
+    FROM rca_sessions r WHERE r.id = NEW.id;
+END;
+
+CREATE TRIGGER search_rca_delete AFTER DELETE ON rca_sessions BEGIN
+    DELETE FROM incident_search WHERE source = 'rca' AND source_id = OLD.id;
+END;
+
+CREATE TRIGGER search_five_whys_insert AFTER INSERT ON five_whys_steps BEGIN
+    DELETE FROM incident_search WHERE source = 'rca' AND source_id = NEW.rca_session_id;
+    INSERT INTO incident_search (source, source_id, incident_id, content)
+    SELECT 'rca', r.id, r.incident_id, concat_ws(' ', r.root_cause_summary,
+                     (SELECT group_concat(question || ' ' || answer, ' ') FROM five_whys_steps WHERE rca_session_id = r.id),
+                     (SELECT group_concat(c.cause_text, ' ') FROM fishbone_causes c
+                      JOIN fishbone_categories fc ON c.category_id = fc.id WHERE fc.rca_session_id = r.id))
+    FROM rca_sessions r WHERE r.id = NEW.rca_session_id;
+END;
+
+CREATE TRIGGER search_five_whys_update AFTER UPDATE ON five_whys_steps BEGIN
+    DELETE FROM incident_search WHERE source = 'rca' AND source_id IN (OLD.rca_session_id, NEW.rca_session_id);
+    INSERT INTO incident_search (source, source_id, incident_id, content)
+    SELECT 'rca', r.id, r.incident_id, concat_ws(' ', r.root_cause_summary,
+                     (SELECT group_concat(question || ' ' || answer, ' ') FROM five_whys_steps WHERE rca_session_id = r.id),
+                     (SELECT group_concat(c.cause_text, ' ') FROM fishbone_causes c
+                      JOIN fishbone_categories fc ON c.category_id = fc.id WHERE fc.rca_session_id = r.id))
+    FROM rca_sessions r WHERE r.id IN (OLD.rca_session_id, NEW.rca_session_id);
+END;
+
+CREATE TRIGGER search_five_whys_delete AFTER DELETE ON five_whys_steps BEGIN
+    DELETE FROM incident_search WHERE source = 'rca' AND source_id = OLD.rca_session_id;
+    INSERT INTO incident_search (source, source_id, incident_id, content)
+    SELECT 'rca', r.id, r.incident_id, concat_ws(' ', r.root_cause_summary,
+                     (SELECT group_concat(question || ' ' || answer, ' ') FROM five_whys_steps WHERE rca_session_id = r.id),
+                     (SELECT group_concat(c.cause_text, ' ') FROM fishbone_causes c
+                      JOIN fishbone_categories fc ON c.category_id = fc.id WHERE fc.rca_session_id = r.id))
+    FROM rca_sessions r WHERE r.id = OLD.rca_session_id;
+END;
+
+CREATE TRIGGER search_fishbone_causes_insert AFTER INSERT ON fishbone_causes BEGIN
+    DELETE FROM incident_search WHERE source = 'rca' AND source_id IN (SELECT rca_session_id FROM fishbone_categories WHERE id = NEW.category_id);
+    INSERT INTO incident_search (source, source_id, incident_id, content)
+    SELECT 'rca', r.id, r.incident_id, concat_ws(' ', r.root_cause_summary,
+                     (SELECT group_concat(question || ' ' || answer, ' ') FROM five_whys_steps WHERE rca_session_id = r.id),
+                     (SELECT group_concat(c.cause_text, ' ') FROM fishbone_causes c
+                      JOIN fishbone_categories fc ON c.category_id = fc.id WHERE fc.rca_session_id = r.id))
+    FROM rca_sessions r WHERE r.id IN (SELECT rca_session_id FROM fishbone_categories WHERE id = NEW.category_id);
+END;
+
+CREATE TRIGGER search_fishbone_causes_update AFTER UPDATE ON fishbone_causes BEGIN
+    DELETE FROM incident_search WHERE source = 'rca' AND source_id IN (SELECT rca_session_id FROM fishbone_categories WHERE id IN (OLD.category_id, NEW.category_id));
+    INSERT INTO incident_search (source, source_id, incident_id, content)
+    SELECT 'rca', r.id, r.incident_id, concat_ws(' ', r.root_cause_summary,
+                     (SELECT group_concat(question || ' ' || answer, ' ') FROM five_whys_steps WHERE rca_session_id = r.id),
+                     (SELECT group_concat(c.cause_text, ' ') FROM fishbone_causes c
+                      JOIN fishbone_categories fc ON c.category_id = fc.id WHERE fc.rca_session_id = r.id))
+    FROM rca_sessions r WHERE r.id IN (SELECT rca_session_id FROM fishbone_categories WHERE id IN (OLD.category_id, NEW.category_id));
+END;
+
+CREATE TRIGGER search_fishbone_causes_delete AFTER DELETE ON fishbone_causes BEGIN
+    DELETE FROM incident_search WHERE source = 'rca' AND source_id IN (SELECT rca_session_id FROM fishbone_categories WHERE id = OLD.category_id);
+    INSERT INTO incident_search (source, source_id, incident_id, content)
+    SELECT 'rca', r.id, r.incident_id, concat_ws(' ', r.root_cause_summary,
+                     (SELECT group_concat(question || ' ' || answer, ' ') FROM five_whys_steps WHERE rca_session_id = r.id),
+                     (SELECT group_concat(c.cause_text, ' ') FROM fishbone_causes c
+                      JOIN fishbone_categories fc ON c.category_id = fc.id WHERE fc.rca_session_id = r.id))
+    FROM rca_sessions r WHERE r.id IN (SELECT rca_session_id FROM fishbone_categories WHERE id = OLD.category_id);
+END;
+
+CREATE TRIGGER search_fishbone_categories_delete AFTER DELETE ON fishbone_categories BEGIN
+    DELETE FROM incident_search WHERE source = 'rca' AND source_id = OLD.rca_session_id;
+    INSERT INTO incident_search (source, source_id, incident_id, content)
+    SELECT 'rca', r.id, r.incident_id, concat_ws(' ', r.root_cause_summary,
+                     (SELECT group_concat(question || ' ' || answer, ' ') FROM five_whys_steps WHERE rca_session_id = r.id),
+                     (SELECT group_concat(c.cause_text, ' ') FROM fishbone_causes c
+                      JOIN fishbone_categories fc ON c.category_id = fc.id WHERE fc.rca_session_id = r.id))
+    FROM rca_sessions r WHERE r.id = OLD.rca_session_id;
+END;
+
+-- Corrective actions
+
+CREATE TRIGGER search_corrective_actions_insert AFTER INSERT ON corrective_actions BEGIN
+    INSERT INTO incident_search (source, source_id, incident_id, content)
+    VALUES ('corrective_action', NEW.id, NEW.incident_id,
+            concat_ws(' ', NEW.description, NEW.assigned_to, NEW.notes));
+END;
+
+CREATE TRIGGER search_corrective_actions_update AFTER UPDATE OF description, assigned_to, notes
+ON corrective_actions BEGIN
+    DELETE FROM incident_search WHERE source = 'corrective_action' AND source_id = OLD.id;
+    INSERT INTO incident_search (source, source_id, incident_id, content)
+    VALUES ('corrective_action', NEW.id, NEW.incident_id,
+            concat_ws(' ', NEW.description, NEW.assigned_to, NEW.notes));
+END;
+
+CREATE TRIGGER search_corrective_actions_delete AFTER DELETE ON corrective_actions BEGIN
+    DELETE FROM incident_search WHERE source = 'corrective_action' AND source_id = OLD.id;
+END;
+
+-- Index what is already there
+
+INSERT INTO incident_search (source, source_id, incident_id, employee_name, content)
+SELECT 'incident', id, id, employee_name,
+       concat_ws(' ', description, where_occurred, activity_before_incident, how_injury_occurred,
+                 injury_description, object_substance, employee_job_title)
+FROM incidents;
+
+INSERT INTO incident_search (source, source_id, incident_id, content)
+SELECT 'rca', r.id, r.incident_id,
+       concat_ws(' ', r.root_cause_summary,
+                 (SELECT group_concat(question || ' ' || answer, ' ') FROM five_whys_steps WHERE rca_session_id = r.id),
+                 (SELECT group_concat(c.cause_text, ' ') FROM fishbone_causes c
+                  JOIN fishbone_categories fc ON c.category_id = fc.id WHERE fc.rca_session_id = r.id))
+FROM rca_sessions r;
+
+INSERT INTO incident_search (source, source_id, incident_id, content)
+SELECT 'corrective_action', id, incident_id, concat_ws(' ', description, assigned_to, notes)
+FROM corrective_actions;
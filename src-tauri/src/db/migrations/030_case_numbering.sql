@@ -0,0 +1,29 @@
+-- Case numbers unique per establishment and year, with a per-establishment
+-- display format. `case_number` stays the sequence; `case_label` is the
+-- formatted number printed on the forms, fixed when the number is assigned.
+
+ALTER TABLE establishments ADD COLUMN case_number_format TEXT;
+ALTER TABLE incidents ADD COLUMN case_label TEXT;
+
+-- Cases that share a number with an earlier case in the same year (left by
+-- date edits or by two devices numbering offline) keep their number, which
+-- may already be on printed or certified forms, but are flagged and left out
+-- of the unique index. Renumbering the year (an audited action that refuses
+-- certified logs) clears the flag.
+ALTER TABLE incidents ADD COLUMN case_number_duplicate INTEGER NOT NULL DEFAULT 0;
+
+UPDATE incidents SET case_number_duplicate = 1
+WHERE case_number IS NOT NULL
+  AND EXISTS (
+      SELECT 1 FROM incidents o
+      WHERE o.establishment_id = incidents.establishment_id
+        AND substr(o.incident_date, 1, 4) = substr(incidents.incident_date, 1, 4)
+        AND o.case_number = incidents.case_number
+        AND o.id < incidents.id
+  );
+
+UPDATE incidents SET case_label = CAST(case_number AS TEXT) WHERE case_number IS NOT NULL;
+
+CREATE UNIQUE INDEX idx_incidents_case_number
+    ON incidents(establishment_id, substr(incident_date, 1, 4), case_number)
+    WHERE case_number IS NOT NULL AND case_number_duplicate = 0;
@@ -0,0 +1,38 @@
+-- Incident lifecycle:
+-- reported -> under_investigation -> pending_rca -> pending_corrective_actions -> closed
+-- with every status change, and the reason for reopening a closed case, kept
+-- in incident_status_changes.
+
+UPDATE incidents SET status = 'under_investigation' WHERE status = 'in_review';
+UPDATE incidents SET status = 'reported'
+WHERE status NOT IN ('reported', 'under_investigation', 'pending_rca', 'pending_corrective_actions', 'closed');
+
+CREATE TABLE incident_status_changes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    incident_id INTEGER NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    reason TEXT,
+    changed_by TEXT,
+    changed_at TEXT NOT NULL DEFAULT (datetime('now')),
+    uuid TEXT
+);
+
+CREATE INDEX idx_incident_status_changes_incident ON incident_status_changes(incident_id);
+CREATE UNIQUE INDEX idx_incident_status_changes_uuid ON incident_status_changes(uuid);
+
+CREATE TRIGGER sync_incident_status_changes_insert AFTER INSERT ON incident_status_changes
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('incident_status_changes', NEW.id, NEW.uuid, 'insert');
+END;
+CREATE TRIGGER sync_incident_status_changes_update AFTER UPDATE ON incident_status_changes
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('incident_status_changes', NEW.id, NEW.uuid, 'update');
+END;
+CREATE TRIGGER sync_incident_status_changes_delete AFTER DELETE ON incident_status_changes
+WHEN (SELECT applying_remote FROM sync_control) = 0
+BEGIN
+    INSERT INTO sync_log (table_name, record_id, record_uuid, operation) VALUES ('incident_status_changes', OLD.id, OLD.uuid, 'delete');
+END;
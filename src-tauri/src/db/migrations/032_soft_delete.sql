@@ -0,0 +1,17 @@
+-- Soft delete: deleting an incident, establishment, location or toolbox talk
+-- moves it to the trash by stamping deleted_at. Lists skip trashed rows;
+-- restoring clears the stamp and purging removes the row for good.
+
+ALTER TABLE incidents ADD COLUMN deleted_at TEXT;
+ALTER TABLE incidents ADD COLUMN deleted_by TEXT;
+ALTER TABLE establishments ADD COLUMN deleted_at TEXT;
+ALTER TABLE establishments ADD COLUMN deleted_by TEXT;
+ALTER TABLE locations ADD COLUMN deleted_at TEXT;
+ALTER TABLE locations ADD COLUMN deleted_by TEXT;
+ALTER TABLE toolbox_talks ADD COLUMN deleted_at TEXT;
+ALTER TABLE toolbox_talks ADD COLUMN deleted_by TEXT;
+
+CREATE INDEX idx_incidents_deleted ON incidents(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX idx_establishments_deleted ON establishments(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX idx_locations_deleted ON locations(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX idx_toolbox_talks_deleted ON toolbox_talks(deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,20 @@
+-- Remote changes that could not be applied (a parent that never arrived, a
+-- constraint the row breaks here). They are set aside in arrival order and
+-- retried on every pull, so the pull cursor keeps moving and one bad change
+-- can't hold back everything after it. Later changes to the same record wait
+-- behind it so they still apply in order.
+
+CREATE TABLE sync_failed_changes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    table_name TEXT NOT NULL,
+    record_uuid TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    data TEXT,
+    changed_at TEXT NOT NULL,
+    error TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    first_failed_at TEXT NOT NULL DEFAULT (datetime('now')),
+    last_failed_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_sync_failed_changes_record ON sync_failed_changes(table_name, record_uuid);
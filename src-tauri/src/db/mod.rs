@@ -1,9 +1,22 @@
+pub mod audit;
+pub mod auth;
+pub mod benchmarks;
+pub mod equipment;
 pub mod incidents;
+pub mod inspections;
 pub mod jsa;
 pub mod locations;
+pub mod near_miss;
 pub mod osha;
 pub mod rca;
+pub mod recordability;
+pub mod search;
+pub mod sync;
 pub mod toolbox;
+pub mod trade_hazards;
+pub mod training;
+pub mod trash;
+pub mod work_status;
 
 use anyhow::{Context, Result};
 use rusqlite::Connection;
@@ -66,6 +79,82 @@ const MIGRATION_FILES: &[(&str, &str)] = &[
         "014_trade_hazards_seed",
         include_str!("migrations/014_trade_hazards_seed.sql"),
     ),
+    (
+        "015_near_miss_incident_link",
+        include_str!("migrations/015_near_miss_incident_link.sql"),
+    ),
+    (
+        "016_user_auth",
+        include_str!("migrations/016_user_auth.sql"),
+    ),
+    (
+        "017_audit_log_record_index",
+        include_str!("migrations/017_audit_log_record_index.sql"),
+    ),
+    (
+        "018_sync_capture",
+        include_str!("migrations/018_sync_capture.sql"),
+    ),
+    (
+        "019_sync_conflicts",
+        include_str!("migrations/019_sync_conflicts.sql"),
+    ),
+    (
+        "020_trade_hazard_library",
+        include_str!("migrations/020_trade_hazard_library.sql"),
+    ),
+    (
+        "021_jsa_template_content",
+        include_str!("migrations/021_jsa_template_content.sql"),
+    ),
+    (
+        "022_jsa_workflow",
+        include_str!("migrations/022_jsa_workflow.sql"),
+    ),
+    (
+        "023_establishment_ita",
+        include_str!("migrations/023_establishment_ita.sql"),
+    ),
+    (
+        "024_incident_recordability",
+        include_str!("migrations/024_incident_recordability.sql"),
+    ),
+    (
+        "025_work_status_periods",
+        include_str!("migrations/025_work_status_periods.sql"),
+    ),
+    (
+        "026_privacy_cases",
+        include_str!("migrations/026_privacy_cases.sql"),
+    ),
+    (
+        "027_osha_log_retention",
+        include_str!("migrations/027_osha_log_retention.sql"),
+    ),
+    (
+        "028_industry_benchmarks",
+        include_str!("migrations/028_industry_benchmarks.sql"),
+    ),
+    (
+        "029_incident_search",
+        include_str!("migrations/029_incident_search.sql"),
+    ),
+    (
+        "030_case_numbering",
+        include_str!("migrations/030_case_numbering.sql"),
+    ),
+    (
+        "031_incident_workflow",
+        include_str!("migrations/031_incident_workflow.sql"),
+    ),
+    (
+        "032_soft_delete",
+        include_str!("migrations/032_soft_delete.sql"),
+    ),
+    (
+        "033_sync_failed_changes",
+        include_str!("migrations/033_sync_failed_changes.sql"),
+    ),
 ];
 
 pub fn open_db(db_path: &Path) -> Result<Connection> {
@@ -109,6 +198,49 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Runs `f` in a `BEGIN IMMEDIATE` transaction, so the write lock is held
+/// before anything is read. Joins the caller's transaction if one is open.
+/// Generic over the error type so the rusqlite-style modules can use it too.
+pub fn immediate_transaction<T, E>(
+    conn: &Connection,
+    f: impl FnOnce(&Connection) -> std::result::Result<T, E>,
+) -> std::result::Result<T, E>
+where
+    E: From<rusqlite::Error>,
+{
+    if !conn.is_autocommit() {
+        return f(conn);
+    }
+    conn.execute_batch("BEGIN IMMEDIATE")?;
+    match f(conn).and_then(|value| {
+        conn.execute_batch("COMMIT")?;
+        Ok(value)
+    }) {
+        Ok(value) => Ok(value),
+        Err(e) => {
+            let _ = conn.execute_batch("ROLLBACK");
+            Err(e)
+        }
+    }
+}
+
+/// Stable identifier for this install, created on first use. Stamped on
+/// audit entries and used as the sync identity.
+pub fn device_id(conn: &Connection) -> rusqlite::Result<String> {
+    use rusqlite::OptionalExtension;
+
+    let existing: Option<String> = conn
+        .query_row("SELECT device_id FROM sync_state ORDER BY id LIMIT 1", [], |row| row.get(0))
+        .optional()?;
+    if let Some(id) = existing {
+        return Ok(id);
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    conn.execute("INSERT INTO sync_state (device_id) VALUES (?1)", [&id])?;
+    Ok(id)
+}
+
 #[cfg(test)]
 pub fn open_test_db() -> Connection {
     let conn = Connection::open_in_memory().expect("open in-memory db");
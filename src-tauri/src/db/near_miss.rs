@@ -0,0 +1,426 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::db::audit::Actor;
+use crate::db::incidents::{self, CreateIncident, Incident};
+use crate::errors::AppError;
+
+pub const SEVERITIES: &[&str] = &["low", "medium", "high", "critical"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NearMissReport {
+    pub id: i64,
+    pub establishment_id: i64,
+    pub location_id: Option<i64>,
+    pub reporter_name: Option<String>,
+    pub is_anonymous: bool,
+    pub report_date: String,
+    pub what_happened: String,
+    pub what_could_have_happened: String,
+    pub potential_severity: String,
+    pub category: Option<String>,
+    pub corrective_action_taken: Option<String>,
+    pub status: String,
+    pub incident_id: Option<i64>,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateNearMiss {
+    pub establishment_id: i64,
+    pub location_id: Option<i64>,
+    pub reporter_name: Option<String>,
+    pub is_anonymous: Option<bool>,
+    pub report_date: String,
+    pub what_happened: String,
+    pub what_could_have_happened: String,
+    pub potential_severity: Option<String>,
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TriageNearMiss {
+    pub potential_severity: Option<String>,
+    pub category: Option<String>,
+    pub corrective_action_taken: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NearMissFilter {
+    pub establishment_id: i64,
+    pub location_id: Option<i64>,
+    pub status: Option<String>,
+    pub potential_severity: Option<String>,
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub search: Option<String>,
+}
+
+/// Injury details that were unknown when the near miss was reported.
+/// Everything else on the new incident is carried over from the report.
+#[derive(Debug, Deserialize)]
+pub struct PromoteNearMiss {
+    pub employee_name: String,
+    pub employee_job_title: Option<String>,
+    pub injury_description: Option<String>,
+    pub outcome_severity: Option<String>,
+    pub days_away_count: Option<i64>,
+    pub days_restricted_count: Option<i64>,
+    pub injury_illness_type: Option<String>,
+    pub is_recordable: Option<bool>,
+}
+
+fn row_to_report(row: &rusqlite::Row<'_>) -> rusqlite::Result<NearMissReport> {
+    Ok(NearMissReport {
+        id: row.get(0)?,
+        establishment_id: row.get(1)?,
+        location_id: row.get(2)?,
+        reporter_name: row.get(3)?,
+        is_anonymous: row.get::<_, i32>(4)? != 0,
+        report_date: row.get(5)?,
+        what_happened: row.get(6)?,
+        what_could_have_happened: row.get(7)?,
+        potential_severity: row.get(8)?,
+        category: row.get(9)?,
+        corrective_action_taken: row.get(10)?,
+        status: row.get(11)?,
+        incident_id: row.get(12)?,
+        created_at: row.get(13)?,
+        updated_at: row.get(14)?,
+    })
+}
+
+const SELECT_COLS: &str = "id, establishment_id, location_id, reporter_name, is_anonymous,
+    report_date, what_happened, what_could_have_happened, potential_severity, category,
+    corrective_action_taken, status, incident_id, created_at, updated_at";
+
+pub fn submit_report(conn: &Connection, data: CreateNearMiss) -> Result<NearMissReport> {
+    let is_anonymous = data.is_anonymous.unwrap_or(false);
+    // Anonymous reports never store the reporter, so it can't leak through
+    // backups, exports or sync.
+    let reporter_name = if is_anonymous { None } else { data.reporter_name };
+
+    conn.execute(
+        "INSERT INTO near_miss_reports (
+            establishment_id, location_id, reporter_name, is_anonymous, report_date,
+            what_happened, what_could_have_happened, potential_severity, category
+         ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            data.establishment_id,
+            data.location_id,
+            reporter_name,
+            is_anonymous as i32,
+            data.report_date,
+            data.what_happened,
+            data.what_could_have_happened,
+            data.potential_severity.as_deref().unwrap_or("medium"),
+            data.category,
+        ],
+    )
+    .context("Failed to submit near miss report")?;
+
+    let id = conn.last_insert_rowid();
+    get_report(conn, id)
+}
+
+pub fn get_report(conn: &Connection, id: i64) -> Result<NearMissReport> {
+    conn.query_row(
+        &format!("SELECT {SELECT_COLS} FROM near_miss_reports WHERE id = ?1"),
+        [id],
+        row_to_report,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            AppError::NotFound(format!("Near miss report {id} not found")).into()
+        }
+        _ => anyhow::Error::new(e),
+    })
+}
+
+pub fn list_reports(conn: &Connection, filter: NearMissFilter) -> Result<Vec<NearMissReport>> {
+    let mut sql = format!("SELECT {SELECT_COLS} FROM near_miss_reports WHERE establishment_id = ?");
+    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> =
+        vec![Box::new(filter.establishment_id)];
+
+    if let Some(loc_id) = filter.location_id {
+        sql.push_str(" AND location_id = ?");
+        values.push(Box::new(loc_id));
+    }
+    if let Some(ref status) = filter.status {
+        sql.push_str(" AND status = ?");
+        values.push(Box::new(status.clone()));
+    }
+    if let Some(ref sev) = filter.potential_severity {
+        sql.push_str(" AND potential_severity = ?");
+        values.push(Box::new(sev.clone()));
+    }
+    if let Some(ref from) = filter.date_from {
+        sql.push_str(" AND report_date >= ?");
+        values.push(Box::new(from.clone()));
+    }
+    if let Some(ref to) = filter.date_to {
+        sql.push_str(" AND report_date <= ?");
+        values.push(Box::new(to.clone()));
+    }
+    if let Some(ref search) = filter.search {
+        sql.push_str(" AND (what_happened LIKE ? OR what_could_have_happened LIKE ?)");
+        let pat = format!("%{search}%");
+        values.push(Box::new(pat.clone()));
+        values.push(Box::new(pat));
+    }
+
+    sql.push_str(" ORDER BY report_date DESC, id DESC");
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+    let rows = stmt
+        .query_map(params.as_slice(), row_to_report)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+/// Moves a report into review, optionally re-rating its potential severity.
+pub fn triage_report(conn: &Connection, id: i64, data: TriageNearMiss) -> Result<NearMissReport> {
+    let existing = get_report(conn, id)?;
+    if existing.status != "reported" && existing.status != "under_review" {
+        return Err(AppError::Validation(format!(
+            "Near miss report {id} is {} and can no longer be triaged",
+            existing.status
+        ))
+        .into());
+    }
+
+    conn.execute(
+        "UPDATE near_miss_reports SET
+            status = 'under_review',
+            potential_severity = COALESCE(?1, potential_severity),
+            category = COALESCE(?2, category),
+            corrective_action_taken = COALESCE(?3, corrective_action_taken),
+            updated_at = datetime('now')
+         WHERE id = ?4",
+        params![data.potential_severity, data.category, data.corrective_action_taken, id],
+    )
+    .context("Failed to triage near miss report")?;
+
+    get_report(conn, id)
+}
+
+pub fn close_report(
+    conn: &Connection,
+    id: i64,
+    corrective_action_taken: Option<&str>,
+) -> Result<NearMissReport> {
+    let existing = get_report(conn, id)?;
+    if existing.status == "closed" || existing.status == "promoted" {
+        return Err(AppError::Validation(format!(
+            "Near miss report {id} is already {}",
+            existing.status
+        ))
+        .into());
+    }
+
+    conn.execute(
+        "UPDATE near_miss_reports SET
+            status = 'closed',
+            corrective_action_taken = COALESCE(?1, corrective_action_taken),
+            updated_at = datetime('now')
+         WHERE id = ?2",
+        params![corrective_action_taken, id],
+    )
+    .context("Failed to close near miss report")?;
+
+    get_report(conn, id)
+}
+
+/// Turns a near miss into a real incident when it emerges that someone was
+/// hurt. The report is kept, marked `promoted` and linked to the new incident.
+pub fn promote_to_incident(
+    conn: &Connection,
+    actor: &Actor,
+    id: i64,
+    data: PromoteNearMiss,
+) -> Result<Incident> {
+    let report = get_report(conn, id)?;
+    if let Some(incident_id) = report.incident_id {
+        return Err(AppError::Validation(format!(
+            "Near miss report {id} was already promoted to incident {incident_id}"
+        ))
+        .into());
+    }
+
+    let tx = conn.unchecked_transaction()?;
+
+    let incident = incidents::create_incident(
+        &tx,
+        actor,
+        CreateIncident {
+            establishment_id: report.establishment_id,
+            location_id: report.location_id,
+            employee_name: data.employee_name,
+            employee_job_title: data.employee_job_title,
+            employee_address: None,
+            employee_city: None,
+            employee_state: None,
+            employee_zip: None,
+            employee_dob: None,
+            employee_hire_date: None,
+            employee_gender: None,
+            is_privacy_case: None,
+            privacy_concern: None,
+            privacy_description: None,
+            incident_date: report.report_date.clone(),
+            incident_time: None,
+            work_start_time: None,
+            where_occurred: None,
+            description: report.what_happened.clone(),
+            activity_before_incident: None,
+            how_injury_occurred: Some(report.what_happened.clone()),
+            injury_description: data.injury_description,
+            object_substance: None,
+            physician_name: None,
+            treatment_facility: None,
+            facility_address: None,
+            facility_city_state_zip: None,
+            treated_in_er: None,
+            hospitalized_overnight: None,
+            outcome_severity: data.outcome_severity,
+            days_away_count: data.days_away_count,
+            days_restricted_count: data.days_restricted_count,
+            date_of_death: None,
+            injury_illness_type: data.injury_illness_type,
+            is_recordable: data.is_recordable,
+        },
+    )?;
+
+    tx.execute(
+        "UPDATE near_miss_reports SET status = 'promoted', incident_id = ?1, updated_at = datetime('now')
+         WHERE id = ?2",
+        params![incident.id, id],
+    )
+    .context("Failed to link near miss report to incident")?;
+
+    tx.commit()?;
+    Ok(incident)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::locations::{create_establishment, CreateEstablishment};
+    use crate::db::open_test_db;
+
+    fn setup(conn: &Connection) -> i64 {
+        create_establishment(
+            conn,
+            &Actor::system(),
+            CreateEstablishment {
+                name: "Test Co".into(),
+                street_address: None, city: None, state: None,
+                zip_code: None, industry_description: None, naics_code: None,
+                ein: None, company_name: None, establishment_type: None, case_number_format: None,
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    fn make_report(est_id: i64, anonymous: bool) -> CreateNearMiss {
+        CreateNearMiss {
+            establishment_id: est_id,
+            location_id: None,
+            reporter_name: Some("Sam Reporter".into()),
+            is_anonymous: Some(anonymous),
+            report_date: "2026-05-02".into(),
+            what_happened: "Unsecured pallet slid off forklift forks".into(),
+            what_could_have_happened: "Struck a laborer walking the aisle".into(),
+            potential_severity: Some("high".into()),
+            category: None,
+        }
+    }
+
+    #[test]
+    fn test_anonymous_report_drops_reporter() {
+        let conn = open_test_db();
+        let est_id = setup(&conn);
+
+        let named = submit_report(&conn, make_report(est_id, false)).unwrap();
+        assert_eq!(named.reporter_name.as_deref(), Some("Sam Reporter"));
+
+        let anon = submit_report(&conn, make_report(est_id, true)).unwrap();
+        assert!(anon.is_anonymous);
+        assert_eq!(anon.reporter_name, None);
+
+        let stored: Option<String> = conn
+            .query_row(
+                "SELECT reporter_name FROM near_miss_reports WHERE id = ?1",
+                [anon.id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(stored, None);
+    }
+
+    #[test]
+    fn test_triage_close_and_filter() {
+        let conn = open_test_db();
+        let est_id = setup(&conn);
+
+        let report = submit_report(&conn, make_report(est_id, false)).unwrap();
+        assert_eq!(report.status, "reported");
+
+        let triaged = triage_report(&conn, report.id, TriageNearMiss {
+            potential_severity: Some("critical".into()),
+            category: Some("Material handling".into()),
+            corrective_action_taken: None,
+        }).unwrap();
+        assert_eq!(triaged.status, "under_review");
+        assert_eq!(triaged.potential_severity, "critical");
+
+        let closed = close_report(&conn, report.id, Some("Banding required on all pallets")).unwrap();
+        assert_eq!(closed.status, "closed");
+        assert!(close_report(&conn, report.id, None).is_err());
+
+        let open = list_reports(&conn, NearMissFilter {
+            establishment_id: est_id, location_id: None, status: Some("reported".into()),
+            potential_severity: None, date_from: None, date_to: None, search: None,
+        }).unwrap();
+        assert!(open.is_empty());
+
+        let found = list_reports(&conn, NearMissFilter {
+            establishment_id: est_id, location_id: None, status: None,
+            potential_severity: None, date_from: None, date_to: None, search: Some("pallet".into()),
+        }).unwrap();
+        assert_eq!(found.len(), 1);
+    }
+
+    #[test]
+    fn test_promote_to_incident() {
+        let conn = open_test_db();
+        let est_id = setup(&conn);
+        let report = submit_report(&conn, make_report(est_id, true)).unwrap();
+
+        let incident = promote_to_incident(&conn, &Actor::system(), report.id, PromoteNearMiss {
+            employee_name: "Lee Laborer".into(),
+            employee_job_title: Some("Laborer".into()),
+            injury_description: Some("Bruised shin".into()),
+            outcome_severity: None, days_away_count: None, days_restricted_count: None,
+            injury_illness_type: None, is_recordable: Some(false),
+        }).unwrap();
+        assert_eq!(incident.incident_date, "2026-05-02");
+        assert_eq!(incident.description, report.what_happened);
+        assert!(!incident.is_recordable);
+
+        let promoted = get_report(&conn, report.id).unwrap();
+        assert_eq!(promoted.status, "promoted");
+        assert_eq!(promoted.incident_id, Some(incident.id));
+
+        assert!(promote_to_incident(&conn, &Actor::system(), report.id, PromoteNearMiss {
+            employee_name: "Lee Laborer".into(), employee_job_title: None,
+            injury_description: None, outcome_severity: None, days_away_count: None,
+            days_restricted_count: None, injury_illness_type: None, is_recordable: None,
+        }).is_err());
+    }
+}
@@ -1,14 +1,21 @@
 use anyhow::{Context, Result};
-use rusqlite::{params, Connection};
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 
+use crate::db::audit::{self, Actor};
+use crate::db::incidents::Incident;
+use crate::db::locations;
 use crate::errors::AppError;
+use crate::redaction::{self, Audience};
 
 // ── OSHA 300 Log Row ──
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Osha300Row {
     pub case_number: i64,
+    /// The case number as printed, in the establishment's format
+    pub case_label: String,
     pub employee_name: String,
     pub job_title: String,
     pub incident_date: String,
@@ -69,6 +76,7 @@ pub struct Osha300ASummary {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Osha301Report {
     pub case_number: i64,
+    pub case_label: String,
     // Section A - Employee
     pub employee_name: String,
     pub employee_address: String,
@@ -115,6 +123,10 @@ pub struct AnnualStats {
     pub certifier_title: Option<String>,
     pub certifier_phone: Option<String>,
     pub certification_date: Option<String>,
+    pub closed_at: Option<String>,
+    pub closed_by: Option<String>,
+    pub certified_at: Option<String>,
+    pub certified_by: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -141,11 +153,13 @@ pub fn get_osha_300_log(
         "SELECT case_number, employee_name, employee_job_title,
                 incident_date, where_occurred, description,
                 outcome_severity, days_away_count, days_restricted_count,
-                injury_illness_type, is_privacy_case
+                injury_illness_type, is_privacy_case, privacy_description,
+                COALESCE(case_label, CAST(case_number AS TEXT), '')
          FROM incidents
          WHERE establishment_id = ?1
            AND incident_date LIKE ?2
            AND is_recordable = 1
+           AND deleted_at IS NULL
          ORDER BY case_number",
     )?;
 
@@ -154,19 +168,23 @@ pub fn get_osha_300_log(
             let severity: String = row.get(6)?;
             let illness_type: String = row.get(9)?;
             let is_privacy: bool = row.get::<_, i32>(10)? != 0;
-            let name: String = row.get(1)?;
+            let privacy_description: Option<String> = row.get(11)?;
+            // The 300 log is available to employees, so it is always redacted
+            let audience = Audience::Restricted;
 
             Ok(Osha300Row {
                 case_number: row.get::<_, Option<i64>>(0)?.unwrap_or(0),
-                employee_name: if is_privacy {
-                    "Privacy Case".to_string()
-                } else {
-                    name
-                },
+                case_label: row.get(12)?,
+                employee_name: redaction::employee_name(audience, is_privacy, row.get(1)?),
                 job_title: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
                 incident_date: row.get(3)?,
                 where_occurred: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
-                description: row.get(5)?,
+                description: redaction::description(
+                    audience,
+                    is_privacy,
+                    row.get(5)?,
+                    privacy_description.as_deref(),
+                ),
                 outcome_death: severity == "death",
                 outcome_days_away: severity == "days_away",
                 outcome_job_transfer: severity == "job_transfer_restriction",
@@ -228,7 +246,7 @@ pub fn get_osha_300a_summary(
                 COALESCE(SUM(days_away_count), 0),
                 COALESCE(SUM(days_restricted_count), 0)
              FROM incidents
-             WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND is_recordable = 1",
+             WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND is_recordable = 1 AND deleted_at IS NULL",
             params![establishment_id, year_str],
             |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)),
         )?;
@@ -243,33 +261,13 @@ pub fn get_osha_300a_summary(
                 COALESCE(SUM(CASE WHEN injury_illness_type = 'hearing_loss' THEN 1 ELSE 0 END), 0),
                 COALESCE(SUM(CASE WHEN injury_illness_type = 'other_illness' THEN 1 ELSE 0 END), 0)
              FROM incidents
-             WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND is_recordable = 1",
+             WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND is_recordable = 1 AND deleted_at IS NULL",
             params![establishment_id, year_str],
             |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)),
         )?;
 
     // Get annual stats
-    let stats: Option<AnnualStats> = conn
-        .query_row(
-            "SELECT id, establishment_id, year, avg_employees, total_hours_worked,
-                    certifier_name, certifier_title, certifier_phone, certification_date
-             FROM annual_stats WHERE establishment_id = ?1 AND year = ?2",
-            params![establishment_id, year],
-            |row| {
-                Ok(AnnualStats {
-                    id: row.get(0)?,
-                    establishment_id: row.get(1)?,
-                    year: row.get(2)?,
-                    avg_employees: row.get(3)?,
-                    total_hours_worked: row.get(4)?,
-                    certifier_name: row.get(5)?,
-                    certifier_title: row.get(6)?,
-                    certifier_phone: row.get(7)?,
-                    certification_date: row.get(8)?,
-                })
-            },
-        )
-        .ok();
+    let stats = get_annual_stats(conn, establishment_id, year)?;
 
     Ok(Osha300ASummary {
         year,
@@ -304,7 +302,13 @@ pub fn get_osha_300a_summary(
     })
 }
 
-pub fn get_osha_301_report(conn: &Connection, incident_id: i64) -> Result<Osha301Report> {
+/// The 301 is a confidential record; `audience` decides how much of a
+/// privacy case it shows.
+pub fn get_osha_301_report(
+    conn: &Connection,
+    incident_id: i64,
+    audience: Audience,
+) -> Result<Osha301Report> {
     conn.query_row(
         "SELECT case_number, employee_name, COALESCE(employee_address,''),
                 COALESCE(employee_city,''), COALESCE(employee_state,''), COALESCE(employee_zip,''),
@@ -319,16 +323,18 @@ pub fn get_osha_301_report(conn: &Connection, incident_id: i64) -> Result<Osha30
                 COALESCE(date_of_death,''),
                 COALESCE(completed_by,''), COALESCE(completed_by_title,''),
                 COALESCE(completed_by_phone,''), COALESCE(completed_date,''),
-                is_privacy_case
+                is_privacy_case, privacy_description,
+                COALESCE(case_label, CAST(case_number AS TEXT), '')
          FROM incidents WHERE id = ?1",
         [incident_id],
         |row| {
             let is_privacy: bool = row.get::<_, i32>(28)? != 0;
-            let name: String = row.get(1)?;
+            let privacy_description: Option<String> = row.get(29)?;
 
-            Ok(Osha301Report {
+            let mut report = Osha301Report {
                 case_number: row.get::<_, Option<i64>>(0)?.unwrap_or(0),
-                employee_name: if is_privacy { "Privacy Case".to_string() } else { name },
+                case_label: row.get(30)?,
+                employee_name: row.get(1)?,
                 employee_address: row.get(2)?,
                 employee_city: row.get(3)?,
                 employee_state: row.get(4)?,
@@ -355,7 +361,9 @@ pub fn get_osha_301_report(conn: &Connection, incident_id: i64) -> Result<Osha30
                 completed_by_title: row.get(25)?,
                 completed_by_phone: row.get(26)?,
                 completed_date: row.get(27)?,
-            })
+            };
+            redaction::osha_301(audience, is_privacy, privacy_description.as_deref(), &mut report);
+            Ok(report)
         },
     )
     .map_err(|e| match e {
@@ -366,81 +374,702 @@ pub fn get_osha_301_report(conn: &Connection, incident_id: i64) -> Result<Osha30
     })
 }
 
-pub fn upsert_annual_stats(conn: &Connection, data: UpsertAnnualStats) -> Result<AnnualStats> {
-    conn.execute(
-        "INSERT INTO annual_stats (establishment_id, year, avg_employees, total_hours_worked,
-                                    certifier_name, certifier_title, certifier_phone, certification_date)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
-         ON CONFLICT(establishment_id, year) DO UPDATE SET
-            avg_employees = excluded.avg_employees,
-            total_hours_worked = excluded.total_hours_worked,
-            certifier_name = excluded.certifier_name,
-            certifier_title = excluded.certifier_title,
-            certifier_phone = excluded.certifier_phone,
-            certification_date = excluded.certification_date",
-        params![
-            data.establishment_id,
-            data.year,
-            data.avg_employees,
-            data.total_hours_worked,
-            data.certifier_name,
-            data.certifier_title,
-            data.certifier_phone,
-            data.certification_date,
-        ],
+fn row_to_annual_stats(row: &rusqlite::Row<'_>) -> rusqlite::Result<AnnualStats> {
+    Ok(AnnualStats {
+        id: row.get(0)?,
+        establishment_id: row.get(1)?,
+        year: row.get(2)?,
+        avg_employees: row.get(3)?,
+        total_hours_worked: row.get(4)?,
+        certifier_name: row.get(5)?,
+        certifier_title: row.get(6)?,
+        certifier_phone: row.get(7)?,
+        certification_date: row.get(8)?,
+        closed_at: row.get(9)?,
+        closed_by: row.get(10)?,
+        certified_at: row.get(11)?,
+        certified_by: row.get(12)?,
+    })
+}
+
+const ANNUAL_STATS_COLS: &str = "id, establishment_id, year, avg_employees, total_hours_worked,
+    certifier_name, certifier_title, certifier_phone, certification_date,
+    closed_at, closed_by, certified_at, certified_by";
+
+pub fn upsert_annual_stats(
+    conn: &Connection,
+    actor: &Actor,
+    data: UpsertAnnualStats,
+) -> Result<AnnualStats> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_annual_stats(conn, data.establishment_id, data.year)?;
+
+        conn.execute(
+            "INSERT INTO annual_stats (establishment_id, year, avg_employees, total_hours_worked,
+                                        certifier_name, certifier_title, certifier_phone, certification_date)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(establishment_id, year) DO UPDATE SET
+                avg_employees = excluded.avg_employees,
+                total_hours_worked = excluded.total_hours_worked,
+                certifier_name = excluded.certifier_name,
+                certifier_title = excluded.certifier_title,
+                certifier_phone = excluded.certifier_phone,
+                certification_date = excluded.certification_date",
+            params![
+                data.establishment_id,
+                data.year,
+                data.avg_employees,
+                data.total_hours_worked,
+                data.certifier_name,
+                data.certifier_title,
+                data.certifier_phone,
+                data.certification_date,
+            ],
+        )
+        .context("Failed to upsert annual stats")?;
+
+        let saved = get_annual_stats(conn, data.establishment_id, data.year)?
+            .context("Annual stats were not saved")?;
+
+        match existing {
+            Some(old) => audit::log_update(conn, actor, "annual_stats", saved.id, &old, &saved)?,
+            None => audit::log_create(conn, actor, "annual_stats", saved.id, &saved)?,
+        }
+        Ok(saved)
+    })
+}
+
+pub fn get_annual_stats(
+    conn: &Connection,
+    establishment_id: i64,
+    year: i64,
+) -> Result<Option<AnnualStats>> {
+    conn.query_row(
+        &format!("SELECT {ANNUAL_STATS_COLS} FROM annual_stats WHERE establishment_id = ?1 AND year = ?2"),
+        params![establishment_id, year],
+        row_to_annual_stats,
     )
-    .context("Failed to upsert annual stats")?;
+    .optional()
+    .context("Failed to get annual stats")
+}
+
+// ── Log close, certification and amendments ──
+
+/// Marks the year's log complete. Expects annual stats for the year.
+pub fn close_log(conn: &Connection, actor: &Actor, establishment_id: i64, year: i64) -> Result<AnnualStats> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_annual_stats(conn, establishment_id, year)?
+            .with_context(|| format!("No annual stats for {year}"))?;
+        conn.execute(
+            "UPDATE annual_stats SET closed_at = datetime('now'), closed_by = ?1 WHERE id = ?2",
+            params![audit::actor_name(conn, actor)?, existing.id],
+        )
+        .context("Failed to close OSHA log")?;
+
+        let updated = get_annual_stats(conn, establishment_id, year)?.context("Annual stats disappeared")?;
+        audit::log_update(conn, actor, "annual_stats", updated.id, &existing, &updated)?;
+        Ok(updated)
+    })
+}
 
+/// Records the executive certification of a closed log. The 300A
+/// certification date defaults to today.
+pub fn certify_log(conn: &Connection, actor: &Actor, establishment_id: i64, year: i64) -> Result<AnnualStats> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_annual_stats(conn, establishment_id, year)?
+            .with_context(|| format!("No annual stats for {year}"))?;
+        conn.execute(
+            "UPDATE annual_stats
+             SET certified_at = datetime('now'), certified_by = ?1,
+                 certification_date = COALESCE(certification_date, date('now'))
+             WHERE id = ?2",
+            params![audit::actor_name(conn, actor)?, existing.id],
+        )
+        .context("Failed to certify OSHA log")?;
+
+        let updated = get_annual_stats(conn, establishment_id, year)?.context("Annual stats disappeared")?;
+        audit::log_update(conn, actor, "annual_stats", updated.id, &existing, &updated)?;
+        Ok(updated)
+    })
+}
+
+pub fn is_certified(conn: &Connection, establishment_id: i64, year: i64) -> Result<bool> {
     conn.query_row(
-        "SELECT id, establishment_id, year, avg_employees, total_hours_worked,
-                certifier_name, certifier_title, certifier_phone, certification_date
-         FROM annual_stats WHERE establishment_id = ?1 AND year = ?2",
-        params![data.establishment_id, data.year],
-        |row| {
-            Ok(AnnualStats {
+        "SELECT EXISTS(SELECT 1 FROM annual_stats
+                       WHERE establishment_id = ?1 AND year = ?2 AND certified_at IS NOT NULL)",
+        params![establishment_id, year],
+        |row| row.get(0),
+    )
+    .context("Failed to check OSHA log certification")
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OshaLogAmendment {
+    pub id: i64,
+    pub incident_id: i64,
+    pub establishment_id: i64,
+    pub year: i64,
+    pub case_number: Option<i64>,
+    pub field_name: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub amended_by: Option<String>,
+    pub amended_at: String,
+}
+
+type LogField = (&'static str, fn(&Incident) -> Option<String>);
+
+/// Incident fields that appear on the 300 log or change what it shows.
+const LOG_FIELDS: &[LogField] = &[
+    ("employee_name", |i| Some(i.employee_name.clone())),
+    ("employee_job_title", |i| i.employee_job_title.clone()),
+    ("is_privacy_case", |i| Some(i.is_privacy_case.to_string())),
+    ("incident_date", |i| Some(i.incident_date.clone())),
+    ("where_occurred", |i| i.where_occurred.clone()),
+    ("description", |i| Some(i.description.clone())),
+    ("outcome_severity", |i| Some(i.outcome_severity.clone())),
+    ("days_away_count", |i| Some(i.days_away_count.to_string())),
+    ("days_restricted_count", |i| Some(i.days_restricted_count.to_string())),
+    ("date_of_death", |i| i.date_of_death.clone()),
+    ("injury_illness_type", |i| Some(i.injury_illness_type.clone())),
+    ("is_recordable", |i| Some(i.is_recordable.to_string())),
+];
+
+pub fn incident_year(incident: &Incident) -> Option<i64> {
+    incident.incident_date.get(..4)?.parse().ok()
+}
+
+/// Writes one dated amendment per changed log field when a recordable case
+/// in a certified year changes. Returns how many were written.
+pub fn record_amendments(conn: &Connection, actor: &Actor, before: &Incident, after: &Incident) -> Result<usize> {
+    if !before.is_recordable && !after.is_recordable {
+        return Ok(0);
+    }
+    let mut year = None;
+    for candidate in [incident_year(before), incident_year(after)].into_iter().flatten() {
+        if is_certified(conn, before.establishment_id, candidate)? {
+            year = Some(candidate);
+            break;
+        }
+    }
+    let Some(year) = year else {
+        return Ok(0);
+    };
+
+    let amended_by = audit::actor_name(conn, actor)?;
+    let mut written = 0;
+    for (field, value) in LOG_FIELDS {
+        let (old, new) = (value(before), value(after));
+        if old == new {
+            continue;
+        }
+        conn.execute(
+            "INSERT INTO osha_log_amendments
+                (incident_id, establishment_id, year, case_number, field_name, old_value, new_value, amended_by)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![after.id, after.establishment_id, year, after.case_number, field, old, new, amended_by],
+        )
+        .context("Failed to record OSHA log amendment")?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+pub fn list_amendments(conn: &Connection, establishment_id: i64, year: i64) -> Result<Vec<OshaLogAmendment>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, incident_id, establishment_id, year, case_number, field_name, old_value, new_value,
+                amended_by, amended_at
+         FROM osha_log_amendments
+         WHERE establishment_id = ?1 AND year = ?2
+         ORDER BY amended_at, id",
+    )?;
+    let rows = stmt
+        .query_map(params![establishment_id, year], |row| {
+            Ok(OshaLogAmendment {
                 id: row.get(0)?,
-                establishment_id: row.get(1)?,
-                year: row.get(2)?,
-                avg_employees: row.get(3)?,
-                total_hours_worked: row.get(4)?,
-                certifier_name: row.get(5)?,
-                certifier_title: row.get(6)?,
-                certifier_phone: row.get(7)?,
-                certification_date: row.get(8)?,
+                incident_id: row.get(1)?,
+                establishment_id: row.get(2)?,
+                year: row.get(3)?,
+                case_number: row.get(4)?,
+                field_name: row.get(5)?,
+                old_value: row.get(6)?,
+                new_value: row.get(7)?,
+                amended_by: row.get(8)?,
+                amended_at: row.get(9)?,
             })
-        },
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+// ── Retention ──
+
+/// Years the 300 log, 300A and 301 are kept after the year they cover,
+/// 29 CFR 1904.33(a).
+pub const RETENTION_YEARS: i64 = 5;
+
+/// Last day records for `year` must be kept.
+pub fn retained_until(year: i64) -> String {
+    format!("{}-12-31", year + RETENTION_YEARS)
+}
+
+/// Whether records for `year` are still inside the retention window.
+pub fn is_retained(year: i64, today: NaiveDate) -> bool {
+    today.year() as i64 <= year + RETENTION_YEARS
+}
+
+/// Latest year of OSHA records an establishment holds: recordable cases and
+/// annual summaries.
+pub fn latest_record_year(conn: &Connection, establishment_id: i64) -> Result<Option<i64>> {
+    conn.query_row(
+        "SELECT MAX(year) FROM (
+            SELECT CAST(substr(incident_date, 1, 4) AS INTEGER) AS year
+            FROM incidents WHERE establishment_id = ?1 AND is_recordable = 1
+            UNION ALL
+            SELECT year FROM annual_stats WHERE establishment_id = ?1
+         )",
+        [establishment_id],
+        |row| row.get(0),
     )
-    .map_err(|e| anyhow::Error::new(e))
+    .context("Failed to check OSHA record retention")
 }
 
-pub fn get_annual_stats(
+// ── Privacy Case List ──
+
+/// One line of the confidential list kept with the 300 log, mapping the case
+/// number of each privacy case to the employee, 1904.29(b)(6).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrivacyCaseEntry {
+    pub incident_id: i64,
+    pub case_number: Option<i64>,
+    pub employee_name: String,
+    pub incident_date: String,
+    pub privacy_concern: Option<String>,
+}
+
+pub fn get_privacy_case_list(
     conn: &Connection,
     establishment_id: i64,
     year: i64,
-) -> Result<Option<AnnualStats>> {
-    match conn.query_row(
-        "SELECT id, establishment_id, year, avg_employees, total_hours_worked,
-                certifier_name, certifier_title, certifier_phone, certification_date
-         FROM annual_stats WHERE establishment_id = ?1 AND year = ?2",
-        params![establishment_id, year],
-        |row| {
-            Ok(AnnualStats {
-                id: row.get(0)?,
-                establishment_id: row.get(1)?,
-                year: row.get(2)?,
-                avg_employees: row.get(3)?,
-                total_hours_worked: row.get(4)?,
-                certifier_name: row.get(5)?,
-                certifier_title: row.get(6)?,
-                certifier_phone: row.get(7)?,
-                certification_date: row.get(8)?,
+) -> Result<Vec<PrivacyCaseEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, case_number, employee_name, incident_date, privacy_concern
+         FROM incidents
+         WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND is_privacy_case = 1 AND deleted_at IS NULL
+         ORDER BY case_number, incident_date",
+    )?;
+    let rows = stmt
+        .query_map(params![establishment_id, format!("{year}%")], |row| {
+            Ok(PrivacyCaseEntry {
+                incident_id: row.get(0)?,
+                case_number: row.get(1)?,
+                employee_name: row.get(2)?,
+                incident_date: row.get(3)?,
+                privacy_concern: row.get(4)?,
             })
-        },
-    ) {
-        Ok(s) => Ok(Some(s)),
-        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(anyhow::Error::new(e)),
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+// ── OSHA ITA electronic submission ──
+
+/// Establishments with this many employees in designated industries must
+/// also submit 300/301 case data.
+pub const ITA_CASE_DATA_MIN_EMPLOYEES: i64 = 100;
+
+/// One row of the ITA 300A summary CSV. Field names are the ITA column names.
+#[derive(Debug, Serialize, Clone)]
+pub struct ItaSummaryRecord {
+    pub establishment_name: String,
+    pub ein: String,
+    pub company_name: String,
+    pub street_address: String,
+    pub city: String,
+    pub state: String,
+    pub zip: String,
+    pub naics_code: String,
+    pub industry_description: String,
+    pub size: String,
+    pub establishment_type: String,
+    pub year_filing_for: i64,
+    pub annual_average_employees: i64,
+    pub total_hours_worked: i64,
+    pub no_injuries_illnesses: String,
+    pub total_deaths: i64,
+    pub total_dafw_cases: i64,
+    pub total_djtr_cases: i64,
+    pub total_other_cases: i64,
+    pub total_dafw_days: i64,
+    pub total_djtr_days: i64,
+    pub total_injuries: i64,
+    pub total_skin_disorders: i64,
+    pub total_respiratory_conditions: i64,
+    pub total_poisonings: i64,
+    pub total_hearing_loss: i64,
+    pub total_other_illnesses: i64,
+    pub change_reason: String,
+}
+
+/// One row of the ITA 300/301 case data CSV. Employee names are never
+/// submitted.
+#[derive(Debug, Serialize, Clone)]
+pub struct ItaCaseRecord {
+    pub establishment_name: String,
+    pub year_of_filing: i64,
+    pub case_number: String,
+    pub job_title: String,
+    pub date_of_incident: String,
+    pub incident_location: String,
+    pub incident_description: String,
+    pub incident_outcome: String,
+    pub dafw_num_away: i64,
+    pub djtr_num_tr: i64,
+    pub type_of_incident: String,
+    pub date_of_birth: String,
+    pub date_of_hire: String,
+    pub sex: String,
+    pub treatment_facility_type: String,
+    pub treatment_in_patient: String,
+    pub time_started_work: String,
+    pub time_of_incident: String,
+    pub time_unknown: String,
+    pub nar_before_incident: String,
+    pub nar_what_happened: String,
+    pub nar_injury_illness: String,
+    pub nar_object_substance: String,
+    pub date_of_death: String,
+}
+
+/// ITA size code from annual average employment.
+pub fn ita_size_code(avg_employees: i64) -> &'static str {
+    match avg_employees {
+        n if n < 20 => "1",
+        n if n < 100 => "21",
+        n if n < 250 => "22",
+        _ => "3",
+    }
+}
+
+fn ita_establishment_type_code(establishment_type: &str) -> &'static str {
+    match establishment_type {
+        "state_government" => "2",
+        "local_government" => "3",
+        _ => "1",
+    }
+}
+
+fn ita_outcome_code(outcome_severity: &str) -> Option<&'static str> {
+    match outcome_severity {
+        "death" => Some("1"),
+        "days_away" => Some("2"),
+        "job_transfer_restriction" => Some("3"),
+        "other_recordable" => Some("4"),
+        _ => None,
+    }
+}
+
+fn ita_type_code(injury_illness_type: &str) -> Option<&'static str> {
+    match injury_illness_type {
+        "injury" => Some("1"),
+        "skin_disorder" => Some("2"),
+        "respiratory" => Some("3"),
+        "poisoning" => Some("4"),
+        "hearing_loss" => Some("5"),
+        "other_illness" => Some("6"),
+        _ => None,
+    }
+}
+
+/// `YYYY-MM-DD` to the ITA's `MM/DD/YYYY`. Anything else passes through.
+fn ita_date(date: &str) -> String {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d.format("%m/%d/%Y").to_string())
+        .unwrap_or_else(|_| date.to_string())
+}
+
+/// `HH:MM` (24h) to the ITA's `hh:mm AM/PM`. Anything else passes through.
+fn ita_time(time: &str) -> String {
+    chrono::NaiveTime::parse_from_str(time, "%H:%M")
+        .map(|t| t.format("%I:%M %p").to_string())
+        .unwrap_or_else(|_| time.to_string())
+}
+
+fn digits(value: &str) -> String {
+    value.chars().filter(char::is_ascii_digit).collect()
+}
+
+struct ItaCaseRow {
+    case_number: Option<i64>,
+    case_label: String,
+    job_title: String,
+    incident_date: String,
+    where_occurred: String,
+    description: String,
+    outcome_severity: String,
+    days_away_count: i64,
+    days_restricted_count: i64,
+    injury_illness_type: String,
+    employee_dob: String,
+    employee_hire_date: String,
+    employee_gender: String,
+    hospitalized_overnight: bool,
+    work_start_time: String,
+    incident_time: String,
+    activity_before_incident: String,
+    how_injury_occurred: String,
+    injury_description: String,
+    object_substance: String,
+    date_of_death: String,
+    is_privacy_case: bool,
+    privacy_description: Option<String>,
+}
+
+fn ita_case_rows(conn: &Connection, establishment_id: i64, year: i64) -> Result<Vec<ItaCaseRow>> {
+    let mut stmt = conn.prepare(
+        "SELECT case_number, COALESCE(employee_job_title,''), incident_date,
+                COALESCE(where_occurred,''), description,
+                outcome_severity, days_away_count, days_restricted_count, injury_illness_type,
+                COALESCE(employee_dob,''), COALESCE(employee_hire_date,''), COALESCE(employee_gender,''),
+                COALESCE(hospitalized_overnight,0), COALESCE(work_start_time,''), COALESCE(incident_time,''),
+                COALESCE(activity_before_incident,''), COALESCE(how_injury_occurred,''),
+                COALESCE(injury_description,''), COALESCE(object_substance,''), COALESCE(date_of_death,''),
+                is_privacy_case, privacy_description,
+                COALESCE(case_label, CAST(case_number AS TEXT), '')
+         FROM incidents
+         WHERE establishment_id = ?1 AND incident_date LIKE ?2 AND is_recordable = 1 AND deleted_at IS NULL
+         ORDER BY case_number",
+    )?;
+    let rows = stmt
+        .query_map(params![establishment_id, format!("{year}%")], |row| {
+            Ok(ItaCaseRow {
+                case_number: row.get(0)?,
+                case_label: row.get(22)?,
+                job_title: row.get(1)?,
+                incident_date: row.get(2)?,
+                where_occurred: row.get(3)?,
+                description: row.get(4)?,
+                outcome_severity: row.get(5)?,
+                days_away_count: row.get(6)?,
+                days_restricted_count: row.get(7)?,
+                injury_illness_type: row.get(8)?,
+                employee_dob: row.get(9)?,
+                employee_hire_date: row.get(10)?,
+                employee_gender: row.get(11)?,
+                hospitalized_overnight: row.get::<_, i32>(12)? != 0,
+                work_start_time: row.get(13)?,
+                incident_time: row.get(14)?,
+                activity_before_incident: row.get(15)?,
+                how_injury_occurred: row.get(16)?,
+                injury_description: row.get(17)?,
+                object_substance: row.get(18)?,
+                date_of_death: row.get(19)?,
+                is_privacy_case: row.get::<_, i32>(20)? != 0,
+                privacy_description: row.get(21)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Everything that would make the ITA reject the establishment's submission
+/// for `year`. An empty list means the files can be generated.
+pub fn validate_ita_submission(
+    conn: &Connection,
+    establishment_id: i64,
+    year: i64,
+    include_case_data: bool,
+) -> Result<Vec<String>> {
+    let est = locations::get_establishment(conn, establishment_id)?;
+    let mut problems = Vec::new();
+    let mut missing = |label: &str, value: &Option<String>| {
+        if value.as_deref().is_none_or(|v| v.trim().is_empty()) {
+            problems.push(format!("{}: {label} is required", est.name));
+        }
+    };
+    missing("Street address", &est.street_address);
+    missing("City", &est.city);
+    missing("State", &est.state);
+    missing("ZIP code", &est.zip_code);
+    missing("Industry description", &est.industry_description);
+
+    if let Some(ref state) = est.state {
+        let state = state.trim();
+        let valid = state.len() == 2 && state.chars().all(|c| c.is_ascii_alphabetic());
+        if !state.is_empty() && !valid {
+            problems.push(format!("{}: State must be a 2-letter code (got: {state})", est.name));
+        }
+    }
+    if let Some(ref zip) = est.zip_code {
+        let zip_digits = digits(zip);
+        if !zip.trim().is_empty() && zip_digits.len() != 5 && zip_digits.len() != 9 {
+            problems.push(format!("{}: ZIP code must have 5 or 9 digits (got: {zip})", est.name));
+        }
+    }
+    match est.naics_code.as_deref().map(str::trim) {
+        Some(naics) if naics.len() == 6 && naics.chars().all(|c| c.is_ascii_digit()) => {}
+        Some(naics) if !naics.is_empty() => {
+            problems.push(format!("{}: NAICS code must be 6 digits (got: {naics})", est.name));
+        }
+        _ => problems.push(format!("{}: NAICS code is required", est.name)),
+    }
+    match est.ein.as_deref() {
+        Some(ein) if digits(ein).len() == 9 => {}
+        Some(ein) if !ein.trim().is_empty() => {
+            problems.push(format!("{}: EIN must be 9 digits (got: {ein})", est.name));
+        }
+        _ => problems.push(format!("{}: EIN is required", est.name)),
+    }
+
+    match get_annual_stats(conn, establishment_id, year)? {
+        None => problems.push(format!(
+            "{}: No annual employee count and hours for {year}",
+            est.name
+        )),
+        Some(stats) => {
+            if stats.avg_employees <= 0 {
+                problems.push(format!("{}: Annual average employees must be at least 1", est.name));
+            }
+            if stats.total_hours_worked <= 0 {
+                problems.push(format!("{}: Total hours worked must be greater than 0", est.name));
+            } else if stats.avg_employees > 0 && stats.total_hours_worked > stats.avg_employees * 8760 {
+                problems.push(format!(
+                    "{}: {} hours is more than {} employees could work in a year",
+                    est.name, stats.total_hours_worked, stats.avg_employees
+                ));
+            }
+        }
     }
+
+    if include_case_data {
+        for case in ita_case_rows(conn, establishment_id, year)? {
+            let label = match case.case_number {
+                Some(n) => format!("{}: Case {n}", est.name),
+                None => format!("{}: Case on {}", est.name, case.incident_date),
+            };
+            if case.case_number.is_none() {
+                problems.push(format!("{label} has no case number"));
+            }
+            if case.job_title.trim().is_empty() {
+                problems.push(format!("{label} is missing the job title"));
+            }
+            if ita_outcome_code(&case.outcome_severity).is_none() {
+                problems.push(format!("{label} has no recordable outcome"));
+            }
+            if ita_type_code(&case.injury_illness_type).is_none() {
+                problems.push(format!("{label} has no injury or illness type"));
+            }
+            if case.outcome_severity == "days_away" && case.days_away_count <= 0 {
+                problems.push(format!("{label} is a days-away case with no days away"));
+            }
+            if case.outcome_severity == "job_transfer_restriction" && case.days_restricted_count <= 0 {
+                problems.push(format!("{label} is a restriction case with no restricted days"));
+            }
+            if case.outcome_severity == "death" && case.date_of_death.trim().is_empty() {
+                problems.push(format!("{label} is a fatality with no date of death"));
+            }
+            if case.how_injury_occurred.trim().is_empty() {
+                problems.push(format!("{label} is missing what happened (301 question 15)"));
+            }
+            if case.injury_description.trim().is_empty() {
+                problems.push(format!("{label} is missing the injury or illness description (301 question 16)"));
+            }
+        }
+    }
+
+    Ok(problems)
+}
+
+pub fn ita_summary_record(conn: &Connection, establishment_id: i64, year: i64) -> Result<ItaSummaryRecord> {
+    let est = locations::get_establishment(conn, establishment_id)?;
+    let summary = get_osha_300a_summary(conn, establishment_id, year)?;
+    let avg_employees = summary.avg_employees.unwrap_or(0);
+    let total_cases = summary.total_deaths
+        + summary.total_days_away_cases
+        + summary.total_transfer_restriction_cases
+        + summary.total_other_recordable_cases;
+
+    Ok(ItaSummaryRecord {
+        establishment_name: summary.establishment_name,
+        ein: est.ein.as_deref().map(digits).unwrap_or_default(),
+        company_name: est.company_name.unwrap_or_default(),
+        street_address: summary.street_address,
+        city: summary.city,
+        state: summary.state.to_uppercase(),
+        zip: digits(&summary.zip_code),
+        naics_code: summary.naics_code,
+        industry_description: summary.industry_description,
+        size: ita_size_code(avg_employees).to_string(),
+        establishment_type: ita_establishment_type_code(&est.establishment_type).to_string(),
+        year_filing_for: year,
+        annual_average_employees: avg_employees,
+        total_hours_worked: summary.total_hours_worked.unwrap_or(0),
+        no_injuries_illnesses: if total_cases > 0 { "1" } else { "2" }.to_string(),
+        total_deaths: summary.total_deaths,
+        total_dafw_cases: summary.total_days_away_cases,
+        total_djtr_cases: summary.total_transfer_restriction_cases,
+        total_other_cases: summary.total_other_recordable_cases,
+        total_dafw_days: summary.total_days_away,
+        total_djtr_days: summary.total_days_restricted,
+        total_injuries: summary.total_injuries,
+        total_skin_disorders: summary.total_skin_disorders,
+        total_respiratory_conditions: summary.total_respiratory,
+        total_poisonings: summary.total_poisonings,
+        total_hearing_loss: summary.total_hearing_loss,
+        total_other_illnesses: summary.total_other_illnesses,
+        change_reason: String::new(),
+    })
+}
+
+pub fn ita_case_records(conn: &Connection, establishment_id: i64, year: i64) -> Result<Vec<ItaCaseRecord>> {
+    let est = locations::get_establishment(conn, establishment_id)?;
+
+    Ok(ita_case_rows(conn, establishment_id, year)?
+        .into_iter()
+        .map(|case| {
+            // Submitted data is published by OSHA, so privacy cases carry
+            // only the general wording
+            let redact = |text: String| {
+                redaction::description(
+                    Audience::Restricted,
+                    case.is_privacy_case,
+                    text,
+                    case.privacy_description.as_deref(),
+                )
+            };
+            ItaCaseRecord {
+                establishment_name: est.name.clone(),
+                year_of_filing: year,
+                case_number: case.case_label,
+                job_title: case.job_title,
+                date_of_incident: ita_date(&case.incident_date),
+                incident_location: case.where_occurred,
+                incident_description: redact(case.description),
+                incident_outcome: ita_outcome_code(&case.outcome_severity).unwrap_or_default().to_string(),
+                dafw_num_away: case.days_away_count,
+                djtr_num_tr: case.days_restricted_count,
+                type_of_incident: ita_type_code(&case.injury_illness_type).unwrap_or_default().to_string(),
+                date_of_birth: ita_date(&case.employee_dob),
+                date_of_hire: ita_date(&case.employee_hire_date),
+                sex: match case.employee_gender.as_str() {
+                    "male" => "M",
+                    "female" => "F",
+                    _ => "",
+                }
+                .to_string(),
+                treatment_facility_type: String::new(),
+                treatment_in_patient: if case.hospitalized_overnight { "1" } else { "0" }.to_string(),
+                time_started_work: ita_time(&case.work_start_time),
+                time_unknown: if case.incident_time.trim().is_empty() { "1" } else { "0" }.to_string(),
+                time_of_incident: ita_time(&case.incident_time),
+                nar_before_incident: case.activity_before_incident,
+                nar_what_happened: redact(case.how_injury_occurred),
+                nar_injury_illness: redact(case.injury_description),
+                nar_object_substance: case.object_substance,
+                date_of_death: ita_date(&case.date_of_death),
+            }
+        })
+        .collect())
 }
 
 #[cfg(test)]
@@ -451,7 +1080,7 @@ mod tests {
     use crate::db::locations::{CreateEstablishment, CreateLocation, create_establishment, create_location};
 
     fn setup(conn: &Connection) -> (i64, i64) {
-        let est = create_establishment(conn, CreateEstablishment {
+        let est = create_establishment(conn, &Actor::system(), CreateEstablishment {
             name: "ABC Construction".into(),
             street_address: Some("100 Main St".into()),
             city: Some("Chicago".into()),
@@ -459,13 +1088,17 @@ mod tests {
             zip_code: Some("60601".into()),
             industry_description: Some("General Construction".into()),
             naics_code: Some("236220".into()),
+            ein: None,
+            company_name: None,
+            establishment_type: None,
+            case_number_format: None,
         }).unwrap();
-        let loc = create_location(conn, CreateLocation {
+        let loc = create_location(conn, &Actor::system(), CreateLocation {
             establishment_id: est.id, name: "Site A".into(),
             address: None, city: None, state: None,
         }).unwrap();
 
-        let inc = create_incident(conn, CreateIncident {
+        let inc = create_incident(conn, &Actor::system(), CreateIncident {
             establishment_id: est.id, location_id: Some(loc.id),
             employee_name: "John Doe".into(), incident_date: "2026-03-15".into(),
             description: "Fell from ladder".into(),
@@ -475,7 +1108,8 @@ mod tests {
             injury_illness_type: Some("injury".into()), is_recordable: Some(true),
             employee_address: None, employee_city: None, employee_state: None,
             employee_zip: None, employee_dob: None, employee_hire_date: None,
-            employee_gender: None, is_privacy_case: None, incident_time: None,
+            employee_gender: None, is_privacy_case: None, privacy_concern: None,
+            privacy_description: None, incident_time: None,
             work_start_time: None, where_occurred: Some("Building A".into()),
             activity_before_incident: None, how_injury_occurred: None,
             injury_description: None, object_substance: None, physician_name: None,
@@ -498,12 +1132,88 @@ mod tests {
         assert_eq!(log[0].days_away_count, 10);
     }
 
+    #[test]
+    fn test_privacy_case_redaction() {
+        let conn = open_test_db();
+        let (est_id, inc_id) = setup(&conn);
+        conn.execute(
+            "UPDATE incidents SET is_privacy_case = 1, privacy_concern = 'sexual_assault',
+                    privacy_description = 'Injury to upper body',
+                    injury_description = 'Bruising from assault', employee_city = 'Evanston'
+             WHERE id = ?1",
+            [inc_id],
+        ).unwrap();
+
+        let log = get_osha_300_log(&conn, est_id, 2026).unwrap();
+        assert_eq!(log[0].employee_name, redaction::PRIVACY_CASE_NAME);
+        assert_eq!(log[0].description, "Injury to upper body");
+
+        let restricted = get_osha_301_report(&conn, inc_id, Audience::Restricted).unwrap();
+        assert_eq!(restricted.employee_name, redaction::PRIVACY_CASE_NAME);
+        assert_eq!(restricted.injury_description, "Injury to upper body");
+        assert!(restricted.employee_city.is_empty());
+        let full = get_osha_301_report(&conn, inc_id, Audience::Full).unwrap();
+        assert_eq!(full.employee_name, "John Doe");
+        assert_eq!(full.injury_description, "Bruising from assault");
+
+        let cases = ita_case_records(&conn, est_id, 2026).unwrap();
+        assert_eq!(cases[0].nar_injury_illness, "Injury to upper body");
+
+        let list = get_privacy_case_list(&conn, est_id, 2026).unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].employee_name, "John Doe");
+        assert_eq!(list[0].case_number, Some(1));
+    }
+
+    #[test]
+    fn test_log_close_certify_and_amendments() {
+        use crate::db::incidents::{update_incident, UpdateIncident};
+
+        let conn = open_test_db();
+        let (est_id, inc_id) = setup(&conn);
+        upsert_annual_stats(&conn, &Actor::system(), UpsertAnnualStats {
+            establishment_id: est_id, year: 2026,
+            avg_employees: 50, total_hours_worked: 100000,
+            certifier_name: Some("Jane Smith".into()),
+            certifier_title: Some("President".into()),
+            certifier_phone: None, certification_date: None,
+        }).unwrap();
+        let edit = |v: serde_json::Value| -> UpdateIncident { serde_json::from_value(v).unwrap() };
+
+        // Edits before certification are not amendments
+        update_incident(&conn, &Actor::system(), inc_id, edit(serde_json::json!({ "days_away_count": 12 }))).unwrap();
+        assert!(list_amendments(&conn, est_id, 2026).unwrap().is_empty());
+
+        let closed = close_log(&conn, &Actor::system(), est_id, 2026).unwrap();
+        assert!(closed.closed_at.is_some());
+        assert!(!is_certified(&conn, est_id, 2026).unwrap());
+        let certified = certify_log(&conn, &Actor::system(), est_id, 2026).unwrap();
+        assert!(certified.certified_at.is_some());
+        assert!(certified.certification_date.is_some());
+
+        update_incident(&conn, &Actor::system(), inc_id, edit(serde_json::json!({
+            "days_away_count": 20,
+            "employee_job_title": "Carpenter",
+        }))).unwrap();
+        let amendments = list_amendments(&conn, est_id, 2026).unwrap();
+        assert_eq!(amendments.len(), 2);
+        let days = amendments.iter().find(|a| a.field_name == "days_away_count").unwrap();
+        assert_eq!(days.old_value.as_deref(), Some("12"));
+        assert_eq!(days.new_value.as_deref(), Some("20"));
+        assert_eq!(days.case_number, Some(1));
+
+        assert_eq!(retained_until(2026), "2031-12-31");
+        assert!(is_retained(2026, NaiveDate::from_ymd_opt(2031, 12, 31).unwrap()));
+        assert!(!is_retained(2026, NaiveDate::from_ymd_opt(2032, 1, 1).unwrap()));
+        assert_eq!(latest_record_year(&conn, est_id).unwrap(), Some(2026));
+    }
+
     #[test]
     fn test_osha_300a_summary() {
         let conn = open_test_db();
         let (est_id, _) = setup(&conn);
 
-        upsert_annual_stats(&conn, UpsertAnnualStats {
+        upsert_annual_stats(&conn, &Actor::system(), UpsertAnnualStats {
             establishment_id: est_id, year: 2026,
             avg_employees: 50, total_hours_worked: 100000,
             certifier_name: Some("Jane Smith".into()),
@@ -523,8 +1233,46 @@ mod tests {
         let conn = open_test_db();
         let (_est_id, inc_id) = setup(&conn);
 
-        let report = get_osha_301_report(&conn, inc_id).unwrap();
+        let report = get_osha_301_report(&conn, inc_id, Audience::Full).unwrap();
         assert_eq!(report.employee_name, "John Doe");
         assert_eq!(report.incident_date, "2026-03-15");
     }
+
+    #[test]
+    fn test_ita_validation_and_records() {
+        let conn = open_test_db();
+        let (est_id, _) = setup(&conn);
+
+        let problems = validate_ita_submission(&conn, est_id, 2026, true).unwrap();
+        assert!(problems.iter().any(|p| p.contains("EIN is required")));
+        assert!(problems.iter().any(|p| p.contains("No annual employee count")));
+        assert!(problems.iter().any(|p| p.contains("what happened")));
+
+        locations::update_establishment(&conn, &Actor::system(), est_id, locations::UpdateEstablishment {
+            name: None, street_address: None, city: None, state: None, zip_code: None,
+            industry_description: None, naics_code: None,
+            ein: Some("12-3456789".into()), company_name: None, establishment_type: None,
+            case_number_format: None,
+        }).unwrap();
+        upsert_annual_stats(&conn, &Actor::system(), UpsertAnnualStats {
+            establishment_id: est_id, year: 2026,
+            avg_employees: 120, total_hours_worked: 240000,
+            certifier_name: None, certifier_title: None, certifier_phone: None, certification_date: None,
+        }).unwrap();
+
+        assert!(validate_ita_submission(&conn, est_id, 2026, false).unwrap().is_empty());
+
+        let summary = ita_summary_record(&conn, est_id, 2026).unwrap();
+        assert_eq!(summary.ein, "123456789");
+        assert_eq!(summary.size, "22");
+        assert_eq!(summary.no_injuries_illnesses, "1");
+        assert_eq!(summary.total_dafw_cases, 1);
+
+        let cases = ita_case_records(&conn, est_id, 2026).unwrap();
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].date_of_incident, "03/15/2026");
+        assert_eq!(cases[0].incident_outcome, "2");
+        assert_eq!(cases[0].type_of_incident, "1");
+        assert_eq!(cases[0].dafw_num_away, 10);
+    }
 }
@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 
+use crate::db::audit::{self, Actor};
 use crate::errors::AppError;
 
 // ── RCA Sessions ──
@@ -109,15 +110,23 @@ pub struct UpdateCorrectiveAction {
 
 // ── RCA Session CRUD ──
 
-pub fn create_rca_session(conn: &Connection, data: CreateRcaSession) -> Result<RcaSession> {
-    conn.execute(
-        "INSERT INTO rca_sessions (incident_id, method) VALUES (?1, ?2)",
-        params![data.incident_id, data.method],
-    )
-    .context("Failed to create RCA session")?;
+pub fn create_rca_session(
+    conn: &Connection,
+    actor: &Actor,
+    data: CreateRcaSession,
+) -> Result<RcaSession> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO rca_sessions (incident_id, method) VALUES (?1, ?2)",
+            params![data.incident_id, data.method],
+        )
+        .context("Failed to create RCA session")?;
 
-    let id = conn.last_insert_rowid();
-    get_rca_session(conn, id)
+        let id = conn.last_insert_rowid();
+        let created = get_rca_session(conn, id)?;
+        audit::log_create(conn, actor, "rca_sessions", id, &created)?;
+        Ok(created)
+    })
 }
 
 pub fn get_rca_session(conn: &Connection, id: i64) -> Result<RcaSession> {
@@ -170,45 +179,68 @@ pub fn list_rca_sessions(conn: &Connection, incident_id: i64) -> Result<Vec<RcaS
 
 pub fn complete_rca_session(
     conn: &Connection,
+    actor: &Actor,
     id: i64,
     root_cause_summary: &str,
 ) -> Result<RcaSession> {
-    let changes = conn
-        .execute(
-            "UPDATE rca_sessions SET status = 'completed', root_cause_summary = ?1, updated_at = datetime('now')
-             WHERE id = ?2",
-            params![root_cause_summary, id],
-        )
-        .context("Failed to complete RCA session")?;
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_rca_session(conn, id)?;
+        let changes = conn
+            .execute(
+                "UPDATE rca_sessions SET status = 'completed', root_cause_summary = ?1, updated_at = datetime('now')
+                 WHERE id = ?2",
+                params![root_cause_summary, id],
+            )
+            .context("Failed to complete RCA session")?;
+
+        if changes == 0 {
+            return Err(AppError::NotFound(format!("RCA session {id} not found")).into());
+        }
 
-    if changes == 0 {
-        return Err(AppError::NotFound(format!("RCA session {id} not found")).into());
-    }
-    get_rca_session(conn, id)
+        let updated = get_rca_session(conn, id)?;
+        audit::log_update(conn, actor, "rca_sessions", id, &existing, &updated)?;
+        Ok(updated)
+    })
 }
 
-pub fn delete_rca_session(conn: &Connection, id: i64) -> Result<()> {
-    let changes = conn
-        .execute("DELETE FROM rca_sessions WHERE id = ?1", [id])
-        .context("Failed to delete RCA session")?;
+pub fn delete_rca_session(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_rca_session(conn, id)?;
+        let changes = conn
+            .execute("DELETE FROM rca_sessions WHERE id = ?1", [id])
+            .context("Failed to delete RCA session")?;
 
-    if changes == 0 {
-        return Err(AppError::NotFound(format!("RCA session {id} not found")).into());
-    }
-    Ok(())
+        if changes == 0 {
+            return Err(AppError::NotFound(format!("RCA session {id} not found")).into());
+        }
+        audit::log_delete(conn, actor, "rca_sessions", id, &existing)?;
+        Ok(())
+    })
 }
 
 // ── Five Whys ──
 
-pub fn add_five_whys_step(conn: &Connection, data: CreateFiveWhysStep) -> Result<FiveWhysStep> {
-    conn.execute(
-        "INSERT INTO five_whys_steps (rca_session_id, step_number, question, answer)
-         VALUES (?1, ?2, ?3, ?4)",
-        params![data.rca_session_id, data.step_number, data.question, data.answer],
-    )
-    .context("Failed to add 5 Whys step")?;
+pub fn add_five_whys_step(
+    conn: &Connection,
+    actor: &Actor,
+    data: CreateFiveWhysStep,
+) -> Result<FiveWhysStep> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO five_whys_steps (rca_session_id, step_number, question, answer)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![data.rca_session_id, data.step_number, data.question, data.answer],
+        )
+        .context("Failed to add 5 Whys step")?;
 
-    let id = conn.last_insert_rowid();
+        let id = conn.last_insert_rowid();
+        let created = get_five_whys_step(conn, id)?;
+        audit::log_create(conn, actor, "five_whys_steps", id, &created)?;
+        Ok(created)
+    })
+}
+
+fn get_five_whys_step(conn: &Connection, id: i64) -> Result<FiveWhysStep> {
     conn.query_row(
         "SELECT id, rca_session_id, step_number, question, answer FROM five_whys_steps WHERE id = ?1",
         [id],
@@ -222,7 +254,12 @@ pub fn add_five_whys_step(conn: &Connection, data: CreateFiveWhysStep) -> Result
             })
         },
     )
-    .map_err(|e| anyhow::Error::new(e))
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            AppError::NotFound(format!("Five Whys step {id} not found")).into()
+        }
+        _ => anyhow::Error::new(e),
+    })
 }
 
 pub fn list_five_whys_steps(conn: &Connection, rca_session_id: i64) -> Result<Vec<FiveWhysStep>> {
@@ -248,57 +285,55 @@ pub fn list_five_whys_steps(conn: &Connection, rca_session_id: i64) -> Result<Ve
 
 pub fn update_five_whys_step(
     conn: &Connection,
+    actor: &Actor,
     id: i64,
     question: &str,
     answer: &str,
 ) -> Result<FiveWhysStep> {
-    let changes = conn
-        .execute(
-            "UPDATE five_whys_steps SET question = ?1, answer = ?2 WHERE id = ?3",
-            params![question, answer, id],
-        )
-        .context("Failed to update 5 Whys step")?;
-
-    if changes == 0 {
-        return Err(AppError::NotFound(format!("Five Whys step {id} not found")).into());
-    }
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_five_whys_step(conn, id)?;
+        let changes = conn
+            .execute(
+                "UPDATE five_whys_steps SET question = ?1, answer = ?2 WHERE id = ?3",
+                params![question, answer, id],
+            )
+            .context("Failed to update 5 Whys step")?;
+
+        if changes == 0 {
+            return Err(AppError::NotFound(format!("Five Whys step {id} not found")).into());
+        }
 
-    conn.query_row(
-        "SELECT id, rca_session_id, step_number, question, answer FROM five_whys_steps WHERE id = ?1",
-        [id],
-        |row| {
-            Ok(FiveWhysStep {
-                id: row.get(0)?,
-                rca_session_id: row.get(1)?,
-                step_number: row.get(2)?,
-                question: row.get(3)?,
-                answer: row.get(4)?,
-            })
-        },
-    )
-    .map_err(|e| anyhow::Error::new(e))
+        let updated = get_five_whys_step(conn, id)?;
+        audit::log_update(conn, actor, "five_whys_steps", id, &existing, &updated)?;
+        Ok(updated)
+    })
 }
 
 // ── Fishbone ──
 
 pub fn add_fishbone_category(
     conn: &Connection,
+    actor: &Actor,
     data: CreateFishboneCategory,
 ) -> Result<FishboneCategory> {
-    conn.execute(
-        "INSERT INTO fishbone_categories (rca_session_id, category, sort_order)
-         VALUES (?1, ?2, ?3)",
-        params![data.rca_session_id, data.category, data.sort_order.unwrap_or(0)],
-    )
-    .context("Failed to add fishbone category")?;
-
-    let id = conn.last_insert_rowid();
-    Ok(FishboneCategory {
-        id,
-        rca_session_id: data.rca_session_id,
-        category: data.category,
-        sort_order: data.sort_order.unwrap_or(0),
-        causes: vec![],
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO fishbone_categories (rca_session_id, category, sort_order)
+             VALUES (?1, ?2, ?3)",
+            params![data.rca_session_id, data.category, data.sort_order.unwrap_or(0)],
+        )
+        .context("Failed to add fishbone category")?;
+
+        let id = conn.last_insert_rowid();
+        let created = FishboneCategory {
+            id,
+            rca_session_id: data.rca_session_id,
+            category: data.category,
+            sort_order: data.sort_order.unwrap_or(0),
+            causes: vec![],
+        };
+        audit::log_create(conn, actor, "fishbone_categories", id, &created)?;
+        Ok(created)
     })
 }
 
@@ -355,50 +390,71 @@ pub fn list_fishbone_categories(
     Ok(cats)
 }
 
-pub fn add_fishbone_cause(conn: &Connection, data: CreateFishboneCause) -> Result<FishboneCause> {
-    conn.execute(
-        "INSERT INTO fishbone_causes (category_id, cause_text, is_root_cause, sort_order)
-         VALUES (?1, ?2, ?3, ?4)",
-        params![
-            data.category_id,
-            data.cause_text,
-            data.is_root_cause.unwrap_or(false) as i32,
-            data.sort_order.unwrap_or(0),
-        ],
-    )
-    .context("Failed to add fishbone cause")?;
-
-    let id = conn.last_insert_rowid();
-    Ok(FishboneCause {
-        id,
-        category_id: data.category_id,
-        cause_text: data.cause_text,
-        is_root_cause: data.is_root_cause.unwrap_or(false),
-        sort_order: data.sort_order.unwrap_or(0),
+pub fn add_fishbone_cause(
+    conn: &Connection,
+    actor: &Actor,
+    data: CreateFishboneCause,
+) -> Result<FishboneCause> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO fishbone_causes (category_id, cause_text, is_root_cause, sort_order)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                data.category_id,
+                data.cause_text,
+                data.is_root_cause.unwrap_or(false) as i32,
+                data.sort_order.unwrap_or(0),
+            ],
+        )
+        .context("Failed to add fishbone cause")?;
+
+        let id = conn.last_insert_rowid();
+        let created = FishboneCause {
+            id,
+            category_id: data.category_id,
+            cause_text: data.cause_text,
+            is_root_cause: data.is_root_cause.unwrap_or(false),
+            sort_order: data.sort_order.unwrap_or(0),
+        };
+        audit::log_create(conn, actor, "fishbone_causes", id, &created)?;
+        Ok(created)
     })
 }
 
 pub fn update_fishbone_cause(
     conn: &Connection,
+    actor: &Actor,
     id: i64,
     cause_text: Option<&str>,
     is_root_cause: Option<bool>,
 ) -> Result<FishboneCause> {
-    if let Some(text) = cause_text {
-        conn.execute(
-            "UPDATE fishbone_causes SET cause_text = ?1 WHERE id = ?2",
-            params![text, id],
-        )
-        .context("Failed to update fishbone cause")?;
-    }
-    if let Some(root) = is_root_cause {
-        conn.execute(
-            "UPDATE fishbone_causes SET is_root_cause = ?1 WHERE id = ?2",
-            params![root as i32, id],
-        )
-        .context("Failed to update fishbone cause")?;
-    }
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_fishbone_cause(conn, id)?;
+
+        if let Some(text) = cause_text {
+            conn.execute(
+                "UPDATE fishbone_causes SET cause_text = ?1 WHERE id = ?2",
+                params![text, id],
+            )
+            .context("Failed to update fishbone cause")?;
+        }
+        if let Some(root) = is_root_cause {
+            conn.execute(
+                "UPDATE fishbone_causes SET is_root_cause = ?1 WHERE id = ?2",
+                params![root as i32, id],
+            )
+            .context("Failed to update fishbone cause")?;
+        }
 
+        let updated = get_fishbone_cause(conn, id)?;
+        if cause_text.is_some() || is_root_cause.is_some() {
+            audit::log_update(conn, actor, "fishbone_causes", id, &existing, &updated)?;
+        }
+        Ok(updated)
+    })
+}
+
+fn get_fishbone_cause(conn: &Connection, id: i64) -> Result<FishboneCause> {
     conn.query_row(
         "SELECT id, category_id, cause_text, is_root_cause, sort_order FROM fishbone_causes WHERE id = ?1",
         [id],
@@ -420,35 +476,44 @@ pub fn update_fishbone_cause(
     })
 }
 
-pub fn delete_fishbone_cause(conn: &Connection, id: i64) -> Result<()> {
-    let changes = conn.execute("DELETE FROM fishbone_causes WHERE id = ?1", [id])?;
-    if changes == 0 {
-        return Err(AppError::NotFound(format!("Fishbone cause {id} not found")).into());
-    }
-    Ok(())
+pub fn delete_fishbone_cause(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_fishbone_cause(conn, id)?;
+        let changes = conn.execute("DELETE FROM fishbone_causes WHERE id = ?1", [id])?;
+        if changes == 0 {
+            return Err(AppError::NotFound(format!("Fishbone cause {id} not found")).into());
+        }
+        audit::log_delete(conn, actor, "fishbone_causes", id, &existing)?;
+        Ok(())
+    })
 }
 
 // ── Corrective Actions ──
 
 pub fn create_corrective_action(
     conn: &Connection,
+    actor: &Actor,
     data: CreateCorrectiveAction,
 ) -> Result<CorrectiveAction> {
-    conn.execute(
-        "INSERT INTO corrective_actions (incident_id, rca_session_id, description, assigned_to, due_date)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
-        params![
-            data.incident_id,
-            data.rca_session_id,
-            data.description,
-            data.assigned_to,
-            data.due_date,
-        ],
-    )
-    .context("Failed to create corrective action")?;
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO corrective_actions (incident_id, rca_session_id, description, assigned_to, due_date)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                data.incident_id,
+                data.rca_session_id,
+                data.description,
+                data.assigned_to,
+                data.due_date,
+            ],
+        )
+        .context("Failed to create corrective action")?;
 
-    let id = conn.last_insert_rowid();
-    get_corrective_action(conn, id)
+        let id = conn.last_insert_rowid();
+        let created = get_corrective_action(conn, id)?;
+        audit::log_create(conn, actor, "corrective_actions", id, &created)?;
+        Ok(created)
+    })
 }
 
 pub fn get_corrective_action(conn: &Connection, id: i64) -> Result<CorrectiveAction> {
@@ -514,63 +579,74 @@ pub fn list_corrective_actions(
 
 pub fn update_corrective_action(
     conn: &Connection,
+    actor: &Actor,
     id: i64,
     data: UpdateCorrectiveAction,
 ) -> Result<CorrectiveAction> {
-    let _existing = get_corrective_action(conn, id)?;
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_corrective_action(conn, id)?;
 
-    let mut sets = Vec::new();
-    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+        let mut sets = Vec::new();
+        let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
 
-    if let Some(ref v) = data.description {
-        sets.push("description = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(ref v) = data.assigned_to {
-        sets.push("assigned_to = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(ref v) = data.due_date {
-        sets.push("due_date = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(ref v) = data.status {
-        sets.push("status = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(ref v) = data.completed_date {
-        sets.push("completed_date = ?");
-        values.push(Box::new(v.clone()));
-    }
-    if let Some(ref v) = data.notes {
-        sets.push("notes = ?");
-        values.push(Box::new(v.clone()));
-    }
+        if let Some(ref v) = data.description {
+            sets.push("description = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.assigned_to {
+            sets.push("assigned_to = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.due_date {
+            sets.push("due_date = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.status {
+            sets.push("status = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.completed_date {
+            sets.push("completed_date = ?");
+            values.push(Box::new(v.clone()));
+        }
+        if let Some(ref v) = data.notes {
+            sets.push("notes = ?");
+            values.push(Box::new(v.clone()));
+        }
 
-    if !sets.is_empty() {
-        sets.push("updated_at = datetime('now')");
-        let sql = format!(
-            "UPDATE corrective_actions SET {} WHERE id = ?",
-            sets.join(", ")
-        );
-        values.push(Box::new(id));
-        let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
-        conn.execute(&sql, params.as_slice())
-            .context("Failed to update corrective action")?;
-    }
+        if !sets.is_empty() {
+            sets.push("updated_at = datetime('now')");
+            let sql = format!(
+                "UPDATE corrective_actions SET {} WHERE id = ?",
+                sets.join(", ")
+            );
+            values.push(Box::new(id));
+            let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+            conn.execute(&sql, params.as_slice())
+                .context("Failed to update corrective action")?;
+        }
 
-    get_corrective_action(conn, id)
+        let updated = get_corrective_action(conn, id)?;
+        if !sets.is_empty() {
+            audit::log_update(conn, actor, "corrective_actions", id, &existing, &updated)?;
+        }
+        Ok(updated)
+    })
 }
 
-pub fn delete_corrective_action(conn: &Connection, id: i64) -> Result<()> {
-    let changes = conn
-        .execute("DELETE FROM corrective_actions WHERE id = ?1", [id])
-        .context("Failed to delete corrective action")?;
+pub fn delete_corrective_action(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_corrective_action(conn, id)?;
+        let changes = conn
+            .execute("DELETE FROM corrective_actions WHERE id = ?1", [id])
+            .context("Failed to delete corrective action")?;
 
-    if changes == 0 {
-        return Err(AppError::NotFound(format!("Corrective action {id} not found")).into());
-    }
-    Ok(())
+        if changes == 0 {
+            return Err(AppError::NotFound(format!("Corrective action {id} not found")).into());
+        }
+        audit::log_delete(conn, actor, "corrective_actions", id, &existing)?;
+        Ok(())
+    })
 }
 
 #[cfg(test)]
@@ -583,14 +659,17 @@ mod tests {
     fn setup(conn: &Connection) -> i64 {
         let est = create_establishment(
             conn,
+            &Actor::system(),
             CreateEstablishment {
                 name: "Test Co".into(),
                 street_address: None, city: None, state: None,
                 zip_code: None, industry_description: None, naics_code: None,
+                ein: None, company_name: None, establishment_type: None, case_number_format: None,
             },
         ).unwrap();
         let loc = create_location(
             conn,
+            &Actor::system(),
             CreateLocation {
                 establishment_id: est.id, name: "Site".into(),
                 address: None, city: None, state: None,
@@ -598,13 +677,14 @@ mod tests {
         ).unwrap();
         let inc = create_incident(
             conn,
+            &Actor::system(),
             CreateIncident {
                 establishment_id: est.id, location_id: Some(loc.id),
                 employee_name: "Jane".into(), incident_date: "2026-01-01".into(),
                 description: "Test".into(),
                 employee_job_title: None, employee_address: None, employee_city: None,
                 employee_state: None, employee_zip: None, employee_dob: None,
-                employee_hire_date: None, employee_gender: None, is_privacy_case: None,
+                employee_hire_date: None, employee_gender: None, is_privacy_case: None, privacy_concern: None, privacy_description: None,
                 incident_time: None, work_start_time: None, where_occurred: None,
                 activity_before_incident: None, how_injury_occurred: None,
                 injury_description: None, object_substance: None, physician_name: None,
@@ -622,12 +702,12 @@ mod tests {
         let conn = open_test_db();
         let inc_id = setup(&conn);
 
-        let session = create_rca_session(&conn, CreateRcaSession {
+        let session = create_rca_session(&conn, &Actor::system(), CreateRcaSession {
             incident_id: inc_id, method: "five_whys".into(),
         }).unwrap();
         assert_eq!(session.status, "in_progress");
 
-        add_five_whys_step(&conn, CreateFiveWhysStep {
+        add_five_whys_step(&conn, &Actor::system(), CreateFiveWhysStep {
             rca_session_id: session.id, step_number: 1,
             question: "Why did the worker fall?".into(),
             answer: "The scaffold was not secured".into(),
@@ -636,7 +716,7 @@ mod tests {
         let steps = list_five_whys_steps(&conn, session.id).unwrap();
         assert_eq!(steps.len(), 1);
 
-        let completed = complete_rca_session(&conn, session.id, "Inadequate scaffold inspection").unwrap();
+        let completed = complete_rca_session(&conn, &Actor::system(), session.id, "Inadequate scaffold inspection").unwrap();
         assert_eq!(completed.status, "completed");
     }
 
@@ -645,7 +725,7 @@ mod tests {
         let conn = open_test_db();
         let inc_id = setup(&conn);
 
-        let action = create_corrective_action(&conn, CreateCorrectiveAction {
+        let action = create_corrective_action(&conn, &Actor::system(), CreateCorrectiveAction {
             incident_id: inc_id, rca_session_id: None,
             description: "Implement scaffold checklist".into(),
             assigned_to: Some("Safety Manager".into()),
@@ -653,7 +733,7 @@ mod tests {
         }).unwrap();
         assert_eq!(action.status, "open");
 
-        let updated = update_corrective_action(&conn, action.id, UpdateCorrectiveAction {
+        let updated = update_corrective_action(&conn, &Actor::system(), action.id, UpdateCorrectiveAction {
             status: Some("completed".into()),
             completed_date: Some("2026-01-25".into()),
             description: None, assigned_to: None, due_date: None, notes: None,
@@ -0,0 +1,350 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::db::audit::{self, Actor};
+use crate::db::incidents;
+use crate::db::osha;
+
+// ── Treatments ──
+
+/// Treatments OSHA defines as first aid, 29 CFR 1904.7(b)(5)(ii)(A)-(N).
+/// Anything else given by a physician or licensed health care professional
+/// is medical treatment.
+pub const FIRST_AID_TREATMENTS: &[(&str, &str)] = &[
+    ("otc_medication_nonprescription_strength", "Non-prescription medication at non-prescription strength"),
+    ("tetanus_immunization", "Tetanus immunization"),
+    ("wound_cleaning", "Cleaning, flushing or soaking wounds on the surface of the skin"),
+    ("bandage_or_closure_strip", "Wound coverings such as bandages, Band-Aids, gauze pads, butterfly bandages or Steri-Strips"),
+    ("hot_cold_therapy", "Hot or cold therapy"),
+    ("non_rigid_support", "Non-rigid means of support such as elastic bandages, wraps or non-rigid back belts"),
+    ("temporary_immobilization", "Temporary immobilization device used to transport the victim"),
+    ("drilling_nail", "Drilling of a fingernail or toenail to relieve pressure, or draining fluid from a blister"),
+    ("eye_patch", "Eye patches"),
+    ("eye_foreign_body_irrigation", "Removing foreign bodies from the eye using only irrigation or a cotton swab"),
+    ("splinter_removal", "Removing splinters or foreign material from areas other than the eye by irrigation, tweezers, cotton swabs or other simple means"),
+    ("finger_guard", "Finger guards"),
+    ("massage", "Massages"),
+    ("fluids_for_heat_stress", "Drinking fluids for relief of heat stress"),
+];
+
+/// Common treatments beyond first aid, 29 CFR 1904.7(b)(5)(i). Listed so the
+/// form can offer them; any treatment not in [`FIRST_AID_TREATMENTS`] counts.
+pub const MEDICAL_TREATMENTS: &[(&str, &str)] = &[
+    ("prescription_medication", "Prescription medication, or non-prescription medication at prescription strength"),
+    ("sutures_staples_glue", "Sutures, staples or surgical glue"),
+    ("rigid_splint", "Rigid means of support such as splints, casts or rigid stays"),
+    ("physical_therapy", "Physical therapy or chiropractic treatment"),
+    ("eye_foreign_body_removal", "Removing foreign bodies from the eye by means other than irrigation or a swab"),
+    ("embedded_foreign_body_removal", "Removing embedded foreign material by means other than simple ones"),
+    ("wound_debridement", "Wound debridement"),
+    ("other_immunization", "Immunizations other than tetanus, such as hepatitis B or rabies"),
+    ("oxygen", "Oxygen administered for a work-related exposure"),
+    ("other_medical_treatment", "Other medical treatment"),
+];
+
+pub fn is_known_treatment(code: &str) -> bool {
+    FIRST_AID_TREATMENTS.iter().chain(MEDICAL_TREATMENTS).any(|(c, _)| *c == code)
+}
+
+pub fn is_first_aid(code: &str) -> bool {
+    FIRST_AID_TREATMENTS.iter().any(|(c, _)| *c == code)
+}
+
+fn treatment_label(code: &str) -> &str {
+    FIRST_AID_TREATMENTS
+        .iter()
+        .chain(MEDICAL_TREATMENTS)
+        .find(|(c, _)| *c == code)
+        .map(|(_, label)| *label)
+        .unwrap_or(code)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TreatmentOption {
+    pub code: String,
+    pub label: String,
+    pub first_aid: bool,
+}
+
+pub fn treatment_options() -> Vec<TreatmentOption> {
+    FIRST_AID_TREATMENTS
+        .iter()
+        .map(|(code, label)| (code, label, true))
+        .chain(MEDICAL_TREATMENTS.iter().map(|(code, label)| (code, label, false)))
+        .map(|(code, label, first_aid)| TreatmentOption {
+            code: code.to_string(),
+            label: label.to_string(),
+            first_aid,
+        })
+        .collect()
+}
+
+// ── Determination ──
+
+/// The facts of a case as the recording rules ask for them.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecordabilityInputs {
+    /// An event or exposure in the work environment caused or contributed to
+    /// the condition and no 1904.5(b)(2) exception applies
+    pub work_related: bool,
+    /// Not a recurrence of a previously recorded case that had not healed
+    #[serde(default = "default_true")]
+    pub new_case: bool,
+    #[serde(default)]
+    pub death: bool,
+    #[serde(default)]
+    pub days_away: bool,
+    #[serde(default)]
+    pub restricted_work_or_transfer: bool,
+    #[serde(default)]
+    pub loss_of_consciousness: bool,
+    /// Cancer, chronic irreversible disease, fractured or cracked bone, or
+    /// punctured eardrum diagnosed by a physician or other licensed professional
+    #[serde(default)]
+    pub significant_diagnosis: bool,
+    /// Codes from [`FIRST_AID_TREATMENTS`] and [`MEDICAL_TREATMENTS`]
+    #[serde(default)]
+    pub treatments: Vec<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RecordabilityDecision {
+    pub recordable: bool,
+    pub reason: String,
+    pub citation: String,
+}
+
+fn decision(recordable: bool, reason: impl Into<String>, citation: &str) -> RecordabilityDecision {
+    RecordabilityDecision {
+        recordable,
+        reason: reason.into(),
+        citation: citation.to_string(),
+    }
+}
+
+/// Applies 1904.4 through 1904.7 in the order the regulation does: work
+/// relationship, new case, then the general recording criteria. The first
+/// criterion met decides the case.
+pub fn evaluate(inputs: &RecordabilityInputs) -> RecordabilityDecision {
+    if !inputs.work_related {
+        return decision(false, "Not work-related", "29 CFR 1904.5");
+    }
+    if !inputs.new_case {
+        return decision(false, "Not a new case; recurrence of a previously recorded condition", "29 CFR 1904.6");
+    }
+    if inputs.death {
+        return decision(true, "Resulted in death", "29 CFR 1904.7(b)(2)");
+    }
+    if inputs.days_away {
+        return decision(true, "Resulted in days away from work", "29 CFR 1904.7(b)(3)");
+    }
+    if inputs.restricted_work_or_transfer {
+        return decision(true, "Resulted in restricted work or job transfer", "29 CFR 1904.7(b)(4)");
+    }
+    let medical: Vec<&str> = inputs
+        .treatments
+        .iter()
+        .filter(|t| !is_first_aid(t))
+        .map(|t| treatment_label(t))
+        .collect();
+    if !medical.is_empty() {
+        return decision(
+            true,
+            format!("Medical treatment beyond first aid: {}", medical.join("; ")),
+            "29 CFR 1904.7(b)(5)",
+        );
+    }
+    if inputs.loss_of_consciousness {
+        return decision(true, "Resulted in loss of consciousness", "29 CFR 1904.7(b)(6)");
+    }
+    if inputs.significant_diagnosis {
+        return decision(
+            true,
+            "Significant injury or illness diagnosed by a physician or other licensed health care professional",
+            "29 CFR 1904.7(b)(7)",
+        );
+    }
+    if !inputs.treatments.is_empty() {
+        return decision(false, "First aid only", "29 CFR 1904.7(b)(5)(ii)");
+    }
+    decision(false, "No general recording criteria met", "29 CFR 1904.7(a)")
+}
+
+/// A stored determination with who made it and the answers it was based on.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecordabilityDetermination {
+    pub incident_id: i64,
+    pub inputs: RecordabilityInputs,
+    pub decision: RecordabilityDecision,
+    pub determined_by: Option<String>,
+    pub determined_at: String,
+}
+
+pub fn get_determination(conn: &Connection, incident_id: i64) -> Result<Option<RecordabilityDetermination>> {
+    let row = conn
+        .query_row(
+            "SELECT recordability_inputs, is_recordable, recordability_reason, recordability_citation,
+                    recordability_determined_by, recordability_determined_at
+             FROM incidents WHERE id = ?1 AND recordability_inputs IS NOT NULL",
+            [incident_id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i32>(1)? != 0,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                ))
+            },
+        )
+        .optional()
+        .context("Failed to get recordability determination")?;
+
+    let Some((inputs, recordable, reason, citation, determined_by, determined_at)) = row else {
+        return Ok(None);
+    };
+    Ok(Some(RecordabilityDetermination {
+        incident_id,
+        inputs: serde_json::from_str(&inputs).context("Invalid stored recordability inputs")?,
+        decision: RecordabilityDecision {
+            recordable,
+            reason: reason.unwrap_or_default(),
+            citation: citation.unwrap_or_default(),
+        },
+        determined_by,
+        determined_at: determined_at.unwrap_or_default(),
+    }))
+}
+
+/// Evaluates the inputs and records the answer on the incident, replacing
+/// the hand-set `is_recordable` flag.
+pub fn determine(
+    conn: &Connection,
+    actor: &Actor,
+    incident_id: i64,
+    inputs: RecordabilityInputs,
+    determined_by: &str,
+) -> Result<RecordabilityDetermination> {
+    super::immediate_transaction(conn, |conn| {
+        // Confirms the incident exists before anything is written
+        let before = incidents::get_incident(conn, incident_id)?;
+        let previous = get_determination(conn, incident_id)?;
+        let decision = evaluate(&inputs);
+
+        conn.execute(
+            "UPDATE incidents SET
+                is_recordable = ?1,
+                recordability_inputs = ?2,
+                recordability_reason = ?3,
+                recordability_citation = ?4,
+                recordability_determined_by = ?5,
+                recordability_determined_at = datetime('now'),
+                updated_at = datetime('now')
+             WHERE id = ?6",
+            params![
+                decision.recordable as i32,
+                serde_json::to_string(&inputs)?,
+                decision.reason,
+                decision.citation,
+                determined_by,
+                incident_id,
+            ],
+        )
+        .context("Failed to record recordability determination")?;
+
+        let after = incidents::get_incident(conn, incident_id)?;
+        osha::record_amendments(conn, actor, &before, &after)?;
+        let determination = get_determination(conn, incident_id)?;
+        audit::log_update(conn, actor, "incidents", incident_id, &previous, &determination)?;
+        determination.context("Recordability determination was not saved")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::open_test_db;
+
+    fn inputs() -> RecordabilityInputs {
+        RecordabilityInputs {
+            work_related: true,
+            new_case: true,
+            death: false,
+            days_away: false,
+            restricted_work_or_transfer: false,
+            loss_of_consciousness: false,
+            significant_diagnosis: false,
+            treatments: vec![],
+        }
+    }
+
+    #[test]
+    fn test_evaluate_follows_recording_criteria() {
+        let first_aid = RecordabilityInputs {
+            treatments: vec!["wound_cleaning".into(), "bandage_or_closure_strip".into()],
+            ..inputs()
+        };
+        let d = evaluate(&first_aid);
+        assert!(!d.recordable);
+        assert_eq!(d.citation, "29 CFR 1904.7(b)(5)(ii)");
+
+        let sutures = RecordabilityInputs {
+            treatments: vec!["wound_cleaning".into(), "sutures_staples_glue".into()],
+            ..inputs()
+        };
+        let d = evaluate(&sutures);
+        assert!(d.recordable);
+        assert_eq!(d.citation, "29 CFR 1904.7(b)(5)");
+        assert!(d.reason.contains("Sutures"));
+
+        let fracture = RecordabilityInputs { significant_diagnosis: true, ..inputs() };
+        assert_eq!(evaluate(&fracture).citation, "29 CFR 1904.7(b)(7)");
+
+        // Work relationship is decided before any outcome
+        let off_duty = RecordabilityInputs { work_related: false, days_away: true, ..inputs() };
+        assert!(!evaluate(&off_duty).recordable);
+
+        let both = RecordabilityInputs { days_away: true, restricted_work_or_transfer: true, ..inputs() };
+        assert_eq!(evaluate(&both).citation, "29 CFR 1904.7(b)(3)");
+        assert_eq!(evaluate(&inputs()).citation, "29 CFR 1904.7(a)");
+    }
+
+    #[test]
+    fn test_determination_is_stored_on_incident() {
+        let conn = open_test_db();
+        conn.execute("INSERT INTO establishments (name) VALUES ('Test Co')", []).unwrap();
+        conn.execute(
+            "INSERT INTO incidents (establishment_id, employee_name, incident_date, description)
+             VALUES (1, 'Pat Doe', '2026-02-10', 'Cut finger on sheet metal')",
+            [],
+        )
+        .unwrap();
+        let id = conn.last_insert_rowid();
+        assert!(get_determination(&conn, id).unwrap().is_none());
+
+        let first_aid = RecordabilityInputs { treatments: vec!["bandage_or_closure_strip".into()], ..inputs() };
+        let stored = determine(&conn, &Actor::system(), id, first_aid, "Sam Supervisor").unwrap();
+        assert!(!stored.decision.recordable);
+        assert_eq!(stored.determined_by.as_deref(), Some("Sam Supervisor"));
+        assert!(!incidents::get_incident(&conn, id).unwrap().is_recordable);
+
+        let fetched = get_determination(&conn, id).unwrap().unwrap();
+        assert_eq!(fetched.inputs.treatments, ["bandage_or_closure_strip"]);
+        assert_eq!(fetched.decision, stored.decision);
+
+        let audits: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM audit_log WHERE table_name = 'incidents' AND record_id = ?1",
+                [id],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(audits, 1);
+    }
+}
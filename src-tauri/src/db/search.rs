@@ -0,0 +1,165 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use crate::redaction::Audience;
+
+/// Wraps matched terms in snippets. The UI splits on these and renders the
+/// pieces as text, so nothing in a snippet is ever treated as markup.
+pub const HIGHLIGHT_START: &str = "<mark>";
+pub const HIGHLIGHT_END: &str = "</mark>";
+
+pub const DEFAULT_LIMIT: i64 = 50;
+pub const MAX_LIMIT: i64 = 200;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SearchHit {
+    /// `incident`, `rca` or `corrective_action`
+    pub source: String,
+    pub source_id: i64,
+    pub incident_id: i64,
+    pub establishment_id: i64,
+    pub case_number: Option<i64>,
+    pub case_label: Option<String>,
+    pub incident_date: String,
+    pub employee_name: String,
+    pub snippet: String,
+    /// bm25 score; lower is a better match
+    pub rank: f64,
+}
+
+/// Turns free text into an FTS5 query: each word must appear, and the last
+/// letters may be left off (`ladd` finds `ladder`). Quoting every word keeps
+/// FTS syntax such as `AND`, `NEAR` or a stray `"` from reaching the parser.
+pub fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| format!("\"{t}\"*"))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Ranked matches across incident narratives, RCA findings and corrective
+/// actions. Privacy cases only appear for audiences that may see them, since
+/// a hit on a name or narrative would otherwise identify the employee.
+pub fn search(
+    conn: &Connection,
+    query: &str,
+    establishment_id: Option<i64>,
+    audience: Audience,
+    limit: i64,
+) -> Result<Vec<SearchHit>> {
+    let Some(fts) = fts_query(query) else {
+        return Ok(Vec::new());
+    };
+
+    let mut stmt = conn.prepare(
+        "SELECT incident_search.source, incident_search.source_id, i.id, i.establishment_id, i.case_number,
+                i.incident_date, i.employee_name,
+                snippet(incident_search, -1, ?1, ?2, '…', 16),
+                bm25(incident_search, 0.0, 0.0, 0.0, 2.0, 1.0) AS rank, i.case_label
+         FROM incident_search
+         JOIN incidents i ON i.id = incident_search.incident_id
+         WHERE incident_search MATCH ?3
+           AND (?4 IS NULL OR i.establishment_id = ?4)
+           AND (?5 OR i.is_privacy_case = 0)
+           AND i.deleted_at IS NULL
+         ORDER BY rank
+         LIMIT ?6",
+    )?;
+    let rows = stmt
+        .query_map(
+            params![
+                HIGHLIGHT_START,
+                HIGHLIGHT_END,
+                fts,
+                establishment_id,
+                audience == Audience::Full,
+                limit.clamp(1, MAX_LIMIT),
+            ],
+            |row| {
+                Ok(SearchHit {
+                    source: row.get(0)?,
+                    source_id: row.get(1)?,
+                    incident_id: row.get(2)?,
+                    establishment_id: row.get(3)?,
+                    case_number: row.get(4)?,
+                    case_label: row.get(9)?,
+                    incident_date: row.get(5)?,
+                    employee_name: row.get(6)?,
+                    snippet: row.get(7)?,
+                    rank: row.get(8)?,
+                })
+            },
+        )?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to search incidents")?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::audit::Actor;
+    use crate::db::open_test_db;
+    use crate::db::rca::{self, CreateCorrectiveAction, CreateFiveWhysStep, CreateRcaSession};
+
+    fn add_incident(conn: &Connection, est: i64, name: &str, how: &str, privacy: bool) -> i64 {
+        conn.execute(
+            "INSERT INTO incidents (establishment_id, employee_name, incident_date, description,
+                                    how_injury_occurred, is_privacy_case)
+             VALUES (?1, ?2, '2026-02-10', 'Injury on site', ?3, ?4)",
+            params![est, name, how, privacy],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    #[test]
+    fn test_search_ranks_highlights_and_tracks_changes() {
+        let conn = open_test_db();
+        conn.execute("INSERT INTO establishments (name) VALUES ('North')", []).unwrap();
+        let north = conn.last_insert_rowid();
+        conn.execute("INSERT INTO establishments (name) VALUES ('South')", []).unwrap();
+        let south = conn.last_insert_rowid();
+
+        let ladder = add_incident(&conn, north, "John Doe", "Fell from a ladder while reaching", false);
+        add_incident(&conn, south, "Ann Lee", "Ladder slipped on wet floor", false);
+        add_incident(&conn, north, "Pat Kim", "Ladder fall, second ladder case", true);
+
+        let hits = search(&conn, "ladd", None, Audience::Full, DEFAULT_LIMIT).unwrap();
+        assert_eq!(hits.len(), 3);
+        assert!(hits.iter().all(|h| h.snippet.contains(HIGHLIGHT_START)));
+
+        let restricted = search(&conn, "ladder", None, Audience::Restricted, DEFAULT_LIMIT).unwrap();
+        assert_eq!(restricted.len(), 2);
+        let scoped = search(&conn, "ladder", Some(south), Audience::Full, DEFAULT_LIMIT).unwrap();
+        assert_eq!(scoped.len(), 1);
+        assert!(search(&conn, "\" AND NEAR(", None, Audience::Full, DEFAULT_LIMIT).unwrap().is_empty());
+
+        // Narrative edits re-index the incident
+        conn.execute("UPDATE incidents SET object_substance = 'extension ladder rung' WHERE id = ?1", [ladder]).unwrap();
+        assert_eq!(search(&conn, "rung", None, Audience::Full, DEFAULT_LIMIT).unwrap().len(), 1);
+
+        let session = rca::create_rca_session(&conn, &Actor::system(), CreateRcaSession {
+            incident_id: ladder, method: "five_whys".into(),
+        }).unwrap();
+        rca::add_five_whys_step(&conn, &Actor::system(), CreateFiveWhysStep {
+            rca_session_id: session.id, step_number: 1,
+            question: "Why did the worker fall?".into(), answer: "Footing was not inspected".into(),
+        }).unwrap();
+        rca::create_corrective_action(&conn, &Actor::system(), CreateCorrectiveAction {
+            incident_id: ladder, rca_session_id: Some(session.id),
+            description: "Add pre-use footing inspection".into(), assigned_to: None, due_date: None,
+        }).unwrap();
+
+        let hits = search(&conn, "footing", None, Audience::Full, DEFAULT_LIMIT).unwrap();
+        let sources: Vec<&str> = hits.iter().map(|h| h.source.as_str()).collect();
+        assert!(sources.contains(&"rca") && sources.contains(&"corrective_action"));
+        assert!(hits.iter().all(|h| h.incident_id == ladder));
+
+        conn.execute("DELETE FROM incidents WHERE id = ?1", [ladder]).unwrap();
+        assert!(search(&conn, "footing", None, Audience::Full, DEFAULT_LIMIT).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,1467 @@
+use anyhow::{anyhow, bail, Context, Result};
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::db::audit::{self, Actor};
+use crate::db::incidents;
+use crate::errors::AppError;
+
+/// Tables replicated between devices, parents before children so upserts can
+/// resolve foreign keys in order. Seeded reference tables (topics, templates,
+/// checklist items, trade hazards) are identical on every install and keep
+/// their integer ids; attachments stay local with their files.
+pub const SYNCED_TABLES: &[&str] = &[
+    "establishments",
+    "locations",
+    "incidents",
+    "incident_work_status",
+    "incident_status_changes",
+    "rca_sessions",
+    "five_whys_steps",
+    "fishbone_categories",
+    "fishbone_causes",
+    "corrective_actions",
+    "annual_stats",
+    "osha_log_amendments",
+    "toolbox_talks",
+    "toolbox_talk_attendees",
+    "jsa_instances",
+    "jsa_steps",
+    "jsa_hazards",
+    "jsa_controls",
+    "inspections",
+    "inspection_responses",
+    "near_miss_reports",
+    "training_courses",
+    "training_records",
+    "equipment",
+    "equipment_inspections",
+    "equipment_maintenance",
+];
+
+/// Tables with a natural unique key get a name-based (v5) uuid instead of a
+/// random one, so the same course, unit or year created on two devices
+/// converges on one row instead of tripping the unique constraint.
+fn natural_key(table: &str) -> Option<&'static str> {
+    match table {
+        "training_courses" => Some("name"),
+        "equipment" => Some("equipment_number"),
+        "annual_stats" => Some(
+            "(SELECT e.uuid FROM establishments e WHERE e.id = annual_stats.establishment_id) || ':' || year",
+        ),
+        _ => None,
+    }
+}
+
+pub const OP_UPSERT: &str = "upsert";
+pub const OP_DELETE: &str = "delete";
+
+/// One row-level change as exchanged with the sync server. Foreign keys to
+/// synced tables are carried as the parent's uuid; `data` is `None` for deletes.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Change {
+    pub table: String,
+    pub uuid: String,
+    pub operation: String,
+    pub data: Option<Map<String, Value>>,
+    pub changed_at: String,
+}
+
+/// Pass to `mark_pushed` once the server has accepted the changes.
+#[derive(Debug)]
+pub struct PendingChanges {
+    pub changes: Vec<Change>,
+    /// Highest sync_log id covered by `changes`
+    pub through_log_id: i64,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct ApplyReport {
+    pub applied: usize,
+    /// Changes merged field by field with unpushed local edits
+    pub merged: usize,
+    /// Fields queued in sync_conflicts for a person to resolve
+    pub conflicts: usize,
+    pub errors: Vec<String>,
+    /// Changes set aside in sync_failed_changes to retry on later pulls
+    pub failed: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncStatus {
+    pub device_id: String,
+    pub sync_endpoint: Option<String>,
+    pub last_sync_at: Option<String>,
+    pub last_pull_seq: i64,
+    pub pending_changes: i64,
+    pub open_conflicts: i64,
+    /// Remote changes waiting in sync_failed_changes
+    pub failed_changes: i64,
+}
+
+fn ensure_synced(table: &str) -> Result<()> {
+    if !SYNCED_TABLES.contains(&table) {
+        bail!("Table {table} is not synced");
+    }
+    Ok(())
+}
+
+/// While set, the sync_log triggers stay quiet so applied remote changes and
+/// uuid backfills are not queued for push.
+fn set_applying_remote(conn: &Connection, applying: bool) -> Result<()> {
+    conn.execute("UPDATE sync_control SET applying_remote = ?1 WHERE id = 1", [applying])
+        .context("Failed to toggle sync capture")?;
+    Ok(())
+}
+
+// ── Status ──
+
+pub fn get_sync_status(conn: &Connection) -> Result<SyncStatus> {
+    let device_id = super::device_id(conn)?;
+    let (sync_endpoint, last_sync_at, last_pull_seq) = conn.query_row(
+        "SELECT sync_endpoint, last_sync_at, last_pull_seq FROM sync_state WHERE device_id = ?1",
+        [&device_id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )?;
+    let pending_changes = conn.query_row(
+        "SELECT COUNT(DISTINCT table_name || ':' || record_id) FROM sync_log WHERE synced = 0",
+        [],
+        |row| row.get(0),
+    )?;
+    let open_conflicts = conn.query_row(
+        "SELECT COUNT(*) FROM sync_conflicts WHERE status = 'open'",
+        [],
+        |row| row.get(0),
+    )?;
+    let failed_changes = conn.query_row("SELECT COUNT(*) FROM sync_failed_changes", [], |row| row.get(0))?;
+
+    Ok(SyncStatus {
+        device_id,
+        sync_endpoint,
+        last_sync_at,
+        last_pull_seq,
+        pending_changes,
+        open_conflicts,
+        failed_changes,
+    })
+}
+
+pub fn set_sync_endpoint(conn: &Connection, endpoint: Option<&str>) -> Result<SyncStatus> {
+    let device_id = super::device_id(conn)?;
+    conn.execute(
+        "UPDATE sync_state SET sync_endpoint = ?1 WHERE device_id = ?2",
+        params![endpoint, device_id],
+    )
+    .context("Failed to set sync endpoint")?;
+    get_sync_status(conn)
+}
+
+/// Advances the pull cursor after a batch has been applied.
+pub fn set_last_pull_seq(conn: &Connection, seq: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE sync_state SET last_pull_seq = ?1 WHERE device_id = ?2",
+        params![seq, super::device_id(conn)?],
+    )?;
+    Ok(())
+}
+
+pub fn mark_sync_completed(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "UPDATE sync_state SET last_sync_at = datetime('now') WHERE device_id = ?1",
+        [super::device_id(conn)?],
+    )?;
+    Ok(())
+}
+
+// ── UUIDs ──
+
+/// Gives every synced row without a uuid one. Runs before each push; rows are
+/// created without a uuid so the db modules don't need to know about sync.
+pub fn assign_missing_uuids(conn: &Connection) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    set_applying_remote(&tx, true)?;
+
+    let mut assigned = 0;
+    for table in SYNCED_TABLES {
+        let key = natural_key(table).unwrap_or("NULL");
+        let rows: Vec<(i64, Option<String>)> = {
+            let mut stmt = tx.prepare(&format!(
+                "SELECT id, {key} FROM {table} WHERE uuid IS NULL"
+            ))?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+
+        for (id, key_value) in rows {
+            let uuid = match key_value {
+                Some(k) => uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_OID, format!("{table}:{k}").as_bytes()),
+                None => uuid::Uuid::new_v4(),
+            };
+            tx.execute(
+                &format!("UPDATE {table} SET uuid = ?1 WHERE id = ?2"),
+                params![uuid.to_string(), id],
+            )
+            .with_context(|| format!("Failed to assign uuid to {table} {id}"))?;
+            assigned += 1;
+        }
+    }
+
+    set_applying_remote(&tx, false)?;
+    tx.commit()?;
+    Ok(assigned)
+}
+
+// ── Row (de)serialization ──
+
+/// (column, parent table) for each foreign key that points at a synced table.
+fn synced_foreign_keys(conn: &Connection, table: &str) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA foreign_key_list({table})"))?;
+    let keys = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(3)?, row.get::<_, String>(2)?)))?
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter(|(_, parent)| SYNCED_TABLES.contains(&parent.as_str()))
+        .collect();
+    Ok(keys)
+}
+
+fn table_columns(conn: &Connection, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    let cols = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(cols)
+}
+
+fn sql_to_json(value: ValueRef<'_>) -> Result<Value> {
+    Ok(match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::from(i),
+        ValueRef::Real(f) => Value::from(f),
+        ValueRef::Text(t) => Value::from(std::str::from_utf8(t)?),
+        ValueRef::Blob(_) => bail!("BLOB columns are not supported by sync"),
+    })
+}
+
+fn json_to_sql(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(*b as i64),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => SqlValue::Text(s.clone()),
+        other => SqlValue::Text(other.to_string()),
+    }
+}
+
+fn uuid_for_id(conn: &Connection, table: &str, id: i64) -> Result<Option<String>> {
+    let uuid = conn
+        .query_row(&format!("SELECT uuid FROM {table} WHERE id = ?1"), [id], |row| row.get(0))
+        .optional()?;
+    Ok(uuid.flatten())
+}
+
+fn id_for_uuid(conn: &Connection, table: &str, uuid: &str) -> Result<Option<i64>> {
+    let id = conn
+        .query_row(&format!("SELECT id FROM {table} WHERE uuid = ?1"), [uuid], |row| row.get(0))
+        .optional()?;
+    Ok(id)
+}
+
+/// Serializes a local row for push, or `None` if it no longer exists.
+fn read_row(conn: &Connection, table: &str, id: i64) -> Result<Option<(String, Map<String, Value>)>> {
+    let mut stmt = conn.prepare(&format!("SELECT * FROM {table} WHERE id = ?1"))?;
+    let names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+
+    let mut rows = stmt.query([id])?;
+    let Some(row) = rows.next()? else {
+        return Ok(None);
+    };
+
+    let mut data = Map::new();
+    for (i, name) in names.iter().enumerate() {
+        if name != "id" {
+            data.insert(name.clone(), sql_to_json(row.get_ref(i)?)?);
+        }
+    }
+    drop(rows);
+
+    for (column, parent) in synced_foreign_keys(conn, table)? {
+        if let Some(parent_id) = data.get(&column).and_then(Value::as_i64) {
+            let parent_uuid = uuid_for_id(conn, &parent, parent_id)?
+                .ok_or_else(|| anyhow!("{parent} {parent_id} has no uuid"))?;
+            data.insert(column, Value::from(parent_uuid));
+        }
+    }
+
+    let uuid = data
+        .remove("uuid")
+        .and_then(|v| v.as_str().map(String::from))
+        .ok_or_else(|| anyhow!("{table} {id} has no uuid"))?;
+    Ok(Some((uuid, data)))
+}
+
+// ── Push ──
+
+/// Collects everything captured in sync_log since the last push, collapsed to
+/// the latest state of each row. Upserts come parents-first, deletes last.
+pub fn pending_changes(conn: &Connection) -> Result<PendingChanges> {
+    assign_missing_uuids(conn)?;
+
+    let through_log_id: i64 = conn.query_row(
+        "SELECT COALESCE(MAX(id), 0) FROM sync_log WHERE synced = 0",
+        [],
+        |row| row.get(0),
+    )?;
+
+    let mut changes = Vec::new();
+    for table in SYNCED_TABLES {
+        let mut stmt = conn.prepare(
+            "SELECT record_id, MAX(created_at) FROM sync_log
+             WHERE synced = 0 AND id <= ?1 AND table_name = ?2 AND operation != 'delete'
+             GROUP BY record_id
+             ORDER BY MIN(id)",
+        )?;
+        let touched = stmt
+            .query_map(params![through_log_id, table], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (id, changed_at) in touched {
+            // Rows deleted after being touched are covered by their delete entry
+            if let Some((uuid, data)) = read_row(conn, table, id)? {
+                changes.push(Change {
+                    table: table.to_string(),
+                    uuid,
+                    operation: OP_UPSERT.into(),
+                    data: Some(data),
+                    changed_at,
+                });
+            }
+        }
+    }
+
+    for table in SYNCED_TABLES.iter().rev() {
+        let mut stmt = conn.prepare(
+            "SELECT record_uuid, MAX(created_at) FROM sync_log
+             WHERE synced = 0 AND id <= ?1 AND table_name = ?2
+               AND operation = 'delete' AND record_uuid IS NOT NULL
+             GROUP BY record_uuid
+             ORDER BY MIN(id)",
+        )?;
+        let deleted = stmt
+            .query_map(params![through_log_id, table], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        changes.extend(deleted.into_iter().map(|(uuid, changed_at)| Change {
+            table: table.to_string(),
+            uuid,
+            operation: OP_DELETE.into(),
+            data: None,
+            changed_at,
+        }));
+    }
+
+    Ok(PendingChanges { changes, through_log_id })
+}
+
+/// Marks captured changes as delivered and records what was pushed as the
+/// new merge base for each row.
+pub fn mark_pushed(conn: &Connection, pending: &PendingChanges) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let n = tx
+        .execute(
+            "UPDATE sync_log SET synced = 1, sync_completed_at = datetime('now')
+             WHERE synced = 0 AND id <= ?1",
+            [pending.through_log_id],
+        )
+        .context("Failed to mark changes as pushed")?;
+
+    for change in &pending.changes {
+        match &change.data {
+            Some(data) => set_base(&tx, &change.table, &change.uuid, data)?,
+            None => clear_base(&tx, &change.table, &change.uuid)?,
+        }
+    }
+    tx.commit()?;
+    Ok(n)
+}
+
+// ── Merge base ──
+
+fn get_base(conn: &Connection, table: &str, uuid: &str) -> Result<Option<Map<String, Value>>> {
+    let data: Option<String> = conn
+        .query_row(
+            "SELECT data FROM sync_base WHERE table_name = ?1 AND record_uuid = ?2",
+            params![table, uuid],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(data.and_then(|s| serde_json::from_str(&s).ok()))
+}
+
+fn set_base(conn: &Connection, table: &str, uuid: &str, data: &Map<String, Value>) -> Result<()> {
+    conn.execute(
+        "INSERT INTO sync_base (table_name, record_uuid, data) VALUES (?1, ?2, ?3)
+         ON CONFLICT(table_name, record_uuid) DO UPDATE SET data = excluded.data, updated_at = datetime('now')",
+        params![table, uuid, serde_json::to_string(data)?],
+    )?;
+    Ok(())
+}
+
+fn clear_base(conn: &Connection, table: &str, uuid: &str) -> Result<()> {
+    conn.execute(
+        "DELETE FROM sync_base WHERE table_name = ?1 AND record_uuid = ?2",
+        params![table, uuid],
+    )?;
+    Ok(())
+}
+
+// ── Pull ──
+
+/// Applies changes pulled from the server in one transaction. A change that
+/// can't be applied is rolled back and set aside in sync_failed_changes, and
+/// later changes to the same record queue behind it; the rest of the batch
+/// still applies. Set-aside changes are retried at the end of every pull.
+pub fn apply_remote_changes(conn: &Connection, changes: &[Change]) -> Result<ApplyReport> {
+    // Local rows need uuids before remote ones can be matched against them
+    assign_missing_uuids(conn)?;
+
+    let tx = conn.unchecked_transaction()?;
+    set_applying_remote(&tx, true)?;
+
+    let mut report = ApplyReport::default();
+    for change in changes {
+        if has_failed_change(&tx, &change.table, &change.uuid)? {
+            set_aside(&tx, change, "Waiting for an earlier change to this record")?;
+            report.failed += 1;
+            continue;
+        }
+        match apply_in_savepoint(&tx, change, &mut report)? {
+            Ok(()) => {}
+            Err(e) => {
+                set_aside(&tx, change, &e)?;
+                report.failed += 1;
+                report.errors.push(format!("{} {}: {e}", change.table, change.uuid));
+            }
+        }
+    }
+    retry_failed_changes(&tx, &mut report)?;
+
+    set_applying_remote(&tx, false)?;
+    tx.commit()?;
+    Ok(report)
+}
+
+/// Applies one change inside a savepoint, so one that fails partway leaves no
+/// queued conflicts, renumbered cases or sync_control flips behind. The inner
+/// result carries the failure; the outer one is for the savepoint itself.
+fn apply_in_savepoint(
+    conn: &Connection,
+    change: &Change,
+    report: &mut ApplyReport,
+) -> Result<std::result::Result<(), String>> {
+    conn.execute_batch("SAVEPOINT apply_change")?;
+    match apply_change(conn, change) {
+        Ok(result) => {
+            conn.execute_batch("RELEASE apply_change")?;
+            report.applied += 1;
+            if let Some(conflicts) = result {
+                report.merged += 1;
+                report.conflicts += conflicts;
+            }
+            Ok(Ok(()))
+        }
+        Err(e) => {
+            conn.execute_batch("ROLLBACK TO apply_change; RELEASE apply_change")?;
+            Ok(Err(format!("{e:#}")))
+        }
+    }
+}
+
+fn has_failed_change(conn: &Connection, table: &str, uuid: &str) -> Result<bool> {
+    Ok(conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sync_failed_changes WHERE table_name = ?1 AND record_uuid = ?2)",
+        params![table, uuid],
+        |row| row.get(0),
+    )?)
+}
+
+fn set_aside(conn: &Connection, change: &Change, error: &str) -> Result<()> {
+    let data = change.data.as_ref().map(serde_json::to_string).transpose()?;
+    conn.execute(
+        "INSERT INTO sync_failed_changes (table_name, record_uuid, operation, data, changed_at, error)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![change.table, change.uuid, change.operation, data, change.changed_at, error],
+    )
+    .context("Failed to set aside sync change")?;
+    Ok(())
+}
+
+/// Retries set-aside changes oldest first. A change that still fails blocks
+/// the later ones for its record until it goes through or is discarded.
+fn retry_failed_changes(conn: &Connection, report: &mut ApplyReport) -> Result<()> {
+    let waiting = {
+        let mut stmt = conn.prepare(
+            "SELECT id, table_name, record_uuid, operation, data, changed_at
+             FROM sync_failed_changes ORDER BY id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                let data: Option<String> = row.get(4)?;
+                let change = Change {
+                    table: row.get(1)?,
+                    uuid: row.get(2)?,
+                    operation: row.get(3)?,
+                    data: data.and_then(|d| serde_json::from_str(&d).ok()),
+                    changed_at: row.get(5)?,
+                };
+                Ok((row.get::<_, i64>(0)?, change))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows
+    };
+
+    let mut blocked = std::collections::HashSet::new();
+    for (id, change) in waiting {
+        if blocked.contains(&(change.table.clone(), change.uuid.clone())) {
+            continue;
+        }
+        match apply_in_savepoint(conn, &change, report)? {
+            Ok(()) => {
+                conn.execute("DELETE FROM sync_failed_changes WHERE id = ?1", [id])?;
+            }
+            Err(e) => {
+                conn.execute(
+                    "UPDATE sync_failed_changes
+                     SET error = ?1, attempts = attempts + 1, last_failed_at = datetime('now')
+                     WHERE id = ?2",
+                    params![e, id],
+                )?;
+                blocked.insert((change.table, change.uuid));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns `Some(queued conflicts)` when the change had to be merged with
+/// unpushed local edits, `None` when it applied cleanly.
+fn apply_change(conn: &Connection, change: &Change) -> Result<Option<usize>> {
+    let table = change.table.as_str();
+    ensure_synced(table)?;
+
+    if change.operation == OP_DELETE {
+        // Unpushed local edits win over a remote delete: the row stays, and
+        // pushing the edit recreates it on the devices that deleted it
+        if let Some(id) = id_for_uuid(conn, table, &change.uuid)? {
+            if pending_local_change(conn, table, id)?.is_some() {
+                return Ok(None);
+            }
+        }
+        conn.execute(&format!("DELETE FROM {table} WHERE uuid = ?1"), [&change.uuid])?;
+        clear_base(conn, table, &change.uuid)?;
+        return Ok(None);
+    }
+    if change.operation != OP_UPSERT {
+        bail!("Unknown operation {}", change.operation);
+    }
+
+    let remote = change
+        .data
+        .clone()
+        .ok_or_else(|| anyhow!("Upsert without data"))?;
+    let local_id = id_for_uuid(conn, table, &change.uuid)?;
+
+    let mut data = remote.clone();
+    let mut merged = None;
+    if let Some(id) = local_id {
+        if let Some(local_changed_at) = pending_local_change(conn, table, id)? {
+            let (_, local) = read_row(conn, table, id)?
+                .ok_or_else(|| anyhow!("{table} {id} disappeared during merge"))?;
+            let base = get_base(conn, table, &change.uuid)?;
+            let local_time = timestamp(&local).unwrap_or(local_changed_at);
+            let remote_time = timestamp(&remote).unwrap_or_else(|| change.changed_at.clone());
+
+            let merge = merge_fields(table, base.as_ref(), &local, &remote, local_time > remote_time);
+            for (field, local_value, remote_value) in &merge.conflicts {
+                queue_conflict(conn, table, id, &change.uuid, field, local_value, remote_value, &local_time, &remote_time)?;
+            }
+            merged = Some(merge.conflicts.len());
+            data = merge.data;
+        }
+    }
+
+    for (column, parent) in synced_foreign_keys(conn, table)? {
+        if let Some(parent_uuid) = data.get(&column).and_then(Value::as_str).map(String::from) {
+            let parent_id = id_for_uuid(conn, &parent, &parent_uuid)?
+                .ok_or_else(|| anyhow!("Unknown {parent} {parent_uuid}"))?;
+            data.insert(column, Value::from(parent_id));
+        }
+    }
+
+    if table == "incidents" {
+        resolve_case_number_clash(conn, &mut data, local_id, &change.uuid)?;
+    }
+
+    // Only columns this schema knows about; ignores anything newer peers send
+    let known = table_columns(conn, table)?;
+    let (columns, values): (Vec<&String>, Vec<SqlValue>) = data
+        .iter()
+        .filter(|(name, _)| *name != "id" && *name != "uuid" && known.contains(name))
+        .map(|(name, value)| (name, json_to_sql(value)))
+        .unzip();
+    if columns.is_empty() {
+        bail!("No known columns in change");
+    }
+
+    match local_id {
+        Some(id) => {
+            let sets: Vec<String> = columns
+                .iter()
+                .enumerate()
+                .map(|(i, c)| format!("{c} = ?{}", i + 1))
+                .collect();
+            let sql = format!(
+                "UPDATE {table} SET {} WHERE id = ?{}",
+                sets.join(", "),
+                columns.len() + 1
+            );
+            let mut values = values;
+            values.push(SqlValue::Integer(id));
+            conn.execute(&sql, params_from_iter(values))?;
+        }
+        None => {
+            let names: Vec<&str> = columns.iter().map(|c| c.as_str()).collect();
+            let placeholders: Vec<String> = (1..=columns.len() + 1).map(|i| format!("?{i}")).collect();
+            let sql = format!(
+                "INSERT INTO {table} ({}, uuid) VALUES ({})",
+                names.join(", "),
+                placeholders.join(", ")
+            );
+            let mut values = values;
+            values.push(SqlValue::Text(change.uuid.clone()));
+            conn.execute(&sql, params_from_iter(values))?;
+        }
+    }
+
+    // The server now holds the remote version; merged local edits stay
+    // queued in sync_log and go out on the next push
+    set_base(conn, table, &change.uuid, &remote)?;
+    Ok(merged)
+}
+
+/// Two devices numbering offline can hand out the same case number. The case
+/// created first (uuid breaking ties) keeps it and the other moves to the end
+/// of the year. Every device makes the same choice, and a local case that
+/// gives up its number is queued for push, so all copies settle on one set.
+fn resolve_case_number_clash(
+    conn: &Connection,
+    data: &mut Map<String, Value>,
+    local_id: Option<i64>,
+    uuid: &str,
+) -> Result<()> {
+    let establishment_id = data.get("establishment_id").and_then(Value::as_i64);
+    let date = data.get("incident_date").and_then(Value::as_str);
+    let number = data.get("case_number").and_then(Value::as_i64);
+    let (Some(establishment_id), Some(date), Some(number)) = (establishment_id, date, number) else {
+        return Ok(());
+    };
+    let year = date.get(..4).unwrap_or(date).to_string();
+
+    let holder: Option<(i64, String, String)> = conn
+        .query_row(
+            "SELECT id, COALESCE(uuid, ''), created_at FROM incidents
+             WHERE establishment_id = ?1 AND substr(incident_date, 1, 4) = ?2 AND case_number = ?3
+               AND id IS NOT ?4",
+            params![establishment_id, year, number, local_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?;
+    let Some((holder_id, holder_uuid, holder_created)) = holder else {
+        return Ok(());
+    };
+
+    let created = data.get("created_at").and_then(Value::as_str).unwrap_or_default();
+    let (next, label) = incidents::next_case_number(conn, establishment_id, &year)?;
+    if (created, uuid) < (holder_created.as_str(), holder_uuid.as_str()) {
+        set_applying_remote(conn, false)?;
+        conn.execute(
+            "UPDATE incidents SET case_number = ?1, case_label = ?2, case_number_duplicate = 0,
+                    updated_at = datetime('now')
+             WHERE id = ?3",
+            params![next, label, holder_id],
+        )?;
+        set_applying_remote(conn, true)?;
+    } else {
+        data.insert("case_number".into(), Value::from(next));
+        data.insert("case_label".into(), Value::from(label));
+        data.insert("case_number_duplicate".into(), Value::from(0));
+    }
+    Ok(())
+}
+
+/// When the row has unpushed local edits, the time of the latest one.
+fn pending_local_change(conn: &Connection, table: &str, id: i64) -> Result<Option<String>> {
+    let changed_at = conn.query_row(
+        "SELECT MAX(created_at) FROM sync_log
+         WHERE synced = 0 AND table_name = ?1 AND record_id = ?2 AND operation != 'delete'",
+        params![table, id],
+        |row| row.get(0),
+    )?;
+    Ok(changed_at)
+}
+
+fn timestamp(row: &Map<String, Value>) -> Option<String> {
+    row.get("updated_at").and_then(Value::as_str).map(String::from)
+}
+
+// ── Field-level merge ──
+
+/// Fields where an automatic pick could change what lands on the OSHA 300
+/// log. When both sides changed one of these, a person decides.
+const CRITICAL_FIELDS: &[(&str, &[&str])] = &[(
+    "incidents",
+    &[
+        "incident_date",
+        "is_privacy_case",
+        "outcome_severity",
+        "days_away_count",
+        "days_restricted_count",
+        "date_of_death",
+        "injury_illness_type",
+        "is_recordable",
+        "recordability_reason",
+    ],
+)];
+
+pub fn is_critical_field(table: &str, field: &str) -> bool {
+    CRITICAL_FIELDS
+        .iter()
+        .any(|(t, fields)| *t == table && fields.contains(&field))
+}
+
+struct Merge {
+    data: Map<String, Value>,
+    /// (field, local value, remote value) left at the local value for review
+    conflicts: Vec<(String, Value, Value)>,
+}
+
+/// Three-way merge of a row against the last version both sides agreed on.
+/// A field changed on one side only takes that side's value. A field changed
+/// on both sides goes to the newer row by `updated_at`, unless it is
+/// OSHA-critical, in which case the local value stays and a conflict is
+/// queued. Without a base every differing field counts as changed on both.
+fn merge_fields(
+    table: &str,
+    base: Option<&Map<String, Value>>,
+    local: &Map<String, Value>,
+    remote: &Map<String, Value>,
+    local_is_newer: bool,
+) -> Merge {
+    let mut data = local.clone();
+    let mut conflicts = Vec::new();
+
+    for (field, remote_value) in remote {
+        let local_value = local.get(field).unwrap_or(&Value::Null);
+        if field == "updated_at" || local_value == remote_value {
+            continue;
+        }
+
+        let base_value = base.and_then(|b| b.get(field));
+        let local_changed = base_value.is_none_or(|b| b != local_value);
+        let remote_changed = base_value.is_none_or(|b| b != remote_value);
+
+        if !local_changed {
+            data.insert(field.clone(), remote_value.clone());
+        } else if !remote_changed {
+            // Keep the local edit
+        } else if is_critical_field(table, field) {
+            conflicts.push((field.clone(), local_value.clone(), remote_value.clone()));
+        } else if !local_is_newer {
+            data.insert(field.clone(), remote_value.clone());
+        }
+    }
+
+    if !local_is_newer {
+        if let Some(updated) = remote.get("updated_at") {
+            data.insert("updated_at".into(), updated.clone());
+        }
+    }
+
+    Merge { data, conflicts }
+}
+
+// ── Conflict queue ──
+
+pub const RESOLUTIONS: &[&str] = &["local", "remote", "custom"];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SyncConflict {
+    pub id: i64,
+    pub table_name: String,
+    pub record_id: i64,
+    pub record_uuid: String,
+    pub field_name: String,
+    pub local_value: Value,
+    pub remote_value: Value,
+    pub local_updated_at: Option<String>,
+    pub remote_updated_at: Option<String>,
+    pub status: String,
+    pub resolution: Option<String>,
+    pub resolved_value: Option<Value>,
+    pub resolved_by: Option<i64>,
+    pub resolved_by_name: Option<String>,
+    pub resolved_at: Option<String>,
+    pub created_at: String,
+}
+
+const CONFLICT_COLS: &str = "c.id, c.table_name, c.record_id, c.record_uuid, c.field_name,
+    c.local_value, c.remote_value, c.local_updated_at, c.remote_updated_at, c.status,
+    c.resolution, c.resolved_value, c.resolved_by, u.display_name, c.resolved_at, c.created_at";
+
+fn parse_json(text: Option<String>) -> Value {
+    text.and_then(|s| serde_json::from_str(&s).ok()).unwrap_or(Value::Null)
+}
+
+fn row_to_conflict(row: &rusqlite::Row<'_>) -> rusqlite::Result<SyncConflict> {
+    let resolved_value: Option<String> = row.get(11)?;
+    Ok(SyncConflict {
+        id: row.get(0)?,
+        table_name: row.get(1)?,
+        record_id: row.get(2)?,
+        record_uuid: row.get(3)?,
+        field_name: row.get(4)?,
+        local_value: parse_json(row.get(5)?),
+        remote_value: parse_json(row.get(6)?),
+        local_updated_at: row.get(7)?,
+        remote_updated_at: row.get(8)?,
+        status: row.get(9)?,
+        resolution: row.get(10)?,
+        resolved_value: resolved_value.map(|s| parse_json(Some(s))),
+        resolved_by: row.get(12)?,
+        resolved_by_name: row.get(13)?,
+        resolved_at: row.get(14)?,
+        created_at: row.get(15)?,
+    })
+}
+
+/// Queues a conflict, or refreshes the remote side of one already open for
+/// the same field.
+#[allow(clippy::too_many_arguments)]
+fn queue_conflict(
+    conn: &Connection,
+    table: &str,
+    record_id: i64,
+    uuid: &str,
+    field: &str,
+    local_value: &Value,
+    remote_value: &Value,
+    local_updated_at: &str,
+    remote_updated_at: &str,
+) -> Result<()> {
+    let updated = conn.execute(
+        "UPDATE sync_conflicts SET local_value = ?1, remote_value = ?2,
+                local_updated_at = ?3, remote_updated_at = ?4
+         WHERE status = 'open' AND table_name = ?5 AND record_id = ?6 AND field_name = ?7",
+        params![
+            local_value.to_string(),
+            remote_value.to_string(),
+            local_updated_at,
+            remote_updated_at,
+            table,
+            record_id,
+            field,
+        ],
+    )?;
+
+    if updated == 0 {
+        conn.execute(
+            "INSERT INTO sync_conflicts (table_name, record_id, record_uuid, field_name,
+                 local_value, remote_value, local_updated_at, remote_updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                table,
+                record_id,
+                uuid,
+                field,
+                local_value.to_string(),
+                remote_value.to_string(),
+                local_updated_at,
+                remote_updated_at,
+            ],
+        )
+        .context("Failed to queue sync conflict")?;
+    }
+    Ok(())
+}
+
+pub fn get_conflict(conn: &Connection, id: i64) -> Result<SyncConflict> {
+    conn.query_row(
+        &format!(
+            "SELECT {CONFLICT_COLS} FROM sync_conflicts c
+             LEFT JOIN users u ON c.resolved_by = u.id
+             WHERE c.id = ?1"
+        ),
+        [id],
+        row_to_conflict,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            AppError::NotFound(format!("Sync conflict {id} not found")).into()
+        }
+        _ => anyhow::Error::new(e),
+    })
+}
+
+/// Conflicts with the given status (all when `None`), oldest first.
+pub fn list_conflicts(conn: &Connection, status: Option<&str>) -> Result<Vec<SyncConflict>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {CONFLICT_COLS} FROM sync_conflicts c
+         LEFT JOIN users u ON c.resolved_by = u.id
+         WHERE ?1 IS NULL OR c.status = ?1
+         ORDER BY c.id"
+    ))?;
+    let rows = stmt
+        .query_map([status], row_to_conflict)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Settles a queued conflict by keeping the local value, taking the remote
+/// one or entering a corrected value. The record is updated like any local
+/// edit, so the outcome syncs to other devices, and the decision is written
+/// to the audit log against the record.
+pub fn resolve_conflict(
+    conn: &Connection,
+    actor: &Actor,
+    id: i64,
+    resolution: &str,
+    custom_value: Option<Value>,
+) -> Result<SyncConflict> {
+    let conflict = get_conflict(conn, id)?;
+    if conflict.status != "open" {
+        return Err(AppError::Validation(format!("Sync conflict {id} is already resolved")).into());
+    }
+
+    let value = match resolution {
+        "local" => conflict.local_value.clone(),
+        "remote" => conflict.remote_value.clone(),
+        "custom" => custom_value
+            .ok_or_else(|| AppError::Validation("A custom resolution needs a value".into()))?,
+        other => {
+            return Err(AppError::Validation(format!(
+                "Invalid resolution: {other}. Must be one of: {}",
+                RESOLUTIONS.join(", ")
+            ))
+            .into())
+        }
+    };
+    if value.is_object() || value.is_array() {
+        return Err(AppError::Validation("Resolved value must be a single value".into()).into());
+    }
+
+    let table = conflict.table_name.as_str();
+    let field = conflict.field_name.as_str();
+    ensure_synced(table)?;
+    let columns = table_columns(conn, table)?;
+    if !columns.iter().any(|c| c == field) {
+        bail!("{table} has no column {field}");
+    }
+
+    let tx = conn.unchecked_transaction()?;
+
+    let current: Option<Value> = tx
+        .query_row(&format!("SELECT {field} FROM {table} WHERE id = ?1"), [conflict.record_id], |row| {
+            Ok(sql_to_json(row.get_ref(0)?).ok())
+        })
+        .optional()?
+        .flatten();
+
+    // A record deleted since the conflict was queued has nothing to update
+    if current.is_some() {
+        let touch = if columns.iter().any(|c| c == "updated_at") {
+            ", updated_at = datetime('now')"
+        } else {
+            ""
+        };
+        tx.execute(
+            &format!("UPDATE {table} SET {field} = ?1{touch} WHERE id = ?2"),
+            params![json_to_sql(&value), conflict.record_id],
+        )
+        .with_context(|| format!("Failed to update {table} {}", conflict.record_id))?;
+    }
+
+    tx.execute(
+        "UPDATE sync_conflicts SET status = 'resolved', resolution = ?1, resolved_value = ?2,
+                resolved_by = ?3, resolved_at = datetime('now')
+         WHERE id = ?4",
+        params![resolution, value.to_string(), actor.user_id, id],
+    )?;
+
+    let mut old = Map::new();
+    old.insert(field.to_string(), current.unwrap_or(Value::Null));
+    let mut new = Map::new();
+    new.insert(field.to_string(), value);
+    audit::record(&tx, actor, "resolve_conflict", table, conflict.record_id, Some(&old), Some(&new))?;
+
+    tx.commit()?;
+    get_conflict(conn, id)
+}
+
+// ── Failed changes ──
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SyncFailure {
+    pub id: i64,
+    pub table_name: String,
+    pub record_uuid: String,
+    pub operation: String,
+    pub data: Value,
+    pub changed_at: String,
+    pub error: String,
+    pub attempts: i64,
+    pub first_failed_at: String,
+    pub last_failed_at: String,
+}
+
+const FAILURE_COLS: &str = "id, table_name, record_uuid, operation, data, changed_at, error,
+    attempts, first_failed_at, last_failed_at";
+
+fn row_to_failure(row: &rusqlite::Row<'_>) -> rusqlite::Result<SyncFailure> {
+    Ok(SyncFailure {
+        id: row.get(0)?,
+        table_name: row.get(1)?,
+        record_uuid: row.get(2)?,
+        operation: row.get(3)?,
+        data: parse_json(row.get(4)?),
+        changed_at: row.get(5)?,
+        error: row.get(6)?,
+        attempts: row.get(7)?,
+        first_failed_at: row.get(8)?,
+        last_failed_at: row.get(9)?,
+    })
+}
+
+pub fn get_failed_change(conn: &Connection, id: i64) -> Result<SyncFailure> {
+    conn.query_row(
+        &format!("SELECT {FAILURE_COLS} FROM sync_failed_changes WHERE id = ?1"),
+        [id],
+        row_to_failure,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            AppError::NotFound(format!("Failed sync change {id} not found")).into()
+        }
+        _ => anyhow::Error::new(e),
+    })
+}
+
+/// Remote changes waiting to be retried, oldest first.
+pub fn list_failed_changes(conn: &Connection) -> Result<Vec<SyncFailure>> {
+    let mut stmt = conn.prepare(&format!("SELECT {FAILURE_COLS} FROM sync_failed_changes ORDER BY id"))?;
+    let rows = stmt.query_map([], row_to_failure)?.collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Drops a remote change that will never apply, so the changes queued behind
+/// it for the same record can go through on the next pull. The dropped change
+/// is kept in the audit log.
+pub fn discard_failed_change(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    let failure = get_failed_change(conn, id)?;
+    super::immediate_transaction(conn, |conn| {
+        conn.execute("DELETE FROM sync_failed_changes WHERE id = ?1", [id])?;
+        let old = serde_json::to_value(&failure)?;
+        audit::record(conn, actor, "discard_sync_change", "sync_failed_changes", id, old.as_object(), None)?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::locations::{self, CreateEstablishment, CreateLocation, UpdateLocation};
+    use crate::db::open_test_db;
+
+    fn establishment(conn: &Connection, name: &str) -> i64 {
+        locations::create_establishment(conn, &Actor::system(), CreateEstablishment {
+            name: name.into(),
+            street_address: None, city: None, state: None,
+            zip_code: None, industry_description: None, naics_code: None,
+            ein: None, company_name: None, establishment_type: None, case_number_format: None,
+        })
+        .unwrap()
+        .id
+    }
+
+    #[test]
+    fn test_every_synced_table_is_captured() {
+        let conn = open_test_db();
+        for table in SYNCED_TABLES {
+            for op in ["insert", "update", "delete"] {
+                let exists: bool = conn
+                    .query_row(
+                        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'trigger' AND name = ?1)",
+                        [format!("sync_{table}_{op}")],
+                        |row| row.get(0),
+                    )
+                    .unwrap();
+                assert!(exists, "missing {op} trigger on {table}");
+            }
+            assert!(table_columns(&conn, table).unwrap().contains(&"uuid".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_changes_round_trip_between_devices() {
+        let a = open_test_db();
+        let b = open_test_db();
+
+        // Give device B a row first so integer ids differ between the two
+        establishment(&b, "Only On B");
+        let est = establishment(&a, "Shared Site");
+        let loc = locations::create_location(&a, &Actor::system(), CreateLocation {
+            establishment_id: est,
+            name: "Level 3".into(),
+            address: None, city: None, state: None,
+        })
+        .unwrap();
+
+        // Seeded training courses are queued too and merge by natural key
+        let pending = pending_changes(&a).unwrap();
+        let tables: Vec<&str> = pending
+            .changes
+            .iter()
+            .map(|c| c.table.as_str())
+            .filter(|t| *t != "training_courses")
+            .collect();
+        assert_eq!(tables, ["establishments", "locations"]);
+        let report = apply_remote_changes(&b, &pending.changes).unwrap();
+        assert_eq!(report.applied, pending.changes.len(), "{:?}", report.errors);
+        mark_pushed(&a, &pending).unwrap();
+        assert!(pending_changes(&a).unwrap().changes.is_empty());
+
+        // The location landed under B's id for the establishment, and applying
+        // remote changes queued nothing for B to push back
+        let (b_est, b_parent): (i64, i64) = b
+            .query_row(
+                "SELECT e.id, l.establishment_id FROM locations l
+                 JOIN establishments e ON e.name = 'Shared Site'
+                 WHERE l.name = 'Level 3'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(b_est, b_parent);
+        assert_ne!(b_est, est);
+        let b_pending: Vec<Change> = pending_changes(&b)
+            .unwrap()
+            .changes
+            .into_iter()
+            .filter(|c| c.table == "establishments")
+            .collect();
+        assert_eq!(b_pending.len(), 1);
+        assert_eq!(b_pending[0].data.as_ref().unwrap()["name"], "Only On B");
+
+        // Updates and deletes follow the uuid
+        let update: UpdateLocation = serde_json::from_value(serde_json::json!({ "name": "Level 4" })).unwrap();
+        locations::update_location(&a, &Actor::system(), loc.id, update).unwrap();
+        let pending = pending_changes(&a).unwrap();
+        apply_remote_changes(&b, &pending.changes).unwrap();
+        mark_pushed(&a, &pending).unwrap();
+        let name: String = b
+            .query_row("SELECT name FROM locations WHERE uuid IS NOT NULL AND establishment_id = ?1", [b_est], |r| r.get(0))
+            .unwrap();
+        assert_eq!(name, "Level 4");
+
+        // Moving to the trash travels as an update, the purge as a delete
+        locations::delete_location(&a, &Actor::system(), loc.id).unwrap();
+        let pending = pending_changes(&a).unwrap();
+        apply_remote_changes(&b, &pending.changes).unwrap();
+        mark_pushed(&a, &pending).unwrap();
+        let trashed: Option<String> = b
+            .query_row("SELECT deleted_at FROM locations WHERE establishment_id = ?1", [b_est], |r| r.get(0))
+            .unwrap();
+        assert!(trashed.is_some());
+
+        locations::purge_location(&a, &Actor::system(), loc.id).unwrap();
+        let pending = pending_changes(&a).unwrap();
+        assert_eq!(pending.changes[0].operation, OP_DELETE);
+        apply_remote_changes(&b, &pending.changes).unwrap();
+        let count: i64 = b.query_row("SELECT COUNT(*) FROM locations", [], |r| r.get(0)).unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_remote_delete_keeps_row_with_unpushed_edits() {
+        let a = open_test_db();
+        let b = open_test_db();
+        let est = establishment(&a, "Shared Site");
+        let loc = locations::create_location(&a, &Actor::system(), CreateLocation {
+            establishment_id: est,
+            name: "Level 3".into(),
+            address: None, city: None, state: None,
+        })
+        .unwrap();
+        let pending = pending_changes(&a).unwrap();
+        apply_remote_changes(&b, &pending.changes).unwrap();
+        mark_pushed(&a, &pending).unwrap();
+
+        // B renames the location offline while A purges it
+        let b_loc: i64 = b.query_row("SELECT id FROM locations", [], |r| r.get(0)).unwrap();
+        let update: UpdateLocation = serde_json::from_value(serde_json::json!({ "name": "Level 3 East" })).unwrap();
+        locations::update_location(&b, &Actor::system(), b_loc, update).unwrap();
+        locations::delete_location(&a, &Actor::system(), loc.id).unwrap();
+        locations::purge_location(&a, &Actor::system(), loc.id).unwrap();
+
+        let pending = pending_changes(&a).unwrap();
+        let report = apply_remote_changes(&b, &pending.changes).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        mark_pushed(&a, &pending).unwrap();
+        let name: String = b.query_row("SELECT name FROM locations WHERE id = ?1", [b_loc], |r| r.get(0)).unwrap();
+        assert_eq!(name, "Level 3 East");
+
+        // B's edit then brings the row back on A
+        let pending = pending_changes(&b).unwrap();
+        apply_remote_changes(&a, &pending.changes).unwrap();
+        let name: String = a.query_row("SELECT name FROM locations", [], |r| r.get(0)).unwrap();
+        assert_eq!(name, "Level 3 East");
+    }
+
+    #[test]
+    fn test_natural_keys_converge_and_bad_changes_are_set_aside() {
+        let a = open_test_db();
+        let b = open_test_db();
+
+        // Seeded courses get the same uuid on every device
+        assign_missing_uuids(&a).unwrap();
+        assign_missing_uuids(&b).unwrap();
+        let uuid_of = |conn: &Connection| -> String {
+            conn.query_row("SELECT uuid FROM training_courses ORDER BY id LIMIT 1", [], |r| r.get(0)).unwrap()
+        };
+        assert_eq!(uuid_of(&a), uuid_of(&b));
+
+        let bad = vec![
+            Change {
+                table: "users".into(), uuid: "x".into(), operation: OP_DELETE.into(),
+                data: None, changed_at: "2026-01-01 00:00:00".into(),
+            },
+            Change {
+                table: "locations".into(), uuid: "y".into(), operation: OP_UPSERT.into(),
+                data: Some(serde_json::from_value(serde_json::json!({
+                    "establishment_id": "no-such-uuid", "name": "Orphan",
+                })).unwrap()),
+                changed_at: "2026-01-01 00:00:00".into(),
+            },
+        ];
+        let report = apply_remote_changes(&b, &bad).unwrap();
+        assert_eq!(report.applied, 0);
+        assert_eq!(report.errors.len(), 2);
+        assert_eq!(report.failed, 2);
+        assert_eq!(get_sync_status(&b).unwrap().failed_changes, 2);
+
+        let orphans: i64 = b.query_row("SELECT COUNT(*) FROM locations WHERE name = 'Orphan'", [], |r| r.get(0)).unwrap();
+        assert_eq!(orphans, 0);
+        let applying: bool = b.query_row("SELECT applying_remote FROM sync_control", [], |r| r.get(0)).unwrap();
+        assert!(!applying);
+
+        // Every pull retries them; a discard clears one for good
+        let attempts = list_failed_changes(&b).unwrap()[0].attempts;
+        apply_remote_changes(&b, &[]).unwrap();
+        let failures = list_failed_changes(&b).unwrap();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].table_name, "users");
+        assert_eq!(failures[0].attempts, attempts + 1);
+        discard_failed_change(&b, &Actor::system(), failures[0].id).unwrap();
+        assert_eq!(list_failed_changes(&b).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_failed_change_holds_its_record_and_applies_once_unblocked() {
+        let b = open_test_db();
+        let upsert = |table: &str, uuid: &str, data: serde_json::Value| Change {
+            table: table.into(),
+            uuid: uuid.into(),
+            operation: OP_UPSERT.into(),
+            data: Some(serde_json::from_value(data).unwrap()),
+            changed_at: "2026-01-01 00:00:00".into(),
+        };
+
+        // The location arrives before its establishment, then is renamed; an
+        // unrelated establishment later in the batch still applies
+        let batch = vec![
+            upsert("locations", "loc-1", serde_json::json!({ "establishment_id": "est-1", "name": "Bay 1" })),
+            upsert("locations", "loc-1", serde_json::json!({ "establishment_id": "est-1", "name": "Bay 1 North" })),
+            upsert("establishments", "est-2", serde_json::json!({ "name": "Other Yard" })),
+        ];
+        let report = apply_remote_changes(&b, &batch).unwrap();
+        assert_eq!(report.applied, 1);
+        assert_eq!(report.failed, 2);
+        let other: i64 = b.query_row("SELECT COUNT(*) FROM establishments WHERE uuid = 'est-2'", [], |r| r.get(0)).unwrap();
+        assert_eq!(other, 1);
+
+        // Once the parent arrives, both location changes apply in order
+        let report = apply_remote_changes(&b, &[upsert("establishments", "est-1", serde_json::json!({ "name": "Main Yard" }))]).unwrap();
+        assert_eq!(report.applied, 3);
+        assert!(list_failed_changes(&b).unwrap().is_empty());
+        let name: String = b.query_row("SELECT name FROM locations WHERE uuid = 'loc-1'", [], |r| r.get(0)).unwrap();
+        assert_eq!(name, "Bay 1 North");
+    }
+
+    #[test]
+    fn test_merge_fields() {
+        let obj = |v: serde_json::Value| v.as_object().unwrap().clone();
+        let base = obj(serde_json::json!({ "description": "Slip", "where_occurred": "Dock", "days_away_count": 0 }));
+        let local = obj(serde_json::json!({ "description": "Slip on ice", "where_occurred": "Dock", "days_away_count": 2 }));
+        let remote = obj(serde_json::json!({ "description": "Slip on oil", "where_occurred": "Bay 2", "days_away_count": 5 }));
+
+        // One-sided edits merge; a two-sided text edit goes to the newer row;
+        // a two-sided OSHA field keeps the local value and is queued
+        let merged = merge_fields("incidents", Some(&base), &local, &remote, false);
+        assert_eq!(merged.data["where_occurred"], "Bay 2");
+        assert_eq!(merged.data["description"], "Slip on oil");
+        assert_eq!(merged.data["days_away_count"], 2);
+        assert_eq!(merged.conflicts, [("days_away_count".to_string(), 2.into(), 5.into())]);
+
+        let merged = merge_fields("incidents", Some(&base), &local, &remote, true);
+        assert_eq!(merged.data["description"], "Slip on ice");
+
+        // Outside the critical list, both-sided edits never queue
+        let merged = merge_fields("locations", Some(&base), &local, &remote, true);
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_offline_incident_edits_merge_and_conflicts_resolve() {
+        use crate::db::incidents::{self, UpdateIncident};
+
+        let a = open_test_db();
+        let b = open_test_db();
+        let est = establishment(&a, "Tower Crane Job");
+        a.execute(
+            "INSERT INTO incidents (establishment_id, employee_name, incident_date, description)
+             VALUES (?1, 'Sam Rigger', '2026-03-04', 'Struck by load')",
+            [est],
+        )
+        .unwrap();
+        let inc: i64 = a.query_row("SELECT id FROM incidents", [], |r| r.get(0)).unwrap();
+
+        let pending = pending_changes(&a).unwrap();
+        apply_remote_changes(&b, &pending.changes).unwrap();
+        mark_pushed(&a, &pending).unwrap();
+        let b_inc: i64 = b.query_row("SELECT id FROM incidents", [], |r| r.get(0)).unwrap();
+        let b_pending = pending_changes(&b).unwrap();
+        mark_pushed(&b, &b_pending).unwrap();
+
+        // Both devices edit the same incident offline
+        let edit = |v: serde_json::Value| -> UpdateIncident { serde_json::from_value(v).unwrap() };
+        incidents::update_incident(&a, &Actor::system(), inc, edit(serde_json::json!({
+            "description": "Struck by swinging load",
+            "outcome_severity": "days_away",
+        }))).unwrap();
+        incidents::update_incident(&b, &Actor::system(), b_inc, edit(serde_json::json!({
+            "where_occurred": "Laydown yard",
+            "outcome_severity": "job_transfer",
+        }))).unwrap();
+
+        // B syncs first, then A pulls B's version
+        let from_b = pending_changes(&b).unwrap();
+        mark_pushed(&b, &from_b).unwrap();
+        let report = apply_remote_changes(&a, &from_b.changes).unwrap();
+        assert_eq!((report.merged, report.conflicts), (1, 1), "{:?}", report.errors);
+
+        let row = |conn: &Connection, id: i64| -> (String, Option<String>, String) {
+            conn.query_row(
+                "SELECT description, where_occurred, outcome_severity FROM incidents WHERE id = ?1",
+                [id],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            row(&a, inc),
+            ("Struck by swinging load".into(), Some("Laydown yard".into()), "days_away".into())
+        );
+
+        let open = list_conflicts(&a, Some("open")).unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].field_name, "outcome_severity");
+        assert_eq!(open[0].local_value, "days_away");
+        assert_eq!(open[0].remote_value, "job_transfer");
+        assert_eq!(get_sync_status(&a).unwrap().open_conflicts, 1);
+
+        let resolved = resolve_conflict(&a, &Actor::user(1), open[0].id, "remote", None).unwrap();
+        assert_eq!(resolved.status, "resolved");
+        assert_eq!(resolved.resolved_by_name.as_deref(), Some("System Administrator"));
+        assert!(resolve_conflict(&a, &Actor::user(1), open[0].id, "local", None).is_err());
+        assert_eq!(row(&a, inc).2, "job_transfer");
+
+        let history = audit::get_record_history(&a, "incidents", inc).unwrap();
+        let last = history.last().unwrap();
+        assert_eq!(last.action, "resolve_conflict");
+        assert_eq!(last.changed_fields, ["outcome_severity"]);
+
+        // A's merged row goes out and B converges on it
+        let from_a = pending_changes(&a).unwrap();
+        apply_remote_changes(&b, &from_a.changes).unwrap();
+        assert_eq!(row(&b, b_inc), row(&a, inc));
+    }
+
+    #[test]
+    fn test_offline_case_number_clash_converges() {
+        let a = open_test_db();
+        let b = open_test_db();
+        let est = establishment(&a, "Tower Crane Job");
+        let setup = pending_changes(&a).unwrap();
+        apply_remote_changes(&b, &setup.changes).unwrap();
+        mark_pushed(&a, &setup).unwrap();
+        let b_est: i64 = b.query_row("SELECT id FROM establishments", [], |r| r.get(0)).unwrap();
+
+        // Both devices hand out case 1 offline; B's case was logged first
+        let add = |conn: &Connection, est: i64, name: &str, created: &str| {
+            conn.execute(
+                "INSERT INTO incidents (case_number, case_label, establishment_id, employee_name, incident_date,
+                                        description, created_at)
+                 VALUES (1, '1', ?1, ?2, '2026-03-04', 'Struck by load', ?3)",
+                params![est, name, created],
+            )
+            .unwrap();
+        };
+        add(&a, est, "Sam Rigger", "2026-03-04 10:00:00");
+        add(&b, b_est, "Lee Signal", "2026-03-04 09:00:00");
+
+        let from_a = pending_changes(&a).unwrap();
+        let from_b = pending_changes(&b).unwrap();
+        mark_pushed(&a, &from_a).unwrap();
+        mark_pushed(&b, &from_b).unwrap();
+        assert!(apply_remote_changes(&a, &from_b.changes).unwrap().errors.is_empty());
+        assert!(apply_remote_changes(&b, &from_a.changes).unwrap().errors.is_empty());
+
+        // A gave up its number and pushes the change; B already agrees
+        let renumbered = pending_changes(&a).unwrap();
+        assert_eq!(renumbered.changes.len(), 1);
+        assert!(apply_remote_changes(&b, &renumbered.changes).unwrap().errors.is_empty());
+
+        let numbers = |conn: &Connection| -> Vec<(String, i64)> {
+            let mut stmt = conn
+                .prepare("SELECT employee_name, case_number FROM incidents ORDER BY case_number")
+                .unwrap();
+            stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap()
+        };
+        let expected = vec![("Lee Signal".to_string(), 1), ("Sam Rigger".to_string(), 2)];
+        assert_eq!(numbers(&a), expected);
+        assert_eq!(numbers(&b), expected);
+    }
+}
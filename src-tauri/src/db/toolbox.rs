@@ -1,6 +1,9 @@
 use rusqlite::{params, Connection, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::db::audit::{self, Actor};
+use crate::db::trash;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ToolboxTalkTopic {
     pub id: i64,
@@ -23,6 +26,9 @@ pub struct ToolboxTalk {
     pub conducted_by: String,
     pub notes: Option<String>,
     pub status: String,
+    /// Set while the talk is in the trash
+    pub deleted_at: Option<String>,
+    pub deleted_by: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -105,28 +111,37 @@ pub fn get_topic(conn: &Connection, id: i64) -> Result<ToolboxTalkTopic> {
 }
 
 // Talk CRUD
-pub fn create_talk(conn: &Connection, data: CreateToolboxTalk) -> Result<ToolboxTalk> {
-    conn.execute(
-        "INSERT INTO toolbox_talks (topic_id, establishment_id, location_id, title, date, conducted_by, notes)
-         VALUES (?, ?, ?, ?, ?, ?, ?)",
-        params![
-            data.topic_id,
-            data.establishment_id,
-            data.location_id,
-            data.title,
-            data.date,
-            data.conducted_by,
-            data.notes,
-        ],
-    )?;
+pub fn create_talk(
+    conn: &Connection,
+    actor: &Actor,
+    data: CreateToolboxTalk,
+) -> Result<ToolboxTalk> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO toolbox_talks (topic_id, establishment_id, location_id, title, date, conducted_by, notes)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            params![
+                data.topic_id,
+                data.establishment_id,
+                data.location_id,
+                data.title,
+                data.date,
+                data.conducted_by,
+                data.notes,
+            ],
+        )?;
 
-    let id = conn.last_insert_rowid();
-    get_talk(conn, id)
+        let id = conn.last_insert_rowid();
+        let created = get_talk(conn, id)?;
+        audit::log_create(conn, actor, "toolbox_talks", id, &created)?;
+        Ok(created)
+    })
 }
 
 pub fn get_talk(conn: &Connection, id: i64) -> Result<ToolboxTalk> {
     conn.query_row(
-        "SELECT id, topic_id, establishment_id, location_id, title, date, conducted_by, notes, status
+        "SELECT id, topic_id, establishment_id, location_id, title, date, conducted_by, notes, status,
+                deleted_at, deleted_by
          FROM toolbox_talks WHERE id = ?",
         [id],
         |row| {
@@ -140,6 +155,8 @@ pub fn get_talk(conn: &Connection, id: i64) -> Result<ToolboxTalk> {
                 conducted_by: row.get(6)?,
                 notes: row.get(7)?,
                 status: row.get(8)?,
+                deleted_at: row.get(9)?,
+                deleted_by: row.get(10)?,
             })
         },
     )
@@ -147,8 +164,9 @@ pub fn get_talk(conn: &Connection, id: i64) -> Result<ToolboxTalk> {
 
 pub fn list_talks(conn: &Connection, establishment_id: i64) -> Result<Vec<ToolboxTalk>> {
     let mut stmt = conn.prepare(
-        "SELECT id, topic_id, establishment_id, location_id, title, date, conducted_by, notes, status
-         FROM toolbox_talks WHERE establishment_id = ? ORDER BY date DESC",
+        "SELECT id, topic_id, establishment_id, location_id, title, date, conducted_by, notes, status,
+                deleted_at, deleted_by
+         FROM toolbox_talks WHERE establishment_id = ? AND deleted_at IS NULL ORDER BY date DESC",
     )?;
 
     let rows = stmt.query_map([establishment_id], |row| {
@@ -162,30 +180,74 @@ pub fn list_talks(conn: &Connection, establishment_id: i64) -> Result<Vec<Toolbo
             conducted_by: row.get(6)?,
             notes: row.get(7)?,
             status: row.get(8)?,
+            deleted_at: row.get(9)?,
+            deleted_by: row.get(10)?,
         })
     })?;
 
     rows.collect()
 }
 
-pub fn complete_talk(conn: &Connection, talk_id: i64) -> Result<ToolboxTalk> {
-    conn.execute(
-        "UPDATE toolbox_talks SET status = 'completed' WHERE id = ?",
-        [talk_id],
-    )?;
-    get_talk(conn, talk_id)
+pub fn complete_talk(conn: &Connection, actor: &Actor, talk_id: i64) -> Result<ToolboxTalk> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_talk(conn, talk_id)?;
+        conn.execute(
+            "UPDATE toolbox_talks SET status = 'completed' WHERE id = ?",
+            [talk_id],
+        )?;
+        let updated = get_talk(conn, talk_id)?;
+        audit::log_update(conn, actor, "toolbox_talks", talk_id, &existing, &updated)?;
+        Ok(updated)
+    })
+}
+
+/// Moves the talk to the trash; its attendees stay with it.
+pub fn delete_talk(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_talk(conn, id)?;
+        trash::mark_deleted(conn, actor, "toolbox_talks", id)?;
+        audit::log_delete(conn, actor, "toolbox_talks", id, &existing)?;
+        Ok(())
+    })
+}
+
+pub fn restore_talk(conn: &Connection, actor: &Actor, id: i64) -> Result<ToolboxTalk> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_talk(conn, id)?;
+        trash::clear_deleted(conn, "toolbox_talks", id)?;
+        let restored = get_talk(conn, id)?;
+        audit::record(conn, actor, "restore", "toolbox_talks", id, Some(&existing), Some(&restored))?;
+        Ok(restored)
+    })
+}
+
+pub fn purge_talk(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_talk(conn, id)?;
+        trash::purge_row(conn, "toolbox_talks", id)?;
+        audit::record(conn, actor, "purge", "toolbox_talks", id, Some(&existing), None)?;
+        Ok(())
+    })
 }
 
 // Attendee management
-pub fn add_attendee(conn: &Connection, data: AddAttendee) -> Result<ToolboxTalkAttendee> {
-    conn.execute(
-        "INSERT INTO toolbox_talk_attendees (talk_id, employee_name, employee_id)
-         VALUES (?, ?, ?)",
-        params![data.talk_id, data.employee_name, data.employee_id],
-    )?;
+pub fn add_attendee(
+    conn: &Connection,
+    actor: &Actor,
+    data: AddAttendee,
+) -> Result<ToolboxTalkAttendee> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO toolbox_talk_attendees (talk_id, employee_name, employee_id)
+             VALUES (?, ?, ?)",
+            params![data.talk_id, data.employee_name, data.employee_id],
+        )?;
 
-    let id = conn.last_insert_rowid();
-    get_attendee(conn, id)
+        let id = conn.last_insert_rowid();
+        let created = get_attendee(conn, id)?;
+        audit::log_create(conn, actor, "toolbox_talk_attendees", id, &created)?;
+        Ok(created)
+    })
 }
 
 pub fn get_attendee(conn: &Connection, id: i64) -> Result<ToolboxTalkAttendee> {
@@ -226,18 +288,31 @@ pub fn list_attendees(conn: &Connection, talk_id: i64) -> Result<Vec<ToolboxTalk
     rows.collect()
 }
 
-pub fn sign_attendee(conn: &Connection, data: SignAttendee) -> Result<ToolboxTalkAttendee> {
-    conn.execute(
-        "UPDATE toolbox_talk_attendees
-         SET signature_data = ?, signed_at = datetime('now')
-         WHERE id = ?",
-        params![data.signature_data, data.attendee_id],
-    )?;
+pub fn sign_attendee(
+    conn: &Connection,
+    actor: &Actor,
+    data: SignAttendee,
+) -> Result<ToolboxTalkAttendee> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_attendee(conn, data.attendee_id)?;
+        conn.execute(
+            "UPDATE toolbox_talk_attendees
+             SET signature_data = ?, signed_at = datetime('now')
+             WHERE id = ?",
+            params![data.signature_data, data.attendee_id],
+        )?;
 
-    get_attendee(conn, data.attendee_id)
+        let updated = get_attendee(conn, data.attendee_id)?;
+        audit::log_update(conn, actor, "toolbox_talk_attendees", updated.id, &existing, &updated)?;
+        Ok(updated)
+    })
 }
 
-pub fn delete_attendee(conn: &Connection, id: i64) -> Result<()> {
-    conn.execute("DELETE FROM toolbox_talk_attendees WHERE id = ?", [id])?;
-    Ok(())
+pub fn delete_attendee(conn: &Connection, actor: &Actor, id: i64) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_attendee(conn, id)?;
+        conn.execute("DELETE FROM toolbox_talk_attendees WHERE id = ?", [id])?;
+        audit::log_delete(conn, actor, "toolbox_talk_attendees", id, &existing)?;
+        Ok(())
+    })
 }
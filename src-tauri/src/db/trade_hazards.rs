@@ -0,0 +1,484 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AppError;
+
+/// Version stamped on exported libraries; imports reject anything newer.
+pub const LIBRARY_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TradeHazard {
+    pub id: i64,
+    pub trade: String,
+    pub hazard_name: String,
+    pub hazard_description: Option<String>,
+    pub osha_standard: Option<String>,
+    pub recommended_controls: Vec<String>,
+    pub is_active: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTradeHazard {
+    pub trade: String,
+    pub hazard_name: String,
+    pub hazard_description: Option<String>,
+    pub osha_standard: Option<String>,
+    #[serde(default)]
+    pub recommended_controls: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTradeHazard {
+    pub trade: Option<String>,
+    pub hazard_name: Option<String>,
+    pub hazard_description: Option<String>,
+    pub osha_standard: Option<String>,
+    pub recommended_controls: Option<Vec<String>>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct TradeHazardFilter {
+    pub trade: Option<String>,
+    /// Matches the standard itself and anything under it, so `1926.45`
+    /// finds `1926.451` and `1926.453`
+    pub osha_standard: Option<String>,
+    /// Free text over name, description and controls
+    pub search: Option<String>,
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+/// One library entry as distributed between installs. Ids are local and
+/// left out; entries are matched on `(trade, hazard_name)`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LibraryEntry {
+    pub trade: String,
+    pub hazard_name: String,
+    pub hazard_description: Option<String>,
+    pub osha_standard: Option<String>,
+    #[serde(default)]
+    pub recommended_controls: Vec<String>,
+    #[serde(default = "default_active")]
+    pub is_active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HazardLibrary {
+    pub format_version: u32,
+    pub exported_at: String,
+    pub hazards: Vec<LibraryEntry>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct LibraryImportResult {
+    pub created: usize,
+    pub updated: usize,
+    pub deactivated: usize,
+    pub errors: Vec<String>,
+}
+
+/// Trades are stored lowercase (`electrical`, `hvac`) to match the seed data.
+pub fn normalize_trade(trade: &str) -> String {
+    trade.trim().to_lowercase()
+}
+
+/// Decodes the JSON array stored in `recommended_controls`. Rows edited by
+/// hand with plain text come back as a single control rather than an error.
+fn decode_controls(raw: Option<String>) -> Vec<String> {
+    match raw {
+        None => Vec::new(),
+        Some(text) if text.trim().is_empty() => Vec::new(),
+        Some(text) => serde_json::from_str(&text).unwrap_or_else(|_| vec![text]),
+    }
+}
+
+fn encode_controls(controls: &[String]) -> Result<String> {
+    let cleaned: Vec<&str> = controls
+        .iter()
+        .map(|c| c.trim())
+        .filter(|c| !c.is_empty())
+        .collect();
+    serde_json::to_string(&cleaned).context("Failed to encode recommended controls")
+}
+
+pub(crate) fn row_to_hazard(row: &rusqlite::Row<'_>) -> rusqlite::Result<TradeHazard> {
+    Ok(TradeHazard {
+        id: row.get(0)?,
+        trade: row.get(1)?,
+        hazard_name: row.get(2)?,
+        hazard_description: row.get(3)?,
+        osha_standard: row.get(4)?,
+        recommended_controls: decode_controls(row.get(5)?),
+        is_active: row.get::<_, i32>(6)? != 0,
+        created_at: row.get(7)?,
+    })
+}
+
+pub(crate) const SELECT_COLS: &str = "id, trade, hazard_name, hazard_description, osha_standard,
+    recommended_controls, is_active, created_at";
+
+// ── CRUD ──
+
+pub fn create_hazard(conn: &Connection, data: CreateTradeHazard) -> Result<TradeHazard> {
+    let trade = normalize_trade(&data.trade);
+    let controls = encode_controls(&data.recommended_controls)?;
+
+    if find_by_name(conn, &trade, data.hazard_name.trim())?.is_some() {
+        return Err(AppError::Validation(format!(
+            "The {trade} library already has a hazard named {}",
+            data.hazard_name.trim()
+        ))
+        .into());
+    }
+
+    conn.execute(
+        "INSERT INTO trade_hazards (trade, hazard_name, hazard_description, osha_standard, recommended_controls)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            trade,
+            data.hazard_name.trim(),
+            data.hazard_description,
+            data.osha_standard,
+            controls,
+        ],
+    )
+    .context("Failed to create trade hazard")?;
+
+    let id = conn.last_insert_rowid();
+    get_hazard(conn, id)
+}
+
+pub fn get_hazard(conn: &Connection, id: i64) -> Result<TradeHazard> {
+    conn.query_row(
+        &format!("SELECT {SELECT_COLS} FROM trade_hazards WHERE id = ?1"),
+        [id],
+        row_to_hazard,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            AppError::NotFound(format!("Trade hazard {id} not found")).into()
+        }
+        _ => anyhow::Error::new(e),
+    })
+}
+
+fn find_by_name(conn: &Connection, trade: &str, hazard_name: &str) -> Result<Option<i64>> {
+    let id = conn
+        .query_row(
+            "SELECT id FROM trade_hazards WHERE trade = ?1 AND hazard_name = ?2",
+            params![trade, hazard_name],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(id)
+}
+
+pub fn list_hazards(conn: &Connection, filter: TradeHazardFilter) -> Result<Vec<TradeHazard>> {
+    let mut sql = format!("SELECT {SELECT_COLS} FROM trade_hazards WHERE 1 = 1");
+    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+    if !filter.include_inactive {
+        sql.push_str(" AND is_active = 1");
+    }
+    if let Some(ref trade) = filter.trade {
+        sql.push_str(" AND trade = ?");
+        values.push(Box::new(normalize_trade(trade)));
+    }
+    if let Some(ref standard) = filter.osha_standard {
+        sql.push_str(" AND (osha_standard = ? OR osha_standard LIKE ? || '%')");
+        values.push(Box::new(standard.trim().to_string()));
+        values.push(Box::new(standard.trim().to_string()));
+    }
+    if let Some(ref text) = filter.search {
+        let pattern = format!("%{}%", text.trim());
+        sql.push_str(
+            " AND (hazard_name LIKE ? OR hazard_description LIKE ? OR recommended_controls LIKE ?)",
+        );
+        values.push(Box::new(pattern.clone()));
+        values.push(Box::new(pattern.clone()));
+        values.push(Box::new(pattern));
+    }
+
+    sql.push_str(" ORDER BY trade, hazard_name");
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+    let rows = stmt
+        .query_map(params.as_slice(), row_to_hazard)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+/// Distinct trades with at least one active hazard.
+pub fn list_trades(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT trade FROM trade_hazards WHERE is_active = 1 ORDER BY trade",
+    )?;
+    let rows = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub fn update_hazard(conn: &Connection, id: i64, data: UpdateTradeHazard) -> Result<TradeHazard> {
+    let existing = get_hazard(conn, id)?;
+
+    let trade = data.trade.as_deref().map(normalize_trade);
+    let hazard_name = data.hazard_name.as_deref().map(str::trim);
+    if trade.is_some() || hazard_name.is_some() {
+        let new_trade = trade.as_deref().unwrap_or(&existing.trade);
+        let new_name = hazard_name.unwrap_or(&existing.hazard_name);
+        if find_by_name(conn, new_trade, new_name)?.is_some_and(|other| other != id) {
+            return Err(AppError::Validation(format!(
+                "The {new_trade} library already has a hazard named {new_name}"
+            ))
+            .into());
+        }
+    }
+
+    let mut sets = Vec::new();
+    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+    if let Some(v) = trade {
+        sets.push("trade = ?");
+        values.push(Box::new(v));
+    }
+    if let Some(v) = hazard_name {
+        sets.push("hazard_name = ?");
+        values.push(Box::new(v.to_string()));
+    }
+    if let Some(ref v) = data.hazard_description {
+        sets.push("hazard_description = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.osha_standard {
+        sets.push("osha_standard = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.recommended_controls {
+        sets.push("recommended_controls = ?");
+        values.push(Box::new(encode_controls(v)?));
+    }
+    if let Some(v) = data.is_active {
+        sets.push("is_active = ?");
+        values.push(Box::new(v as i32));
+    }
+
+    if !sets.is_empty() {
+        let sql = format!("UPDATE trade_hazards SET {} WHERE id = ?", sets.join(", "));
+        values.push(Box::new(id));
+        let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+        conn.execute(&sql, params.as_slice())
+            .context("Failed to update trade hazard")?;
+    }
+
+    get_hazard(conn, id)
+}
+
+pub fn delete_hazard(conn: &Connection, id: i64) -> Result<()> {
+    let changes = conn
+        .execute("DELETE FROM trade_hazards WHERE id = ?1", [id])
+        .context("Failed to delete trade hazard")?;
+
+    if changes == 0 {
+        return Err(AppError::NotFound(format!("Trade hazard {id} not found")).into());
+    }
+    Ok(())
+}
+
+// ── Library import/export ──
+
+pub fn export_library(conn: &Connection, include_inactive: bool) -> Result<HazardLibrary> {
+    let hazards = list_hazards(conn, TradeHazardFilter { include_inactive, ..Default::default() })?
+        .into_iter()
+        .map(|h| LibraryEntry {
+            trade: h.trade,
+            hazard_name: h.hazard_name,
+            hazard_description: h.hazard_description,
+            osha_standard: h.osha_standard,
+            recommended_controls: h.recommended_controls,
+            is_active: h.is_active,
+        })
+        .collect();
+
+    Ok(HazardLibrary {
+        format_version: LIBRARY_FORMAT_VERSION,
+        exported_at: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        hazards,
+    })
+}
+
+/// Merges a distributed library into this install. Entries are matched on
+/// `(trade, hazard_name)`: existing ones are overwritten, new ones created.
+/// With `deactivate_missing`, local hazards not in the library are retired
+/// (deactivated, not deleted) so the install mirrors the vetted set.
+pub fn import_library(
+    conn: &Connection,
+    library: &HazardLibrary,
+    deactivate_missing: bool,
+) -> Result<LibraryImportResult> {
+    if library.format_version > LIBRARY_FORMAT_VERSION {
+        return Err(AppError::Validation(format!(
+            "Hazard library format {} is newer than this app supports ({LIBRARY_FORMAT_VERSION})",
+            library.format_version
+        ))
+        .into());
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let mut result = LibraryImportResult::default();
+    let mut seen = Vec::new();
+
+    for (i, entry) in library.hazards.iter().enumerate() {
+        let trade = normalize_trade(&entry.trade);
+        let name = entry.hazard_name.trim();
+        if trade.is_empty() || name.is_empty() {
+            result.errors.push(format!("Entry {}: trade and hazard name are required", i + 1));
+            continue;
+        }
+        let controls = encode_controls(&entry.recommended_controls)?;
+
+        match find_by_name(&tx, &trade, name)? {
+            Some(id) => {
+                tx.execute(
+                    "UPDATE trade_hazards SET hazard_description = ?1, osha_standard = ?2,
+                            recommended_controls = ?3, is_active = ?4
+                     WHERE id = ?5",
+                    params![entry.hazard_description, entry.osha_standard, controls, entry.is_active as i32, id],
+                )
+                .with_context(|| format!("Failed to update {trade} hazard {name}"))?;
+                result.updated += 1;
+                seen.push(id);
+            }
+            None => {
+                tx.execute(
+                    "INSERT INTO trade_hazards (trade, hazard_name, hazard_description, osha_standard,
+                                                recommended_controls, is_active)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![trade, name, entry.hazard_description, entry.osha_standard, controls, entry.is_active as i32],
+                )
+                .with_context(|| format!("Failed to create {trade} hazard {name}"))?;
+                result.created += 1;
+                seen.push(tx.last_insert_rowid());
+            }
+        }
+    }
+
+    if deactivate_missing {
+        let ids: Vec<i64> = {
+            let mut stmt = tx.prepare("SELECT id FROM trade_hazards WHERE is_active = 1")?;
+            let ids = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            ids
+        };
+        for id in ids.into_iter().filter(|id| !seen.contains(id)) {
+            tx.execute("UPDATE trade_hazards SET is_active = 0 WHERE id = ?1", [id])?;
+            result.deactivated += 1;
+        }
+    }
+
+    tx.commit()?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::open_test_db;
+
+    #[test]
+    fn test_seeded_controls_decode_and_filters() {
+        let conn = open_test_db();
+
+        let electrical = list_hazards(&conn, TradeHazardFilter {
+            trade: Some("Electrical".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(electrical.len(), 3);
+        let arc = electrical.iter().find(|h| h.hazard_name == "Arc Flash").unwrap();
+        assert_eq!(arc.recommended_controls[0], "Use arc-rated PPE");
+
+        let by_standard = list_hazards(&conn, TradeHazardFilter {
+            osha_standard: Some("1926.416".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(by_standard.iter().all(|h| h.osha_standard.as_deref() == Some("1926.416")));
+        assert_eq!(by_standard.len(), 2);
+
+        let searched = list_hazards(&conn, TradeHazardFilter {
+            search: Some("fire watch".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(searched.iter().any(|h| h.hazard_name == "Hot Work (Soldering)"));
+
+        let created = create_hazard(&conn, CreateTradeHazard {
+            trade: " Roofing ".into(),
+            hazard_name: "Skylight Fall-Through".into(),
+            hazard_description: None,
+            osha_standard: Some("1926.501(b)(4)".into()),
+            recommended_controls: vec!["Skylight screens".into(), " ".into()],
+        })
+        .unwrap();
+        assert_eq!(created.trade, "roofing");
+        assert_eq!(created.recommended_controls, ["Skylight screens"]);
+        assert!(create_hazard(&conn, CreateTradeHazard {
+            trade: "roofing".into(),
+            hazard_name: "Skylight Fall-Through".into(),
+            hazard_description: None, osha_standard: None, recommended_controls: vec![],
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_library_round_trip() {
+        let source = open_test_db();
+        let target = open_test_db();
+
+        let arc_id = list_hazards(&source, TradeHazardFilter { search: Some("Arc Flash".into()), ..Default::default() })
+            .unwrap()[0]
+            .id;
+        update_hazard(&source, arc_id, UpdateTradeHazard {
+            trade: None, hazard_name: None, hazard_description: None, osha_standard: None,
+            recommended_controls: Some(vec!["Energized work permit".into()]),
+            is_active: None,
+        })
+        .unwrap();
+        create_hazard(&source, CreateTradeHazard {
+            trade: "masonry".into(),
+            hazard_name: "Silica Dust".into(),
+            hazard_description: Some("Cutting block and brick".into()),
+            osha_standard: Some("1926.1153".into()),
+            recommended_controls: vec!["Wet cutting".into()],
+        })
+        .unwrap();
+        target.execute("INSERT INTO trade_hazards (trade, hazard_name) VALUES ('misc', 'Unvetted')", []).unwrap();
+
+        let library = export_library(&source, false).unwrap();
+        let json = serde_json::to_string(&library).unwrap();
+        let library: HazardLibrary = serde_json::from_str(&json).unwrap();
+
+        let result = import_library(&target, &library, true).unwrap();
+        assert_eq!(result.created, 1);
+        assert_eq!(result.updated, library.hazards.len() - 1);
+        assert_eq!(result.deactivated, 1);
+
+        let arc = list_hazards(&target, TradeHazardFilter { search: Some("Arc Flash".into()), ..Default::default() }).unwrap();
+        assert_eq!(arc[0].recommended_controls, ["Energized work permit"]);
+        assert!(!list_trades(&target).unwrap().contains(&"misc".to_string()));
+
+        let future = HazardLibrary { format_version: LIBRARY_FORMAT_VERSION + 1, exported_at: String::new(), hazards: vec![] };
+        assert!(import_library(&target, &future, false).is_err());
+    }
+}
@@ -0,0 +1,670 @@
+use anyhow::{Context, Result};
+use chrono::{Duration, Months, NaiveDate};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AppError;
+
+// ── Courses ──
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrainingCourse {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub duration_hours: Option<f64>,
+    pub certification_valid_months: Option<i64>,
+    pub certifying_body: Option<String>,
+    pub is_required: bool,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTrainingCourse {
+    pub name: String,
+    pub description: Option<String>,
+    pub duration_hours: Option<f64>,
+    pub certification_valid_months: Option<i64>,
+    pub certifying_body: Option<String>,
+    pub is_required: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTrainingCourse {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub duration_hours: Option<f64>,
+    pub certification_valid_months: Option<i64>,
+    pub certifying_body: Option<String>,
+    pub is_required: Option<bool>,
+    pub is_active: Option<bool>,
+}
+
+// ── Records ──
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrainingRecord {
+    pub id: i64,
+    pub course_id: i64,
+    pub employee_name: String,
+    pub employee_id: Option<String>,
+    pub completion_date: String,
+    pub expiration_date: Option<String>,
+    pub instructor_name: Option<String>,
+    pub certification_number: Option<String>,
+    pub score: Option<f64>,
+    pub status: String,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTrainingRecord {
+    pub course_id: i64,
+    pub employee_name: String,
+    pub employee_id: Option<String>,
+    pub completion_date: String,
+    pub expiration_date: Option<String>,
+    pub instructor_name: Option<String>,
+    pub certification_number: Option<String>,
+    pub score: Option<f64>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTrainingRecord {
+    pub employee_name: Option<String>,
+    pub employee_id: Option<String>,
+    pub completion_date: Option<String>,
+    pub expiration_date: Option<String>,
+    pub instructor_name: Option<String>,
+    pub certification_number: Option<String>,
+    pub score: Option<f64>,
+    pub status: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrainingRecordFilter {
+    pub course_id: Option<i64>,
+    pub employee_name: Option<String>,
+    pub status: Option<String>,
+    pub expiring_before: Option<String>,
+}
+
+// ── Compliance matrix ──
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CourseCompliance {
+    pub course_id: i64,
+    pub course_name: String,
+    /// One of `current`, `expiring`, `expired` or `missing`
+    pub status: String,
+    pub completion_date: Option<String>,
+    pub expiration_date: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EmployeeCompliance {
+    pub employee_name: String,
+    pub employee_id: Option<String>,
+    pub is_compliant: bool,
+    pub courses: Vec<CourseCompliance>,
+}
+
+// ── Course CRUD ──
+
+fn row_to_course(row: &rusqlite::Row<'_>) -> rusqlite::Result<TrainingCourse> {
+    Ok(TrainingCourse {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        duration_hours: row.get(3)?,
+        certification_valid_months: row.get(4)?,
+        certifying_body: row.get(5)?,
+        is_required: row.get::<_, i32>(6)? != 0,
+        is_active: row.get::<_, i32>(7)? != 0,
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+    })
+}
+
+const COURSE_COLS: &str = "id, name, description, duration_hours, certification_valid_months,
+    certifying_body, is_required, is_active, created_at, updated_at";
+
+pub fn create_course(conn: &Connection, data: CreateTrainingCourse) -> Result<TrainingCourse> {
+    conn.execute(
+        "INSERT INTO training_courses (name, description, duration_hours, certification_valid_months,
+                                       certifying_body, is_required)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            data.name,
+            data.description,
+            data.duration_hours,
+            data.certification_valid_months,
+            data.certifying_body,
+            data.is_required.unwrap_or(false) as i32,
+        ],
+    )
+    .context("Failed to create training course")?;
+
+    let id = conn.last_insert_rowid();
+    get_course(conn, id)
+}
+
+pub fn get_course(conn: &Connection, id: i64) -> Result<TrainingCourse> {
+    conn.query_row(
+        &format!("SELECT {COURSE_COLS} FROM training_courses WHERE id = ?1"),
+        [id],
+        row_to_course,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            AppError::NotFound(format!("Training course {id} not found")).into()
+        }
+        _ => anyhow::Error::new(e),
+    })
+}
+
+pub fn list_courses(conn: &Connection, include_inactive: bool) -> Result<Vec<TrainingCourse>> {
+    let sql = if include_inactive {
+        format!("SELECT {COURSE_COLS} FROM training_courses ORDER BY name")
+    } else {
+        format!("SELECT {COURSE_COLS} FROM training_courses WHERE is_active = 1 ORDER BY name")
+    };
+
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([], row_to_course)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+pub fn update_course(
+    conn: &Connection,
+    id: i64,
+    data: UpdateTrainingCourse,
+) -> Result<TrainingCourse> {
+    let _existing = get_course(conn, id)?;
+
+    let mut sets = Vec::new();
+    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+    if let Some(ref v) = data.name {
+        sets.push("name = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.description {
+        sets.push("description = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(v) = data.duration_hours {
+        sets.push("duration_hours = ?");
+        values.push(Box::new(v));
+    }
+    if let Some(v) = data.certification_valid_months {
+        sets.push("certification_valid_months = ?");
+        values.push(Box::new(v));
+    }
+    if let Some(ref v) = data.certifying_body {
+        sets.push("certifying_body = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(v) = data.is_required {
+        sets.push("is_required = ?");
+        values.push(Box::new(v as i32));
+    }
+    if let Some(v) = data.is_active {
+        sets.push("is_active = ?");
+        values.push(Box::new(v as i32));
+    }
+
+    if !sets.is_empty() {
+        sets.push("updated_at = datetime('now')");
+        let sql = format!("UPDATE training_courses SET {} WHERE id = ?", sets.join(", "));
+        values.push(Box::new(id));
+        let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+        conn.execute(&sql, params.as_slice())
+            .context("Failed to update training course")?;
+    }
+
+    get_course(conn, id)
+}
+
+pub fn delete_course(conn: &Connection, id: i64) -> Result<()> {
+    let record_count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM training_records WHERE course_id = ?1",
+        [id],
+        |row| row.get(0),
+    )?;
+    if record_count > 0 {
+        return Err(AppError::Validation(format!(
+            "Training course {id} has {record_count} training records; deactivate it instead"
+        ))
+        .into());
+    }
+
+    let changes = conn
+        .execute("DELETE FROM training_courses WHERE id = ?1", [id])
+        .context("Failed to delete training course")?;
+
+    if changes == 0 {
+        return Err(AppError::NotFound(format!("Training course {id} not found")).into());
+    }
+    Ok(())
+}
+
+// ── Record CRUD ──
+
+/// `completion_date + certification_valid_months`, or `None` for courses
+/// that never expire.
+pub fn compute_expiration(completion_date: &str, valid_months: Option<i64>) -> Result<Option<String>> {
+    let Some(months) = valid_months.filter(|&m| m > 0) else {
+        return Ok(None);
+    };
+
+    let completed = NaiveDate::parse_from_str(completion_date, "%Y-%m-%d").map_err(|_| {
+        AppError::Validation(format!("Invalid completion date: {completion_date}"))
+    })?;
+    let expires = completed
+        .checked_add_months(Months::new(months as u32))
+        .ok_or_else(|| AppError::Validation(format!("Expiration out of range for {completion_date}")))?;
+
+    Ok(Some(expires.format("%Y-%m-%d").to_string()))
+}
+
+fn row_to_record(row: &rusqlite::Row<'_>) -> rusqlite::Result<TrainingRecord> {
+    Ok(TrainingRecord {
+        id: row.get(0)?,
+        course_id: row.get(1)?,
+        employee_name: row.get(2)?,
+        employee_id: row.get(3)?,
+        completion_date: row.get(4)?,
+        expiration_date: row.get(5)?,
+        instructor_name: row.get(6)?,
+        certification_number: row.get(7)?,
+        score: row.get(8)?,
+        status: row.get(9)?,
+        notes: row.get(10)?,
+        created_at: row.get(11)?,
+        updated_at: row.get(12)?,
+    })
+}
+
+const RECORD_COLS: &str = "id, course_id, employee_name, employee_id, completion_date,
+    expiration_date, instructor_name, certification_number, score, status, notes,
+    created_at, updated_at";
+
+pub fn create_record(conn: &Connection, data: CreateTrainingRecord) -> Result<TrainingRecord> {
+    let course = get_course(conn, data.course_id)?;
+    let expiration_date = match data.expiration_date {
+        Some(date) => Some(date),
+        None => compute_expiration(&data.completion_date, course.certification_valid_months)?,
+    };
+
+    conn.execute(
+        "INSERT INTO training_records (course_id, employee_name, employee_id, completion_date,
+                                       expiration_date, instructor_name, certification_number,
+                                       score, notes)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            data.course_id,
+            data.employee_name,
+            data.employee_id,
+            data.completion_date,
+            expiration_date,
+            data.instructor_name,
+            data.certification_number,
+            data.score,
+            data.notes,
+        ],
+    )
+    .context("Failed to create training record")?;
+
+    let id = conn.last_insert_rowid();
+    get_record(conn, id)
+}
+
+pub fn get_record(conn: &Connection, id: i64) -> Result<TrainingRecord> {
+    conn.query_row(
+        &format!("SELECT {RECORD_COLS} FROM training_records WHERE id = ?1"),
+        [id],
+        row_to_record,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            AppError::NotFound(format!("Training record {id} not found")).into()
+        }
+        _ => anyhow::Error::new(e),
+    })
+}
+
+pub fn list_records(conn: &Connection, filter: TrainingRecordFilter) -> Result<Vec<TrainingRecord>> {
+    let mut sql = format!("SELECT {RECORD_COLS} FROM training_records WHERE 1 = 1");
+    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+    if let Some(course_id) = filter.course_id {
+        sql.push_str(" AND course_id = ?");
+        values.push(Box::new(course_id));
+    }
+    if let Some(ref name) = filter.employee_name {
+        sql.push_str(" AND employee_name = ?");
+        values.push(Box::new(name.clone()));
+    }
+    if let Some(ref status) = filter.status {
+        sql.push_str(" AND status = ?");
+        values.push(Box::new(status.clone()));
+    }
+    if let Some(ref before) = filter.expiring_before {
+        sql.push_str(" AND expiration_date IS NOT NULL AND expiration_date <= ?");
+        values.push(Box::new(before.clone()));
+    }
+
+    sql.push_str(" ORDER BY employee_name, completion_date DESC");
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+    let rows = stmt
+        .query_map(params.as_slice(), row_to_record)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+pub fn update_record(
+    conn: &Connection,
+    id: i64,
+    data: UpdateTrainingRecord,
+) -> Result<TrainingRecord> {
+    let existing = get_record(conn, id)?;
+
+    // A new completion date without an explicit expiration re-derives it
+    let expiration_date = match (&data.expiration_date, &data.completion_date) {
+        (Some(date), _) => Some(date.clone()),
+        (None, Some(completed)) => {
+            let course = get_course(conn, existing.course_id)?;
+            compute_expiration(completed, course.certification_valid_months)?
+        }
+        (None, None) => existing.expiration_date,
+    };
+
+    let mut sets = vec!["expiration_date = ?"];
+    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = vec![Box::new(expiration_date)];
+
+    if let Some(ref v) = data.employee_name {
+        sets.push("employee_name = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.employee_id {
+        sets.push("employee_id = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.completion_date {
+        sets.push("completion_date = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.instructor_name {
+        sets.push("instructor_name = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.certification_number {
+        sets.push("certification_number = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(v) = data.score {
+        sets.push("score = ?");
+        values.push(Box::new(v));
+    }
+    if let Some(ref v) = data.status {
+        sets.push("status = ?");
+        values.push(Box::new(v.clone()));
+    }
+    if let Some(ref v) = data.notes {
+        sets.push("notes = ?");
+        values.push(Box::new(v.clone()));
+    }
+
+    sets.push("updated_at = datetime('now')");
+    let sql = format!("UPDATE training_records SET {} WHERE id = ?", sets.join(", "));
+    values.push(Box::new(id));
+    let params: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+    conn.execute(&sql, params.as_slice())
+        .context("Failed to update training record")?;
+
+    get_record(conn, id)
+}
+
+pub fn delete_record(conn: &Connection, id: i64) -> Result<()> {
+    let changes = conn
+        .execute("DELETE FROM training_records WHERE id = ?1", [id])
+        .context("Failed to delete training record")?;
+
+    if changes == 0 {
+        return Err(AppError::NotFound(format!("Training record {id} not found")).into());
+    }
+    Ok(())
+}
+
+// ── Compliance matrix ──
+
+/// For each employee, reports every active required course as `current`,
+/// `expiring` (within `expiring_within_days` of `as_of`), `expired` or
+/// `missing`. The most recent active record per course wins.
+///
+/// When `employee_names` is given only those employees are reported, including
+/// ones with no training on file; otherwise everyone with an active record is.
+pub fn get_compliance_matrix(
+    conn: &Connection,
+    as_of: NaiveDate,
+    expiring_within_days: i64,
+    employee_names: Option<Vec<String>>,
+) -> Result<Vec<EmployeeCompliance>> {
+    let required: Vec<(i64, String)> = {
+        let mut stmt = conn.prepare(
+            "SELECT id, name FROM training_courses
+             WHERE is_required = 1 AND is_active = 1 ORDER BY name",
+        )?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows
+    };
+
+    let employees: Vec<(String, Option<String>)> = match employee_names {
+        Some(names) => {
+            let mut id_stmt = conn.prepare(
+                "SELECT employee_id FROM training_records
+                 WHERE employee_name = ?1 AND employee_id IS NOT NULL
+                 ORDER BY completion_date DESC LIMIT 1",
+            )?;
+            names
+                .into_iter()
+                .map(|name| {
+                    let employee_id = id_stmt
+                        .query_row([&name], |row| row.get(0))
+                        .optional()?;
+                    Ok((name, employee_id))
+                })
+                .collect::<rusqlite::Result<Vec<_>>>()?
+        }
+        None => {
+            let mut stmt = conn.prepare(
+                "SELECT employee_name, MAX(employee_id) FROM training_records
+                 WHERE status = 'active' GROUP BY employee_name ORDER BY employee_name",
+            )?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        }
+    };
+
+    let mut latest_stmt = conn.prepare(
+        "SELECT completion_date, expiration_date FROM training_records
+         WHERE employee_name = ?1 AND course_id = ?2 AND status = 'active'
+         ORDER BY COALESCE(expiration_date, '9999-12-31') DESC, completion_date DESC
+         LIMIT 1",
+    )?;
+
+    let as_of_str = as_of.format("%Y-%m-%d").to_string();
+    let warn_str = (as_of + Duration::days(expiring_within_days.max(0)))
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let mut matrix = Vec::with_capacity(employees.len());
+    for (employee_name, employee_id) in employees {
+        let mut courses = Vec::with_capacity(required.len());
+        for (course_id, course_name) in &required {
+            let latest: Option<(String, Option<String>)> = latest_stmt
+                .query_row(params![employee_name, course_id], |row| {
+                    Ok((row.get(0)?, row.get(1)?))
+                })
+                .optional()?;
+
+            let (status, completion_date, expiration_date) = match latest {
+                None => ("missing", None, None),
+                Some((completed, None)) => ("current", Some(completed), None),
+                Some((completed, Some(expires))) => {
+                    let status = if expires < as_of_str {
+                        "expired"
+                    } else if expires <= warn_str {
+                        "expiring"
+                    } else {
+                        "current"
+                    };
+                    (status, Some(completed), Some(expires))
+                }
+            };
+
+            courses.push(CourseCompliance {
+                course_id: *course_id,
+                course_name: course_name.clone(),
+                status: status.to_string(),
+                completion_date,
+                expiration_date,
+            });
+        }
+
+        let is_compliant = courses
+            .iter()
+            .all(|c| c.status == "current" || c.status == "expiring");
+
+        matrix.push(EmployeeCompliance {
+            employee_name,
+            employee_id,
+            is_compliant,
+            courses,
+        });
+    }
+
+    Ok(matrix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::open_test_db;
+
+    fn course_id(conn: &Connection, name: &str) -> i64 {
+        list_courses(conn, true)
+            .unwrap()
+            .into_iter()
+            .find(|c| c.name == name)
+            .unwrap()
+            .id
+    }
+
+    fn record(conn: &Connection, course_id: i64, employee: &str, completed: &str) -> TrainingRecord {
+        create_record(conn, CreateTrainingRecord {
+            course_id, employee_name: employee.into(), employee_id: None,
+            completion_date: completed.into(), expiration_date: None,
+            instructor_name: None, certification_number: None, score: None, notes: None,
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_expiration_derived_from_course() {
+        let conn = open_test_db();
+
+        let cpr = record(&conn, course_id(&conn, "First Aid/CPR"), "Ana", "2026-01-31");
+        assert_eq!(cpr.expiration_date.as_deref(), Some("2028-01-31"));
+
+        // OSHA 10 never expires
+        let osha10 = record(&conn, course_id(&conn, "OSHA 10-Hour Construction"), "Ana", "2026-01-31");
+        assert_eq!(osha10.expiration_date, None);
+
+        let updated = update_record(&conn, cpr.id, UpdateTrainingRecord {
+            completion_date: Some("2026-03-15".into()),
+            employee_name: None, employee_id: None, expiration_date: None,
+            instructor_name: None, certification_number: None, score: None,
+            status: None, notes: None,
+        }).unwrap();
+        assert_eq!(updated.expiration_date.as_deref(), Some("2028-03-15"));
+    }
+
+    #[test]
+    fn test_course_crud() {
+        let conn = open_test_db();
+
+        let course = create_course(&conn, CreateTrainingCourse {
+            name: "Aerial Lift Operator".into(), description: None, duration_hours: Some(4.0),
+            certification_valid_months: Some(36), certifying_body: None, is_required: None,
+        }).unwrap();
+        assert!(!course.is_required);
+
+        let updated = update_course(&conn, course.id, UpdateTrainingCourse {
+            is_required: Some(true),
+            name: None, description: None, duration_hours: None,
+            certification_valid_months: None, certifying_body: None, is_active: None,
+        }).unwrap();
+        assert!(updated.is_required);
+
+        record(&conn, course.id, "Ana", "2026-01-01");
+        assert!(delete_course(&conn, course.id).is_err());
+    }
+
+    #[test]
+    fn test_compliance_matrix() {
+        let conn = open_test_db();
+        let as_of = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+
+        // Fall Protection is valid for 12 months
+        let fall = course_id(&conn, "Fall Protection");
+        record(&conn, fall, "Ana", "2025-06-20"); // expires 2026-06-20 -> expiring
+        record(&conn, fall, "Ben", "2025-03-01"); // expires 2026-03-01 -> expired
+        record(&conn, fall, "Ben", "2026-02-01"); // renewed -> current
+        let hazcom = course_id(&conn, "Hazard Communication");
+        record(&conn, hazcom, "Ana", "2025-01-10"); // expires 2026-01-10 -> expired
+
+        let matrix = get_compliance_matrix(&conn, as_of, 30, None).unwrap();
+        assert_eq!(matrix.len(), 2);
+
+        let status_of = |employee: &str, course: i64| {
+            matrix
+                .iter()
+                .find(|e| e.employee_name == employee)
+                .unwrap()
+                .courses
+                .iter()
+                .find(|c| c.course_id == course)
+                .unwrap()
+                .status
+                .clone()
+        };
+        assert_eq!(status_of("Ana", fall), "expiring");
+        assert_eq!(status_of("Ana", hazcom), "expired");
+        assert_eq!(status_of("Ben", fall), "current");
+        assert_eq!(status_of("Ben", hazcom), "missing");
+
+        // Nobody has taken every required course
+        assert!(matrix.iter().all(|e| !e.is_compliant));
+
+        let crew = get_compliance_matrix(&conn, as_of, 30, Some(vec!["Cal".into()])).unwrap();
+        assert_eq!(crew.len(), 1);
+        assert!(crew[0].courses.iter().all(|c| c.status == "missing"));
+    }
+}
@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use crate::db::audit::{self, Actor};
+
+/// Record kinds that go to the trash when deleted, and their tables.
+pub const TRASH_KINDS: &[(&str, &str)] = &[
+    ("incident", "incidents"),
+    ("establishment", "establishments"),
+    ("location", "locations"),
+    ("toolbox_talk", "toolbox_talks"),
+];
+
+pub fn table_for_kind(kind: &str) -> Option<&'static str> {
+    TRASH_KINDS.iter().find(|(k, _)| *k == kind).map(|(_, table)| *table)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TrashItem {
+    /// `incident`, `establishment`, `location` or `toolbox_talk`
+    pub kind: String,
+    pub id: i64,
+    pub establishment_id: i64,
+    /// What the UI shows for the row: case and employee, name or talk title
+    pub label: String,
+    pub deleted_at: String,
+    pub deleted_by: Option<String>,
+}
+
+// The helpers below return plain rusqlite errors so both the anyhow and
+// rusqlite-style db modules can call them; `QueryReturnedNoRows` means the
+// row was missing or not in the expected trash state.
+
+fn expect_one(changes: usize) -> rusqlite::Result<()> {
+    match changes {
+        0 => Err(rusqlite::Error::QueryReturnedNoRows),
+        _ => Ok(()),
+    }
+}
+
+/// Reports a `QueryReturnedNoRows` from the helpers above as `message`, the
+/// trash state the row was not in. Other errors pass through unchanged.
+pub(crate) fn state_error(e: rusqlite::Error, message: impl FnOnce() -> String) -> anyhow::Error {
+    match e {
+        rusqlite::Error::QueryReturnedNoRows => anyhow::anyhow!(message()),
+        e => anyhow::Error::new(e),
+    }
+}
+
+/// Stamps a row as deleted, unless it is already in the trash.
+pub(crate) fn mark_deleted(conn: &Connection, actor: &Actor, table: &str, id: i64) -> rusqlite::Result<()> {
+    let deleted_by = audit::actor_name(conn, actor)?;
+    expect_one(conn.execute(
+        &format!(
+            "UPDATE {table} SET deleted_at = datetime('now'), deleted_by = ?1, updated_at = datetime('now')
+             WHERE id = ?2 AND deleted_at IS NULL"
+        ),
+        params![deleted_by, id],
+    )?)
+}
+
+/// Takes a row out of the trash.
+pub(crate) fn clear_deleted(conn: &Connection, table: &str, id: i64) -> rusqlite::Result<()> {
+    expect_one(conn.execute(
+        &format!(
+            "UPDATE {table} SET deleted_at = NULL, deleted_by = NULL, updated_at = datetime('now')
+             WHERE id = ?1 AND deleted_at IS NOT NULL"
+        ),
+        [id],
+    )?)
+}
+
+/// Removes a trashed row for good. Only rows already in the trash can be
+/// purged, so nothing skips the restore window by accident.
+pub(crate) fn purge_row(conn: &Connection, table: &str, id: i64) -> rusqlite::Result<()> {
+    expect_one(conn.execute(&format!("DELETE FROM {table} WHERE id = ?1 AND deleted_at IS NOT NULL"), [id])?)
+}
+
+/// Everything in the trash, newest first, optionally for one establishment.
+/// Privacy cases are listed by case number only.
+pub fn list_trash(conn: &Connection, establishment_id: Option<i64>) -> Result<Vec<TrashItem>> {
+    let mut stmt = conn.prepare(
+        "SELECT kind, id, establishment_id, label, deleted_at, deleted_by FROM (
+             SELECT 'incident' AS kind, id, establishment_id,
+                    'Case ' || COALESCE(case_label, CAST(case_number AS TEXT), '-') || ' · ' || incident_date
+                        || CASE WHEN is_privacy_case = 1 THEN '' ELSE ' · ' || employee_name END AS label,
+                    deleted_at, deleted_by
+             FROM incidents WHERE deleted_at IS NOT NULL
+             UNION ALL
+             SELECT 'establishment', id, id, name, deleted_at, deleted_by
+             FROM establishments WHERE deleted_at IS NOT NULL
+             UNION ALL
+             SELECT 'location', id, establishment_id, name, deleted_at, deleted_by
+             FROM locations WHERE deleted_at IS NOT NULL
+             UNION ALL
+             SELECT 'toolbox_talk', id, establishment_id, title || ' · ' || date, deleted_at, deleted_by
+             FROM toolbox_talks WHERE deleted_at IS NOT NULL
+         )
+         WHERE ?1 IS NULL OR establishment_id = ?1
+         ORDER BY deleted_at DESC, kind, id",
+    )?;
+    let rows = stmt
+        .query_map([establishment_id], |row| {
+            Ok(TrashItem {
+                kind: row.get(0)?,
+                id: row.get(1)?,
+                establishment_id: row.get(2)?,
+                label: row.get(3)?,
+                deleted_at: row.get(4)?,
+                deleted_by: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to list the trash")?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::incidents::{self, IncidentFilter};
+    use crate::db::locations::{self, CreateEstablishment, CreateLocation};
+    use crate::db::open_test_db;
+    use crate::db::osha;
+    use crate::db::toolbox::{self, CreateToolboxTalk};
+    use crate::redaction::Audience;
+
+    #[test]
+    fn test_trash_restore_and_purge() {
+        let conn = open_test_db();
+        let actor = Actor::system();
+        let est = locations::create_establishment(&conn, &actor, CreateEstablishment {
+            name: "Harbor Tower".into(),
+            street_address: None, city: None, state: None,
+            zip_code: None, industry_description: None, naics_code: None,
+            ein: None, company_name: None, establishment_type: None, case_number_format: None,
+        })
+        .unwrap();
+        let loc = locations::create_location(&conn, &actor, CreateLocation {
+            establishment_id: est.id, name: "Pier 3".into(), address: None, city: None, state: None,
+        })
+        .unwrap();
+        let talk = toolbox::create_talk(&conn, &actor, CreateToolboxTalk {
+            topic_id: None, establishment_id: est.id, location_id: None,
+            title: "Ladder safety".into(), date: "2026-03-02".into(), conducted_by: "Sam".into(), notes: None,
+        })
+        .unwrap();
+        conn.execute(
+            "INSERT INTO incidents (case_number, establishment_id, employee_name, incident_date, description, is_recordable,
+                                    outcome_severity, injury_illness_type)
+             VALUES (1, ?1, 'Pat Doe', '2026-03-04', 'Cut hand', 1, 'other_recordable', 'injury')",
+            [est.id],
+        )
+        .unwrap();
+        let inc = conn.last_insert_rowid();
+
+        incidents::delete_incident(&conn, &actor, inc).unwrap();
+        locations::delete_location(&conn, &actor, loc.id).unwrap();
+        toolbox::delete_talk(&conn, &actor, talk.id).unwrap();
+        assert!(incidents::delete_incident(&conn, &actor, inc).is_err());
+
+        // Trashed rows drop out of lists, the log and the counts
+        let filter = || IncidentFilter { establishment_id: est.id, ..Default::default() };
+        assert_eq!(incidents::list_incidents(&conn, filter(), Audience::Full).unwrap().total, 0);
+        assert!(osha::get_osha_300_log(&conn, est.id, 2026).unwrap().is_empty());
+        assert!(locations::list_locations(&conn, est.id).unwrap().is_empty());
+        assert!(toolbox::list_talks(&conn, est.id).unwrap().is_empty());
+        assert!(incidents::get_incident(&conn, inc).unwrap().deleted_at.is_some());
+
+        let trash = list_trash(&conn, Some(est.id)).unwrap();
+        let kinds: Vec<&str> = trash.iter().map(|t| t.kind.as_str()).collect();
+        assert_eq!(kinds.len(), 3);
+        assert!(kinds.contains(&"incident") && kinds.contains(&"location") && kinds.contains(&"toolbox_talk"));
+
+        let restored = incidents::restore_incident(&conn, &actor, inc).unwrap();
+        assert!(restored.deleted_at.is_none());
+        assert_eq!(restored.case_number, Some(1));
+        assert_eq!(osha::get_osha_300_log(&conn, est.id, 2026).unwrap().len(), 1);
+        assert!(incidents::restore_incident(&conn, &actor, inc).is_err());
+
+        // Only trashed rows can be purged
+        assert!(incidents::purge_incident(&conn, &actor, inc).is_err());
+        toolbox::purge_talk(&conn, &actor, talk.id).unwrap();
+        assert!(toolbox::get_talk(&conn, talk.id).is_err());
+        locations::purge_location(&conn, &actor, loc.id).unwrap();
+        assert_eq!(list_trash(&conn, None).unwrap().len(), 0);
+
+        let history = audit::get_record_history(&conn, "incidents", inc).unwrap();
+        let actions: Vec<&str> = history.iter().map(|h| h.action.as_str()).collect();
+        assert_eq!(actions, ["delete", "restore"]);
+    }
+}
@@ -0,0 +1,417 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::db::audit::{self, Actor};
+use crate::db::incidents::{self, Incident};
+use crate::db::osha;
+use crate::errors::AppError;
+
+pub const WORK_STATUSES: &[&str] = &["full_duty", "restricted", "away", "transferred"];
+
+/// Combined cap on days away and days restricted, 29 CFR 1904.7(b)(3)(vii).
+pub const MAX_COUNTED_DAYS: i64 = 180;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkStatusPeriod {
+    pub id: i64,
+    pub incident_id: i64,
+    pub status: String,
+    pub start_date: String,
+    /// Inclusive; `None` while the period is still running
+    pub end_date: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWorkStatusPeriod {
+    pub incident_id: i64,
+    pub status: String,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Replaces every field, so an end date can be cleared to reopen a period.
+#[derive(Debug, Deserialize)]
+pub struct UpdateWorkStatusPeriod {
+    pub status: String,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub notes: Option<String>,
+}
+
+fn row_to_period(row: &rusqlite::Row<'_>) -> rusqlite::Result<WorkStatusPeriod> {
+    Ok(WorkStatusPeriod {
+        id: row.get(0)?,
+        incident_id: row.get(1)?,
+        status: row.get(2)?,
+        start_date: row.get(3)?,
+        end_date: row.get(4)?,
+        notes: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+const SELECT_COLS: &str = "id, incident_id, status, start_date, end_date, notes, created_at, updated_at";
+
+pub fn get_period(conn: &Connection, id: i64) -> Result<WorkStatusPeriod> {
+    conn.query_row(
+        &format!("SELECT {SELECT_COLS} FROM incident_work_status WHERE id = ?1"),
+        [id],
+        row_to_period,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            AppError::NotFound(format!("Work status period {id} not found")).into()
+        }
+        other => anyhow::Error::from(other).context("Failed to get work status period"),
+    })
+}
+
+pub fn list_periods(conn: &Connection, incident_id: i64) -> Result<Vec<WorkStatusPeriod>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {SELECT_COLS} FROM incident_work_status WHERE incident_id = ?1 ORDER BY start_date, id"
+    ))?;
+    let rows = stmt
+        .query_map([incident_id], row_to_period)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub fn add_period(
+    conn: &Connection,
+    actor: &Actor,
+    data: CreateWorkStatusPeriod,
+    today: NaiveDate,
+) -> Result<WorkStatusPeriod> {
+    super::immediate_transaction(conn, |conn| {
+        conn.execute(
+            "INSERT INTO incident_work_status (incident_id, status, start_date, end_date, notes)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![data.incident_id, data.status, data.start_date, data.end_date, data.notes],
+        )
+        .context("Failed to add work status period")?;
+
+        let period = get_period(conn, conn.last_insert_rowid())?;
+        audit::log_create(conn, actor, "incident_work_status", period.id, &period)?;
+        recompute_counts(conn, actor, period.incident_id, today)?;
+        Ok(period)
+    })
+}
+
+pub fn update_period(
+    conn: &Connection,
+    actor: &Actor,
+    id: i64,
+    data: UpdateWorkStatusPeriod,
+    today: NaiveDate,
+) -> Result<WorkStatusPeriod> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_period(conn, id)?;
+        conn.execute(
+            "UPDATE incident_work_status
+             SET status = ?1, start_date = ?2, end_date = ?3, notes = ?4, updated_at = datetime('now')
+             WHERE id = ?5",
+            params![data.status, data.start_date, data.end_date, data.notes, id],
+        )
+        .context("Failed to update work status period")?;
+
+        let updated = get_period(conn, id)?;
+        audit::log_update(conn, actor, "incident_work_status", id, &existing, &updated)?;
+        recompute_counts(conn, actor, updated.incident_id, today)?;
+        Ok(updated)
+    })
+}
+
+pub fn delete_period(conn: &Connection, actor: &Actor, id: i64, today: NaiveDate) -> Result<()> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = get_period(conn, id)?;
+        conn.execute("DELETE FROM incident_work_status WHERE id = ?1", [id])
+            .context("Failed to delete work status period")?;
+        audit::log_delete(conn, actor, "incident_work_status", id, &existing)?;
+        recompute_counts(conn, actor, existing.incident_id, today)?;
+        Ok(())
+    })
+}
+
+// ── Derived counts ──
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivedCounts {
+    pub days_away: i64,
+    pub days_restricted: i64,
+    pub outcome_severity: String,
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// Weight of a status when periods overlap on the same day; the more serious
+/// one wins. Full duty counts toward nothing.
+fn status_rank(status: &str) -> u8 {
+    match status {
+        "away" => 2,
+        "restricted" | "transferred" => 1,
+        _ => 0,
+    }
+}
+
+/// Counts calendar days the way 1904.7(b)(3) and (b)(4) do: starting the day
+/// after the injury, through `today` at the latest, with away and restricted
+/// days together capped at 180. A running period counts up to `today`, so the
+/// result grows while the case stays open, including across year-end.
+pub fn derive_counts(
+    incident_date: &str,
+    periods: &[WorkStatusPeriod],
+    today: NaiveDate,
+    died: bool,
+) -> DerivedCounts {
+    let first_day = parse_date(incident_date).and_then(|d| d.succ_opt());
+    let ranges: Vec<(NaiveDate, NaiveDate, u8)> = periods
+        .iter()
+        .filter_map(|p| {
+            let rank = status_rank(&p.status);
+            let start = parse_date(&p.start_date)?.max(first_day?);
+            let end = match &p.end_date {
+                Some(end) => parse_date(end)?.min(today),
+                None => today,
+            };
+            (rank > 0 && start <= end).then_some((start, end, rank))
+        })
+        .collect();
+
+    let had_away = ranges.iter().any(|r| r.2 == 2);
+    let mut days_away = 0;
+    let mut days_restricted = 0;
+    if let (Some(mut day), Some(last)) = (
+        ranges.iter().map(|r| r.0).min(),
+        ranges.iter().map(|r| r.1).max(),
+    ) {
+        while day <= last && days_away + days_restricted < MAX_COUNTED_DAYS {
+            let rank = ranges
+                .iter()
+                .filter(|(start, end, _)| *start <= day && day <= *end)
+                .map(|r| r.2)
+                .max();
+            match rank {
+                Some(2) => days_away += 1,
+                Some(_) => days_restricted += 1,
+                None => {}
+            }
+            match day.succ_opt() {
+                Some(next) => day = next,
+                None => break,
+            }
+        }
+    }
+
+    let outcome_severity = if died {
+        "death"
+    } else if had_away {
+        "days_away"
+    } else if days_restricted > 0 {
+        "job_transfer_restriction"
+    } else {
+        "other_recordable"
+    };
+
+    DerivedCounts {
+        days_away,
+        days_restricted,
+        outcome_severity: outcome_severity.to_string(),
+    }
+}
+
+pub fn has_periods(conn: &Connection, incident_id: i64) -> Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM incident_work_status WHERE incident_id = ?1)",
+        [incident_id],
+        |row| row.get(0),
+    )
+    .context("Failed to check work status periods")
+}
+
+/// Rewrites the incident's day counts and outcome from its periods. Incidents
+/// without periods keep their hand-entered values.
+pub fn recompute_counts(
+    conn: &Connection,
+    actor: &Actor,
+    incident_id: i64,
+    today: NaiveDate,
+) -> Result<Incident> {
+    super::immediate_transaction(conn, |conn| {
+        let existing = incidents::get_incident(conn, incident_id)?;
+        let periods = list_periods(conn, incident_id)?;
+        if periods.is_empty() {
+            return Ok(existing);
+        }
+
+        let died = existing.outcome_severity == "death"
+            || existing.date_of_death.as_deref().is_some_and(|d| !d.is_empty());
+        let derived = derive_counts(&existing.incident_date, &periods, today, died);
+        if derived.days_away == existing.days_away_count
+            && derived.days_restricted == existing.days_restricted_count
+            && derived.outcome_severity == existing.outcome_severity
+        {
+            return Ok(existing);
+        }
+
+        conn.execute(
+            "UPDATE incidents
+             SET days_away_count = ?1, days_restricted_count = ?2, outcome_severity = ?3,
+                 updated_at = datetime('now')
+             WHERE id = ?4",
+            params![derived.days_away, derived.days_restricted, derived.outcome_severity, incident_id],
+        )
+        .context("Failed to update derived day counts")?;
+
+        let updated = incidents::get_incident(conn, incident_id)?;
+        osha::record_amendments(conn, actor, &existing, &updated)?;
+        audit::log_update(conn, actor, "incidents", incident_id, &existing, &updated)?;
+        Ok(updated)
+    })
+}
+
+/// Recomputes every live incident whose counts can still grow: those with a
+/// running period or one ending after the counts were last written. Run once
+/// at startup; period edits recompute their own incident, so reads never
+/// rewrite the log. Returns how many incidents were checked.
+pub fn refresh_open_cases(conn: &Connection, actor: &Actor, today: NaiveDate) -> Result<usize> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT i.id FROM incidents i
+         JOIN incident_work_status w ON w.incident_id = i.id
+         WHERE i.deleted_at IS NULL
+           AND (w.end_date IS NULL OR w.end_date >= date(i.updated_at))",
+    )?;
+    let ids = stmt
+        .query_map([], |row| row.get::<_, i64>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    for &id in &ids {
+        recompute_counts(conn, actor, id, today)?;
+    }
+    Ok(ids.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::open_test_db;
+
+    fn date(s: &str) -> NaiveDate {
+        parse_date(s).unwrap()
+    }
+
+    fn period(status: &str, start: &str, end: Option<&str>) -> WorkStatusPeriod {
+        WorkStatusPeriod {
+            id: 0,
+            incident_id: 0,
+            status: status.into(),
+            start_date: start.into(),
+            end_date: end.map(String::from),
+            notes: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_derive_counts_calendar_days_and_cap() {
+        let today = date("2026-06-30");
+
+        // Injury day itself is not counted; weekends are
+        let counts = derive_counts(
+            "2026-03-02",
+            &[
+                period("away", "2026-03-02", Some("2026-03-08")),
+                period("restricted", "2026-03-09", Some("2026-03-13")),
+                period("full_duty", "2026-03-14", None),
+            ],
+            today,
+            false,
+        );
+        assert_eq!((counts.days_away, counts.days_restricted), (6, 5));
+        assert_eq!(counts.outcome_severity, "days_away");
+
+        // Overlapping days count once, as the more serious status
+        let counts = derive_counts(
+            "2026-03-02",
+            &[
+                period("transferred", "2026-03-03", Some("2026-03-10")),
+                period("away", "2026-03-05", Some("2026-03-06")),
+            ],
+            today,
+            false,
+        );
+        assert_eq!((counts.days_away, counts.days_restricted), (2, 6));
+
+        // A running period keeps counting into the next year, up to 180 total
+        let counts = derive_counts(
+            "2025-11-20",
+            &[
+                period("restricted", "2025-11-21", Some("2025-12-20")),
+                period("away", "2025-12-21", None),
+            ],
+            today,
+            false,
+        );
+        assert_eq!((counts.days_away, counts.days_restricted), (150, 30));
+
+        let counts = derive_counts("2026-06-01", &[period("restricted", "2026-06-02", None)], today, false);
+        assert_eq!(counts.days_restricted, 29);
+        assert_eq!(counts.outcome_severity, "job_transfer_restriction");
+        assert_eq!(derive_counts("2026-06-01", &[], today, true).outcome_severity, "death");
+    }
+
+    #[test]
+    fn test_periods_drive_incident_counts() {
+        let conn = open_test_db();
+        let actor = Actor::system();
+        conn.execute("INSERT INTO establishments (name) VALUES ('Test Co')", []).unwrap();
+        conn.execute(
+            "INSERT INTO incidents (establishment_id, employee_name, incident_date, description,
+                                    outcome_severity, days_away_count)
+             VALUES (1, 'Pat Doe', '2026-12-28', 'Fell from ladder', 'other_recordable', 0)",
+            [],
+        )
+        .unwrap();
+        let id = conn.last_insert_rowid();
+
+        let away = add_period(
+            &conn,
+            &actor,
+            CreateWorkStatusPeriod {
+                incident_id: id,
+                status: "away".into(),
+                start_date: "2026-12-29".into(),
+                end_date: None,
+                notes: None,
+            },
+            date("2026-12-31"),
+        )
+        .unwrap();
+        let inc = incidents::get_incident(&conn, id).unwrap();
+        assert_eq!(inc.days_away_count, 3);
+        assert_eq!(inc.outcome_severity, "days_away");
+
+        // Still open after year-end
+        refresh_open_cases(&conn, &actor, date("2027-01-10")).unwrap();
+        assert_eq!(incidents::get_incident(&conn, id).unwrap().days_away_count, 13);
+
+        // Trashed cases are left alone
+        incidents::delete_incident(&conn, &actor, id).unwrap();
+        assert_eq!(refresh_open_cases(&conn, &actor, date("2027-01-20")).unwrap(), 0);
+        assert_eq!(incidents::get_incident(&conn, id).unwrap().days_away_count, 13);
+        incidents::restore_incident(&conn, &actor, id).unwrap();
+
+        delete_period(&conn, &actor, away.id, date("2027-01-10")).unwrap();
+        assert!(!has_periods(&conn, id).unwrap());
+        // The last derived values stay once every period is removed
+        assert_eq!(incidents::get_incident(&conn, id).unwrap().days_away_count, 13);
+    }
+}
@@ -1,6 +1,10 @@
 mod commands;
 mod db;
 mod errors;
+mod pdf;
+mod permissions;
+mod redaction;
+mod sync_client;
 mod validation;
 
 use std::sync::Mutex;
@@ -25,7 +29,19 @@ pub fn run() {
             let conn = db::open_db(&db_path)
                 .map_err(|e| format!("Failed to open database at {:?}: {}", db_path, e))?;
 
+            db::auth::purge_expired_sessions(&conn)
+                .map_err(|e| format!("Failed to purge expired sessions: {}", e))?;
+
+            // Day counts on open cases grow with the calendar
+            db::work_status::refresh_open_cases(
+                &conn,
+                &db::audit::Actor::system(),
+                chrono::Local::now().date_naive(),
+            )
+            .map_err(|e| format!("Failed to refresh work-status day counts: {}", e))?;
+
             app.manage(Mutex::new(conn));
+            app.manage(commands::auth::SessionState::default());
 
             // Create attachments directory
             let attachments_dir = app_data.join("attachments");
@@ -35,6 +51,25 @@ pub fn run() {
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
+            // Auth
+            commands::auth::needs_initial_setup,
+            commands::auth::setup_initial_admin,
+            commands::auth::login,
+            commands::auth::logout,
+            commands::auth::whoami,
+            commands::auth::change_password,
+            commands::auth::list_users,
+            commands::auth::create_user,
+            // Audit
+            commands::audit::get_audit_history,
+            // Sync
+            commands::sync::get_sync_status,
+            commands::sync::set_sync_endpoint,
+            commands::sync::sync_now,
+            commands::sync::list_sync_conflicts,
+            commands::sync::resolve_sync_conflict,
+            commands::sync::list_sync_failures,
+            commands::sync::discard_sync_failure,
             // Establishments
             commands::locations::create_establishment,
             commands::locations::get_establishment,
@@ -51,8 +86,20 @@ pub fn run() {
             commands::incidents::create_incident,
             commands::incidents::get_incident,
             commands::incidents::list_incidents,
+            commands::incidents::search_incidents,
             commands::incidents::update_incident,
             commands::incidents::delete_incident,
+            commands::incidents::update_incident_status,
+            commands::incidents::list_incident_status_changes,
+            commands::incidents::renumber_incident_cases,
+            commands::incidents::list_recordability_treatments,
+            commands::incidents::evaluate_recordability,
+            commands::incidents::get_incident_recordability,
+            commands::incidents::determine_incident_recordability,
+            commands::incidents::list_work_status_periods,
+            commands::incidents::add_work_status_period,
+            commands::incidents::update_work_status_period,
+            commands::incidents::delete_work_status_period,
             // Attachments
             commands::attachments::add_attachment,
             commands::attachments::list_attachments,
@@ -80,9 +127,18 @@ pub fn run() {
             commands::osha::get_osha_300_log,
             commands::osha::get_osha_300a_summary,
             commands::osha::get_osha_301_report,
+            commands::osha::get_privacy_case_list,
             commands::osha::export_osha_300_csv,
+            commands::osha::export_osha_300_pdf,
+            commands::osha::export_osha_300a_pdf,
+            commands::osha::export_osha_301_pdf,
+            commands::osha::validate_osha_ita_submission,
+            commands::osha::export_osha_ita_csv,
             commands::osha::upsert_annual_stats,
             commands::osha::get_annual_stats,
+            commands::osha::close_osha_log,
+            commands::osha::certify_osha_log,
+            commands::osha::list_osha_log_amendments,
             // Dashboard
             commands::dashboard::get_dashboard_summary,
             commands::dashboard::get_incidents_by_month,
@@ -90,6 +146,9 @@ pub fn run() {
             commands::dashboard::get_incidents_by_location,
             commands::dashboard::get_incidents_by_type,
             commands::dashboard::get_corrective_action_summary,
+            commands::dashboard::list_industry_benchmarks,
+            commands::dashboard::export_industry_benchmarks,
+            commands::dashboard::import_industry_benchmarks,
             // Import
             commands::import::preview_csv,
             commands::import::import_csv,
@@ -100,12 +159,18 @@ pub fn run() {
             commands::toolbox::get_toolbox_talk,
             commands::toolbox::list_toolbox_talks,
             commands::toolbox::complete_toolbox_talk,
+            commands::toolbox::delete_toolbox_talk,
             commands::toolbox::add_toolbox_attendee,
             commands::toolbox::list_toolbox_attendees,
             commands::toolbox::sign_toolbox_attendee,
             commands::toolbox::delete_toolbox_attendee,
             // JSA/JHA
             commands::jsa::list_jsa_templates,
+            commands::jsa::get_jsa_template_steps,
+            commands::jsa::add_jsa_template_step,
+            commands::jsa::add_jsa_template_hazard,
+            commands::jsa::add_jsa_template_control,
+            commands::jsa::delete_jsa_template_step,
             commands::jsa::create_jsa_instance,
             commands::jsa::get_jsa_instance,
             commands::jsa::list_jsa_instances,
@@ -113,6 +178,71 @@ pub fn run() {
             commands::jsa::add_jsa_step,
             commands::jsa::list_jsa_steps,
             commands::jsa::toggle_jsa_step,
+            commands::jsa::add_jsa_hazard,
+            commands::jsa::list_jsa_hazards,
+            commands::jsa::update_jsa_hazard,
+            commands::jsa::delete_jsa_hazard,
+            commands::jsa::add_jsa_control,
+            commands::jsa::list_jsa_controls,
+            commands::jsa::update_jsa_control,
+            commands::jsa::delete_jsa_control,
+            commands::jsa::suggest_jsa_hazards,
+            commands::jsa::add_suggested_jsa_hazard,
+            commands::jsa::check_jsa_controls,
+            // Safety Inspections
+            commands::inspections::list_inspection_templates,
+            commands::inspections::list_inspection_checklist_items,
+            commands::inspections::create_inspection,
+            commands::inspections::get_inspection,
+            commands::inspections::list_inspections,
+            commands::inspections::update_inspection,
+            commands::inspections::delete_inspection,
+            commands::inspections::record_inspection_response,
+            commands::inspections::list_inspection_responses,
+            // Near Miss Reporting
+            commands::near_miss::submit_near_miss,
+            commands::near_miss::get_near_miss,
+            commands::near_miss::list_near_misses,
+            commands::near_miss::triage_near_miss,
+            commands::near_miss::close_near_miss,
+            commands::near_miss::promote_near_miss_to_incident,
+            // Training
+            commands::training::list_training_courses,
+            commands::training::get_training_course,
+            commands::training::create_training_course,
+            commands::training::update_training_course,
+            commands::training::delete_training_course,
+            commands::training::create_training_record,
+            commands::training::get_training_record,
+            commands::training::list_training_records,
+            commands::training::update_training_record,
+            commands::training::delete_training_record,
+            commands::training::get_training_compliance_matrix,
+            // Equipment
+            commands::equipment::create_equipment,
+            commands::equipment::get_equipment,
+            commands::equipment::list_equipment,
+            commands::equipment::update_equipment,
+            commands::equipment::delete_equipment,
+            commands::equipment::record_equipment_inspection,
+            commands::equipment::list_equipment_inspections,
+            commands::equipment::list_overdue_equipment_inspections,
+            commands::equipment::add_equipment_maintenance,
+            commands::equipment::list_equipment_maintenance,
+            // Trade hazards
+            commands::trade_hazards::list_trade_hazards,
+            commands::trade_hazards::search_trade_hazards,
+            commands::trade_hazards::list_hazard_trades,
+            commands::trade_hazards::get_trade_hazard,
+            commands::trade_hazards::create_trade_hazard,
+            commands::trade_hazards::update_trade_hazard,
+            commands::trade_hazards::delete_trade_hazard,
+            commands::trade_hazards::export_trade_hazard_library,
+            commands::trade_hazards::import_trade_hazard_library,
+            // Trash
+            commands::trash::list_trash,
+            commands::trash::restore_from_trash,
+            commands::trash::purge_from_trash,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
@@ -0,0 +1,385 @@
+//! Minimal PDF writer for printable reports.
+//!
+//! Pages are drawn with the standard Helvetica fonts, which every PDF viewer
+//! ships, so nothing is embedded and output works fully offline. Text is
+//! WinAnsi encoded; characters outside Latin-1 print as `?`. Coordinates are
+//! in points from the top-left corner of the page.
+
+pub mod osha;
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// US Letter, portrait.
+pub const LETTER: (f32, f32) = (612.0, 792.0);
+/// US Letter, landscape.
+pub const LETTER_LANDSCAPE: (f32, f32) = (792.0, 612.0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+/// Glyph widths (1/1000 em) for ASCII 32..=126, from the Adobe core font
+/// metrics.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722, 722, 667,
+    611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556,
+    278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Width of `text` in points when set in `font` at `size`.
+pub fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    let table = match font {
+        Font::Regular => &HELVETICA_WIDTHS,
+        Font::Bold => &HELVETICA_BOLD_WIDTHS,
+    };
+    let units: u32 = text
+        .chars()
+        .map(|c| match c as u32 {
+            code @ 32..=126 => table[(code - 32) as usize] as u32,
+            _ => 556,
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+/// Cuts `text` to fit in `max_width`, ending with `...` when shortened.
+pub fn truncate_to_width(text: &str, font: Font, size: f32, max_width: f32) -> String {
+    if text_width(text, font, size) <= max_width {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    for c in text.chars() {
+        out.push(c);
+        if text_width(&format!("{out}..."), font, size) > max_width {
+            out.pop();
+            break;
+        }
+    }
+    format!("{}...", out.trim_end())
+}
+
+/// Splits `text` into lines no wider than `max_width`, breaking on spaces
+/// and hard-breaking words that don't fit on a line of their own.
+pub fn wrap_text(text: &str, font: Font, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{line} {word}") };
+            if text_width(&candidate, font, size) <= max_width {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            let mut rest = word.to_string();
+            while text_width(&rest, font, size) > max_width {
+                let mut head = String::new();
+                for c in rest.chars() {
+                    if text_width(&format!("{head}{c}"), font, size) > max_width && !head.is_empty() {
+                        break;
+                    }
+                    head.push(c);
+                }
+                rest = rest[head.len()..].to_string();
+                lines.push(head);
+            }
+            line = rest;
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+/// Encodes `text` as a PDF literal string in WinAnsiEncoding.
+fn pdf_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('(');
+    for c in text.chars() {
+        let byte: u8 = match c {
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201C}' => 0x93,
+            '\u{201D}' => 0x94,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            c if (c as u32) < 32 => b' ',
+            c if (c as u32) <= 126 || (160..=255).contains(&(c as u32)) => c as u32 as u8,
+            _ => b'?',
+        };
+        match byte {
+            b'(' | b')' | b'\\' => {
+                out.push('\\');
+                out.push(byte as char);
+            }
+            32..=126 => out.push(byte as char),
+            _ => {
+                let _ = write!(out, "\\{byte:03o}");
+            }
+        }
+    }
+    out.push(')');
+    out
+}
+
+/// One page of drawing operations.
+pub struct Page {
+    width: f32,
+    height: f32,
+    content: String,
+}
+
+impl Page {
+    pub fn new((width, height): (f32, f32)) -> Self {
+        Page { width, height, content: String::new() }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// Draws `text` with its baseline at `y`.
+    pub fn text(&mut self, x: f32, y: f32, font: Font, size: f32, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let _ = writeln!(
+            self.content,
+            "BT /{} {size:.1} Tf {x:.2} {:.2} Td {} Tj ET",
+            font.resource(),
+            self.height - y,
+            pdf_string(text)
+        );
+    }
+
+    /// Draws `text` so that it ends at `right`.
+    pub fn text_right(&mut self, right: f32, y: f32, font: Font, size: f32, text: &str) {
+        let x = right - text_width(text, font, size);
+        self.text(x, y, font, size, text);
+    }
+
+    /// Draws `text` centred on `center`.
+    pub fn text_centered(&mut self, center: f32, y: f32, font: Font, size: f32, text: &str) {
+        let x = center - text_width(text, font, size) / 2.0;
+        self.text(x, y, font, size, text);
+    }
+
+    /// Draws `text` cut down to `max_width`.
+    pub fn text_fit(&mut self, x: f32, y: f32, font: Font, size: f32, text: &str, max_width: f32) {
+        let fitted = truncate_to_width(text, font, size, max_width);
+        self.text(x, y, font, size, &fitted);
+    }
+
+    /// Draws wrapped text starting with the first baseline at `y`, stopping
+    /// after `max_lines` (the last line is truncated if more text remains).
+    /// Returns the number of lines drawn.
+    #[allow(clippy::too_many_arguments)]
+    pub fn paragraph(
+        &mut self,
+        x: f32,
+        y: f32,
+        font: Font,
+        size: f32,
+        text: &str,
+        max_width: f32,
+        max_lines: usize,
+    ) -> usize {
+        let lines = wrap_text(text, font, size, max_width);
+        let shown = lines.len().min(max_lines);
+        let leading = size * 1.2;
+        for (i, line) in lines.iter().take(shown).enumerate() {
+            let line = if i + 1 == shown && lines.len() > shown {
+                truncate_to_width(&format!("{line} {}", lines[shown]), font, size, max_width)
+            } else {
+                line.clone()
+            };
+            self.text(x, y + i as f32 * leading, font, size, &line);
+        }
+        shown
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, width: f32) {
+        let _ = writeln!(
+            self.content,
+            "{width:.2} w {x1:.2} {:.2} m {x2:.2} {:.2} l S",
+            self.height - y1,
+            self.height - y2
+        );
+    }
+
+    /// Outlines a rectangle whose top-left corner is at `(x, y)`.
+    pub fn rect(&mut self, x: f32, y: f32, w: f32, h: f32, width: f32) {
+        let _ = writeln!(
+            self.content,
+            "{width:.2} w {x:.2} {:.2} {w:.2} {h:.2} re S",
+            self.height - y - h
+        );
+    }
+
+    /// Fills a rectangle with a grey level from 0 (black) to 1 (white).
+    pub fn fill_rect(&mut self, x: f32, y: f32, w: f32, h: f32, gray: f32) {
+        let _ = writeln!(
+            self.content,
+            "{gray:.2} g {x:.2} {:.2} {w:.2} {h:.2} re f 0 g",
+            self.height - y - h
+        );
+    }
+
+    /// A square box with its top-left corner at `(x, y)`, crossed when
+    /// `checked`.
+    pub fn checkbox(&mut self, x: f32, y: f32, size: f32, checked: bool) {
+        self.rect(x, y, size, size, 0.5);
+        if checked {
+            self.line(x + 1.5, y + 1.5, x + size - 1.5, y + size - 1.5, 0.8);
+            self.line(x + 1.5, y + size - 1.5, x + size - 1.5, y + 1.5, 0.8);
+        }
+    }
+}
+
+/// A PDF document built page by page.
+pub struct Document {
+    title: String,
+    pages: Vec<Page>,
+}
+
+impl Document {
+    pub fn new(title: &str) -> Self {
+        Document { title: title.to_string(), pages: Vec::new() }
+    }
+
+    pub fn add_page(&mut self, page: Page) {
+        self.pages.push(page);
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Serialises the document. Content streams are left uncompressed so
+    /// the writer needs no compression library.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Object numbers: 1 catalog, 2 page tree, 3-4 fonts, 5 info, then a
+        // page object and a content stream per page.
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| 6 + i * 2).collect();
+        let mut objects: Vec<String> = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                page_ids.iter().map(|id| format!("{id} 0 R")).collect::<Vec<_>>().join(" "),
+                self.pages.len()
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_string(),
+            format!(
+                "<< /Title {} /Producer (Construction Safety Tracker) /CreationDate (D:{}) >>",
+                pdf_string(&self.title),
+                chrono::Local::now().format("%Y%m%d%H%M%S")
+            ),
+        ];
+        for (page, id) in self.pages.iter().zip(&page_ids) {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.0} {:.0}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                page.width,
+                page.height,
+                id + 1
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                page.content.len(),
+                page.content
+            ));
+        }
+
+        let mut out = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, body) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            let _ = write!(out, "{} 0 obj\n{body}\nendobj\n", i + 1);
+        }
+        let xref_at = out.len();
+        let _ = write!(out, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(out, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            out,
+            "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{xref_at}\n%%EOF\n",
+            objects.len() + 1
+        );
+        out.into_bytes()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_structure() {
+        let mut page = Page::new(LETTER);
+        page.text(72.0, 72.0, Font::Bold, 12.0, "Caf\u{e9} (test) \\ 100%");
+        page.checkbox(72.0, 90.0, 8.0, true);
+        let mut doc = Document::new("Test");
+        doc.add_page(page);
+        doc.add_page(Page::new(LETTER_LANDSCAPE));
+
+        let text = String::from_utf8(doc.to_bytes()).unwrap();
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("(Caf\\351 \\(test\\) \\\\ 100%) Tj"));
+
+        // Every xref offset points at the start of its object
+        let xref = text.rfind("xref\n").unwrap();
+        for (i, entry) in text[xref..].lines().skip(3).take_while(|l| l.ends_with(" n ")).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(text[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+    }
+
+    #[test]
+    fn test_wrap_and_truncate() {
+        let lines = wrap_text("Struck by falling debris from the second floor deck", Font::Regular, 10.0, 100.0);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| text_width(l, Font::Regular, 10.0) <= 100.0));
+
+        let cut = truncate_to_width("A very long employee job title indeed", Font::Regular, 8.0, 60.0);
+        assert!(cut.ends_with("..."));
+        assert!(text_width(&cut, Font::Regular, 8.0) <= 60.0);
+    }
+}
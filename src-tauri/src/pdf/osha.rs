@@ -0,0 +1,709 @@
+//! OSHA Form 300, 300A and 301 layouts.
+//!
+//! These follow the column order, lettering and wording of the official
+//! forms so the printout can be posted or handed to an inspector as-is.
+
+use super::{Document, Font, Page, LETTER, LETTER_LANDSCAPE};
+use crate::db::osha::{Osha300ASummary, Osha300Row, Osha301Report};
+
+const MARGIN: f32 = 24.0;
+
+/// Case rows per Form 300 page.
+pub const ROWS_PER_300_PAGE: usize = 13;
+
+/// Establishment details printed in the Form 300 header.
+pub struct EstablishmentHeader {
+    pub name: String,
+    pub city: String,
+    pub state: String,
+}
+
+fn mark(checked: bool) -> &'static str {
+    if checked { "X" } else { "" }
+}
+
+// ── Form 300 ──
+
+enum Cell {
+    Text,
+    Check,
+    Number,
+}
+
+struct Column {
+    letter: &'static str,
+    label: &'static str,
+    width: f32,
+    cell: Cell,
+}
+
+const COLUMNS_300: &[Column] = &[
+    Column { letter: "(A)", label: "Case no.", width: 32.0, cell: Cell::Text },
+    Column { letter: "(B)", label: "Employee's name", width: 80.0, cell: Cell::Text },
+    Column { letter: "(C)", label: "Job title (e.g., Welder)", width: 70.0, cell: Cell::Text },
+    Column { letter: "(D)", label: "Date of injury or onset of illness", width: 46.0, cell: Cell::Text },
+    Column { letter: "(E)", label: "Where the event occurred", width: 80.0, cell: Cell::Text },
+    Column {
+        letter: "(F)",
+        label: "Describe injury or illness, parts of body affected, and object/substance that directly injured or made person ill",
+        width: 140.0,
+        cell: Cell::Text,
+    },
+    Column { letter: "(G)", label: "Death", width: 26.0, cell: Cell::Check },
+    Column { letter: "(H)", label: "Days away from work", width: 26.0, cell: Cell::Check },
+    Column { letter: "(I)", label: "Job transfer or restriction", width: 26.0, cell: Cell::Check },
+    Column { letter: "(J)", label: "Other record-able cases", width: 26.0, cell: Cell::Check },
+    Column { letter: "(K)", label: "Away from work (days)", width: 30.0, cell: Cell::Number },
+    Column { letter: "(L)", label: "On job transfer or restriction (days)", width: 30.0, cell: Cell::Number },
+    Column { letter: "(1)", label: "Injury", width: 22.0, cell: Cell::Check },
+    Column { letter: "(2)", label: "Skin disorder", width: 22.0, cell: Cell::Check },
+    Column { letter: "(3)", label: "Respira-tory condition", width: 22.0, cell: Cell::Check },
+    Column { letter: "(4)", label: "Poison-ing", width: 22.0, cell: Cell::Check },
+    Column { letter: "(5)", label: "Hearing loss", width: 22.0, cell: Cell::Check },
+    Column { letter: "(6)", label: "All other illnesses", width: 22.0, cell: Cell::Check },
+];
+
+fn row_cells(row: &Osha300Row) -> Vec<String> {
+    vec![
+        row.case_label.clone(),
+        row.employee_name.clone(),
+        row.job_title.clone(),
+        format_short_date(&row.incident_date),
+        row.where_occurred.clone(),
+        row.description.clone(),
+        mark(row.outcome_death).into(),
+        mark(row.outcome_days_away).into(),
+        mark(row.outcome_job_transfer).into(),
+        mark(row.outcome_other_recordable).into(),
+        row.days_away_count.to_string(),
+        row.days_restricted_count.to_string(),
+        mark(row.type_injury).into(),
+        mark(row.type_skin_disorder).into(),
+        mark(row.type_respiratory).into(),
+        mark(row.type_poisoning).into(),
+        mark(row.type_hearing_loss).into(),
+        mark(row.type_other_illness).into(),
+    ]
+}
+
+/// Column totals G through M(6) for the rows on one page.
+pub fn page_totals(rows: &[Osha300Row]) -> [i64; 12] {
+    let count = |f: fn(&Osha300Row) -> bool| rows.iter().filter(|r| f(r)).count() as i64;
+    [
+        count(|r| r.outcome_death),
+        count(|r| r.outcome_days_away),
+        count(|r| r.outcome_job_transfer),
+        count(|r| r.outcome_other_recordable),
+        rows.iter().map(|r| r.days_away_count).sum(),
+        rows.iter().map(|r| r.days_restricted_count).sum(),
+        count(|r| r.type_injury),
+        count(|r| r.type_skin_disorder),
+        count(|r| r.type_respiratory),
+        count(|r| r.type_poisoning),
+        count(|r| r.type_hearing_loss),
+        count(|r| r.type_other_illness),
+    ]
+}
+
+/// `YYYY-MM-DD` as `MM/DD`, the way dates are entered on the 300.
+fn format_short_date(date: &str) -> String {
+    match chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        Ok(d) => d.format("%m/%d").to_string(),
+        Err(_) => date.to_string(),
+    }
+}
+
+fn format_date(date: &str) -> String {
+    match chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        Ok(d) => d.format("%m/%d/%Y").to_string(),
+        Err(_) => date.to_string(),
+    }
+}
+
+/// Renders the Form 300 log, 13 cases per page with page totals. An empty
+/// log still produces one blank page so it can be posted.
+pub fn render_300(establishment: &EstablishmentHeader, year: i64, rows: &[Osha300Row]) -> Document {
+    let mut doc = Document::new(&format!("OSHA Form 300 - {} - {year}", establishment.name));
+    let chunks: Vec<&[Osha300Row]> = if rows.is_empty() {
+        vec![&[]]
+    } else {
+        rows.chunks(ROWS_PER_300_PAGE).collect()
+    };
+    let page_count = chunks.len();
+
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        let mut page = Page::new(LETTER_LANDSCAPE);
+        let right = page.width() - MARGIN;
+
+        // Title block
+        page.text(MARGIN, 34.0, Font::Bold, 14.0, "OSHA's Form 300");
+        page.text(MARGIN, 46.0, Font::Regular, 7.0, "(Rev. 01/2004)");
+        page.text(140.0, 34.0, Font::Bold, 13.0, "Log of Work-Related Injuries and Illnesses");
+        page.text_right(right, 30.0, Font::Bold, 10.0, &format!("Year {year}"));
+        page.text_right(right, 42.0, Font::Regular, 7.0, "U.S. Department of Labor");
+        page.text_right(right, 51.0, Font::Regular, 7.0, "Occupational Safety and Health Administration");
+        page.paragraph(
+            140.0,
+            48.0,
+            Font::Regular,
+            6.5,
+            "Attention: This form contains information relating to employee health and must be used in a manner \
+             that protects the confidentiality of employees to the extent possible while the information is being \
+             used for occupational safety and health purposes.",
+            360.0,
+            3,
+        );
+        page.text(MARGIN, 86.0, Font::Regular, 8.0, "Establishment name:");
+        page.text_fit(104.0, 86.0, Font::Bold, 8.0, &establishment.name, 250.0);
+        page.text(370.0, 86.0, Font::Regular, 8.0, "City:");
+        page.text_fit(392.0, 86.0, Font::Bold, 8.0, &establishment.city, 150.0);
+        page.text(560.0, 86.0, Font::Regular, 8.0, "State:");
+        page.text_fit(586.0, 86.0, Font::Bold, 8.0, &establishment.state, 40.0);
+
+        // Group labels above the column headers
+        let table_top = 110.0;
+        let header_h = 62.0;
+        let row_h = 26.0;
+        let mut x = MARGIN;
+        let groups: [(&str, usize); 4] = [
+            ("Identify the person", 3),
+            ("Describe the case", 3),
+            ("Classify the case", 6),
+            ("Check the \"injury\" column or choose one type of illness:", 6),
+        ];
+        let mut col = 0;
+        for (label, span) in groups {
+            let width: f32 = COLUMNS_300[col..col + span].iter().map(|c| c.width).sum();
+            page.text_centered(x + width / 2.0, table_top - 4.0, Font::Bold, 7.0, label);
+            x += width;
+            col += span;
+        }
+
+        // Column headers
+        let mut x = MARGIN;
+        for column in COLUMNS_300 {
+            page.rect(x, table_top, column.width, header_h, 0.5);
+            page.text_centered(x + column.width / 2.0, table_top + 9.0, Font::Bold, 7.0, column.letter);
+            page.paragraph(x + 2.0, table_top + 18.0, Font::Regular, 5.5, column.label, column.width - 4.0, 7);
+            x += column.width;
+        }
+
+        // Case rows
+        for slot in 0..ROWS_PER_300_PAGE {
+            let y = table_top + header_h + slot as f32 * row_h;
+            let cells = chunk.get(slot).map(row_cells);
+            let mut x = MARGIN;
+            for (i, column) in COLUMNS_300.iter().enumerate() {
+                page.rect(x, y, column.width, row_h, 0.3);
+                if let Some(ref cells) = cells {
+                    let value = &cells[i];
+                    match column.cell {
+                        Cell::Text => {
+                            page.paragraph(x + 2.0, y + 9.0, Font::Regular, 6.5, value, column.width - 4.0, 3);
+                        }
+                        Cell::Check => {
+                            page.text_centered(x + column.width / 2.0, y + 16.0, Font::Bold, 9.0, value);
+                        }
+                        Cell::Number => {
+                            page.text_right(x + column.width - 3.0, y + 16.0, Font::Regular, 8.0, value);
+                        }
+                    }
+                }
+                x += column.width;
+            }
+        }
+
+        // Page totals under columns G through M(6)
+        let totals_y = table_top + header_h + ROWS_PER_300_PAGE as f32 * row_h;
+        let totals = page_totals(chunk);
+        let label_width: f32 = COLUMNS_300[..6].iter().map(|c| c.width).sum();
+        page.text_right(MARGIN + label_width - 4.0, totals_y + 13.0, Font::Bold, 8.0, "Page totals");
+        let mut x = MARGIN + label_width;
+        for (column, total) in COLUMNS_300[6..].iter().zip(totals) {
+            page.rect(x, totals_y, column.width, 20.0, 0.8);
+            page.text_centered(x + column.width / 2.0, totals_y + 13.0, Font::Bold, 8.0, &total.to_string());
+            x += column.width;
+        }
+
+        page.text(
+            MARGIN,
+            totals_y + 34.0,
+            Font::Bold,
+            7.0,
+            "Be sure to transfer these totals to the Summary page (Form 300A) before you post it.",
+        );
+        page.paragraph(
+            MARGIN,
+            totals_y + 46.0,
+            Font::Regular,
+            6.0,
+            "Public reporting burden for this collection of information is estimated to average 14 minutes per \
+             response. Persons are not required to respond to the collection of information unless it displays a \
+             currently valid OMB control number.",
+            420.0,
+            3,
+        );
+        page.text_right(right, totals_y + 34.0, Font::Regular, 8.0, &format!("Page {} of {page_count}", index + 1));
+
+        doc.add_page(page);
+    }
+
+    doc
+}
+
+// ── Form 300A ──
+
+fn labelled_value(page: &mut Page, x: f32, y: f32, width: f32, value: &str, label: &str) {
+    page.text_fit(x, y, Font::Bold, 10.0, value, width);
+    page.line(x, y + 3.0, x + width, y + 3.0, 0.5);
+    page.text(x, y + 12.0, Font::Regular, 7.0, label);
+}
+
+fn total_box(page: &mut Page, x: f32, y: f32, value: i64, letter: &str, label: &str) {
+    page.rect(x, y, 100.0, 24.0, 0.8);
+    page.text_centered(x + 50.0, y + 16.0, Font::Bold, 12.0, &value.to_string());
+    page.text_centered(x + 50.0, y + 34.0, Font::Bold, 7.0, letter);
+    page.paragraph(x, y + 43.0, Font::Regular, 6.5, label, 100.0, 3);
+}
+
+/// Renders the Form 300A summary with the establishment, employment and
+/// certification blocks.
+pub fn render_300a(summary: &Osha300ASummary) -> Document {
+    let mut doc = Document::new(&format!(
+        "OSHA Form 300A - {} - {}",
+        summary.establishment_name, summary.year
+    ));
+    let mut page = Page::new(LETTER);
+    let right = page.width() - MARGIN - 12.0;
+    let left = MARGIN + 12.0;
+
+    page.text(left, 44.0, Font::Bold, 15.0, "OSHA's Form 300A");
+    page.text(left, 56.0, Font::Regular, 7.0, "(Rev. 01/2004)");
+    page.text(left, 76.0, Font::Bold, 12.0, "Summary of Work-Related Injuries and Illnesses");
+    page.text_right(right, 40.0, Font::Bold, 11.0, &format!("Year {}", summary.year));
+    page.text_right(right, 52.0, Font::Regular, 7.0, "U.S. Department of Labor");
+    page.text_right(right, 61.0, Font::Regular, 7.0, "Occupational Safety and Health Administration");
+
+    page.paragraph(
+        left,
+        94.0,
+        Font::Regular,
+        7.5,
+        "All establishments covered by Part 1904 must complete this Summary page, even if no work-related \
+         injuries or illnesses occurred during the year. Remember to review the Log to verify that the entries \
+         are complete and accurate before completing this summary. Using the Log, count the individual entries \
+         you made for each category. Then write the totals below, making sure you've added the entries from \
+         every page of the Log. If you had no cases, write \"0.\"",
+        right - left,
+        5,
+    );
+    page.paragraph(
+        left,
+        150.0,
+        Font::Regular,
+        7.5,
+        "Employees, former employees, and their representatives have the right to review the OSHA Form 300 in \
+         its entirety. They also have limited access to the OSHA Form 301 or its equivalent.",
+        right - left,
+        3,
+    );
+
+    // Number of cases
+    page.fill_rect(left, 176.0, right - left, 14.0, 0.85);
+    page.text(left + 4.0, 186.0, Font::Bold, 9.0, "Number of Cases");
+    let cases = [
+        (summary.total_deaths, "(G)", "Total number of deaths"),
+        (summary.total_days_away_cases, "(H)", "Total number of cases with days away from work"),
+        (summary.total_transfer_restriction_cases, "(I)", "Total number of cases with job transfer or restriction"),
+        (summary.total_other_recordable_cases, "(J)", "Total number of other recordable cases"),
+    ];
+    for (i, (value, letter, label)) in cases.into_iter().enumerate() {
+        total_box(&mut page, left + 10.0 + i as f32 * 135.0, 198.0, value, letter, label);
+    }
+
+    // Number of days
+    page.fill_rect(left, 272.0, right - left, 14.0, 0.85);
+    page.text(left + 4.0, 282.0, Font::Bold, 9.0, "Number of Days");
+    total_box(&mut page, left + 10.0, 294.0, summary.total_days_away, "(K)", "Total number of days away from work");
+    total_box(
+        &mut page,
+        left + 145.0,
+        294.0,
+        summary.total_days_restricted,
+        "(L)",
+        "Total number of days of job transfer or restriction",
+    );
+
+    // Injury and illness types
+    page.fill_rect(left, 366.0, right - left, 14.0, 0.85);
+    page.text(left + 4.0, 376.0, Font::Bold, 9.0, "Injury and Illness Types");
+    page.text(left + 10.0, 394.0, Font::Regular, 8.0, "Total number of ...");
+    let types = [
+        ("(M)(1) Injuries", summary.total_injuries),
+        ("(2) Skin disorders", summary.total_skin_disorders),
+        ("(3) Respiratory conditions", summary.total_respiratory),
+        ("(4) Poisonings", summary.total_poisonings),
+        ("(5) Hearing loss", summary.total_hearing_loss),
+        ("(6) All other illnesses", summary.total_other_illnesses),
+    ];
+    for (i, (label, value)) in types.into_iter().enumerate() {
+        let x = left + 10.0 + (i / 3) as f32 * 260.0;
+        let y = 410.0 + (i % 3) as f32 * 20.0;
+        page.text(x, y, Font::Regular, 9.0, label);
+        page.rect(x + 150.0, y - 11.0, 60.0, 15.0, 0.8);
+        page.text_centered(x + 180.0, y, Font::Bold, 10.0, &value.to_string());
+    }
+
+    page.paragraph(
+        left,
+        476.0,
+        Font::Bold,
+        8.0,
+        "Post this Summary page from February 1 to April 30 of the year following the year covered by the form.",
+        right - left,
+        2,
+    );
+
+    // Establishment information
+    let col2 = left + 280.0;
+    page.fill_rect(left, 494.0, 260.0, 14.0, 0.85);
+    page.text(left + 4.0, 504.0, Font::Bold, 9.0, "Establishment information");
+    labelled_value(&mut page, left, 526.0, 250.0, &summary.establishment_name, "Your establishment name");
+    labelled_value(&mut page, left, 552.0, 250.0, &summary.street_address, "Street");
+    let city_line = [summary.city.as_str(), summary.state.as_str(), summary.zip_code.as_str()]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    labelled_value(&mut page, left, 578.0, 250.0, &city_line, "City, State, ZIP");
+    labelled_value(
+        &mut page,
+        left,
+        604.0,
+        250.0,
+        &summary.industry_description,
+        "Industry description (e.g., Manufacture of motor truck trailers)",
+    );
+    labelled_value(&mut page, left, 630.0, 120.0, &summary.naics_code, "North American Industrial Classification (NAICS)");
+
+    // Employment information
+    page.fill_rect(col2, 494.0, right - col2, 14.0, 0.85);
+    page.text(col2 + 4.0, 504.0, Font::Bold, 9.0, "Employment information");
+    labelled_value(
+        &mut page,
+        col2,
+        526.0,
+        right - col2,
+        &summary.avg_employees.map(|n| n.to_string()).unwrap_or_default(),
+        "Annual average number of employees",
+    );
+    labelled_value(
+        &mut page,
+        col2,
+        552.0,
+        right - col2,
+        &summary.total_hours_worked.map(|n| n.to_string()).unwrap_or_default(),
+        "Total hours worked by all employees last year",
+    );
+
+    // Certification
+    page.fill_rect(col2, 572.0, right - col2, 14.0, 0.85);
+    page.text(col2 + 4.0, 582.0, Font::Bold, 9.0, "Sign here");
+    page.text(col2, 598.0, Font::Bold, 7.0, "Knowingly falsifying this document may result in a fine.");
+    page.paragraph(
+        col2,
+        610.0,
+        Font::Regular,
+        7.0,
+        "I certify that I have examined this document and that to the best of my knowledge the entries are \
+         true, accurate, and complete.",
+        right - col2,
+        3,
+    );
+    let name = summary.certifier_name.clone().unwrap_or_default();
+    let title = summary.certifier_title.clone().unwrap_or_default();
+    let phone = summary.certifier_phone.clone().unwrap_or_default();
+    let date = summary.certification_date.as_deref().map(format_date).unwrap_or_default();
+    let half = (right - col2 - 10.0) / 2.0;
+    labelled_value(&mut page, col2, 650.0, half, &name, "Company executive");
+    labelled_value(&mut page, col2 + half + 10.0, 650.0, half, &title, "Title");
+    labelled_value(&mut page, col2, 678.0, half, &phone, "Phone");
+    labelled_value(&mut page, col2 + half + 10.0, 678.0, half, &date, "Date");
+
+    page.paragraph(
+        left,
+        730.0,
+        Font::Regular,
+        6.0,
+        "Public reporting burden for this collection of information is estimated to average 58 minutes per \
+         response, including time to review the instructions, search and gather the data needed, and complete \
+         and review the collection of information.",
+        right - left,
+        3,
+    );
+
+    doc.add_page(page);
+    doc
+}
+
+// ── Form 301 ──
+
+/// Draws a numbered question with its answer underneath and returns the y
+/// position for the next one.
+#[allow(clippy::too_many_arguments)]
+fn question(page: &mut Page, x: f32, y: f32, width: f32, number: u8, prompt: &str, answer: &str, lines: usize) -> f32 {
+    page.text(x, y, Font::Bold, 7.5, &format!("{number})"));
+    let used = page.paragraph(x + 14.0, y, Font::Regular, 7.5, prompt, width - 14.0, 2);
+    let answer_y = y + used as f32 * 9.0 + 3.0;
+    for i in 0..lines {
+        page.line(x + 14.0, answer_y + 3.0 + i as f32 * 11.0, x + width, answer_y + 3.0 + i as f32 * 11.0, 0.3);
+    }
+    page.paragraph(x + 16.0, answer_y, Font::Bold, 8.5, answer, width - 18.0, lines);
+    answer_y + lines as f32 * 11.0 + 6.0
+}
+
+fn yes_no(page: &mut Page, x: f32, y: f32, value: bool) {
+    page.checkbox(x + 14.0, y - 7.0, 7.0, value);
+    page.text(x + 24.0, y, Font::Regular, 7.5, "Yes");
+    page.checkbox(x + 50.0, y - 7.0, 7.0, !value);
+    page.text(x + 60.0, y, Font::Regular, 7.5, "No");
+}
+
+/// Renders one Form 301 incident report.
+pub fn render_301(report: &Osha301Report) -> Document {
+    let mut doc = Document::new(&format!("OSHA Form 301 - Case {}", report.case_label));
+    let mut page = Page::new(LETTER);
+    let left = MARGIN + 12.0;
+    let right = page.width() - MARGIN - 12.0;
+    let split = left + 230.0;
+
+    page.text(left, 44.0, Font::Bold, 15.0, "OSHA's Form 301");
+    page.text(left, 56.0, Font::Regular, 7.0, "(Rev. 01/2004)");
+    page.text(left, 76.0, Font::Bold, 12.0, "Injury and Illness Incident Report");
+    page.text_right(right, 52.0, Font::Regular, 7.0, "U.S. Department of Labor");
+    page.text_right(right, 61.0, Font::Regular, 7.0, "Occupational Safety and Health Administration");
+    page.paragraph(
+        left,
+        92.0,
+        Font::Regular,
+        6.5,
+        "Attention: This form contains information relating to employee health and must be used in a manner \
+         that protects the confidentiality of employees to the extent possible while the information is being \
+         used for occupational safety and health purposes.",
+        right - left,
+        3,
+    );
+
+    // Left column: completed by, then employee and health care professional
+    let col_w = split - left - 12.0;
+    page.paragraph(
+        left,
+        128.0,
+        Font::Regular,
+        7.0,
+        "This Injury and Illness Incident Report is one of the first forms you must fill out when a recordable \
+         work-related injury or illness has occurred. Together with the Log and the accompanying Summary, these \
+         forms help the employer and OSHA develop a picture of the extent and severity of work-related incidents.",
+        col_w,
+        8,
+    );
+    let mut y = 214.0;
+    page.text(left, y, Font::Bold, 8.0, "Completed by");
+    y += 14.0;
+    labelled_value(&mut page, left, y, col_w, &report.completed_by, "Name");
+    labelled_value(&mut page, left, y + 24.0, col_w, &report.completed_by_title, "Title");
+    labelled_value(&mut page, left, y + 48.0, col_w / 2.0 - 4.0, &report.completed_by_phone, "Phone");
+    labelled_value(
+        &mut page,
+        left + col_w / 2.0 + 4.0,
+        y + 48.0,
+        col_w / 2.0 - 4.0,
+        &format_date(&report.completed_date),
+        "Date",
+    );
+
+    y += 80.0;
+    page.fill_rect(left, y - 10.0, col_w, 14.0, 0.85);
+    page.text(left + 4.0, y, Font::Bold, 8.5, "Information about the employee");
+    y += 16.0;
+    y = question(&mut page, left, y, col_w, 1, "Full name", &report.employee_name, 1);
+    let city_line = [report.employee_city.as_str(), report.employee_state.as_str(), report.employee_zip.as_str()]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    y = question(&mut page, left, y, col_w, 2, "Street, City, State, ZIP", &format!("{}\n{city_line}", report.employee_address), 2);
+    y = question(&mut page, left, y, col_w, 3, "Date of birth", &format_date(&report.employee_dob), 1);
+    y = question(&mut page, left, y, col_w, 4, "Date hired", &format_date(&report.employee_hire_date), 1);
+    page.text(left, y, Font::Bold, 7.5, "5)");
+    page.checkbox(left + 14.0, y - 7.0, 7.0, report.employee_gender == "male");
+    page.text(left + 24.0, y, Font::Regular, 7.5, "Male");
+    page.checkbox(left + 56.0, y - 7.0, 7.0, report.employee_gender == "female");
+    page.text(left + 66.0, y, Font::Regular, 7.5, "Female");
+    y += 20.0;
+
+    page.fill_rect(left, y - 10.0, col_w, 14.0, 0.85);
+    page.paragraph(left + 4.0, y, Font::Bold, 8.0, "Information about the physician or other health care professional", col_w - 8.0, 2);
+    y += 22.0;
+    y = question(&mut page, left, y, col_w, 6, "Name of physician or other health care professional", &report.physician_name, 1);
+    let facility = [
+        report.treatment_facility.as_str(),
+        report.facility_address.as_str(),
+        report.facility_city_state_zip.as_str(),
+    ]
+    .iter()
+    .filter(|s| !s.is_empty())
+    .cloned()
+    .collect::<Vec<_>>()
+    .join("\n");
+    y = question(
+        &mut page,
+        left,
+        y,
+        col_w,
+        7,
+        "If treatment was given away from the worksite, where was it given?",
+        &facility,
+        3,
+    );
+    page.text(left, y, Font::Bold, 7.5, "8)");
+    page.paragraph(left + 14.0, y, Font::Regular, 7.5, "Was employee treated in an emergency room?", col_w - 14.0, 1);
+    yes_no(&mut page, left, y + 12.0, report.treated_in_er);
+    y += 28.0;
+    page.text(left, y, Font::Bold, 7.5, "9)");
+    page.paragraph(left + 14.0, y, Font::Regular, 7.5, "Was employee hospitalized overnight as an in-patient?", col_w - 14.0, 1);
+    yes_no(&mut page, left, y + 12.0, report.hospitalized_overnight);
+
+    // Right column: the case
+    let rx = split;
+    let rw = right - split;
+    let mut y = 128.0;
+    page.fill_rect(rx, y - 10.0, rw, 14.0, 0.85);
+    page.text(rx + 4.0, y, Font::Bold, 8.5, "Information about the case");
+    y += 16.0;
+    y = question(&mut page, rx, y, rw, 10, "Case number from the Log", &report.case_label, 1);
+    y = question(&mut page, rx, y, rw, 11, "Date of injury or illness", &format_date(&report.incident_date), 1);
+    y = question(&mut page, rx, y, rw, 12, "Time employee began work", &report.work_start_time, 1);
+    let time_of_event = if report.incident_time.is_empty() {
+        "Time cannot be determined".to_string()
+    } else {
+        report.incident_time.clone()
+    };
+    y = question(&mut page, rx, y, rw, 13, "Time of event", &time_of_event, 1);
+    y = question(
+        &mut page,
+        rx,
+        y,
+        rw,
+        14,
+        "What was the employee doing just before the incident occurred? Describe the activity, as well as the \
+         tools, equipment, or material the employee was using.",
+        &report.activity_before_incident,
+        4,
+    );
+    y = question(
+        &mut page,
+        rx,
+        y,
+        rw,
+        15,
+        "What happened? Tell us how the injury occurred.",
+        &report.how_injury_occurred,
+        5,
+    );
+    y = question(
+        &mut page,
+        rx,
+        y,
+        rw,
+        16,
+        "What was the injury or illness? Tell us the part of the body that was affected and how it was affected.",
+        &report.injury_description,
+        4,
+    );
+    y = question(
+        &mut page,
+        rx,
+        y,
+        rw,
+        17,
+        "What object or substance directly harmed the employee?",
+        &report.object_substance,
+        3,
+    );
+    question(
+        &mut page,
+        rx,
+        y,
+        rw,
+        18,
+        "If the employee died, when did death occur? Date of death",
+        &format_date(&report.date_of_death),
+        1,
+    );
+
+    page.paragraph(
+        left,
+        752.0,
+        Font::Regular,
+        6.0,
+        "Public reporting burden for this collection of information is estimated to average 22 minutes per \
+         response, including time for reviewing instructions, searching existing data sources, gathering and \
+         maintaining the data needed, and completing and reviewing the collection of information.",
+        right - left,
+        3,
+    );
+
+    doc.add_page(page);
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(case_number: i64, days_away: i64) -> Osha300Row {
+        Osha300Row {
+            case_number,
+            case_label: case_number.to_string(),
+            employee_name: "Pat Doe".into(),
+            job_title: "Carpenter".into(),
+            incident_date: "2026-03-15".into(),
+            where_occurred: "Level 2".into(),
+            description: "Laceration to left hand from circular saw".into(),
+            outcome_death: false,
+            outcome_days_away: days_away > 0,
+            outcome_job_transfer: false,
+            outcome_other_recordable: days_away == 0,
+            days_away_count: days_away,
+            days_restricted_count: 0,
+            type_injury: true,
+            type_skin_disorder: false,
+            type_respiratory: false,
+            type_poisoning: false,
+            type_hearing_loss: false,
+            type_other_illness: false,
+        }
+    }
+
+    #[test]
+    fn test_300_paginates_with_page_totals() {
+        let rows: Vec<Osha300Row> = (1..=15).map(|n| row(n, if n % 2 == 0 { 3 } else { 0 })).collect();
+        let header = EstablishmentHeader { name: "ABC Construction".into(), city: "Chicago".into(), state: "IL".into() };
+
+        let doc = render_300(&header, 2026, &rows);
+        assert_eq!(doc.page_count(), 2);
+
+        let first = page_totals(&rows[..ROWS_PER_300_PAGE]);
+        assert_eq!(first[1], 6); // (H) days-away cases
+        assert_eq!(first[3], 7); // (J) other recordable
+        assert_eq!(first[4], 18); // (K) days away
+        assert_eq!(page_totals(&rows[ROWS_PER_300_PAGE..])[6], 2);
+
+        let text = String::from_utf8(doc.to_bytes()).unwrap();
+        assert!(text.contains("(Page 2 of 2) Tj"));
+        assert_eq!(render_300(&header, 2026, &[]).page_count(), 1);
+    }
+}
@@ -0,0 +1,305 @@
+use crate::commands::auth::{require_session, SessionState};
+use crate::db::auth::Session;
+use crate::errors::AppError;
+
+/// Roles from least to most privileged. Each role can do everything the
+/// roles before it can.
+pub const ROLE_HIERARCHY: &[&str] = &["field_worker", "supervisor", "safety_manager", "admin"];
+
+const FIELD_WORKER: &str = "field_worker";
+const SUPERVISOR: &str = "supervisor";
+const SAFETY_MANAGER: &str = "safety_manager";
+const ADMIN: &str = "admin";
+
+/// Minimum role for every command that requires a session. A command missing
+/// from this table is denied for everyone.
+const PERMISSIONS: &[(&str, &str)] = &[
+    // Auth
+    ("change_password", FIELD_WORKER),
+    ("list_users", ADMIN),
+    ("create_user", ADMIN),
+    // Audit
+    ("get_audit_history", SAFETY_MANAGER),
+    // Sync
+    ("get_sync_status", FIELD_WORKER),
+    ("set_sync_endpoint", ADMIN),
+    ("sync_now", FIELD_WORKER),
+    ("list_sync_conflicts", SUPERVISOR),
+    ("resolve_sync_conflict", SAFETY_MANAGER),
+    ("list_sync_failures", SUPERVISOR),
+    ("discard_sync_failure", SAFETY_MANAGER),
+    // Establishments & locations
+    ("create_establishment", SAFETY_MANAGER),
+    ("get_establishment", FIELD_WORKER),
+    ("list_establishments", FIELD_WORKER),
+    ("update_establishment", SAFETY_MANAGER),
+    ("delete_establishment", SAFETY_MANAGER),
+    ("create_location", SAFETY_MANAGER),
+    ("get_location", FIELD_WORKER),
+    ("list_locations", FIELD_WORKER),
+    ("update_location", SAFETY_MANAGER),
+    ("delete_location", SAFETY_MANAGER),
+    // Incidents
+    ("create_incident", FIELD_WORKER),
+    ("get_incident", FIELD_WORKER),
+    ("list_incidents", FIELD_WORKER),
+    ("search_incidents", FIELD_WORKER),
+    ("update_incident", SUPERVISOR),
+    ("delete_incident", SAFETY_MANAGER),
+    ("update_incident_status", SUPERVISOR),
+    ("list_incident_status_changes", FIELD_WORKER),
+    ("renumber_incident_cases", SAFETY_MANAGER),
+    ("list_recordability_treatments", FIELD_WORKER),
+    ("evaluate_recordability", FIELD_WORKER),
+    ("get_incident_recordability", FIELD_WORKER),
+    ("determine_incident_recordability", SUPERVISOR),
+    ("list_work_status_periods", FIELD_WORKER),
+    ("add_work_status_period", SUPERVISOR),
+    ("update_work_status_period", SUPERVISOR),
+    ("delete_work_status_period", SUPERVISOR),
+    // Attachments
+    ("add_attachment", FIELD_WORKER),
+    ("list_attachments", FIELD_WORKER),
+    ("delete_attachment", SUPERVISOR),
+    ("upload_attachment", FIELD_WORKER),
+    // RCA
+    ("create_rca_session", SUPERVISOR),
+    ("get_rca_session", FIELD_WORKER),
+    ("list_rca_sessions", FIELD_WORKER),
+    ("complete_rca_session", SAFETY_MANAGER),
+    ("delete_rca_session", SAFETY_MANAGER),
+    ("add_five_whys_step", SUPERVISOR),
+    ("list_five_whys_steps", FIELD_WORKER),
+    ("update_five_whys_step", SUPERVISOR),
+    ("add_fishbone_category", SUPERVISOR),
+    ("list_fishbone_categories", FIELD_WORKER),
+    ("add_fishbone_cause", SUPERVISOR),
+    ("update_fishbone_cause", SUPERVISOR),
+    ("delete_fishbone_cause", SAFETY_MANAGER),
+    ("create_corrective_action", SUPERVISOR),
+    ("list_corrective_actions", FIELD_WORKER),
+    ("update_corrective_action", SUPERVISOR),
+    ("delete_corrective_action", SAFETY_MANAGER),
+    // OSHA
+    ("get_osha_300_log", SAFETY_MANAGER),
+    ("get_osha_300a_summary", SAFETY_MANAGER),
+    ("get_osha_301_report", SAFETY_MANAGER),
+    ("get_privacy_case_list", SAFETY_MANAGER),
+    ("export_osha_300_csv", SAFETY_MANAGER),
+    ("export_osha_300_pdf", SAFETY_MANAGER),
+    ("export_osha_300a_pdf", SAFETY_MANAGER),
+    ("export_osha_301_pdf", SAFETY_MANAGER),
+    ("validate_osha_ita_submission", SAFETY_MANAGER),
+    ("export_osha_ita_csv", SAFETY_MANAGER),
+    ("upsert_annual_stats", SAFETY_MANAGER),
+    ("get_annual_stats", SAFETY_MANAGER),
+    ("close_osha_log", SAFETY_MANAGER),
+    ("certify_osha_log", SAFETY_MANAGER),
+    ("list_osha_log_amendments", SAFETY_MANAGER),
+    // Dashboard
+    ("get_dashboard_summary", SUPERVISOR),
+    ("get_incidents_by_month", SUPERVISOR),
+    ("get_incidents_by_severity", SUPERVISOR),
+    ("get_incidents_by_location", SUPERVISOR),
+    ("get_incidents_by_type", SUPERVISOR),
+    ("get_corrective_action_summary", SUPERVISOR),
+    ("list_industry_benchmarks", SUPERVISOR),
+    ("export_industry_benchmarks", SAFETY_MANAGER),
+    ("import_industry_benchmarks", ADMIN),
+    // Import
+    ("preview_csv", SAFETY_MANAGER),
+    ("import_csv", SAFETY_MANAGER),
+    // Toolbox talks
+    ("list_toolbox_topics", FIELD_WORKER),
+    ("get_toolbox_topic", FIELD_WORKER),
+    ("create_toolbox_talk", SUPERVISOR),
+    ("get_toolbox_talk", FIELD_WORKER),
+    ("list_toolbox_talks", FIELD_WORKER),
+    ("complete_toolbox_talk", SUPERVISOR),
+    ("add_toolbox_attendee", SUPERVISOR),
+    ("list_toolbox_attendees", FIELD_WORKER),
+    ("sign_toolbox_attendee", FIELD_WORKER),
+    ("delete_toolbox_attendee", SUPERVISOR),
+    ("delete_toolbox_talk", SUPERVISOR),
+    // JSA
+    ("list_jsa_templates", FIELD_WORKER),
+    ("get_jsa_template_steps", FIELD_WORKER),
+    ("add_jsa_template_step", SAFETY_MANAGER),
+    ("add_jsa_template_hazard", SAFETY_MANAGER),
+    ("add_jsa_template_control", SAFETY_MANAGER),
+    ("delete_jsa_template_step", SAFETY_MANAGER),
+    ("create_jsa_instance", SUPERVISOR),
+    ("get_jsa_instance", FIELD_WORKER),
+    ("list_jsa_instances", FIELD_WORKER),
+    ("update_jsa_status", SUPERVISOR),
+    ("add_jsa_step", SUPERVISOR),
+    ("list_jsa_steps", FIELD_WORKER),
+    ("toggle_jsa_step", FIELD_WORKER),
+    ("add_jsa_hazard", SUPERVISOR),
+    ("list_jsa_hazards", FIELD_WORKER),
+    ("update_jsa_hazard", SUPERVISOR),
+    ("delete_jsa_hazard", SUPERVISOR),
+    ("add_jsa_control", SUPERVISOR),
+    ("list_jsa_controls", FIELD_WORKER),
+    ("update_jsa_control", SUPERVISOR),
+    ("delete_jsa_control", SUPERVISOR),
+    ("suggest_jsa_hazards", FIELD_WORKER),
+    ("add_suggested_jsa_hazard", SUPERVISOR),
+    ("check_jsa_controls", FIELD_WORKER),
+    // Safety inspections
+    ("list_inspection_templates", FIELD_WORKER),
+    ("list_inspection_checklist_items", FIELD_WORKER),
+    ("create_inspection", SUPERVISOR),
+    ("get_inspection", FIELD_WORKER),
+    ("list_inspections", FIELD_WORKER),
+    ("update_inspection", SUPERVISOR),
+    ("delete_inspection", SAFETY_MANAGER),
+    ("record_inspection_response", SUPERVISOR),
+    ("list_inspection_responses", FIELD_WORKER),
+    // Near miss reporting
+    ("submit_near_miss", FIELD_WORKER),
+    ("get_near_miss", FIELD_WORKER),
+    ("list_near_misses", FIELD_WORKER),
+    ("triage_near_miss", SUPERVISOR),
+    ("close_near_miss", SAFETY_MANAGER),
+    ("promote_near_miss_to_incident", SAFETY_MANAGER),
+    // Training
+    ("list_training_courses", FIELD_WORKER),
+    ("get_training_course", FIELD_WORKER),
+    ("create_training_course", SAFETY_MANAGER),
+    ("update_training_course", SAFETY_MANAGER),
+    ("delete_training_course", SAFETY_MANAGER),
+    ("create_training_record", SUPERVISOR),
+    ("get_training_record", FIELD_WORKER),
+    ("list_training_records", FIELD_WORKER),
+    ("update_training_record", SUPERVISOR),
+    ("delete_training_record", SAFETY_MANAGER),
+    ("get_training_compliance_matrix", SUPERVISOR),
+    // Equipment
+    ("create_equipment", SUPERVISOR),
+    ("get_equipment", FIELD_WORKER),
+    ("list_equipment", FIELD_WORKER),
+    ("update_equipment", SUPERVISOR),
+    ("delete_equipment", SAFETY_MANAGER),
+    ("record_equipment_inspection", FIELD_WORKER),
+    ("list_equipment_inspections", FIELD_WORKER),
+    ("list_overdue_equipment_inspections", SUPERVISOR),
+    ("add_equipment_maintenance", SUPERVISOR),
+    ("list_equipment_maintenance", FIELD_WORKER),
+    // Trade hazards
+    ("list_trade_hazards", FIELD_WORKER),
+    ("search_trade_hazards", FIELD_WORKER),
+    ("list_hazard_trades", FIELD_WORKER),
+    ("get_trade_hazard", FIELD_WORKER),
+    ("create_trade_hazard", SAFETY_MANAGER),
+    ("update_trade_hazard", SAFETY_MANAGER),
+    ("delete_trade_hazard", SAFETY_MANAGER),
+    ("export_trade_hazard_library", SAFETY_MANAGER),
+    ("import_trade_hazard_library", SAFETY_MANAGER),
+    // Trash
+    ("list_trash", SAFETY_MANAGER),
+    ("restore_from_trash", SAFETY_MANAGER),
+    ("purge_from_trash", ADMIN),
+];
+
+fn role_rank(role: &str) -> Option<usize> {
+    ROLE_HIERARCHY.iter().position(|r| *r == role)
+}
+
+/// True if `role` is `min_role` or above. Unknown roles have no rights.
+pub fn has_role(role: &str, min_role: &str) -> bool {
+    match (role_rank(role), role_rank(min_role)) {
+        (Some(have), Some(need)) => have >= need,
+        _ => false,
+    }
+}
+
+/// Minimum role required for a command, or `None` if no rule exists.
+pub fn required_role(command: &str) -> Option<&'static str> {
+    PERMISSIONS
+        .iter()
+        .find(|(name, _)| *name == command)
+        .map(|(_, role)| *role)
+}
+
+/// Checks that the logged-in user may call `command` and returns their session.
+/// Call this first in every command handler, before taking the DB lock.
+pub fn authorize(session: &SessionState, command: &str) -> Result<Session, AppError> {
+    let current = require_session(session)?;
+    check_role(&current.role, command)?;
+    Ok(current)
+}
+
+fn check_role(role: &str, command: &str) -> Result<(), AppError> {
+    let min_role = required_role(command)
+        .ok_or_else(|| AppError::Forbidden(format!("No permission rule for {command}")))?;
+
+    if !has_role(role, min_role) {
+        return Err(AppError::Forbidden(format!(
+            "{command} requires the {min_role} role or higher"
+        )));
+    }
+    Ok(())
+}
+
+/// Employee names and details of privacy cases (1904.29(b)(7)) are limited
+/// to safety managers and admins. Applied through `redaction`.
+pub fn can_view_privacy_case_names(role: &str) -> bool {
+    has_role(role, SAFETY_MANAGER)
+}
+
+/// Trashed records are only opened by those who can manage the trash.
+pub fn can_view_trash(role: &str) -> bool {
+    required_role("list_trash").is_some_and(|min_role| has_role(role, min_role))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Commands callable without a session (login flow).
+    const PUBLIC_COMMANDS: &[&str] = &[
+        "needs_initial_setup",
+        "setup_initial_admin",
+        "login",
+        "logout",
+        "whoami",
+    ];
+
+    #[test]
+    fn test_role_hierarchy() {
+        assert!(has_role("admin", "field_worker"));
+        assert!(has_role("supervisor", "supervisor"));
+        assert!(!has_role("supervisor", "safety_manager"));
+        assert!(!has_role("contractor", "field_worker"));
+
+        assert!(check_role("field_worker", "delete_incident").is_err());
+        assert!(check_role("field_worker", "upsert_annual_stats").is_err());
+        assert!(check_role("safety_manager", "delete_incident").is_ok());
+        assert!(check_role("admin", "no_such_command").is_err());
+
+        assert!(!can_view_privacy_case_names("supervisor"));
+        assert!(can_view_privacy_case_names("safety_manager"));
+        assert!(!can_view_trash("supervisor"));
+        assert!(can_view_trash("safety_manager"));
+    }
+
+    #[test]
+    fn test_every_registered_command_has_a_rule() {
+        let lib = include_str!("lib.rs");
+        let registered: Vec<&str> = lib
+            .lines()
+            .map(str::trim)
+            .filter(|l| l.starts_with("commands::") && l.ends_with(','))
+            .filter_map(|l| l.trim_end_matches(',').rsplit("::").next())
+            .collect();
+        assert!(!registered.is_empty());
+
+        for command in registered {
+            assert!(
+                PUBLIC_COMMANDS.contains(&command) || required_role(command).is_some(),
+                "{command} has no permission rule"
+            );
+        }
+    }
+}
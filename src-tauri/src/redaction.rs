@@ -0,0 +1,148 @@
+//! Privacy concern case masking, 29 CFR 1904.29(b)(6)-(9).
+//!
+//! Every output that can carry an employee's name or the details of a
+//! privacy case goes through here. Forms that are posted, handed to
+//! employees or submitted to OSHA always use [`Audience::Restricted`];
+//! in-app views use the caller's role.
+
+use crate::db::incidents::Incident;
+use crate::db::osha::Osha301Report;
+use crate::permissions;
+
+/// Printed in place of the employee's name, as 1904.29(b)(6) requires.
+pub const PRIVACY_CASE_NAME: &str = "Privacy Case";
+
+/// Printed in place of the description when no general wording was given.
+pub const WITHHELD_DESCRIPTION: &str = "Details withheld (privacy case)";
+
+/// The 1904.29(b)(7) categories.
+pub const PRIVACY_CONCERNS: &[&str] = &[
+    "intimate_body_part",
+    "sexual_assault",
+    "mental_illness",
+    "hiv_hepatitis_tuberculosis",
+    "contaminated_sharps",
+    "employee_request",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Audience {
+    /// Sees names and details of privacy cases
+    Full,
+    /// Sees the case with the employee and the details removed
+    Restricted,
+}
+
+impl Audience {
+    pub fn for_role(role: &str) -> Self {
+        if permissions::can_view_privacy_case_names(role) {
+            Audience::Full
+        } else {
+            Audience::Restricted
+        }
+    }
+
+    fn masks(self, is_privacy_case: bool) -> bool {
+        is_privacy_case && self == Audience::Restricted
+    }
+}
+
+pub fn employee_name(audience: Audience, is_privacy_case: bool, name: String) -> String {
+    if audience.masks(is_privacy_case) {
+        PRIVACY_CASE_NAME.to_string()
+    } else {
+        name
+    }
+}
+
+/// The general wording from `privacy_description` when the case is masked,
+/// per 1904.29(b)(9).
+pub fn description(
+    audience: Audience,
+    is_privacy_case: bool,
+    description: String,
+    privacy_description: Option<&str>,
+) -> String {
+    if !audience.masks(is_privacy_case) {
+        return description;
+    }
+    privacy_description
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .unwrap_or(WITHHELD_DESCRIPTION)
+        .to_string()
+}
+
+/// Masks the name and description and drops everything else that could
+/// identify the employee or reveal the nature of the case.
+pub fn incident(audience: Audience, incident: &mut Incident) {
+    if !audience.masks(incident.is_privacy_case) {
+        return;
+    }
+    incident.employee_name = PRIVACY_CASE_NAME.to_string();
+    incident.description = description(
+        audience,
+        true,
+        std::mem::take(&mut incident.description),
+        incident.privacy_description.as_deref(),
+    );
+    incident.privacy_concern = None;
+    for field in [
+        &mut incident.employee_address,
+        &mut incident.employee_city,
+        &mut incident.employee_state,
+        &mut incident.employee_zip,
+        &mut incident.employee_dob,
+        &mut incident.employee_hire_date,
+        &mut incident.employee_gender,
+        &mut incident.activity_before_incident,
+        &mut incident.how_injury_occurred,
+        &mut incident.injury_description,
+        &mut incident.object_substance,
+        &mut incident.physician_name,
+        &mut incident.treatment_facility,
+        &mut incident.facility_address,
+        &mut incident.facility_city_state_zip,
+    ] {
+        *field = None;
+    }
+}
+
+pub fn incidents(audience: Audience, incidents: &mut [Incident]) {
+    for item in incidents {
+        incident(audience, item);
+    }
+}
+
+/// Masks the employee section and the case narratives of a 301.
+pub fn osha_301(
+    audience: Audience,
+    is_privacy_case: bool,
+    privacy_description: Option<&str>,
+    report: &mut Osha301Report,
+) {
+    if !audience.masks(is_privacy_case) {
+        return;
+    }
+    report.employee_name = PRIVACY_CASE_NAME.to_string();
+    report.injury_description = description(
+        audience,
+        true,
+        std::mem::take(&mut report.injury_description),
+        privacy_description,
+    );
+    for field in [
+        &mut report.employee_address,
+        &mut report.employee_city,
+        &mut report.employee_state,
+        &mut report.employee_zip,
+        &mut report.employee_dob,
+        &mut report.employee_hire_date,
+        &mut report.employee_gender,
+        &mut report.activity_before_incident,
+        &mut report.how_injury_occurred,
+        &mut report.object_substance,
+    ] {
+        field.clear();
+    }
+}
@@ -0,0 +1,77 @@
+//! HTTP client for the sync server (see `sync-server/`).
+//!
+//! `POST {endpoint}/push` uploads captured changes; `GET {endpoint}/pull`
+//! returns changes from other devices after a server sequence number.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::db::sync::Change;
+
+/// Changes per push request, so a long offline period doesn't become one
+/// giant body.
+pub const PUSH_BATCH_SIZE: usize = 500;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Serialize)]
+struct PushRequest<'a> {
+    device_id: &'a str,
+    changes: &'a [Change],
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PushResponse {
+    pub accepted: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RemoteChange {
+    pub seq: i64,
+    pub device_id: String,
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PullResponse {
+    pub changes: Vec<RemoteChange>,
+    /// Cursor to send as `since` on the next pull
+    pub last_seq: i64,
+    pub has_more: bool,
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new().timeout(TIMEOUT).build()
+}
+
+fn url(endpoint: &str, path: &str) -> String {
+    format!("{}/{path}", endpoint.trim_end_matches('/'))
+}
+
+pub fn push(endpoint: &str, device_id: &str, changes: &[Change]) -> Result<usize> {
+    let mut accepted = 0;
+    for batch in changes.chunks(PUSH_BATCH_SIZE) {
+        let response: PushResponse = agent()
+            .post(&url(endpoint, "push"))
+            .send_json(PushRequest { device_id, changes: batch })
+            .with_context(|| format!("Push to {endpoint} failed"))?
+            .into_json()
+            .context("Invalid push response from sync server")?;
+        accepted += response.accepted;
+    }
+    Ok(accepted)
+}
+
+/// Changes made by other devices after `since`.
+pub fn pull(endpoint: &str, device_id: &str, since: i64) -> Result<PullResponse> {
+    agent()
+        .get(&url(endpoint, "pull"))
+        .query("device_id", device_id)
+        .query("since", &since.to_string())
+        .call()
+        .with_context(|| format!("Pull from {endpoint} failed"))?
+        .into_json()
+        .context("Invalid pull response from sync server")
+}
@@ -0,0 +1,236 @@
+//! Reference sync server for Construction Safety Tracker.
+//!
+//! Keeps an append-only log of row changes pushed by devices in SQLite and
+//! hands them back to other devices in order. Conflict handling happens on
+//! the clients; the server only orders and relays.
+//!
+//! Usage: `sync-server [--db PATH] [--addr HOST:PORT]`
+
+use anyhow::{anyhow, Context, Result};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const DEFAULT_DB: &str = "sync-server.db";
+const DEFAULT_ADDR: &str = "127.0.0.1:8787";
+const PULL_LIMIT: i64 = 500;
+
+/// Same shape as the client's `db::sync::Change`.
+#[derive(Debug, Serialize, Deserialize)]
+struct Change {
+    table: String,
+    uuid: String,
+    operation: String,
+    data: Option<Map<String, Value>>,
+    changed_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PushRequest {
+    device_id: String,
+    changes: Vec<Change>,
+}
+
+#[derive(Debug, Serialize)]
+struct PushResponse {
+    accepted: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct RemoteChange {
+    seq: i64,
+    device_id: String,
+    #[serde(flatten)]
+    change: Change,
+}
+
+#[derive(Debug, Serialize)]
+struct PullResponse {
+    changes: Vec<RemoteChange>,
+    last_seq: i64,
+    has_more: bool,
+}
+
+fn open_store(path: &str) -> Result<Connection> {
+    let conn = Connection::open(path).with_context(|| format!("Failed to open {path}"))?;
+    conn.execute_batch(
+        "PRAGMA journal_mode=WAL;
+         CREATE TABLE IF NOT EXISTS changes (
+             seq INTEGER PRIMARY KEY AUTOINCREMENT,
+             device_id TEXT NOT NULL,
+             table_name TEXT NOT NULL,
+             uuid TEXT NOT NULL,
+             operation TEXT NOT NULL,
+             data TEXT,
+             changed_at TEXT NOT NULL,
+             received_at TEXT NOT NULL DEFAULT (datetime('now'))
+         );
+         CREATE INDEX IF NOT EXISTS idx_changes_record ON changes(table_name, uuid);",
+    )
+    .context("Failed to initialise change store")?;
+    Ok(conn)
+}
+
+fn push(conn: &Connection, request: PushRequest) -> Result<PushResponse> {
+    if request.device_id.trim().is_empty() {
+        return Err(anyhow!("device_id is required"));
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    for change in &request.changes {
+        let data = change.data.as_ref().map(serde_json::to_string).transpose()?;
+        tx.execute(
+            "INSERT INTO changes (device_id, table_name, uuid, operation, data, changed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                request.device_id,
+                change.table,
+                change.uuid,
+                change.operation,
+                data,
+                change.changed_at,
+            ],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(PushResponse { accepted: request.changes.len() })
+}
+
+/// Changes from other devices after `since`. When there are none the cursor
+/// still advances past the caller's own changes.
+fn pull(conn: &Connection, device_id: &str, since: i64) -> Result<PullResponse> {
+    let mut stmt = conn.prepare(
+        "SELECT seq, device_id, table_name, uuid, operation, data, changed_at
+         FROM changes
+         WHERE seq > ?1 AND device_id != ?2
+         ORDER BY seq
+         LIMIT ?3",
+    )?;
+    let mut changes = stmt
+        .query_map(params![since, device_id, PULL_LIMIT + 1], |row| {
+            let data: Option<String> = row.get(5)?;
+            Ok(RemoteChange {
+                seq: row.get(0)?,
+                device_id: row.get(1)?,
+                change: Change {
+                    table: row.get(2)?,
+                    uuid: row.get(3)?,
+                    operation: row.get(4)?,
+                    data: data.and_then(|s| serde_json::from_str(&s).ok()),
+                    changed_at: row.get(6)?,
+                },
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let has_more = changes.len() as i64 > PULL_LIMIT;
+    changes.truncate(PULL_LIMIT as usize);
+
+    let last_seq = match changes.last() {
+        Some(c) if has_more => c.seq,
+        _ => conn.query_row("SELECT COALESCE(MAX(seq), ?1) FROM changes", [since], |row| row.get(0))?,
+    };
+
+    Ok(PullResponse { changes, last_seq, has_more })
+}
+
+fn query_param<'a>(url: &'a str, name: &str) -> Option<&'a str> {
+    url.split_once('?')?
+        .1
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn json_response<T: Serialize>(status: u16, body: &T) -> Response<std::io::Cursor<Vec<u8>>> {
+    let body = serde_json::to_vec(body).unwrap_or_default();
+    Response::from_data(body)
+        .with_status_code(status)
+        .with_header(Header::from_bytes("Content-Type", "application/json").expect("static header"))
+}
+
+fn handle(conn: &Connection, request: &mut Request) -> Result<Response<std::io::Cursor<Vec<u8>>>> {
+    let url = request.url().to_string();
+    let path = url.split('?').next().unwrap_or_default();
+
+    match (request.method(), path) {
+        (Method::Post, "/push") => {
+            let body: PushRequest = serde_json::from_reader(request.as_reader())
+                .context("Invalid push body")?;
+            Ok(json_response(200, &push(conn, body)?))
+        }
+        (Method::Get, "/pull") => {
+            let device_id = query_param(&url, "device_id").ok_or_else(|| anyhow!("device_id is required"))?;
+            let since = query_param(&url, "since")
+                .map(str::parse::<i64>)
+                .transpose()
+                .context("since must be an integer")?
+                .unwrap_or(0);
+            Ok(json_response(200, &pull(conn, device_id, since)?))
+        }
+        _ => Ok(json_response(404, &serde_json::json!({ "error": "Not found" }))),
+    }
+}
+
+fn main() -> Result<()> {
+    let mut db_path = DEFAULT_DB.to_string();
+    let mut addr = DEFAULT_ADDR.to_string();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--db" => db_path = args.next().context("--db needs a path")?,
+            "--addr" => addr = args.next().context("--addr needs HOST:PORT")?,
+            other => return Err(anyhow!("Unknown argument: {other}")),
+        }
+    }
+
+    let conn = open_store(&db_path)?;
+    let server = Server::http(&addr).map_err(|e| anyhow!("Failed to listen on {addr}: {e}"))?;
+    println!("Sync server listening on http://{addr} (store: {db_path})");
+
+    for mut request in server.incoming_requests() {
+        let response = handle(&conn, &mut request).unwrap_or_else(|e| {
+            json_response(400, &serde_json::json!({ "error": format!("{e:#}") }))
+        });
+        if let Err(e) = request.respond(response) {
+            eprintln!("Failed to send response: {e}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(uuid: &str) -> Change {
+        Change {
+            table: "locations".into(),
+            uuid: uuid.into(),
+            operation: "upsert".into(),
+            data: Some(Map::new()),
+            changed_at: "2026-05-01 08:00:00".into(),
+        }
+    }
+
+    #[test]
+    fn test_pull_skips_own_changes() {
+        let conn = open_store(":memory:").unwrap();
+        push(&conn, PushRequest { device_id: "a".into(), changes: vec![change("1"), change("2")] }).unwrap();
+        push(&conn, PushRequest { device_id: "b".into(), changes: vec![change("3")] }).unwrap();
+
+        let for_a = pull(&conn, "a", 0).unwrap();
+        assert_eq!(for_a.changes.len(), 1);
+        assert_eq!(for_a.changes[0].change.uuid, "3");
+        assert_eq!(for_a.last_seq, 3);
+        assert!(!for_a.has_more);
+
+        let for_b = pull(&conn, "b", 0).unwrap();
+        assert_eq!(for_b.changes.len(), 2);
+        assert!(pull(&conn, "b", for_b.last_seq).unwrap().changes.is_empty());
+    }
+}
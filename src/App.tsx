@@ -12,12 +12,14 @@ import { RcaPage } from './components/rca/RcaPage';
 import { OshaPage } from './components/osha/OshaPage';
 import { SettingsPage } from './components/settings/SettingsPage';
 import { SetupWizard } from './components/settings/SetupWizard';
+import { LoginPage } from './components/auth/LoginPage';
 import { ImportPage } from './components/import/ImportPage';
 import { ToolboxTalksPage } from './pages/ToolboxTalksPage';
 import { ToolboxCreatePage } from './pages/ToolboxCreatePage';
 import { ToolboxTalkDetailPage } from './pages/ToolboxTalkDetailPage';
 import { JsaPage } from './pages/JsaPage';
 import { useSettingsStore } from './stores/settingsStore';
+import { useAuthStore } from './stores/authStore';
 import { useKeyboardShortcuts, createGlobalShortcuts } from './hooks/useKeyboardShortcuts';
 import './index.css';
 
@@ -48,24 +50,47 @@ function AppRoutes() {
   );
 }
 
+function LoadingScreen() {
+  return (
+    <div className="flex items-center justify-center h-screen bg-gray-50">
+      <div className="text-center">
+        <div className="animate-spin rounded-full h-12 w-12 border-b-2 border-safety-orange mx-auto" />
+        <p className="mt-4 text-gray-600">Loading...</p>
+      </div>
+    </div>
+  );
+}
+
 function App() {
+  const { session, checking, checkSession } = useAuthStore();
   const { loadEstablishments, establishments, loading } = useSettingsStore();
 
   useEffect(() => {
-    loadEstablishments();
-  }, [loadEstablishments]);
+    checkSession();
+  }, [checkSession]);
 
-  if (loading) {
+  // Every command needs a session, so nothing loads until there is one
+  useEffect(() => {
+    if (session) loadEstablishments();
+  }, [session, loadEstablishments]);
+
+  if (checking) {
+    return <LoadingScreen />;
+  }
+
+  if (!session) {
     return (
-      <div className="flex items-center justify-center h-screen bg-gray-50">
-        <div className="text-center">
-          <div className="animate-spin rounded-full h-12 w-12 border-b-2 border-safety-orange mx-auto" />
-          <p className="mt-4 text-gray-600">Loading...</p>
-        </div>
-      </div>
+      <>
+        <ToastContainer />
+        <LoginPage />
+      </>
     );
   }
 
+  if (loading) {
+    return <LoadingScreen />;
+  }
+
   if (!loading && establishments.length === 0) {
     return <SetupWizard />;
   }
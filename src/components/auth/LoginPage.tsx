@@ -0,0 +1,80 @@
+import { useState, type FormEvent } from 'react';
+import { useAuthStore } from '../../stores/authStore';
+
+const MIN_PASSWORD_LENGTH = 8;
+
+export function LoginPage() {
+  const { needsSetup, notice, setupAdmin, login } = useAuthStore();
+  const [username, setUsername] = useState('');
+  const [password, setPassword] = useState('');
+  const [confirm, setConfirm] = useState('');
+  const [error, setError] = useState('');
+  const [saving, setSaving] = useState(false);
+
+  const handleSubmit = async (e: FormEvent) => {
+    e.preventDefault();
+    setError('');
+    if (needsSetup) {
+      if (password.length < MIN_PASSWORD_LENGTH) {
+        setError(`Password must be at least ${MIN_PASSWORD_LENGTH} characters`);
+        return;
+      }
+      if (password !== confirm) {
+        setError('Passwords do not match');
+        return;
+      }
+    }
+    setSaving(true);
+    try {
+      if (needsSetup) {
+        await setupAdmin(password);
+      } else {
+        await login(username.trim(), password);
+      }
+    } catch (err) {
+      setError(String(err));
+      setSaving(false);
+    }
+  };
+
+  return (
+    <div className="min-h-screen bg-gray-50 flex items-center justify-center p-4">
+      <form onSubmit={handleSubmit} className="bg-white rounded-lg shadow-lg max-w-sm w-full p-8 space-y-4">
+        <div className="text-center">
+          <h1 className="text-2xl font-bold text-gray-900">Safety Tracker</h1>
+          <p className="text-gray-500 mt-2 text-sm">
+            {needsSetup
+              ? 'Choose a password for the admin account to finish first-time setup'
+              : 'Sign in to continue'}
+          </p>
+        </div>
+
+        {notice && !needsSetup && (
+          <p className="text-sm text-amber-700 bg-amber-50 rounded p-2">{notice}</p>
+        )}
+
+        {!needsSetup && (
+          <input type="text" value={username} onChange={e => setUsername(e.target.value)} autoFocus
+            className="w-full border rounded px-3 py-2 text-sm" placeholder="Username" autoComplete="username" />
+        )}
+        <input type="password" value={password} onChange={e => setPassword(e.target.value)} autoFocus={needsSetup}
+          className="w-full border rounded px-3 py-2 text-sm" placeholder={needsSetup ? 'Admin password' : 'Password'}
+          autoComplete={needsSetup ? 'new-password' : 'current-password'} />
+        {needsSetup && (
+          <input type="password" value={confirm} onChange={e => setConfirm(e.target.value)}
+            className="w-full border rounded px-3 py-2 text-sm" placeholder="Confirm password" autoComplete="new-password" />
+        )}
+
+        {error && <p className="text-sm text-red-600">{error}</p>}
+
+        <button type="submit" disabled={saving}
+          className="w-full bg-safety-orange text-white px-4 py-2 rounded text-sm disabled:opacity-50">
+          {needsSetup ? 'Set Password' : 'Sign In'}
+        </button>
+        {needsSetup && (
+          <p className="text-xs text-gray-500 text-center">You will sign in as <span className="font-mono">admin</span></p>
+        )}
+      </form>
+    </div>
+  );
+}
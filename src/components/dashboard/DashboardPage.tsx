@@ -50,6 +50,26 @@ export function DashboardPage() {
             <SummaryCard label="TRIR" value={summary?.trir != null ? summary.trir.toFixed(2) : 'N/A'} />
           </div>
 
+          {/* Rates vs. industry */}
+          <div className="bg-white rounded-lg shadow p-4">
+            <h3 className="font-semibold mb-3">
+              Rates vs. Industry
+              {summary?.benchmark && (
+                <span className="ml-2 text-sm font-normal text-gray-500">
+                  {summary.benchmark.industry} (BLS {summary.benchmark.source_year})
+                </span>
+              )}
+            </h3>
+            <div className="grid grid-cols-2 md:grid-cols-6 gap-4">
+              <RateCell label="TRIR" value={summary?.trir} benchmark={summary?.benchmark?.trir} />
+              <RateCell label="DART" value={summary?.dart_rate} benchmark={summary?.benchmark?.dart_rate} />
+              <RateCell label="LTIR" value={summary?.ltir} benchmark={summary?.benchmark?.ltir} />
+              <RateCell label="Severity" value={summary?.severity_rate} />
+              <RateCell label="Fatality (per 100k)" value={summary?.fatality_rate} benchmark={summary?.benchmark?.fatality_rate} />
+              <RateCell label="Near Misses / Incident" value={summary?.near_miss_ratio} />
+            </div>
+          </div>
+
           {/* Charts */}
           <div className="grid grid-cols-1 lg:grid-cols-2 gap-6">
             {/* Incidents by Month */}
@@ -145,6 +165,19 @@ export function DashboardPage() {
   );
 }
 
+function RateCell({ label, value, benchmark }: { label: string; value?: number | null; benchmark?: number | null }) {
+  const above = value != null && benchmark != null && value > benchmark;
+  return (
+    <div>
+      <p className="text-sm text-gray-500">{label}</p>
+      <p className={`text-xl font-bold ${above ? 'text-safety-red' : 'text-gray-900'}`}>
+        {value != null ? value.toFixed(2) : 'N/A'}
+      </p>
+      {benchmark != null && <p className="text-xs text-gray-500">Industry {benchmark.toFixed(1)}</p>}
+    </div>
+  );
+}
+
 function SummaryCard({ label, value, color }: { label: string; value: string | number; color?: string }) {
   return (
     <div className="bg-white rounded-lg shadow p-4">
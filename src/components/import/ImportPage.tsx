@@ -1,5 +1,5 @@
 import { useState } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../../lib/invoke';
 import { open } from '@tauri-apps/plugin-dialog';
 import { useSettingsStore } from '../../stores/settingsStore';
 import type { CsvPreview, ColumnMapping, ImportResult } from '../../lib/types';
@@ -15,6 +15,7 @@ const MAPPABLE_FIELDS = [
   { key: 'days_restricted_count', label: 'Days Restricted' },
   { key: 'injury_illness_type', label: 'Injury/Illness Type' },
   { key: 'employee_gender', label: 'Gender' },
+  { key: 'is_privacy_case', label: 'Privacy Case' },
 ] as const;
 
 export function ImportPage() {
@@ -1,5 +1,5 @@
 import { useState } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../../lib/invoke';
 import { X, Image, Volume2, FileText, Trash2 } from 'lucide-react';
 import { useToast } from '../../hooks/useToast';
 import type { Attachment } from '../../lib/types';
@@ -1,5 +1,5 @@
 import { useState } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../../lib/invoke';
 import { open } from '@tauri-apps/plugin-dialog';
 import { Upload, Camera, Mic, FileText } from 'lucide-react';
 import { useToast } from '../../hooks/useToast';
@@ -1,26 +1,49 @@
 import { useEffect, useState } from 'react';
 import { useParams, useNavigate, Link } from 'react-router-dom';
+import { invoke } from '../../lib/invoke';
 import { useIncidentStore } from '../../stores/incidentStore';
-import { OUTCOME_SEVERITY_LABELS, INJURY_TYPE_LABELS, INCIDENT_STATUS_LABELS } from '../../lib/constants';
-import type { IncidentStatus } from '../../lib/types';
+import { useToast } from '../../hooks/useToast';
+import {
+  OUTCOME_SEVERITY_LABELS, INJURY_TYPE_LABELS, INCIDENT_STATUS_LABELS, INCIDENT_STATUS_TRANSITIONS,
+} from '../../lib/constants';
+import type { IncidentStatus, IncidentStatusChange } from '../../lib/types';
 
 export function IncidentDetailPage() {
   const { id } = useParams<{ id: string }>();
   const navigate = useNavigate();
-  const { currentIncident: incident, attachments, loading, loadIncident, updateIncident, deleteIncident, clearCurrent } = useIncidentStore();
+  const { currentIncident: incident, attachments, loading, loadIncident, updateIncidentStatus, deleteIncident, clearCurrent } = useIncidentStore();
+  const toast = useToast();
   const [confirmDelete, setConfirmDelete] = useState(false);
+  const [statusHistory, setStatusHistory] = useState<IncidentStatusChange[]>([]);
 
   useEffect(() => {
     if (id) loadIncident(Number(id));
     return () => clearCurrent();
   }, [id, loadIncident, clearCurrent]);
 
+  useEffect(() => {
+    if (!incident) return;
+    invoke<IncidentStatusChange[]>('list_incident_status_changes', { incidentId: incident.id })
+      .then(setStatusHistory)
+      .catch(() => setStatusHistory([]));
+  }, [incident?.id, incident?.status]);
+
   if (loading || !incident) {
     return <p className="text-gray-500">{loading ? 'Loading...' : 'Incident not found'}</p>;
   }
 
   const handleStatusChange = async (status: IncidentStatus) => {
-    await updateIncident(incident.id, { status });
+    let reason: string | undefined;
+    if (incident.status === 'closed') {
+      reason = prompt('Why is this incident being reopened?')?.trim();
+      if (!reason) return;
+    }
+    try {
+      await updateIncidentStatus(incident.id, status, reason);
+      toast.success(`Status changed to ${INCIDENT_STATUS_LABELS[status]}`);
+    } catch (error) {
+      toast.error(`${error}`);
+    }
   };
 
   const handleDelete = async () => {
@@ -46,12 +69,12 @@ export function IncidentDetailPage() {
         <div>
           <div className="flex items-center gap-3 mb-1">
             <h1 className="text-2xl font-bold">
-              Case #{incident.case_number ?? 'N/A'}
+              Case #{incident.case_label ?? incident.case_number ?? 'N/A'}
             </h1>
             <span className={`text-xs px-2 py-1 rounded-full font-medium ${
-              incident.status === 'open' ? 'bg-orange-100 text-orange-800' :
-              incident.status === 'in_review' ? 'bg-blue-100 text-blue-800' :
-              'bg-green-100 text-green-800'
+              incident.status === 'reported' ? 'bg-orange-100 text-orange-800' :
+              incident.status === 'closed' ? 'bg-green-100 text-green-800' :
+              'bg-blue-100 text-blue-800'
             }`}>
               {INCIDENT_STATUS_LABELS[incident.status]}
             </span>
@@ -63,11 +86,12 @@ export function IncidentDetailPage() {
             className="bg-safety-blue text-white px-3 py-1.5 rounded text-sm hover:bg-blue-600">
             Root Cause Analysis
           </Link>
-          <select value={incident.status} onChange={e => handleStatusChange(e.target.value as IncidentStatus)}
+          <select value="" onChange={e => e.target.value && handleStatusChange(e.target.value as IncidentStatus)}
             className="border rounded px-3 py-1.5 text-sm">
-            <option value="open">Open</option>
-            <option value="in_review">In Review</option>
-            <option value="closed">Closed</option>
+            <option value="">{incident.status === 'closed' ? 'Reopen...' : 'Move to...'}</option>
+            {(INCIDENT_STATUS_TRANSITIONS[incident.status] ?? []).map(s => (
+              <option key={s} value={s}>{INCIDENT_STATUS_LABELS[s]}</option>
+            ))}
           </select>
         </div>
       </div>
@@ -147,12 +171,30 @@ export function IncidentDetailPage() {
         )}
       </section>
 
+      {/* Status History */}
+      {statusHistory.length > 0 && (
+        <section className="bg-white rounded-lg shadow p-6">
+          <h2 className="font-semibold text-lg mb-4">Status History</h2>
+          <ul className="space-y-2 text-sm">
+            {statusHistory.map(change => (
+              <li key={change.id} className="border-l-2 border-gray-200 pl-3">
+                <span className="font-medium">
+                  {INCIDENT_STATUS_LABELS[change.from_status]} → {INCIDENT_STATUS_LABELS[change.to_status]}
+                </span>
+                <span className="text-gray-500 ml-2">{change.changed_at}{change.changed_by ? ` by ${change.changed_by}` : ''}</span>
+                {change.reason && <p className="text-gray-700 mt-0.5">{change.reason}</p>}
+              </li>
+            ))}
+          </ul>
+        </section>
+      )}
+
       {/* Danger Zone */}
       <section className="border border-red-200 rounded-lg p-6">
         <h2 className="font-semibold text-lg text-red-700 mb-2">Danger Zone</h2>
         {confirmDelete ? (
           <div className="flex items-center gap-3">
-            <p className="text-sm text-red-600">Are you sure? This cannot be undone.</p>
+            <p className="text-sm text-red-600">Move this incident to the trash? It can be restored from Settings.</p>
             <button onClick={handleDelete} className="bg-red-600 text-white px-3 py-1.5 rounded text-sm">
               Yes, Delete
             </button>
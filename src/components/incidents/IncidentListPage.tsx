@@ -1,19 +1,41 @@
 import { useEffect, useState } from 'react';
+import { invoke } from '../../lib/invoke';
 import { Link, useNavigate } from 'react-router-dom';
 import { useSettingsStore } from '../../stores/settingsStore';
 import { useIncidentStore } from '../../stores/incidentStore';
-import { OUTCOME_SEVERITY_LABELS, INCIDENT_STATUS_LABELS } from '../../lib/constants';
-import type { IncidentStatus, OutcomeSeverity } from '../../lib/types';
+import { OUTCOME_SEVERITY_LABELS, INCIDENT_STATUS_LABELS, INJURY_TYPE_LABELS } from '../../lib/constants';
+import type {
+  IncidentSortColumn, IncidentStatus, InjuryIllnessType, OutcomeSeverity, SearchHit, SearchSource,
+} from '../../lib/types';
+
+const PAGE_SIZE = 50;
+
+const SEARCH_SOURCE_LABELS: Record<SearchSource, string> = {
+  incident: 'Incident',
+  rca: 'Root cause analysis',
+  corrective_action: 'Corrective action',
+};
 
 export function IncidentListPage() {
   const navigate = useNavigate();
   const { activeEstablishment, locations } = useSettingsStore();
-  const { incidents, loading, loadIncidents } = useIncidentStore();
+  const { incidents, total, loading, loadIncidents } = useIncidentStore();
 
   const [statusFilter, setStatusFilter] = useState<IncidentStatus | ''>('');
   const [severityFilter, setSeverityFilter] = useState<OutcomeSeverity | ''>('');
   const [locationFilter, setLocationFilter] = useState<number | ''>('');
+  const [typeFilter, setTypeFilter] = useState<InjuryIllnessType | ''>('');
+  const [recordableOnly, setRecordableOnly] = useState(false);
   const [search, setSearch] = useState('');
+  const [hits, setHits] = useState<SearchHit[]>([]);
+  const [sortBy, setSortBy] = useState<IncidentSortColumn>('incident_date');
+  const [sortDir, setSortDir] = useState<'asc' | 'desc'>('desc');
+  const [page, setPage] = useState(0);
+
+  // Any filter change starts again from the first page
+  useEffect(() => {
+    setPage(0);
+  }, [activeEstablishment, statusFilter, severityFilter, locationFilter, typeFilter, recordableOnly, search]);
 
   useEffect(() => {
     if (!activeEstablishment) return;
@@ -22,9 +44,44 @@ export function IncidentListPage() {
       status: statusFilter || undefined,
       outcome_severity: severityFilter || undefined,
       location_id: locationFilter || undefined,
+      injury_illness_type: typeFilter || undefined,
+      is_recordable: recordableOnly || undefined,
       search: search || undefined,
+      sort_by: sortBy,
+      sort_dir: sortDir,
+      limit: PAGE_SIZE,
+      offset: page * PAGE_SIZE,
     });
-  }, [activeEstablishment, statusFilter, severityFilter, locationFilter, search, loadIncidents]);
+  }, [activeEstablishment, statusFilter, severityFilter, locationFilter, typeFilter, recordableOnly, search,
+    sortBy, sortDir, page, loadIncidents]);
+
+  const toggleSort = (column: IncidentSortColumn) => {
+    if (column === sortBy) {
+      setSortDir(sortDir === 'asc' ? 'desc' : 'asc');
+    } else {
+      setSortBy(column);
+      setSortDir(column === 'incident_date' ? 'desc' : 'asc');
+    }
+  };
+
+  const sortHeader = (column: IncidentSortColumn, label: string) => (
+    <th className="text-left px-4 py-3 font-medium text-gray-600 cursor-pointer select-none"
+      onClick={() => toggleSort(column)}>
+      {label}{sortBy === column && (sortDir === 'asc' ? ' ▲' : ' ▼')}
+    </th>
+  );
+
+  const pageCount = Math.max(1, Math.ceil(total / PAGE_SIZE));
+
+  useEffect(() => {
+    if (!activeEstablishment || !search.trim()) {
+      setHits([]);
+      return;
+    }
+    invoke<SearchHit[]>('search_incidents', { query: search, establishmentId: activeEstablishment.id, limit: 10 })
+      .then(setHits)
+      .catch(() => setHits([]));
+  }, [activeEstablishment, search]);
 
   const severityBadge = (severity: string) => {
     const colors: Record<string, string> = {
@@ -42,8 +99,10 @@ export function IncidentListPage() {
 
   const statusBadge = (status: string) => {
     const colors: Record<string, string> = {
-      open: 'bg-orange-100 text-orange-800',
-      in_review: 'bg-blue-100 text-blue-800',
+      reported: 'bg-orange-100 text-orange-800',
+      under_investigation: 'bg-blue-100 text-blue-800',
+      pending_rca: 'bg-blue-100 text-blue-800',
+      pending_corrective_actions: 'bg-blue-100 text-blue-800',
       closed: 'bg-green-100 text-green-800',
     };
     return (
@@ -67,7 +126,7 @@ export function IncidentListPage() {
       <div className="flex flex-wrap gap-3 bg-white rounded-lg shadow p-4">
         <input
           type="text"
-          placeholder="Search by name or description..."
+          placeholder="Search names, narratives, root causes and actions..."
           value={search}
           onChange={e => setSearch(e.target.value)}
           className="border rounded px-3 py-1.5 text-sm flex-1 min-w-[200px]"
@@ -75,9 +134,9 @@ export function IncidentListPage() {
         <select value={statusFilter} onChange={e => setStatusFilter(e.target.value as IncidentStatus | '')}
           className="border rounded px-3 py-1.5 text-sm">
           <option value="">All Statuses</option>
-          <option value="open">Open</option>
-          <option value="in_review">In Review</option>
-          <option value="closed">Closed</option>
+          {Object.entries(INCIDENT_STATUS_LABELS).map(([value, label]) => (
+            <option key={value} value={value}>{label}</option>
+          ))}
         </select>
         <select value={severityFilter} onChange={e => setSeverityFilter(e.target.value as OutcomeSeverity | '')}
           className="border rounded px-3 py-1.5 text-sm">
@@ -91,8 +150,35 @@ export function IncidentListPage() {
           <option value="">All Locations</option>
           {locations.map(l => <option key={l.id} value={l.id}>{l.name}</option>)}
         </select>
+        <select value={typeFilter} onChange={e => setTypeFilter(e.target.value as InjuryIllnessType | '')}
+          className="border rounded px-3 py-1.5 text-sm">
+          <option value="">All Types</option>
+          {Object.entries(INJURY_TYPE_LABELS).map(([k, v]) => (
+            <option key={k} value={k}>{v}</option>
+          ))}
+        </select>
+        <label className="flex items-center gap-1.5 text-sm">
+          <input type="checkbox" checked={recordableOnly} onChange={e => setRecordableOnly(e.target.checked)} />
+          Recordable only
+        </label>
       </div>
 
+      {/* Ranked matches */}
+      {hits.length > 0 && (
+        <div className="bg-white rounded-lg shadow divide-y">
+          {hits.map(hit => (
+            <div key={`${hit.source}-${hit.source_id}`}
+              onClick={() => navigate(`/incidents/${hit.incident_id}`)}
+              className="px-4 py-2 text-sm hover:bg-gray-50 cursor-pointer">
+              <div className="text-xs text-gray-500">
+                {SEARCH_SOURCE_LABELS[hit.source]} · Case {hit.case_label ?? hit.case_number ?? '-'} · {hit.incident_date} · {hit.employee_name}
+              </div>
+              <Snippet text={hit.snippet} />
+            </div>
+          ))}
+        </div>
+      )}
+
       {/* List */}
       {loading ? (
         <p className="text-gray-500">Loading...</p>
@@ -108,12 +194,12 @@ export function IncidentListPage() {
           <table className="w-full text-sm">
             <thead className="bg-gray-50 border-b">
               <tr>
-                <th className="text-left px-4 py-3 font-medium text-gray-600">Case #</th>
-                <th className="text-left px-4 py-3 font-medium text-gray-600">Date</th>
-                <th className="text-left px-4 py-3 font-medium text-gray-600">Employee</th>
+                {sortHeader('case_number', 'Case #')}
+                {sortHeader('incident_date', 'Date')}
+                {sortHeader('employee_name', 'Employee')}
                 <th className="text-left px-4 py-3 font-medium text-gray-600">Description</th>
-                <th className="text-left px-4 py-3 font-medium text-gray-600">Severity</th>
-                <th className="text-left px-4 py-3 font-medium text-gray-600">Status</th>
+                {sortHeader('outcome_severity', 'Severity')}
+                {sortHeader('status', 'Status')}
               </tr>
             </thead>
             <tbody className="divide-y">
@@ -121,7 +207,7 @@ export function IncidentListPage() {
                 <tr key={inc.id}
                   onClick={() => navigate(`/incidents/${inc.id}`)}
                   className="hover:bg-gray-50 cursor-pointer">
-                  <td className="px-4 py-3 font-mono">{inc.case_number ?? '-'}</td>
+                  <td className="px-4 py-3 font-mono">{inc.case_label ?? inc.case_number ?? '-'}</td>
                   <td className="px-4 py-3">{inc.incident_date}</td>
                   <td className="px-4 py-3">
                     {inc.is_privacy_case ? <span className="italic text-gray-400">Privacy Case</span> : inc.employee_name}
@@ -133,8 +219,31 @@ export function IncidentListPage() {
               ))}
             </tbody>
           </table>
+          <div className="flex items-center justify-between px-4 py-3 border-t text-sm text-gray-600">
+            <span>
+              {page * PAGE_SIZE + 1}–{page * PAGE_SIZE + incidents.length} of {total}
+            </span>
+            <div className="flex gap-2">
+              <button onClick={() => setPage(page - 1)} disabled={page === 0}
+                className="border rounded px-3 py-1 disabled:opacity-40">Previous</button>
+              <button onClick={() => setPage(page + 1)} disabled={page + 1 >= pageCount}
+                className="border rounded px-3 py-1 disabled:opacity-40">Next</button>
+            </div>
+          </div>
         </div>
       )}
     </div>
   );
 }
+
+/** Renders an FTS snippet with its <mark> markers as highlighted text. */
+function Snippet({ text }: { text: string }) {
+  const parts = text.split(/<mark>|<\/mark>/);
+  return (
+    <p>
+      {parts.map((part, i) => i % 2 === 1
+        ? <mark key={i} className="bg-yellow-100">{part}</mark>
+        : <span key={i}>{part}</span>)}
+    </p>
+  );
+}
@@ -1,9 +1,9 @@
 import { useEffect, useState } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../../lib/invoke';
 import { useSettingsStore } from '../../stores/settingsStore';
 import { useToast } from '../../hooks/useToast';
 // Constants used for label display in the component
-import type { Osha300Row, Osha300ASummary, AnnualStats, UpsertAnnualStats } from '../../lib/types';
+import type { Osha300Row, Osha300ASummary, AnnualStats, UpsertAnnualStats, CaseRenumbering } from '../../lib/types';
 
 type Tab = '300' | '300a' | '301';
 
@@ -51,6 +51,16 @@ export function OshaPage() {
     }
   };
 
+  const handleExportPdf = async (command: 'export_osha_300_pdf' | 'export_osha_300a_pdf') => {
+    if (!activeEstablishment) return;
+    try {
+      const path = await invoke<string>(command, { establishmentId: activeEstablishment.id, year });
+      toast.success(`Exported to ${path}`);
+    } catch (error) {
+      toast.error(`Export failed: ${error}`);
+    }
+  };
+
   const handleSaveStats = async () => {
     if (!activeEstablishment) return;
     await invoke('upsert_annual_stats', {
@@ -69,6 +79,32 @@ export function OshaPage() {
     loadData();
   };
 
+  const handleLogAction = async (command: 'close_osha_log' | 'certify_osha_log') => {
+    if (!activeEstablishment) return;
+    try {
+      await invoke(command, { establishmentId: activeEstablishment.id, year });
+      toast.success(command === 'close_osha_log' ? `${year} log closed` : `${year} log certified`);
+      loadData();
+    } catch (error) {
+      toast.error(`${error}`);
+    }
+  };
+
+  const handleRenumber = async () => {
+    if (!activeEstablishment) return;
+    if (!confirm(`Renumber all ${year} cases in date order? Changes are recorded in the audit log.`)) return;
+    try {
+      const changes = await invoke<CaseRenumbering[]>('renumber_incident_cases', {
+        establishmentId: activeEstablishment.id,
+        year,
+      });
+      toast.success(changes.length ? `${changes.length} case(s) renumbered` : 'Case numbers already in order');
+      loadData();
+    } catch (error) {
+      toast.error(`${error}`);
+    }
+  };
+
   const years = Array.from({ length: 5 }, (_, i) => currentYear - i);
   const boolCell = (v: boolean) => v ? <span className="text-red-600 font-bold">X</span> : '';
 
@@ -103,10 +139,21 @@ export function OshaPage() {
         <div className="bg-white rounded-lg shadow">
           <div className="flex items-center justify-between p-4 border-b">
             <h2 className="font-semibold">Log of Work-Related Injuries and Illnesses ({year})</h2>
-            <button onClick={handleExport300}
-              className="bg-safety-orange text-white px-3 py-1.5 rounded text-sm">
-              Export CSV
-            </button>
+            <div className="flex gap-2">
+              <button onClick={handleExport300}
+                className="bg-safety-orange text-white px-3 py-1.5 rounded text-sm">
+                Export CSV
+              </button>
+              <button onClick={() => handleExportPdf('export_osha_300_pdf')}
+                className="border border-safety-orange text-safety-orange px-3 py-1.5 rounded text-sm">
+                Export PDF
+              </button>
+              {!annualStats?.certified_at && (
+                <button onClick={handleRenumber} className="border px-3 py-1.5 rounded text-sm">
+                  Renumber Cases
+                </button>
+              )}
+            </div>
           </div>
           <div className="overflow-x-auto">
             <table className="w-full text-xs">
@@ -129,7 +176,7 @@ export function OshaPage() {
               <tbody className="divide-y">
                 {log300.map((row, i) => (
                   <tr key={i} className="hover:bg-gray-50">
-                    <td className="px-2 py-2 font-mono">{row.case_number}</td>
+                    <td className="px-2 py-2 font-mono">{row.case_label}</td>
                     <td className="px-2 py-2">{row.employee_name}</td>
                     <td className="px-2 py-2">{row.job_title}</td>
                     <td className="px-2 py-2">{row.incident_date}</td>
@@ -155,7 +202,13 @@ export function OshaPage() {
       {/* 300A Summary */}
       {tab === '300a' && summary300a && (
         <div className="bg-white rounded-lg shadow p-6 space-y-6">
-          <h2 className="font-semibold text-lg">Summary of Work-Related Injuries and Illnesses ({year})</h2>
+          <div className="flex items-center justify-between">
+            <h2 className="font-semibold text-lg">Summary of Work-Related Injuries and Illnesses ({year})</h2>
+            <button onClick={() => handleExportPdf('export_osha_300a_pdf')}
+              className="border border-safety-orange text-safety-orange px-3 py-1.5 rounded text-sm">
+              Export PDF
+            </button>
+          </div>
 
           <div className="grid grid-cols-2 md:grid-cols-3 gap-4 text-sm">
             <div><span className="text-gray-500">Establishment:</span> {summary300a.establishment_name}</div>
@@ -208,7 +261,7 @@ export function OshaPage() {
         <div className="bg-white rounded-lg shadow p-6">
           <div className="flex items-center justify-between mb-4">
             <h2 className="font-semibold text-lg">Annual Workforce Data ({year})</h2>
-            {!editingStats && (
+            {!editingStats && !annualStats?.certified_at && (
               <button onClick={() => {
                 setStatsForm({
                   avg_employees: annualStats?.avg_employees ?? 0,
@@ -266,6 +319,18 @@ export function OshaPage() {
               <div><span className="text-gray-500">Total Hours:</span> {annualStats.total_hours_worked.toLocaleString()}</div>
               <div><span className="text-gray-500">Certifier:</span> {annualStats.certifier_name ?? 'Not set'}</div>
               <div><span className="text-gray-500">Title:</span> {annualStats.certifier_title ?? 'Not set'}</div>
+              <div><span className="text-gray-500">Closed:</span> {annualStats.closed_at ? `${annualStats.closed_at} by ${annualStats.closed_by ?? 'system'}` : 'Open'}</div>
+              <div><span className="text-gray-500">Certified:</span> {annualStats.certified_at ? `${annualStats.certified_at} by ${annualStats.certified_by ?? 'system'}` : 'Not certified'}</div>
+              <div className="col-span-2 flex gap-2 pt-2">
+                {!annualStats.closed_at && year < currentYear && (
+                  <button onClick={() => handleLogAction('close_osha_log')}
+                    className="border px-3 py-1.5 rounded text-sm">Close Log</button>
+                )}
+                {annualStats.closed_at && !annualStats.certified_at && (
+                  <button onClick={() => handleLogAction('certify_osha_log')}
+                    className="bg-safety-orange text-white px-3 py-1.5 rounded text-sm">Certify</button>
+                )}
+              </div>
             </div>
           ) : (
             <p className="text-sm text-gray-500">No annual stats entered for {year}. Click &quot;Add Data&quot; to enter workforce data for OSHA 300A calculations.</p>
@@ -43,7 +43,7 @@ export function RcaPage() {
             Back to Incident
           </Link>
           <h1 className="text-2xl font-bold mt-1">
-            Root Cause Analysis - Case #{currentIncident?.case_number ?? 'N/A'}
+            Root Cause Analysis - Case #{currentIncident?.case_label ?? currentIncident?.case_number ?? 'N/A'}
           </h1>
         </div>
         <button onClick={() => setShowCreate(true)}
@@ -1,18 +1,24 @@
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import { useSettingsStore } from '../../stores/settingsStore';
-import { US_STATES } from '../../lib/constants';
-import type { CreateLocation } from '../../lib/types';
+import { useToast } from '../../hooks/useToast';
+import { US_STATES, TRASH_KIND_LABELS } from '../../lib/constants';
+import type { CreateLocation, TrashItem } from '../../lib/types';
 
 export function SettingsPage() {
   const {
     activeEstablishment, locations,
     updateEstablishment, createLocation, updateLocation, deleteLocation,
+    trash, loadTrash, restoreFromTrash, purgeFromTrash,
   } = useSettingsStore();
+  const toast = useToast();
 
   const [editingEst, setEditingEst] = useState(false);
   const [estForm, setEstForm] = useState({ ...activeEstablishment });
   const [showAddLoc, setShowAddLoc] = useState(false);
   const [newLoc, setNewLoc] = useState<Partial<CreateLocation>>({});
+  const [confirmPurge, setConfirmPurge] = useState<TrashItem | null>(null);
+
+  useEffect(() => { loadTrash(); }, [loadTrash]);
 
   if (!activeEstablishment) return <p>No establishment selected</p>;
 
@@ -25,6 +31,7 @@ export function SettingsPage() {
       zip_code: estForm?.zip_code ?? undefined,
       industry_description: estForm?.industry_description ?? undefined,
       naics_code: estForm?.naics_code ?? undefined,
+      case_number_format: estForm?.case_number_format ?? undefined,
     });
     setEditingEst(false);
   };
@@ -42,6 +49,25 @@ export function SettingsPage() {
     setShowAddLoc(false);
   };
 
+  const handleRestore = async (item: TrashItem) => {
+    try {
+      await restoreFromTrash(item.kind, item.id);
+      toast.success(`${TRASH_KIND_LABELS[item.kind]} restored`);
+    } catch (e) {
+      toast.error(String(e));
+    }
+  };
+
+  const handlePurge = async (item: TrashItem) => {
+    try {
+      await purgeFromTrash(item.kind, item.id);
+      toast.success(`${TRASH_KIND_LABELS[item.kind]} permanently deleted`);
+    } catch (e) {
+      toast.error(String(e));
+    }
+    setConfirmPurge(null);
+  };
+
   return (
     <div className="space-y-8">
       <h1 className="text-2xl font-bold">Settings</h1>
@@ -77,6 +103,8 @@ export function SettingsPage() {
               className="w-full border rounded px-3 py-2 text-sm" placeholder="Industry Description" />
             <input type="text" value={estForm?.naics_code ?? ''} onChange={e => setEstForm(prev => ({ ...prev!, naics_code: e.target.value }))}
               className="w-full border rounded px-3 py-2 text-sm" placeholder="NAICS Code" />
+            <input type="text" value={estForm?.case_number_format ?? ''} onChange={e => setEstForm(prev => ({ ...prev!, case_number_format: e.target.value }))}
+              className="w-full border rounded px-3 py-2 text-sm font-mono" placeholder="Case Number Format, e.g. {year}-EST01-{seq:4}" />
             <div className="flex gap-2">
               <button onClick={handleSaveEst} className="bg-safety-orange text-white px-4 py-2 rounded text-sm">Save</button>
               <button onClick={() => setEditingEst(false)} className="border px-4 py-2 rounded text-sm">Cancel</button>
@@ -88,6 +116,7 @@ export function SettingsPage() {
             <p><span className="font-medium">Address:</span> {[activeEstablishment.street_address, activeEstablishment.city, activeEstablishment.state, activeEstablishment.zip_code].filter(Boolean).join(', ') || 'Not set'}</p>
             <p><span className="font-medium">Industry:</span> {activeEstablishment.industry_description ?? 'Not set'}</p>
             <p><span className="font-medium">NAICS:</span> {activeEstablishment.naics_code ?? 'Not set'}</p>
+            <p><span className="font-medium">Case number format:</span> {activeEstablishment.case_number_format ?? 'Plain number'}</p>
           </div>
         )}
       </section>
@@ -153,6 +182,46 @@ export function SettingsPage() {
           )}
         </div>
       </section>
+
+      {/* Trash */}
+      <section className="bg-white rounded-lg shadow p-6">
+        <h2 className="text-lg font-semibold mb-1">Trash</h2>
+        <p className="text-xs text-gray-500 mb-4">
+          Deleted incidents, establishments, locations and toolbox talks can be restored from here.
+          Recordable cases cannot be permanently deleted until OSHA's five-year retention period ends.
+        </p>
+        <div className="space-y-2">
+          {trash.map(item => (
+            <div key={`${item.kind}-${item.id}`} className="flex items-center justify-between border rounded p-3">
+              <div>
+                <p className="font-medium text-sm">{item.label}</p>
+                <p className="text-xs text-gray-500">
+                  {TRASH_KIND_LABELS[item.kind]} · deleted {item.deleted_at}{item.deleted_by ? ` by ${item.deleted_by}` : ''}
+                </p>
+              </div>
+              {confirmPurge === item ? (
+                <div className="flex items-center gap-2">
+                  <span className="text-xs text-red-600">This cannot be undone.</span>
+                  <button onClick={() => handlePurge(item)}
+                    className="bg-red-600 text-white px-2 py-1 rounded text-xs">Delete Forever</button>
+                  <button onClick={() => setConfirmPurge(null)}
+                    className="border px-2 py-1 rounded text-xs">Cancel</button>
+                </div>
+              ) : (
+                <div className="flex items-center gap-2">
+                  <button onClick={() => handleRestore(item)}
+                    className="text-xs text-safety-orange hover:underline">Restore</button>
+                  <button onClick={() => setConfirmPurge(item)}
+                    className="text-xs text-red-500 hover:text-red-700">Delete Forever</button>
+                </div>
+              )}
+            </div>
+          ))}
+          {trash.length === 0 && (
+            <p className="text-sm text-gray-500 text-center py-4">The trash is empty</p>
+          )}
+        </div>
+      </section>
     </div>
   );
 }
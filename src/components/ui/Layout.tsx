@@ -1,5 +1,6 @@
 import { NavLink } from 'react-router-dom';
 import { useSettingsStore } from '../../stores/settingsStore';
+import { useAuthStore } from '../../stores/authStore';
 import type { ReactNode } from 'react';
 
 interface LayoutProps {
@@ -18,6 +19,7 @@ const navItems = [
 
 export function Layout({ children }: LayoutProps) {
   const { activeEstablishment, establishments, setActiveEstablishment } = useSettingsStore();
+  const { session, logout } = useAuthStore();
 
   return (
     <div className="flex h-screen bg-gray-50">
@@ -64,8 +66,14 @@ export function Layout({ children }: LayoutProps) {
           ))}
         </nav>
 
-        <div className="p-4 border-t border-gray-700 text-xs text-gray-500">
-          {activeEstablishment?.name ?? 'No establishment'}
+        <div className="p-4 border-t border-gray-700 text-xs text-gray-500 space-y-1">
+          <p>{activeEstablishment?.name ?? 'No establishment'}</p>
+          {session && (
+            <div className="flex items-center justify-between">
+              <span className="text-gray-400">{session.display_name}</span>
+              <button onClick={logout} className="text-gray-400 hover:text-white">Sign Out</button>
+            </div>
+          )}
         </div>
       </aside>
 
@@ -15,11 +15,29 @@ export const INJURY_TYPE_LABELS: Record<string, string> = {
 };
 
 export const INCIDENT_STATUS_LABELS: Record<string, string> = {
-  open: 'Open',
-  in_review: 'In Review',
+  reported: 'Reported',
+  under_investigation: 'Under Investigation',
+  pending_rca: 'Pending RCA',
+  pending_corrective_actions: 'Pending Corrective Actions',
   closed: 'Closed',
 };
 
+/** Status changes the workflow allows; mirrors the backend transition table */
+export const INCIDENT_STATUS_TRANSITIONS: Record<string, string[]> = {
+  reported: ['under_investigation'],
+  under_investigation: ['pending_rca', 'closed'],
+  pending_rca: ['under_investigation', 'pending_corrective_actions', 'closed'],
+  pending_corrective_actions: ['pending_rca', 'closed'],
+  closed: ['under_investigation'],
+};
+
+export const TRASH_KIND_LABELS: Record<string, string> = {
+  incident: 'Incident',
+  establishment: 'Establishment',
+  location: 'Location',
+  toolbox_talk: 'Toolbox Talk',
+};
+
 export const FISHBONE_CATEGORIES = [
   'manpower',
   'methods',
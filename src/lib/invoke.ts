@@ -0,0 +1,18 @@
+import { invoke as tauriInvoke, type InvokeArgs } from '@tauri-apps/api/core';
+import { useAuthStore } from '../stores/authStore';
+
+/**
+ * Tauri `invoke` that sends the user back to the login screen when the
+ * backend reports the session as missing or expired.
+ */
+export async function invoke<T>(cmd: string, args?: InvokeArgs): Promise<T> {
+  try {
+    return await tauriInvoke<T>(cmd, args);
+  } catch (e) {
+    const message = String(e);
+    if (message.startsWith('Unauthorized:')) {
+      useAuthStore.getState().sessionExpired(message.replace('Unauthorized: ', ''));
+    }
+    throw e;
+  }
+}
@@ -9,8 +9,16 @@ export interface Establishment {
   zip_code: string | null;
   industry_description: string | null;
   naics_code: string | null;
+  ein: string | null;
+  company_name: string | null;
+  establishment_type: 'private' | 'state_government' | 'local_government';
+  /** `{year}` and `{seq}` / `{seq:N}` fields, e.g. `{year}-EST01-{seq:4}`; null uses the plain number */
+  case_number_format: string | null;
   created_at: string;
   updated_at: string;
+  /** Set while the establishment is in the trash */
+  deleted_at: string | null;
+  deleted_by: string | null;
 }
 
 export interface CreateEstablishment {
@@ -21,6 +29,10 @@ export interface CreateEstablishment {
   zip_code?: string;
   industry_description?: string;
   naics_code?: string;
+  ein?: string;
+  company_name?: string;
+  establishment_type?: 'private' | 'state_government' | 'local_government';
+  case_number_format?: string;
 }
 
 export interface UpdateEstablishment {
@@ -31,6 +43,10 @@ export interface UpdateEstablishment {
   zip_code?: string;
   industry_description?: string;
   naics_code?: string;
+  ein?: string;
+  company_name?: string;
+  establishment_type?: 'private' | 'state_government' | 'local_government';
+  case_number_format?: string;
 }
 
 export interface Location {
@@ -43,6 +59,8 @@ export interface Location {
   is_active: boolean;
   created_at: string;
   updated_at: string;
+  deleted_at: string | null;
+  deleted_by: string | null;
 }
 
 export interface CreateLocation {
@@ -65,11 +83,26 @@ export interface UpdateLocation {
 
 export type OutcomeSeverity = 'death' | 'days_away' | 'job_transfer_restriction' | 'other_recordable';
 export type InjuryIllnessType = 'injury' | 'skin_disorder' | 'respiratory' | 'poisoning' | 'hearing_loss' | 'other_illness';
-export type IncidentStatus = 'open' | 'in_review' | 'closed';
+export type IncidentStatus =
+  | 'reported'
+  | 'under_investigation'
+  | 'pending_rca'
+  | 'pending_corrective_actions'
+  | 'closed';
+
+export type PrivacyConcern =
+  | 'intimate_body_part'
+  | 'sexual_assault'
+  | 'mental_illness'
+  | 'hiv_hepatitis_tuberculosis'
+  | 'contaminated_sharps'
+  | 'employee_request';
 
 export interface Incident {
   id: number;
   case_number: number | null;
+  /** The case number in the establishment's format, as printed on the forms */
+  case_label: string | null;
   establishment_id: number;
   location_id: number | null;
   employee_name: string;
@@ -82,6 +115,8 @@ export interface Incident {
   employee_hire_date: string | null;
   employee_gender: string | null;
   is_privacy_case: boolean;
+  privacy_concern: PrivacyConcern | null;
+  privacy_description: string | null;
   incident_date: string;
   incident_time: string | null;
   work_start_time: string | null;
@@ -110,6 +145,9 @@ export interface Incident {
   completed_date: string | null;
   created_at: string;
   updated_at: string;
+  /** Set while the incident is in the trash */
+  deleted_at: string | null;
+  deleted_by: string | null;
 }
 
 export interface CreateIncident {
@@ -125,6 +163,8 @@ export interface CreateIncident {
   employee_hire_date?: string;
   employee_gender?: string;
   is_privacy_case?: boolean;
+  privacy_concern?: PrivacyConcern;
+  privacy_description?: string;
   incident_date: string;
   incident_time?: string;
   work_start_time?: string;
@@ -160,6 +200,8 @@ export interface UpdateIncident {
   employee_hire_date?: string;
   employee_gender?: string;
   is_privacy_case?: boolean;
+  privacy_concern?: PrivacyConcern;
+  privacy_description?: string;
   incident_date?: string;
   incident_time?: string;
   work_start_time?: string;
@@ -181,21 +223,137 @@ export interface UpdateIncident {
   date_of_death?: string;
   injury_illness_type?: InjuryIllnessType;
   is_recordable?: boolean;
-  status?: IncidentStatus;
   completed_by?: string;
   completed_by_title?: string;
   completed_by_phone?: string;
   completed_date?: string;
 }
 
+export type IncidentSortColumn =
+  | 'incident_date' | 'case_number' | 'employee_name' | 'outcome_severity' | 'injury_illness_type'
+  | 'status' | 'days_away_count' | 'days_restricted_count' | 'created_at' | 'updated_at';
+
 export interface IncidentFilter {
   establishment_id: number;
   location_id?: number;
+  location_ids?: number[];
   status?: IncidentStatus;
   outcome_severity?: OutcomeSeverity;
+  injury_illness_type?: InjuryIllnessType;
+  is_recordable?: boolean;
+  case_number_from?: number;
+  case_number_to?: number;
   date_from?: string;
   date_to?: string;
   search?: string;
+  sort_by?: IncidentSortColumn;
+  sort_dir?: 'asc' | 'desc';
+  limit?: number;
+  offset?: number;
+}
+
+export interface IncidentPage {
+  incidents: Incident[];
+  total: number;
+  limit: number;
+  offset: number;
+}
+
+export type SearchSource = 'incident' | 'rca' | 'corrective_action';
+
+export interface IncidentStatusChange {
+  id: number;
+  incident_id: number;
+  from_status: IncidentStatus;
+  to_status: IncidentStatus;
+  reason: string | null;
+  changed_by: string | null;
+  changed_at: string;
+}
+
+export interface CaseRenumbering {
+  incident_id: number;
+  old_case_number: number | null;
+  new_case_number: number;
+  old_case_label: string | null;
+  new_case_label: string;
+}
+
+export interface SearchHit {
+  source: SearchSource;
+  source_id: number;
+  incident_id: number;
+  establishment_id: number;
+  case_number: number | null;
+  case_label: string | null;
+  incident_date: string;
+  employee_name: string;
+  /** Matched terms are wrapped in <mark>…</mark>; render as text, not HTML */
+  snippet: string;
+  rank: number;
+}
+
+// ── Recordability ──
+
+export interface TreatmentOption {
+  code: string;
+  label: string;
+  first_aid: boolean;
+}
+
+export interface RecordabilityInputs {
+  work_related: boolean;
+  new_case?: boolean;
+  death?: boolean;
+  days_away?: boolean;
+  restricted_work_or_transfer?: boolean;
+  loss_of_consciousness?: boolean;
+  significant_diagnosis?: boolean;
+  treatments?: string[];
+}
+
+export interface RecordabilityDecision {
+  recordable: boolean;
+  reason: string;
+  citation: string;
+}
+
+export interface RecordabilityDetermination {
+  incident_id: number;
+  inputs: RecordabilityInputs;
+  decision: RecordabilityDecision;
+  determined_by: string | null;
+  determined_at: string;
+}
+
+// ── Work Status ──
+
+export type WorkStatus = 'full_duty' | 'restricted' | 'away' | 'transferred';
+
+export interface WorkStatusPeriod {
+  id: number;
+  incident_id: number;
+  status: WorkStatus;
+  start_date: string;
+  end_date: string | null;
+  notes: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface CreateWorkStatusPeriod {
+  incident_id: number;
+  status: WorkStatus;
+  start_date: string;
+  end_date?: string | null;
+  notes?: string | null;
+}
+
+export interface UpdateWorkStatusPeriod {
+  status: WorkStatus;
+  start_date: string;
+  end_date: string | null;
+  notes: string | null;
 }
 
 // ── Attachments ──
@@ -264,6 +422,7 @@ export interface CorrectiveAction {
 
 export interface Osha300Row {
   case_number: number;
+  case_label: string;
   employee_name: string;
   job_title: string;
   incident_date: string;
@@ -312,8 +471,17 @@ export interface Osha300ASummary {
   certification_date: string | null;
 }
 
+export interface PrivacyCaseEntry {
+  incident_id: number;
+  case_number: number | null;
+  employee_name: string;
+  incident_date: string;
+  privacy_concern: PrivacyConcern | null;
+}
+
 export interface Osha301Report {
   case_number: number;
+  case_label: string;
   employee_name: string;
   employee_address: string;
   employee_city: string;
@@ -353,6 +521,10 @@ export interface AnnualStats {
   certifier_title: string | null;
   certifier_phone: string | null;
   certification_date: string | null;
+  closed_at: string | null;
+  closed_by: string | null;
+  certified_at: string | null;
+  certified_by: string | null;
 }
 
 export interface UpsertAnnualStats {
@@ -366,6 +538,19 @@ export interface UpsertAnnualStats {
   certification_date?: string;
 }
 
+export interface OshaLogAmendment {
+  id: number;
+  incident_id: number;
+  establishment_id: number;
+  year: number;
+  case_number: number | null;
+  field_name: string;
+  old_value: string | null;
+  new_value: string | null;
+  amended_by: string | null;
+  amended_at: string;
+}
+
 // ── Dashboard ──
 
 export interface DashboardSummary {
@@ -374,6 +559,28 @@ export interface DashboardSummary {
   total_recordable: number;
   days_since_last_incident: number | null;
   trir: number | null;
+  dart_rate: number | null;
+  ltir: number | null;
+  severity_rate: number | null;
+  fatality_rate: number | null;
+  near_miss_ratio: number | null;
+  benchmark: IndustryBenchmark | null;
+}
+
+export interface IndustryBenchmark {
+  naics_code: string;
+  industry: string;
+  source_year: number;
+  trir: number | null;
+  dart_rate: number | null;
+  ltir: number | null;
+  fatality_rate: number | null;
+}
+
+export interface BenchmarkImportResult {
+  created: number;
+  updated: number;
+  errors: string[];
 }
 
 export interface IncidentsByMonth {
@@ -422,9 +629,35 @@ export interface ColumnMapping {
   days_restricted_count?: string;
   injury_illness_type?: string;
   employee_gender?: string;
+  is_privacy_case?: string;
 }
 
 export interface ImportResult {
   imported: number;
   errors: string[];
 }
+
+// ── Auth ──
+
+export type Role = 'admin' | 'safety_manager' | 'supervisor' | 'field_worker';
+
+export interface Session {
+  user_id: number;
+  username: string;
+  display_name: string;
+  role: Role;
+  expires_at: string;
+}
+
+// ── Trash ──
+
+export type TrashKind = 'incident' | 'establishment' | 'location' | 'toolbox_talk';
+
+export interface TrashItem {
+  kind: TrashKind;
+  id: number;
+  establishment_id: number;
+  label: string;
+  deleted_at: string;
+  deleted_by: string | null;
+}
@@ -1,5 +1,5 @@
 import { useEffect, useState } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../lib/invoke';
 import { useSettingsStore } from '../stores/settingsStore';
 import { useToast } from '../hooks/useToast';
 
@@ -1,6 +1,6 @@
 import { useEffect, useState } from 'react';
 import { useNavigate } from 'react-router-dom';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../lib/invoke';
 import { Plus, Calendar, Users } from 'lucide-react';
 import { useSettingsStore } from '../stores/settingsStore';
 
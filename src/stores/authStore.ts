@@ -0,0 +1,53 @@
+import { create } from 'zustand';
+import { invoke } from '@tauri-apps/api/core';
+import type { Session } from '../lib/types';
+
+interface AuthState {
+  session: Session | null;
+  /** True until the seeded admin account has been given a password */
+  needsSetup: boolean;
+  checking: boolean;
+  /** Why the user was sent back to the login screen, if not by logging out */
+  notice: string | null;
+
+  checkSession: () => Promise<void>;
+  setupAdmin: (password: string) => Promise<void>;
+  login: (username: string, password: string) => Promise<void>;
+  logout: () => Promise<void>;
+  sessionExpired: (message: string) => void;
+}
+
+export const useAuthStore = create<AuthState>((set) => ({
+  session: null,
+  needsSetup: false,
+  checking: true,
+  notice: null,
+
+  checkSession: async () => {
+    set({ checking: true });
+    try {
+      const needsSetup = await invoke<boolean>('needs_initial_setup');
+      const session = needsSetup ? null : await invoke<Session | null>('whoami');
+      set({ needsSetup, session, checking: false });
+    } catch (e) {
+      set({ session: null, notice: String(e), checking: false });
+    }
+  },
+
+  setupAdmin: async (password: string) => {
+    const session = await invoke<Session>('setup_initial_admin', { password });
+    set({ session, needsSetup: false, notice: null });
+  },
+
+  login: async (username: string, password: string) => {
+    const session = await invoke<Session>('login', { username, password });
+    set({ session, notice: null });
+  },
+
+  logout: async () => {
+    await invoke('logout');
+    set({ session: null, notice: null });
+  },
+
+  sessionExpired: (message: string) => set({ session: null, notice: message }),
+}));
@@ -1,9 +1,13 @@
 import { create } from 'zustand';
-import { invoke } from '@tauri-apps/api/core';
-import type { Incident, CreateIncident, UpdateIncident, IncidentFilter, Attachment } from '../lib/types';
+import { invoke } from '../lib/invoke';
+import type {
+  Incident, CreateIncident, UpdateIncident, IncidentFilter, IncidentPage, IncidentStatus, Attachment,
+} from '../lib/types';
 
 interface IncidentState {
   incidents: Incident[];
+  /** Rows matching the last filter across all pages */
+  total: number;
   currentIncident: Incident | null;
   attachments: Attachment[];
   loading: boolean;
@@ -13,6 +17,7 @@ interface IncidentState {
   loadIncident: (id: number) => Promise<void>;
   createIncident: (data: CreateIncident) => Promise<Incident>;
   updateIncident: (id: number, data: UpdateIncident) => Promise<Incident>;
+  updateIncidentStatus: (id: number, status: IncidentStatus, reason?: string) => Promise<Incident>;
   deleteIncident: (id: number) => Promise<void>;
   loadAttachments: (incidentId: number) => Promise<void>;
   addAttachment: (incidentId: number, fileName: string, filePath: string, fileType: string, fileSize: number | null) => Promise<Attachment>;
@@ -22,6 +27,7 @@ interface IncidentState {
 
 export const useIncidentStore = create<IncidentState>((set, get) => ({
   incidents: [],
+  total: 0,
   currentIncident: null,
   attachments: [],
   loading: false,
@@ -30,8 +36,8 @@ export const useIncidentStore = create<IncidentState>((set, get) => ({
   loadIncidents: async (filter: IncidentFilter) => {
     set({ loading: true, error: null });
     try {
-      const incidents = await invoke<Incident[]>('list_incidents', { filter });
-      set({ incidents, loading: false });
+      const page = await invoke<IncidentPage>('list_incidents', { filter });
+      set({ incidents: page.incidents, total: page.total, loading: false });
     } catch (e) {
       set({ error: String(e), loading: false });
     }
@@ -59,6 +65,12 @@ export const useIncidentStore = create<IncidentState>((set, get) => ({
     return incident;
   },
 
+  updateIncidentStatus: async (id: number, status: IncidentStatus, reason?: string) => {
+    const incident = await invoke<Incident>('update_incident_status', { id, status, reason });
+    set({ currentIncident: incident });
+    return incident;
+  },
+
   deleteIncident: async (id: number) => {
     await invoke('delete_incident', { id });
     set({ currentIncident: null });
@@ -1,10 +1,14 @@
 import { create } from 'zustand';
-import { invoke } from '@tauri-apps/api/core';
-import type { Establishment, Location, CreateEstablishment, CreateLocation, UpdateEstablishment, UpdateLocation } from '../lib/types';
+import { invoke } from '../lib/invoke';
+import type {
+  Establishment, Location, CreateEstablishment, CreateLocation, UpdateEstablishment, UpdateLocation, TrashItem, TrashKind,
+} from '../lib/types';
 
 interface SettingsState {
   establishments: Establishment[];
   locations: Location[];
+  /** Deleted records across all establishments, newest first */
+  trash: TrashItem[];
   activeEstablishment: Establishment | null;
   loading: boolean;
   error: string | null;
@@ -18,11 +22,15 @@ interface SettingsState {
   createLocation: (data: CreateLocation) => Promise<Location>;
   updateLocation: (id: number, data: UpdateLocation) => Promise<Location>;
   deleteLocation: (id: number) => Promise<void>;
+  loadTrash: () => Promise<void>;
+  restoreFromTrash: (kind: TrashKind, id: number) => Promise<void>;
+  purgeFromTrash: (kind: TrashKind, id: number) => Promise<void>;
 }
 
 export const useSettingsStore = create<SettingsState>((set, get) => ({
   establishments: [],
   locations: [],
+  trash: [],
   activeEstablishment: null,
   loading: false,
   error: null,
@@ -94,5 +102,28 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
     await invoke('delete_location', { id });
     const est = get().activeEstablishment;
     if (est) await get().loadLocations(est.id);
+    await get().loadTrash();
+  },
+
+  loadTrash: async () => {
+    try {
+      const trash = await invoke<TrashItem[]>('list_trash', { establishmentId: null });
+      set({ trash });
+    } catch (e) {
+      set({ error: String(e) });
+    }
+  },
+
+  restoreFromTrash: async (kind: TrashKind, id: number) => {
+    await invoke('restore_from_trash', { kind, id });
+    await get().loadEstablishments();
+    const est = get().activeEstablishment;
+    if (est) await get().loadLocations(est.id);
+    await get().loadTrash();
+  },
+
+  purgeFromTrash: async (kind: TrashKind, id: number) => {
+    await invoke('purge_from_trash', { kind, id });
+    await get().loadTrash();
   },
 }));